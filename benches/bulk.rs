@@ -0,0 +1,82 @@
+//! Throughput of bulk generation: `cargo bench --bench bulk`.
+//!
+//! Token draws dominate the time spent on a secret, so the patterns below stress the markov
+//! chain with and without other elements. Each pattern is generated with and without
+//! [buffered draws](Generator::set_buffered_draws), from the default seeded random number
+//! generator and from the operating system one. The benchmark only uses `std`, and each
+//! pattern runs from the same seed so that runs are comparable across changes.
+//!
+//! The default random number generator already produces its output in blocks, so buffering
+//! is expected to pay off with the operating system one only: the benchmark fails when
+//! buffering is slower than [`MIN_SPEEDUP`] there, or slower than the plain draws with the
+//! default one.
+use cryptirust::{pattern::Pattern, Generator};
+use rand::rngs::OsRng;
+use rand::{CryptoRng, RngCore};
+use std::hint::black_box;
+use std::process::ExitCode;
+use std::time::Instant;
+
+/// Secrets generated for each pattern.
+const SECRETS: usize = 200_000;
+
+/// Minimum speedup of buffered draws from the operating system random number generator.
+const MIN_SPEEDUP: f64 = 1.5;
+
+/// Tolerated slowdown of buffered draws from the default random number generator, for noise.
+const MAX_SLOWDOWN: f64 = 0.9;
+
+/// Secrets per second generated by `generator` from `pattern`.
+fn throughput<R: RngCore + CryptoRng>(generator: &mut Generator<R>, pattern: &Pattern) -> f64 {
+    let start = Instant::now();
+    for _ in 0..SECRETS {
+        black_box(generator.gen_from_parsed(black_box(pattern)));
+    }
+    SECRETS as f64 / start.elapsed().as_secs_f64()
+}
+
+fn main() -> ExitCode {
+    let mut passed = true;
+    println!(
+        "{:<18} {:<8} {:>12} {:>12} {:>8}",
+        "pattern", "rng", "plain/s", "buffered/s", "speedup"
+    );
+    for pattern in ["w-w-w-w-dd", "cccccccc", "W.s.dddddd"] {
+        let parsed = Pattern::parse(pattern);
+        let mut plain = Generator::new_with_seed(0x719);
+        let mut buffered = Generator::new_with_seed(0x719);
+        buffered.set_buffered_draws(true);
+        let seeded = (
+            throughput(&mut plain, &parsed),
+            throughput(&mut buffered, &parsed),
+        );
+
+        let mut plain = Generator::new().with_rng(OsRng);
+        let mut buffered = Generator::new().with_rng(OsRng);
+        buffered.set_buffered_draws(true);
+        let os = (
+            throughput(&mut plain, &parsed),
+            throughput(&mut buffered, &parsed),
+        );
+
+        for (rng, (plain, buffered), gate) in
+            [("chacha8", seeded, MAX_SLOWDOWN), ("os", os, MIN_SPEEDUP)]
+        {
+            passed &= buffered / plain >= gate;
+            println!(
+                "{:<18} {:<8} {:>12.0} {:>12.0} {:>7.2}x",
+                pattern,
+                rng,
+                plain,
+                buffered,
+                buffered / plain
+            );
+        }
+    }
+    if passed {
+        ExitCode::SUCCESS
+    } else {
+        eprintln!("buffered draws are slower than expected");
+        ExitCode::FAILURE
+    }
+}
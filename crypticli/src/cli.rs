@@ -0,0 +1,2031 @@
+use crate::csv::{self, csv_row};
+use crate::error::{CliError, ErrorFormat, ErrorKind};
+use crate::interrupt;
+use crate::json::json_object;
+use crate::output::{append_private, AtomicFile, LineEndings, ProgressBar};
+use crate::sort::{ExternalSort, CHUNK_SECRETS};
+use argh::*;
+use cryptirust::analysis;
+use cryptirust::batch;
+use cryptirust::confirm::{self, Mask};
+use cryptirust::encode::{self, WordDiff};
+use cryptirust::grouping::DigitGrouping;
+use cryptirust::kdf::{self, KdfParams, SecretSeed};
+use cryptirust::layout::{self, Layout};
+use cryptirust::manifest::{Manifest, Seeding};
+use cryptirust::messages::Catalog;
+use cryptirust::normalize::{self, NormalizationForm};
+use cryptirust::pattern::{self, Pattern};
+use cryptirust::plan::PlanConstraints;
+use cryptirust::registry::{Annotated, Registry};
+use cryptirust::store::HistoryStore;
+use cryptirust::story;
+use cryptirust::template;
+use cryptirust::test_vectors::ALGORITHM_VERSION;
+use cryptirust::theme::Theme;
+use cryptirust::translit;
+use cryptirust::voice;
+use cryptirust::word_list::*;
+use cryptirust::Generator;
+use std::io::{BufRead, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+enum WordList {
+    English(),
+    Italian(),
+    Russian(),
+    CV(),
+}
+
+impl FromArgValue for WordList {
+    fn from_arg_value(value: &str) -> Result<Self, String> {
+        match value {
+            "italian" => Ok(WordList::Italian()),
+            "russian" => Ok(WordList::Russian()),
+            "eff" => Ok(WordList::English()),
+            "cv" => Ok(WordList::CV()),
+            _ => Err("non existant word list, use one of [italian, russian, cv, eff].".to_string()),
+        }
+    }
+}
+
+enum ThemeArg {
+    Plain(),
+    Alliteration(),
+    Rhyme(),
+}
+
+impl FromArgValue for ThemeArg {
+    fn from_arg_value(value: &str) -> Result<Self, String> {
+        match value {
+            "plain" => Ok(ThemeArg::Plain()),
+            "alliteration" => Ok(ThemeArg::Alliteration()),
+            "rhyme" => Ok(ThemeArg::Rhyme()),
+            _ => Err("unknown theme, use one of [plain, alliteration, rhyme].".to_string()),
+        }
+    }
+}
+
+struct LayoutArg(Layout);
+
+impl FromArgValue for LayoutArg {
+    fn from_arg_value(value: &str) -> Result<Self, String> {
+        Layout::ALL
+            .into_iter()
+            .find(|layout| layout.name() == value)
+            .map(LayoutArg)
+            .ok_or_else(|| "unknown layout, use one of [us, uk, de, it, mobile].".to_string())
+    }
+}
+
+enum SortKey {
+    Effort(),
+}
+
+impl FromArgValue for SortKey {
+    fn from_arg_value(value: &str) -> Result<Self, String> {
+        match value {
+            "effort" => Ok(SortKey::Effort()),
+            _ => Err("unknown sort key, use one of [effort].".to_string()),
+        }
+    }
+}
+
+/// Interval of `--watch`: a number followed by ms, s or m, seconds when bare.
+struct Interval(Duration);
+
+impl FromArgValue for Interval {
+    fn from_arg_value(value: &str) -> Result<Self, String> {
+        let (number, unit) = match value.find(|c: char| c.is_ascii_alphabetic()) {
+            Some(i) => value.split_at(i),
+            None => (value, "s"),
+        };
+        let scale = match unit {
+            "ms" => 0.001,
+            "s" => 1.0,
+            "m" => 60.0,
+            _ => return Err("unknown interval unit, use one of [ms, s, m].".to_string()),
+        };
+        match number.parse::<f64>() {
+            Ok(n) if n > 0.0 && n.is_finite() => Ok(Interval(Duration::from_secs_f64(n * scale))),
+            _ => Err("the interval must be a positive number, e.g. 5s.".to_string()),
+        }
+    }
+}
+
+struct NormalizeArg(NormalizationForm);
+
+impl FromArgValue for NormalizeArg {
+    fn from_arg_value(value: &str) -> Result<Self, String> {
+        match value {
+            "nfc" => Ok(NormalizeArg(NormalizationForm::Nfc)),
+            "nfd" => Ok(NormalizeArg(NormalizationForm::Nfd)),
+            _ => Err("unknown normalization form, use one of [nfc, nfd].".to_string()),
+        }
+    }
+}
+
+enum Newline {
+    Lf(),
+    Crlf(),
+}
+
+impl FromArgValue for Newline {
+    fn from_arg_value(value: &str) -> Result<Self, String> {
+        match value {
+            "lf" => Ok(Newline::Lf()),
+            "crlf" => Ok(Newline::Crlf()),
+            _ => Err("unknown line ending, use one of [lf, crlf].".to_string()),
+        }
+    }
+}
+
+enum Format {
+    Table(),
+    ExternalProgram(),
+}
+
+impl FromArgValue for Format {
+    fn from_arg_value(value: &str) -> Result<Self, String> {
+        match value {
+            "table" => Ok(Format::Table()),
+            "external-program" => Ok(Format::ExternalProgram()),
+            _ => Err("unknown format, use one of [table, external-program].".to_string()),
+        }
+    }
+}
+
+#[derive(FromArgs)]
+/// Flexible password generator based on the Cryptirust library.
+struct Cli {
+    /// string representing the desired structure of the generated passphrases, default is `w-c-s-d` (word-token-symbol-digit).
+    #[argh(option, short = 'p', default = "String::from(\"w-c-s-d\")")]
+    pattern: String,
+
+    /// value of an arg placeholder of the pattern, as name=value, e.g. --arg site=dw.com for
+    /// arg:site in braces; env placeholders take environment variables. Values are copied
+    /// verbatim. Repeatable.
+    #[argh(option)]
+    arg: Vec<String>,
+
+    /// generate grammatical mini-sentences instead (adjective-noun-verb-adjective-noun), easier
+    /// to memorize; patterns can also use the adj, noun and verb elements in braces.
+    #[argh(switch)]
+    story: bool,
+
+    /// number of passphrases to generate, must be a positive integer; a seq element in braces
+    /// in the pattern numbers them.
+    #[argh(option, short = 'n', default = "5")]
+    num: usize,
+
+    /// depth of the markov model, 1...3 are reasonable values.
+    #[argh(option, short = 'd', default = "3")]
+    depth: usize,
+
+    /// word style: eff (english), italian, russian (cyrillic), or cv (consonant-vowel pairs)
+    #[argh(option, short = 's', default = "WordList::English()")]
+    style: WordList,
+
+    /// drop the words which are prefixes of other words from the word list before training;
+    /// the generated words are new and can still be prefixes of one another, keep separators.
+    #[argh(switch)]
+    prefix_free: bool,
+
+    /// keep a single spelling of the words of the word list which sound alike (their, there),
+    /// so that passphrases can be dictated reliably.
+    #[argh(switch)]
+    no_homophones: bool,
+
+    /// drop the transitions of the model observed fewer than this many times in the word
+    /// list, removing one-off noise.
+    #[argh(option)]
+    min_count: Option<usize>,
+
+    /// report the exact entropy of each passphrase, accounting for the different ways the
+    /// generator can produce the same text (e.g. words without separators); slower.
+    #[argh(switch)]
+    exact_entropy: bool,
+
+    /// draw the length of each word from the lengths of the words in the word list, instead of
+    /// always generating about 8 characters.
+    #[argh(switch)]
+    natural_lengths: bool,
+
+    /// never emit groups of four digits looking like a year or a date, such as 1987 or 0412;
+    /// the reported entropy accounts for the excluded values.
+    #[argh(switch)]
+    date_safe: bool,
+
+    /// split runs of digits longer than this into hyphen separated groups, e.g. 123-456 for
+    /// six digits with groups of 3.
+    #[argh(option)]
+    group_digits: Option<usize>,
+
+    /// keyboard layout: us, uk, de, it or mobile; symbols are limited to those easily typed on
+    /// it.
+    #[argh(option)]
+    layout: Option<LayoutArg>,
+
+    /// characters drawn by s in the pattern, e.g. for sites rejecting some symbols; overrides
+    /// the symbols of --layout and --mobile.
+    #[argh(option)]
+    symbols: Option<String>,
+
+    /// characters drawn by d in the pattern, e.g. 23456789 to avoid the digits mistaken for
+    /// letters.
+    #[argh(option)]
+    digits: Option<String>,
+
+    /// mobile preset: lowercase words, digits and symbols grouped so that they are quick to
+    /// type on a phone; implies --layout mobile and overrides the pattern.
+    #[argh(switch)]
+    mobile: bool,
+
+    /// mix words from the bundled natural language lists (english and italian) instead of
+    /// using --style.
+    #[argh(switch)]
+    mix_languages: bool,
+
+    /// print the language of every word next to each secret.
+    #[argh(switch)]
+    annotate: bool,
+
+    /// print a sentence built around the words of each secret, as a memory aid; the words added
+    /// to the sentence are not part of the secret.
+    #[argh(switch)]
+    mnemonic: bool,
+
+    /// print the secrets transliterated to ASCII (e.g. ж as zh), followed by the original to
+    /// memorize; warns when the transliteration can merge distinct secrets.
+    #[argh(switch)]
+    ascii: bool,
+
+    /// color each part of the secrets by its entropy per character in the table output: green
+    /// for 3 bits or more, yellow for 2 bits or more, red below; only on a terminal, never in
+    /// an --output file or a pipe.
+    #[argh(switch)]
+    color: bool,
+
+    /// sort the generated secrets: effort (fewest keystrokes first on the --layout keyboard,
+    /// us by default).
+    #[argh(option)]
+    sort: Option<SortKey>,
+
+    /// print the secrets in byte order, so that large batches compress well and are quick to
+    /// deduplicate or compare; batches over a million secrets are sorted in chunks written to
+    /// --temp-dir, which hold the secrets until the output is complete.
+    #[argh(switch)]
+    sorted: bool,
+
+    /// directory of the temporary files of --sorted, in a private subdirectory removed at the
+    /// end; default is the system temporary directory.
+    #[argh(option)]
+    temp_dir: Option<String>,
+
+    /// generate this many candidates for each secret and keep the easiest to type (fewest
+    /// keystrokes on the --layout keyboard); the reported entropy is lowered by log2 of the
+    /// number of candidates to account for the choice.
+    #[argh(option)]
+    best_of: Option<usize>,
+
+    /// show every secret on standard error and ask to retype it before it is printed or
+    /// stored, failing with exit code 6 after three wrong attempts.
+    #[argh(switch)]
+    confirm: bool,
+
+    /// regenerate and print the secrets again at this interval (e.g. 5s, 500ms, 1m) until
+    /// interrupted with Ctrl-C.
+    #[argh(option)]
+    watch: Option<Interval>,
+
+    /// theme of the words: plain (default), alliteration (same first letter) or rhyme (same
+    /// final token); the reported entropy accounts for the constraint.
+    #[argh(option, default = "ThemeArg::Plain()")]
+    theme: ThemeArg,
+
+    /// output format: table (default) or external-program (a single JSON object of strings, as
+    /// expected by Terraform's external data source).
+    #[argh(option, default = "Format::Table()")]
+    format: Format,
+
+    /// unicode normalization form of the secrets: nfc (composed) or nfd (decomposed), for
+    /// systems comparing them byte by byte; by default they are kept as generated.
+    #[argh(option)]
+    normalize: Option<NormalizeArg>,
+
+    /// line endings of the output: lf (default) or crlf.
+    #[argh(option, default = "Newline::Lf()")]
+    newline: Newline,
+
+    /// end the output without a line ending, e.g. for a single secret read verbatim by another
+    /// program.
+    #[argh(switch)]
+    no_final_newline: bool,
+
+    /// make every secret differ from all the others by at least this many characters
+    /// (Levenshtein distance), so that codes cannot be mistaken for each other.
+    #[argh(option)]
+    min_distance: Option<usize>,
+
+    /// refuse patterns which can yield secrets with less than this many bits of entropy, and
+    /// such secrets.
+    #[argh(option)]
+    min_entropy: Option<f64>,
+
+    /// write the secrets to this file instead of standard output, keeping them out of the
+    /// terminal scrollback; the file gets 0600 permissions and is replaced atomically once
+    /// generation completes or is interrupted with Ctrl-C.
+    #[argh(option, short = 'o')]
+    output: Option<String>,
+
+    /// append to the `--output` file instead of replacing it.
+    #[argh(switch)]
+    append: bool,
+
+    /// write a JSON manifest of the run to this file: versions, model digest, depth, pattern,
+    /// policy, counts and entropy statistics, without the secrets.
+    #[argh(option)]
+    manifest: Option<String>,
+
+    /// derive the secrets from the secret seed read from `CRYPTICLI_SEED` instead of the system
+    /// random number generator, so that `regen` can reproduce them from the --manifest, which
+    /// is required.
+    #[argh(switch)]
+    seed: bool,
+
+    /// record salted hashes of the generated secrets in this encrypted history file, see
+    /// `history-check`; the passphrase is read from `CRYPTICLI_HISTORY_PASSPHRASE`.
+    #[cfg(feature = "history")]
+    #[argh(option)]
+    history: Option<String>,
+
+    /// never output a secret recorded in this Bloom filter file by a previous run, and record
+    /// the new ones; the filter rotates every million secrets and its key is read from
+    /// `CRYPTICLI_SEEN_KEY`.
+    #[argh(option)]
+    seen: Option<String>,
+
+    /// demo mode: reproducible, watermarked, zero-entropy outputs for docs and screenshots.
+    #[argh(switch)]
+    demo: bool,
+
+    /// write the generated secrets to this HashiCorp Vault path (e.g. `secret/data/app`) instead of
+    /// printing them, the token is read from `VAULT_TOKEN` and the address, an https:// address
+    /// or an http:// loopback address, from `VAULT_ADDR`.
+    #[cfg(feature = "vault")]
+    #[argh(option)]
+    vault_path: Option<String>,
+
+    /// version of the KV secrets engine of --vault-path: 1, or 2 (default), whose paths have a
+    /// data/ segment after the mount.
+    #[cfg(feature = "vault")]
+    #[argh(option, default = "crate::vault::KvVersion::V2()")]
+    vault_kv: crate::vault::KvVersion,
+
+    /// error output format: text (default) or json, see the exit codes in the documentation.
+    #[argh(option, default = "ErrorFormat::Text()")]
+    errors: ErrorFormat,
+
+    #[argh(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(FromArgs)]
+#[argh(subcommand)]
+enum Command {
+    Template(TemplateCmd),
+    Analyze(AnalyzeCmd),
+    WordlistAudit(WordlistAuditCmd),
+    Challenge(ChallengeCmd),
+    Batch(BatchCmd),
+    Plan(PlanCmd),
+    ModelExport(ModelExportCmd),
+    TrainMemory(TrainMemoryCmd),
+    Encode(EncodeCmd),
+    Decode(DecodeCmd),
+    Compare(CompareCmd),
+    #[cfg(feature = "history")]
+    HistoryCheck(HistoryCheckCmd),
+    Regen(RegenCmd),
+}
+
+#[derive(FromArgs)]
+/// Reproduce the secrets of a run made with --seed and --manifest, from its manifest and the
+/// seed read from `CRYPTICLI_SEED`, e.g. to recover derived credentials; the model and the
+/// generation algorithm must not have changed since.
+#[argh(subcommand, name = "regen")]
+struct RegenCmd {
+    /// path of the manifest of the run.
+    #[argh(positional)]
+    manifest: String,
+}
+
+#[derive(FromArgs)]
+/// Show a new passphrase, generated with the other options, then quiz you to retype it with
+/// less and less of it visible and longer and longer pauses, to memorize it before adopting it.
+#[argh(subcommand, name = "train-memory")]
+struct TrainMemoryCmd {
+    /// number of rounds recalling the passphrase with nothing visible, default 3.
+    #[argh(option, default = "3")]
+    rounds: usize,
+
+    /// pause before the first recall round, in seconds, doubling at every round; default 5.
+    #[argh(option, default = "5")]
+    pause: u64,
+}
+
+/// Output format of `model-export`.
+enum ModelFormat {
+    Dot(),
+    Json(),
+    Binary(),
+}
+
+impl FromArgValue for ModelFormat {
+    fn from_arg_value(value: &str) -> Result<Self, String> {
+        match value {
+            "dot" => Ok(ModelFormat::Dot()),
+            "json" => Ok(ModelFormat::Json()),
+            "binary" => Ok(ModelFormat::Binary()),
+            _ => Err("unknown model format, use one of [dot, json, binary].".to_string()),
+        }
+    }
+}
+
+#[derive(FromArgs)]
+/// Export the markov model trained with the word list and depth chosen by the other options,
+/// as a Graphviz graph (dot), a JSON array of transitions with their counts (json) or the
+/// compact form loaded by Generator::from_model_bytes (binary); combine with --min-count to
+/// prune rare transitions first.
+#[argh(subcommand, name = "model-export")]
+struct ModelExportCmd {
+    /// output format: dot (default), json or binary.
+    #[argh(option, default = "ModelFormat::Dot()")]
+    format: ModelFormat,
+
+    /// write the model to this file instead of standard output.
+    #[argh(option, short = 'o')]
+    output: Option<String>,
+}
+
+#[derive(FromArgs)]
+/// Design a pattern reaching a target entropy on average, with the given constraints, for the
+/// word list and settings chosen by the other options.
+#[argh(subcommand, name = "plan")]
+struct PlanCmd {
+    /// target entropy, in bits.
+    #[argh(positional)]
+    bits: f64,
+
+    /// maximum length of the secrets, in characters.
+    #[argh(option)]
+    max_length: Option<usize>,
+
+    /// require a digit.
+    #[argh(switch)]
+    digit: bool,
+
+    /// require a symbol.
+    #[argh(switch)]
+    symbol: bool,
+
+    /// require an uppercase letter.
+    #[argh(switch)]
+    uppercase: bool,
+}
+
+#[derive(FromArgs)]
+/// Generate the secrets described by a CSV file of jobs with the columns label, pattern, count
+/// and optionally policy (e.g. `min-entropy=60 min-distance=3`), printing the jobs as CSV with
+/// one row per secret and the secret and entropy columns added.
+#[argh(subcommand, name = "batch")]
+struct BatchCmd {
+    /// path of the CSV file of jobs.
+    #[argh(positional)]
+    file: String,
+
+    /// write the resulting CSV to this file (created with 0600 permissions) instead of standard
+    /// output.
+    #[argh(option, short = 'o')]
+    output: Option<String>,
+}
+
+/// Requirements on the secrets of a `batch` job.
+#[derive(Default)]
+struct Policy {
+    min_entropy: Option<f64>,
+    min_distance: Option<usize>,
+}
+
+impl Policy {
+    /// Parses whitespace separated `min-entropy=BITS` and `min-distance=N` settings.
+    fn parse(text: &str) -> Result<Policy, String> {
+        let mut policy = Policy::default();
+        for setting in text.split_whitespace() {
+            let invalid = || format!("invalid policy setting {:?}", setting);
+            match setting.split_once('=').ok_or_else(invalid)? {
+                ("min-entropy", bits) => {
+                    policy.min_entropy = Some(bits.parse().map_err(|_| invalid())?)
+                }
+                ("min-distance", n) => {
+                    policy.min_distance = Some(n.parse().map_err(|_| invalid())?)
+                }
+                _ => return Err(invalid()),
+            }
+        }
+        Ok(policy)
+    }
+}
+
+#[derive(FromArgs)]
+/// Generate short challenge phrases for voice authentication, made of words which do not sound
+/// alike, or verify a speech transcript against one.
+#[argh(subcommand, name = "challenge")]
+struct ChallengeCmd {
+    /// number of words of each phrase.
+    #[argh(option, default = "voice::CHALLENGE_WORDS")]
+    words: usize,
+
+    /// instead of generating phrases, check the transcript read from standard input against
+    /// this phrase; exits with code 6 if it does not match.
+    #[argh(option)]
+    verify: Option<String>,
+}
+
+/// Pronounceable encoding of `encode` and `decode`.
+enum Encoding {
+    Alias(),
+    Proquint(),
+    BubbleBabble(),
+    Words(),
+}
+
+impl FromArgValue for Encoding {
+    fn from_arg_value(value: &str) -> Result<Self, String> {
+        match value {
+            "alias" => Ok(Encoding::Alias()),
+            "proquint" => Ok(Encoding::Proquint()),
+            "bubble-babble" => Ok(Encoding::BubbleBabble()),
+            "words" => Ok(Encoding::Words()),
+            _ => Err(
+                "unknown encoding, use one of [alias, proquint, bubble-babble, words].".to_string(),
+            ),
+        }
+    }
+}
+
+#[derive(FromArgs)]
+/// Encode a UUID as a pronounceable alias, hexadecimal bytes as proquints or Bubble Babble, or
+/// a hexadecimal fingerprint as a few words to compare by voice.
+#[argh(subcommand, name = "encode")]
+struct EncodeCmd {
+    /// the UUID (alias) or the hexadecimal bytes (proquint, bubble-babble, words) to encode.
+    #[argh(positional)]
+    input: String,
+
+    /// encoding: alias (default), proquint, bubble-babble or words, which cannot be decoded.
+    #[argh(option, default = "Encoding::Alias()")]
+    format: Encoding,
+
+    /// number of words of the words encoding, default 6.
+    #[argh(option, default = "6")]
+    words: usize,
+}
+
+#[derive(FromArgs)]
+/// Decode a pronounceable alias to its UUID, or proquints or Bubble Babble to hexadecimal bytes.
+#[argh(subcommand, name = "decode")]
+struct DecodeCmd {
+    /// the text to decode.
+    #[argh(positional)]
+    input: String,
+
+    /// encoding: alias (default), proquint or bubble-babble.
+    #[argh(option, default = "Encoding::Alias()")]
+    format: Encoding,
+}
+
+#[derive(FromArgs)]
+/// Compare two phrases word by word, e.g. fingerprint words read over the phone, ignoring case
+/// and punctuation; prints the differing words and exits with code 6 if the phrases differ.
+#[argh(subcommand, name = "compare")]
+struct CompareCmd {
+    /// the first phrase.
+    #[argh(positional)]
+    first: String,
+
+    /// the second phrase.
+    #[argh(positional)]
+    second: String,
+}
+
+#[cfg(feature = "history")]
+#[derive(FromArgs)]
+/// Tell whether a secret, read from standard input, was recorded in an encrypted history file
+/// with `--history`; exits with code 6 if it was not. The passphrase of the history is read from
+/// `CRYPTICLI_HISTORY_PASSPHRASE`.
+#[argh(subcommand, name = "history-check")]
+struct HistoryCheckCmd {
+    /// path of the history file.
+    #[argh(positional)]
+    file: String,
+}
+
+#[derive(FromArgs)]
+/// Check a word list (one word per line) before adopting it for diceware passphrases.
+#[argh(subcommand, name = "wordlist-audit")]
+struct WordlistAuditCmd {
+    /// path of the word list.
+    #[argh(positional)]
+    file: String,
+}
+
+#[derive(FromArgs)]
+/// Estimate the strength of passwords read from standard input, one per line, and warn about
+/// those which other systems can silently truncate, trim or normalize.
+#[argh(subcommand, name = "analyze")]
+struct AnalyzeCmd {
+    /// language of the strength classes and warnings, as a language tag such as it or de-AT:
+    /// english (default), italian, german, french or spanish.
+    #[argh(option, default = "String::from(\"en\")")]
+    language: String,
+
+    /// check the passwords as passphrases chosen by people: report dictionary words, common
+    /// passwords and predictable letters, and suggest random words to append, a new passphrase
+    /// or a strengthened version of the password.
+    #[argh(switch)]
+    check: bool,
+}
+
+#[derive(FromArgs)]
+/// Fill the `cryptirust:<pattern>` placeholders of a template file with new secrets.
+#[argh(subcommand, name = "template")]
+struct TemplateCmd {
+    /// path of the template file.
+    #[argh(positional)]
+    file: String,
+
+    /// write the rendered template to this file (created with 0600 permissions) instead of
+    /// standard output.
+    #[argh(option, short = 'o')]
+    output: Option<String>,
+}
+
+pub fn cli_main() {
+    let strings: Vec<String> = std::env::args().collect();
+    let cmd = std::path::Path::new(&strings[0])
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or("crypticli");
+    let rest: Vec<&str> = strings[1..].iter().map(|s| s.as_str()).collect();
+    let mut args = match Cli::from_args(&[cmd], &rest) {
+        Ok(args) => args,
+        Err(EarlyExit { output, status }) => {
+            if status.is_ok() {
+                print!("{}", output);
+                std::process::exit(0);
+            }
+            // the arguments could not be parsed, so look for `--errors json` by hand.
+            let format = if rest.windows(2).any(|w| w == ["--errors", "json"]) {
+                ErrorFormat::Json()
+            } else {
+                ErrorFormat::Text()
+            };
+            let error = CliError::new(ErrorKind::Usage, output.trim_end());
+            error.report(&format);
+            std::process::exit(error.kind.exit_code());
+        }
+    };
+    if let Err(error) = expand_pattern(&mut args)
+        .and_then(|()| run(&args, &recorded_arguments(&rest, &args.pattern), None))
+    {
+        error.report(&args.errors);
+        std::process::exit(error.kind.exit_code());
+    }
+}
+
+impl Cli {
+    /// The pattern of the secrets, taking `--story` and `--mobile` into account.
+    fn pattern(&self) -> &str {
+        if self.story {
+            story::LONG
+        } else if self.mobile {
+            layout::MOBILE_PATTERN
+        } else {
+            &self.pattern
+        }
+    }
+
+    /// Whether to color the secrets: the escape sequences would corrupt files and pipes.
+    fn colored(&self) -> bool {
+        use std::io::IsTerminal;
+        self.color && self.output.is_none() && std::io::stdout().is_terminal()
+    }
+
+    /// The layout used to measure the typing effort.
+    fn effort_layout(&self) -> Layout {
+        match &self.layout {
+            _ if self.mobile => Layout::Mobile,
+            Some(LayoutArg(layout)) => *layout,
+            None => Layout::Us,
+        }
+    }
+
+    /// `out` with the line endings of `--newline` and `--no-final-newline`.
+    fn line_endings<W: Write>(&self, out: W) -> LineEndings<W> {
+        LineEndings::new(
+            out,
+            matches!(self.newline, Newline::Crlf()),
+            !self.no_final_newline,
+        )
+    }
+}
+
+/// Replaces the env and arg placeholders of the pattern, see `--arg`.
+fn expand_pattern(args: &mut Cli) -> Result<(), CliError> {
+    let mut values = Vec::with_capacity(args.arg.len());
+    for arg in &args.arg {
+        let (name, value) = arg.split_once('=').ok_or_else(|| {
+            CliError::new(
+                ErrorKind::Usage,
+                format!("invalid --arg {:?}, use name=value.", arg),
+            )
+        })?;
+        values.push((name, value));
+    }
+    let expanded = pattern::expand_variables(&args.pattern, |source, name| match source {
+        "env" => std::env::var(name).ok(),
+        _ => values
+            .iter()
+            .rev()
+            .find(|&&(n, _)| n == name)
+            .map(|(_, value)| value.to_string()),
+    })
+    .map_err(|e| CliError::new(ErrorKind::Usage, format!("{} in the pattern.", e)))?;
+    args.pattern = expanded;
+    Ok(())
+}
+
+/// The arguments of a run to record in its manifest for `regen`: the expanded pattern instead
+/// of `--pattern` and `--arg`, and without the seed or the destinations of the secrets.
+fn recorded_arguments(rest: &[&str], pattern: &str) -> Vec<String> {
+    const DROPPED_OPTIONS: &[&str] = &[
+        "-p",
+        "--pattern",
+        "--arg",
+        "-o",
+        "--output",
+        "--manifest",
+        "--history",
+        "--vault-path",
+        "--vault-kv",
+    ];
+    const DROPPED_SWITCHES: &[&str] = &["--append", "--confirm", "--seed"];
+    let mut arguments = Vec::new();
+    let mut rest = rest.iter();
+    while let Some(&arg) = rest.next() {
+        if DROPPED_OPTIONS.contains(&arg) {
+            rest.next();
+        } else if !DROPPED_SWITCHES.contains(&arg) {
+            arguments.push(arg.to_string());
+        }
+    }
+    arguments.push(String::from("--pattern"));
+    arguments.push(pattern.to_string());
+    arguments
+}
+
+/// Reads the secret seed of `--seed` and `regen` from `CRYPTICLI_SEED`, so that it does not
+/// appear in the shell history nor in the arguments of the process seen by other users.
+fn read_seed() -> Result<String, CliError> {
+    match std::env::var("CRYPTICLI_SEED") {
+        Ok(secret) if !secret.is_empty() => Ok(secret),
+        _ => Err(CliError::new(
+            ErrorKind::Usage,
+            "CRYPTICLI_SEED is not set, cannot seed the run.",
+        )),
+    }
+}
+
+/// Seeds `generator` from `secret` with a new salt, or with the seeding of the run being
+/// replayed, failing if `secret` is not its seed.
+fn seed_generator(
+    generator: &mut Generator,
+    secret: &str,
+    replayed: Option<&Seeding>,
+) -> Result<Seeding, CliError> {
+    let (salt, params) = match replayed {
+        Some(seeding) => {
+            let salt = encode::parse_hex(&seeding.salt).map_err(|e| {
+                CliError::new(ErrorKind::Usage, format!("invalid seeding salt: {}.", e))
+            })?;
+            let params = KdfParams {
+                iterations: seeding.iterations,
+            };
+            (salt, params)
+        }
+        None => (kdf::random_salt().to_vec(), KdfParams::RECOMMENDED),
+    };
+    let seed = SecretSeed::derive(secret, &salt, &params).map_err(|e| {
+        CliError::new(
+            ErrorKind::Usage,
+            format!("invalid seeding parameters: {}.", e),
+        )
+    })?;
+    let check = encode::format_hex(&seed.check());
+    if replayed.is_some_and(|seeding| !seeding.check.eq_ignore_ascii_case(&check)) {
+        return Err(CliError::new(
+            ErrorKind::Mismatch,
+            "the seed is not the one of the manifest.",
+        ));
+    }
+    generator.set_rng(seed.rng());
+    Ok(Seeding {
+        salt: encode::format_hex(&salt),
+        iterations: params.iterations,
+        check,
+    })
+}
+
+/// Runs the generation, with `arguments` recorded for `regen`; `replayed` is the manifest of
+/// the run which `regen` reproduces.
+fn run(args: &Cli, arguments: &[String], replayed: Option<&Manifest>) -> Result<(), CliError> {
+    match &args.command {
+        Some(Command::Analyze(cmd)) => return run_analyze(cmd),
+        Some(Command::WordlistAudit(cmd)) => return run_wordlist_audit(cmd),
+        Some(Command::Challenge(cmd)) => return run_challenge(cmd, args.num),
+        Some(Command::Encode(cmd)) => return run_encode(cmd),
+        Some(Command::Decode(cmd)) => return run_decode(cmd),
+        Some(Command::Compare(cmd)) => return run_compare(cmd),
+        #[cfg(feature = "history")]
+        Some(Command::HistoryCheck(cmd)) => return run_history_check(cmd),
+        Some(Command::Regen(cmd)) => return run_regen(cmd),
+        _ => (),
+    }
+    if args.symbols.as_deref() == Some("") || args.digits.as_deref() == Some("") {
+        return Err(CliError::new(
+            ErrorKind::Usage,
+            "--symbols and --digits cannot be empty.",
+        ));
+    }
+    let mut list = match args.style {
+        WordList::English() => eff::list(),
+        WordList::Italian() => italian::list(),
+        WordList::Russian() => russian::list(),
+        WordList::CV() => cv::list(),
+    };
+    if args.prefix_free {
+        let (cleaned, dropped) = remove_prefixes(&list);
+        eprintln!("prefix-free word list: dropped {} words.", dropped);
+        list = cleaned;
+    }
+    if args.no_homophones {
+        let (cleaned, dropped) = filter_homophones(&list);
+        eprintln!("homophone-free word list: dropped {} words.", dropped);
+        list = cleaned;
+    }
+    let mut generator = Generator::new_custom(list, args.depth).unwrap();
+    if let Some(min_count) = args.min_count {
+        let stats = generator
+            .prune(min_count)
+            .map_err(|e| CliError::new(ErrorKind::Usage, format!("--min-count: {}.", e)))?;
+        eprintln!(
+            "pruned model: {} -> {} states, {} -> {} transitions.",
+            stats.before.states,
+            stats.after.states,
+            stats.before.transitions,
+            stats.after.transitions
+        );
+    }
+    generator.set_language(match args.style {
+        WordList::English() => "en",
+        WordList::Italian() => "it",
+        WordList::Russian() => "ru",
+        WordList::CV() => "cv",
+    });
+    configure(&mut generator, args)?;
+    if let Some(manifest) = replayed {
+        if generator.model_digest() != manifest.model_digest {
+            return Err(CliError::new(
+                ErrorKind::Mismatch,
+                "the model differs from the one of the manifest, the secrets cannot be reproduced.",
+            ));
+        }
+    }
+    let seeding = if args.seed {
+        let seeded_elsewhere = args.mix_languages || args.demo || args.watch.is_some();
+        #[cfg(feature = "vault")]
+        let seeded_elsewhere = seeded_elsewhere || args.vault_path.is_some();
+        if seeded_elsewhere || (args.manifest.is_none() && replayed.is_none()) {
+            return Err(CliError::new(
+                ErrorKind::Usage,
+                "--seed requires --manifest, and cannot be combined with --mix-languages, \
+                 --demo, --watch or --vault-path.",
+            ));
+        }
+        let replayed = replayed.and_then(|manifest| manifest.seeding.as_ref());
+        Some(seed_generator(&mut generator, &read_seed()?, replayed)?)
+    } else {
+        None
+    };
+    match &args.command {
+        Some(Command::Template(cmd)) => return run_template(&mut generator, cmd),
+        Some(Command::Batch(cmd)) => return run_batch(&mut generator, cmd),
+        Some(Command::Plan(cmd)) => return run_plan(&generator, cmd),
+        Some(Command::ModelExport(cmd)) => return run_model_export(&generator, cmd),
+        Some(Command::TrainMemory(cmd)) => return run_train_memory(&mut generator, args, cmd),
+        _ => (),
+    }
+    #[cfg(feature = "vault")]
+    if let Some(path) = &args.vault_path {
+        let mut fields = Vec::new();
+        for i in 0..args.num {
+            generator.set_sequence(i as u64);
+            let (passphrase, _) = generate(&mut generator, args)?;
+            if args.confirm {
+                confirm_secret(&passphrase)?;
+            }
+            let key = if args.num == 1 {
+                String::from("secret")
+            } else {
+                format!("secret_{}", i + 1)
+            };
+            fields.push((key, passphrase));
+        }
+        crate::vault::write_secret(path, &fields, args.vault_kv)
+            .map_err(|e| CliError::new(ErrorKind::Remote, e))?;
+        println!("{}", path);
+        return Ok(());
+    }
+    let mut registry = if args.mix_languages {
+        let mut registry = Registry::bundled(args.depth);
+        for generator in registry.generators_mut() {
+            configure(generator, args)?;
+        }
+        Some(registry)
+    } else {
+        None
+    };
+    let pattern = Pattern::parse(args.pattern());
+    if let Some(bits) = pattern
+        .highest_floor()
+        .filter(|&bits| bits > pattern::MAX_ELEMENT_FLOOR)
+    {
+        return Err(CliError::new(
+            ErrorKind::Usage,
+            format!(
+                "The pattern has an entropy floor of {} bits, the limit of a single element is {}.",
+                bits,
+                pattern::MAX_ELEMENT_FLOOR
+            ),
+        ));
+    }
+    if let Some((min, max, largest)) = pattern.numeral_out_of_range() {
+        return Err(CliError::new(
+            ErrorKind::Usage,
+            format!(
+                "The pattern asks for the numbers {} to {}, the supported ones are 1 to {}.",
+                min, max, largest
+            ),
+        ));
+    }
+    let mut manifest = args.manifest.as_ref().map(|_| {
+        let mut manifest = Manifest::new(&generator, args.pattern(), args.num);
+        manifest.arguments = arguments.to_vec();
+        manifest.seeding = seeding.clone();
+        let settings = [
+            ("min-entropy", args.min_entropy.map(|bits| bits.to_string())),
+            ("min-distance", args.min_distance.map(|d| d.to_string())),
+            ("best-of", args.best_of.map(|n| n.to_string())),
+        ];
+        for (name, value) in settings {
+            if let Some(value) = value {
+                manifest.add_policy(name, &value);
+            }
+        }
+        manifest
+    });
+    if args.ascii {
+        let mut generators = registry.iter_mut().flat_map(|r| r.generators_mut().iter());
+        if !generator.transliteration_is_injective(&pattern)
+            || generators.any(|g| !g.transliteration_is_injective(&pattern))
+        {
+            eprintln!(
+                "warning: the ASCII transliteration can merge distinct secrets, the reported \
+                 entropy is an upper bound."
+            );
+        }
+    }
+    if let (Some(floor), None) = (args.min_entropy, registry.as_ref()) {
+        let penalty = (args.best_of.unwrap_or(1).max(1) as f64).log2();
+        let max = generator.max_achievable_entropy(args.pattern());
+        if let Some(max) = max.map(|max| max - penalty).filter(|&max| max < floor) {
+            return Err(CliError::new(
+                ErrorKind::EntropyFloor,
+                format!(
+                    "the pattern yields at most {:.2} bits of entropy with this word list, below \
+                     the minimum of {:.2} bits.",
+                    max.max(0.0),
+                    floor
+                ),
+            ));
+        }
+        // every secret must meet the floor, not just the lucky draws
+        let min = generator.min_achievable_entropy(args.pattern());
+        if let Some(min) = min.map(|min| min - penalty).filter(|&min| min < floor) {
+            return Err(CliError::new(
+                ErrorKind::EntropyFloor,
+                format!(
+                    "the pattern can yield secrets with as little as {:.2} bits of entropy with \
+                     this word list, below the minimum of {:.2} bits.",
+                    min.max(0.0),
+                    floor
+                ),
+            ));
+        }
+    }
+    // candidates of the same secret share its position for {seq} elements
+    let mut candidate = |position: u64| match registry.as_mut() {
+        Some(registry) => {
+            for generator in registry.generators_mut() {
+                generator.set_sequence(position);
+            }
+            registry.gen_annotated(&pattern)
+        }
+        None => {
+            generator.set_sequence(position);
+            generator.gen_annotated(&pattern)
+        }
+    };
+    let candidates = args.best_of.unwrap_or(1).max(1);
+    let layout = args.effort_layout();
+    let mut generate = |position: u64| {
+        let mut best = candidate(position);
+        for _ in 1..candidates {
+            let other = candidate(position);
+            if analysis::typing_effort(&other.text, layout)
+                < analysis::typing_effort(&best.text, layout)
+            {
+                best = other;
+            }
+        }
+        best.entropy = (best.entropy - (candidates as f64).log2()).max(0.0);
+        if let Some(NormalizeArg(form)) = args.normalize {
+            best.normalize(form);
+        }
+        best
+    };
+    let mut seen = match &args.seen {
+        Some(_) if args.watch.is_some() => {
+            return Err(CliError::new(
+                ErrorKind::Usage,
+                "--watch never ends, it cannot be combined with --seen.",
+            ))
+        }
+        Some(path) => Some(crate::seen::open(path)?),
+        None => None,
+    };
+    let mut accepted: Vec<String> = Vec::new();
+    let mut distinct = |position: u64| {
+        if args.min_distance.is_none() && seen.is_none() {
+            return Ok(generate(position));
+        }
+        for _ in 0..batch::MAX_REJECTIONS {
+            let secret = generate(position);
+            if let Some(min_distance) = args.min_distance {
+                if !accepted
+                    .iter()
+                    .all(|other| batch::levenshtein(&secret.text, other) >= min_distance)
+                {
+                    continue;
+                }
+            }
+            if let Some(seen) = seen.as_mut() {
+                if !seen.insert(&secret.text) {
+                    continue;
+                }
+            }
+            if args.min_distance.is_some() {
+                accepted.push(secret.text.clone());
+            }
+            return Ok(secret);
+        }
+        Err(CliError::from(match args.min_distance {
+            Some(min_distance) => cryptirust::Error::BatchExhausted {
+                generated: accepted.len(),
+                requested: args.num,
+                min_distance,
+            },
+            None => cryptirust::Error::SecretsExhausted {
+                attempts: batch::MAX_REJECTIONS,
+            },
+        }))
+    };
+    #[cfg(feature = "history")]
+    let mut history = match &args.history {
+        Some(_) if args.watch.is_some() => {
+            return Err(CliError::new(
+                ErrorKind::Usage,
+                "--watch never ends, it cannot be combined with --history.",
+            ))
+        }
+        Some(path) => Some(crate::history::open(path)?),
+        None => None,
+    };
+    let mut position = 0;
+    let mut next = || {
+        let secret = distinct(position)?;
+        position += 1;
+        if let Some(manifest) = manifest.as_mut() {
+            manifest.record(secret.entropy);
+        }
+        #[cfg(feature = "history")]
+        if let Some(history) = history.as_mut() {
+            history.record(&secret.text);
+        }
+        if args.confirm {
+            confirm_secret(&secret.text)?;
+        }
+        Ok(secret)
+    };
+    interrupt::install();
+    if args.append && args.output.is_none() {
+        return Err(CliError::new(
+            ErrorKind::Usage,
+            "--append requires --output.",
+        ));
+    }
+    if args.sorted && args.sort.is_some() {
+        return Err(CliError::new(
+            ErrorKind::Usage,
+            "--sorted and --sort are exclusive, choose one order.",
+        ));
+    }
+    if args.temp_dir.is_some() && !args.sorted {
+        return Err(CliError::new(
+            ErrorKind::Usage,
+            "--temp-dir requires --sorted.",
+        ));
+    }
+    if let Some(Interval(interval)) = args.watch {
+        if args.output.is_some() || args.confirm || args.manifest.is_some() {
+            return Err(CliError::new(
+                ErrorKind::Usage,
+                "--watch prints to the terminal, it cannot be combined with --output, --confirm \
+                 or --manifest.",
+            ));
+        }
+        return watch(&mut next, args, interval);
+    }
+    let result = match &args.output {
+        Some(path) if args.append => {
+            let file = append_private(Path::new(path)).map_err(|e| {
+                CliError::new(ErrorKind::Io, format!("cannot open {}: {}", path, e))
+            })?;
+            write_secrets(&mut next, args, &mut args.line_endings(file))
+        }
+        Some(path) => {
+            let mut file = AtomicFile::create(Path::new(path)).map_err(|e| {
+                CliError::new(ErrorKind::Io, format!("cannot create {}: {}", path, e))
+            })?;
+            let result = write_secrets(&mut next, args, &mut args.line_endings(&mut file));
+            if result.is_ok() || matches!(&result, Err(e) if e.kind == ErrorKind::Interrupted) {
+                file.commit().map_err(|e| {
+                    CliError::new(ErrorKind::Io, format!("cannot write {}: {}", path, e))
+                })?;
+            }
+            result
+        }
+        None => write_secrets(
+            &mut next,
+            args,
+            &mut args.line_endings(BufWriter::new(std::io::stdout())),
+        ),
+    };
+    // the manifest also records runs cut short by an error or Ctrl-C
+    if let (Some(path), Some(manifest)) = (&args.manifest, manifest) {
+        std::fs::write(path, manifest.to_json() + "\n")
+            .map_err(|e| CliError::new(ErrorKind::Io, format!("cannot write {}: {}", path, e)))?;
+    }
+    // and so do the history and the filter, with every secret which may have been written
+    #[cfg(feature = "history")]
+    if let (Some(path), Some(history)) = (&args.history, history) {
+        crate::history::save(path, &history)?;
+    }
+    if let (Some(path), Some(seen)) = (&args.seen, seen) {
+        crate::seen::save(path, &seen)?;
+    }
+    result
+}
+
+/// Prints a new batch of secrets every `interval` until Ctrl-C, which ends the command
+/// successfully.
+fn watch(
+    next: &mut dyn FnMut() -> Result<Annotated, CliError>,
+    args: &Cli,
+    interval: Duration,
+) -> Result<(), CliError> {
+    const TICK: Duration = Duration::from_millis(100);
+    let mut out = args.line_endings(BufWriter::new(std::io::stdout()));
+    loop {
+        match write_secrets(next, args, &mut out) {
+            Err(e) if e.kind == ErrorKind::Interrupted => return Ok(()),
+            result => result?,
+        }
+        writeln!(out)
+            .and_then(|_| out.flush())
+            .map_err(|e| CliError::new(ErrorKind::Io, format!("cannot write output: {}", e)))?;
+        let started = Instant::now();
+        while started.elapsed() < interval {
+            if interrupt::interrupted() {
+                return Ok(());
+            }
+            std::thread::sleep(TICK.min(interval.saturating_sub(started.elapsed())));
+        }
+    }
+}
+
+/// Applies the generation options shared by every generator.
+fn configure(generator: &mut Generator, args: &Cli) -> Result<(), CliError> {
+    if args.exact_entropy {
+        generator.set_exact_entropy(true);
+    }
+    if args.natural_lengths {
+        generator.set_empirical_word_lengths(true);
+    }
+    if args.date_safe {
+        generator.set_date_safe_digits(true);
+    }
+    if let Some(size) = args.group_digits {
+        generator.set_digit_grouping(Some(DigitGrouping::new(size, '-')));
+    }
+    if let Some(LayoutArg(layout)) = args.layout {
+        generator.set_layout(layout);
+    }
+    if args.mobile {
+        generator.set_layout(Layout::Mobile);
+    }
+    if let Some(symbols) = &args.symbols {
+        generator.set_symbols(symbols)?;
+    }
+    if let Some(digits) = &args.digits {
+        generator.set_digits(digits)?;
+    }
+    generator.set_theme(match args.theme {
+        ThemeArg::Plain() => Theme::Plain,
+        ThemeArg::Alliteration() => Theme::Alliteration,
+        ThemeArg::Rhyme() => Theme::Rhyme,
+    });
+    if args.demo {
+        generator.set_demo_mode(true);
+    }
+    if let Some(floor) = args.min_entropy {
+        generator.set_entropy_floor(floor);
+    }
+    Ok(())
+}
+
+/// Generates the secrets and writes them to `out` in the requested format, stopping cleanly
+/// on errors and on Ctrl-C after flushing what was generated so far.
+fn write_secrets(
+    next: &mut dyn FnMut() -> Result<Annotated, CliError>,
+    args: &Cli,
+    out: &mut dyn Write,
+) -> Result<(), CliError> {
+    let io_error =
+        |e: std::io::Error| CliError::new(ErrorKind::Io, format!("cannot write output: {}", e));
+    let table = matches!(args.format, Format::Table());
+    let colored = args.colored();
+    if table {
+        let languages = if args.annotate { "    languages" } else { "" };
+        writeln!(
+            out,
+            "{:10}    {:15}    secret{}",
+            "        n.", " log2(guesses)", languages
+        )
+        .map_err(io_error)?;
+    }
+    let mut fields = Vec::new();
+    let mut bar = ProgressBar::new(args.num, 10_000);
+    let mut error = None;
+    let interrupted = |i: usize| {
+        CliError::new(
+            ErrorKind::Interrupted,
+            format!("interrupted, {} of {} secrets written", i, args.num),
+        )
+    };
+    let mut emit = |i: usize, secret: Annotated| {
+        let languages = secret.languages().join(",");
+        let mnemonic = args.mnemonic.then(|| {
+            let words: Vec<&str> = secret
+                .words
+                .iter()
+                .map(|word| &secret.text[word.range.clone()])
+                .collect();
+            story::mnemonic_sentence(&words).sentence
+        });
+        if args.min_entropy.is_some_and(|floor| secret.entropy < floor) {
+            Err(CliError::from(cryptirust::Error::WeakSecret {
+                entropy: secret.entropy,
+                floor: args.min_entropy.unwrap_or_default(),
+            }))
+        } else if table {
+            let languages = if args.annotate {
+                format!("    {}", languages)
+            } else {
+                String::new()
+            };
+            let text = if args.ascii {
+                format!(
+                    "{}    ({})",
+                    translit::transliterate(&secret.text),
+                    secret.text
+                )
+            } else if colored {
+                colorize(&secret)
+            } else {
+                secret.text
+            };
+            writeln!(
+                out,
+                "{:10}    {:15.2}    {}{}",
+                i + 1,
+                (secret.entropy - 1.0).max(0.0),
+                text,
+                languages
+            )
+            .map_err(io_error)?;
+            match mnemonic {
+                Some(sentence) => writeln!(out, "{:33}({})", "", sentence).map_err(io_error),
+                None => Ok(()),
+            }
+        } else {
+            let suffix = if args.num == 1 {
+                String::new()
+            } else {
+                format!("_{}", i + 1)
+            };
+            if args.ascii {
+                let ascii = translit::transliterate(&secret.text);
+                fields.push((format!("secret{}", suffix), ascii));
+                fields.push((format!("original{}", suffix), secret.text));
+            } else {
+                fields.push((format!("secret{}", suffix), secret.text));
+            }
+            fields.push((
+                format!("entropy{}", suffix),
+                format!("{:.2}", secret.entropy),
+            ));
+            if args.annotate {
+                fields.push((format!("languages{}", suffix), languages));
+            }
+            if let Some(sentence) = mnemonic {
+                fields.push((format!("mnemonic{}", suffix), sentence));
+            }
+            Ok(())
+        }
+    };
+    match args.sort {
+        Some(SortKey::Effort()) => {
+            // the whole batch is needed before sorting, Ctrl-C writes what was generated
+            let mut secrets = Vec::with_capacity(args.num);
+            for done in 1..=args.num {
+                bar.update(done, args.num);
+                if interrupt::interrupted() {
+                    error = Some(interrupted(done - 1));
+                    break;
+                }
+                match next() {
+                    Ok(secret) => secrets.push(secret),
+                    Err(e) => {
+                        error = Some(e);
+                        break;
+                    }
+                }
+            }
+            let layout = args.effort_layout();
+            secrets.sort_by_cached_key(|secret| analysis::typing_effort(&secret.text, layout));
+            for (i, secret) in secrets.into_iter().enumerate() {
+                if let Err(e) = emit(i, secret) {
+                    error = Some(e);
+                    break;
+                }
+            }
+        }
+        None if args.sorted => {
+            let temp_dir = args
+                .temp_dir
+                .as_ref()
+                .map_or_else(std::env::temp_dir, PathBuf::from);
+            let sort_error = |e: std::io::Error| {
+                CliError::new(
+                    ErrorKind::Io,
+                    format!("cannot sort in {}: {}", temp_dir.display(), e),
+                )
+            };
+            // as with --sort, Ctrl-C writes what was generated, sorted
+            let mut sort = ExternalSort::new(&temp_dir, CHUNK_SECRETS);
+            for done in 1..=args.num {
+                bar.update(done, args.num);
+                if interrupt::interrupted() {
+                    error = Some(interrupted(done - 1));
+                    break;
+                }
+                let result = next().and_then(|secret| sort.push(secret).map_err(sort_error));
+                if let Err(e) = result {
+                    error = Some(e);
+                    break;
+                }
+            }
+            match sort.finish() {
+                Ok(sorted) => {
+                    for (i, secret) in sorted.enumerate() {
+                        let result = secret.map_err(sort_error).and_then(|s| emit(i, s));
+                        if let Err(e) = result {
+                            error = Some(e);
+                            break;
+                        }
+                    }
+                }
+                Err(e) => error = Some(sort_error(e)),
+            }
+        }
+        None => {
+            for done in 1..=args.num {
+                let i = done - 1;
+                let result = if interrupt::interrupted() {
+                    Err(interrupted(i))
+                } else {
+                    next().and_then(|secret| emit(i, secret))
+                };
+                bar.update(done, args.num);
+                if let Err(e) = result {
+                    error = Some(e);
+                    break;
+                }
+            }
+        }
+    }
+    bar.finish();
+    if !table {
+        writeln!(out, "{}", json_object(&fields)).map_err(io_error)?;
+    }
+    out.flush().map_err(io_error)?;
+    error.map_or(Ok(()), Err)
+}
+
+/// The secret with the output of each pattern element colored by its entropy per character,
+/// see `--color`; literal text is left uncolored.
+fn colorize(secret: &Annotated) -> String {
+    const RESET: &str = "\x1b[0m";
+    let mut colored = String::new();
+    let mut last = 0;
+    for span in &secret.spans {
+        colored.push_str(&secret.text[last..span.range.start]);
+        let text = &secret.text[span.range.clone()];
+        let density = span.entropy / text.chars().count() as f64;
+        let color = match density {
+            _ if span.entropy == 0.0 => None,
+            d if d >= 3.0 => Some("\x1b[32m"),
+            d if d >= 2.0 => Some("\x1b[33m"),
+            _ => Some("\x1b[31m"),
+        };
+        match color {
+            Some(color) => colored.push_str(&format!("{}{}{}", color, text, RESET)),
+            None => colored.push_str(text),
+        }
+        last = span.range.end;
+    }
+    colored.push_str(&secret.text[last..]);
+    colored
+}
+
+/// Shows `secret` on standard error and has the user retype it, for `--confirm`.
+fn confirm_secret(secret: &str) -> Result<(), CliError> {
+    let mut prompt = std::io::stderr();
+    let io_error =
+        |e: std::io::Error| CliError::new(ErrorKind::Io, format!("cannot confirm: {}", e));
+    writeln!(prompt, "secret: {}", secret).map_err(io_error)?;
+    let stdin = &mut std::io::stdin().lock();
+    if confirm::verify_typed(secret, stdin, &mut prompt, confirm::DEFAULT_ATTEMPTS)
+        .map_err(io_error)?
+    {
+        Ok(())
+    } else {
+        Err(CliError::new(
+            ErrorKind::Mismatch,
+            "the secret was not retyped correctly.",
+        ))
+    }
+}
+
+/// Generates one secret from the pattern, honouring `--min-entropy` and `--normalize`.
+fn generate(generator: &mut Generator, args: &Cli) -> Result<(String, f64), CliError> {
+    let (passphrase, entropy) = if args.min_entropy.is_some() {
+        generator.try_gen_from_pattern(args.pattern())?
+    } else {
+        generator.gen_from_pattern(args.pattern())
+    };
+    match args.normalize {
+        Some(NormalizeArg(form)) => Ok((normalize::normalize(&passphrase, form), entropy)),
+        None => Ok((passphrase, entropy)),
+    }
+}
+
+fn run_template(generator: &mut Generator, cmd: &TemplateCmd) -> Result<(), CliError> {
+    let source = std::fs::read_to_string(&cmd.file)
+        .map_err(|e| CliError::new(ErrorKind::Io, format!("cannot read {}: {}", cmd.file, e)))?;
+    let rendered = template::render(generator, &source)
+        .map_err(|e| CliError::new(ErrorKind::Usage, format!("{}: {}.", cmd.file, e)))?;
+    match &cmd.output {
+        Some(path) => {
+            let io_error = |e: std::io::Error| {
+                CliError::new(ErrorKind::Io, format!("cannot write {}: {}", path, e))
+            };
+            let mut file = AtomicFile::create(Path::new(path)).map_err(io_error)?;
+            file.write_all(rendered.text.as_bytes()).map_err(io_error)?;
+            file.commit().map_err(io_error)?;
+        }
+        None => print!("{}", rendered.text),
+    }
+    Ok(())
+}
+
+fn run_batch(generator: &mut Generator, cmd: &BatchCmd) -> Result<(), CliError> {
+    let source = std::fs::read_to_string(&cmd.file)
+        .map_err(|e| CliError::new(ErrorKind::Io, format!("cannot read {}: {}", cmd.file, e)))?;
+    let in_row = |row: usize, e: CliError| {
+        CliError::new(e.kind, format!("{} row {}: {}", cmd.file, row, e.message))
+    };
+    let invalid =
+        |row: usize, message: String| in_row(row, CliError::new(ErrorKind::Usage, message));
+    let records = csv::parse(&source).map_err(|e| invalid(1, e))?;
+    let Some((header, jobs)) = records.split_first() else {
+        return Err(invalid(1, "missing header".to_string()));
+    };
+    let column = |name: &str| header.iter().position(|h| h.trim() == name);
+    let (Some(label), Some(pattern), Some(count)) =
+        (column("label"), column("pattern"), column("count"))
+    else {
+        return Err(invalid(
+            1,
+            "the header must name the label, pattern and count columns".to_string(),
+        ));
+    };
+    let policy = column("policy");
+    let mut rows = vec![csv_row(
+        &[header.as_slice(), &["secret".into(), "entropy".into()]].concat(),
+    )];
+    for (i, job) in jobs.iter().enumerate() {
+        let row = i + 2;
+        let field = |c: usize| job.get(c).map_or("", |f| f.trim());
+        if field(label).is_empty() {
+            return Err(invalid(row, "missing label".to_string()));
+        }
+        let count: usize = field(count)
+            .parse()
+            .map_err(|_| invalid(row, format!("invalid count {:?}", field(count))))?;
+        let policy = Policy::parse(policy.map_or("", field)).map_err(|e| invalid(row, e))?;
+        // refuse the row before generating if some of its secrets could miss the floor
+        let min = generator.min_achievable_entropy(field(pattern));
+        if let Some((min, floor)) = min
+            .zip(policy.min_entropy)
+            .filter(|(min, floor)| min < floor)
+        {
+            let e = CliError::new(
+                ErrorKind::EntropyFloor,
+                format!(
+                    "the pattern can yield secrets with as little as {:.2} bits of entropy with \
+                     this word list, below the minimum of {:.2} bits.",
+                    min.max(0.0),
+                    floor
+                ),
+            );
+            return Err(in_row(row, e));
+        }
+        let secrets = match policy.min_distance {
+            Some(min_distance) => generator
+                .gen_batch_distinct(field(pattern), count, min_distance)
+                .map_err(|e| in_row(row, e.into()))?,
+            None => generator.gen_batch(field(pattern), count),
+        };
+        for (secret, entropy) in secrets {
+            if let Some(floor) = policy.min_entropy.filter(|&floor| entropy < floor) {
+                let e = cryptirust::Error::WeakSecret { entropy, floor };
+                return Err(in_row(row, e.into()));
+            }
+            let mut fields: Vec<String> = (0..header.len()).map(|c| field(c).to_string()).collect();
+            fields.push(secret);
+            fields.push(format!("{:.2}", entropy));
+            rows.push(csv_row(&fields));
+        }
+    }
+    let csv = rows.join("\n") + "\n";
+    match &cmd.output {
+        Some(path) => {
+            let io_error = |e: std::io::Error| {
+                CliError::new(ErrorKind::Io, format!("cannot write {}: {}", path, e))
+            };
+            let mut file = AtomicFile::create(Path::new(path)).map_err(io_error)?;
+            file.write_all(csv.as_bytes()).map_err(io_error)?;
+            file.commit().map_err(io_error)?;
+        }
+        None => print!("{}", csv),
+    }
+    Ok(())
+}
+
+fn run_plan(generator: &Generator, cmd: &PlanCmd) -> Result<(), CliError> {
+    let constraints = PlanConstraints {
+        max_length: cmd.max_length,
+        digit: cmd.digit,
+        symbol: cmd.symbol,
+        uppercase: cmd.uppercase,
+    };
+    let pattern = generator
+        .plan_pattern(cmd.bits, &constraints)
+        .ok_or_else(|| {
+            CliError::new(
+                ErrorKind::Usage,
+                "no pattern meets the target with these constraints.",
+            )
+        })?;
+    let expected = Pattern::parse(&pattern)
+        .expected_entropy(generator)
+        .unwrap_or_default();
+    println!("{}", pattern);
+    eprintln!("expected entropy: {:.2} bits.", expected);
+    Ok(())
+}
+
+fn run_challenge(cmd: &ChallengeCmd, num: usize) -> Result<(), CliError> {
+    if let Some(expected) = &cmd.verify {
+        let heard = std::io::read_to_string(std::io::stdin()).map_err(|e| {
+            CliError::new(ErrorKind::Io, format!("cannot read the transcript: {}", e))
+        })?;
+        if !voice::verify_challenge(expected, &heard) {
+            return Err(CliError::new(
+                ErrorKind::Mismatch,
+                "the transcript does not match the challenge phrase.",
+            ));
+        }
+        println!("match");
+        return Ok(());
+    }
+    let mut generator = Generator::new();
+    for _ in 0..num {
+        let (phrase, _) = generator.gen_challenge(cmd.words);
+        println!("{}", phrase);
+    }
+    Ok(())
+}
+
+fn run_encode(cmd: &EncodeCmd) -> Result<(), CliError> {
+    let usage = |e: encode::DecodeError| CliError::new(ErrorKind::Usage, format!("{}.", e));
+    let encoded = match cmd.format {
+        Encoding::Alias() => encode::encode_uuid(encode::parse_uuid(&cmd.input).map_err(usage)?),
+        Encoding::Proquint() => encode::encode_proquint(
+            &encode::parse_hex(&cmd.input).map_err(usage)?,
+        )
+        .ok_or_else(|| {
+            CliError::new(
+                ErrorKind::Usage,
+                "proquints encode an even number of bytes.",
+            )
+        })?,
+        Encoding::BubbleBabble() => {
+            encode::encode_bubble_babble(&encode::parse_hex(&cmd.input).map_err(usage)?)
+        }
+        Encoding::Words() => {
+            let bytes = encode::parse_hex(&cmd.input).map_err(usage)?;
+            eprintln!(
+                "{} words carry {:.1} bits of the fingerprint.",
+                cmd.words,
+                encode::humanized_entropy(cmd.words)
+            );
+            encode::humanize_digest(&bytes, cmd.words)
+        }
+    };
+    println!("{}", encoded);
+    Ok(())
+}
+
+fn run_decode(cmd: &DecodeCmd) -> Result<(), CliError> {
+    let decoded = match cmd.format {
+        Encoding::Alias() => encode::decode(&cmd.input).map(encode::format_uuid),
+        Encoding::Proquint() => encode::decode_proquint(&cmd.input).map(|b| encode::format_hex(&b)),
+        Encoding::BubbleBabble() => {
+            encode::decode_bubble_babble(&cmd.input).map(|b| encode::format_hex(&b))
+        }
+        Encoding::Words() => {
+            return Err(CliError::new(
+                ErrorKind::Usage,
+                "the words encoding cannot be decoded, encode the fingerprint and compare.",
+            ))
+        }
+    }
+    .map_err(|e| CliError::new(ErrorKind::Usage, format!("{}.", e)))?;
+    println!("{}", decoded);
+    Ok(())
+}
+
+fn run_regen(cmd: &RegenCmd) -> Result<(), CliError> {
+    let text = std::fs::read_to_string(&cmd.manifest).map_err(|e| {
+        CliError::new(
+            ErrorKind::Io,
+            format!("cannot read {}: {}", cmd.manifest, e),
+        )
+    })?;
+    let manifest = Manifest::from_json(&text)
+        .map_err(|e| CliError::new(ErrorKind::Usage, format!("{}: {}.", cmd.manifest, e)))?;
+    if manifest.seeding.is_none() {
+        return Err(CliError::new(
+            ErrorKind::Usage,
+            "the run was not made with --seed, it cannot be reproduced.",
+        ));
+    }
+    if manifest.algorithm_version != ALGORITHM_VERSION {
+        return Err(CliError::new(
+            ErrorKind::Mismatch,
+            format!(
+                "the run used version {} of the generation algorithm, this is version {}.",
+                manifest.algorithm_version, ALGORITHM_VERSION
+            ),
+        ));
+    }
+    let arguments: Vec<&str> = manifest.arguments.iter().map(String::as_str).collect();
+    let mut args = Cli::from_args(&["crypticli"], &arguments).map_err(|e| {
+        CliError::new(
+            ErrorKind::Usage,
+            format!("invalid arguments in the manifest: {}", e.output.trim_end()),
+        )
+    })?;
+    if args.command.is_some() {
+        return Err(CliError::new(
+            ErrorKind::Usage,
+            "invalid arguments in the manifest: unexpected subcommand.",
+        ));
+    }
+    args.seed = true;
+    run(&args, &manifest.arguments, Some(&manifest))
+}
+
+#[cfg(feature = "history")]
+fn run_history_check(cmd: &HistoryCheckCmd) -> Result<(), CliError> {
+    let history = crate::history::open(&cmd.file)?;
+    let mut secret = String::new();
+    std::io::stdin()
+        .lock()
+        .read_line(&mut secret)
+        .map_err(|e| CliError::new(ErrorKind::Io, format!("cannot read the secret: {}", e)))?;
+    let secret = secret.trim_end_matches(['\r', '\n']);
+    if history.contains(secret) {
+        println!("found");
+        Ok(())
+    } else {
+        Err(CliError::new(
+            ErrorKind::Mismatch,
+            format!(
+                "the secret is not among the {} in the history.",
+                history.len()
+            ),
+        ))
+    }
+}
+
+fn run_compare(cmd: &CompareCmd) -> Result<(), CliError> {
+    let diff = encode::compare_phrases(&cmd.first, &cmd.second);
+    let differing = diff.iter().filter(|w| w.differs()).count();
+    if differing == 0 {
+        println!("match");
+        return Ok(());
+    }
+    let width = diff
+        .iter()
+        .map(|w| match w {
+            WordDiff::Same(first)
+            | WordDiff::Changed { first, .. }
+            | WordDiff::OnlyFirst(first) => first.chars().count(),
+            WordDiff::OnlySecond(_) => 0,
+        })
+        .max()
+        .unwrap_or_default();
+    for word in &diff {
+        let (mark, first, second) = match word {
+            WordDiff::Same(word) => (' ', word.as_str(), word.as_str()),
+            WordDiff::Changed { first, second } => ('!', first.as_str(), second.as_str()),
+            WordDiff::OnlyFirst(first) => ('-', first.as_str(), ""),
+            WordDiff::OnlySecond(second) => ('+', "", second.as_str()),
+        };
+        let line = format!("{} {:<width$}  {}", mark, first, second, width = width);
+        println!("{}", line.trim_end());
+    }
+    Err(CliError::new(
+        ErrorKind::Mismatch,
+        format!(
+            "the phrases differ in {} of {} words.",
+            differing,
+            diff.len()
+        ),
+    ))
+}
+
+fn run_model_export(generator: &Generator, cmd: &ModelExportCmd) -> Result<(), CliError> {
+    let exported = match cmd.format {
+        ModelFormat::Dot() => generator.model_dot().into_bytes(),
+        ModelFormat::Json() => generator.model_json().into_bytes(),
+        ModelFormat::Binary() => generator.to_model_bytes(),
+    };
+    match &cmd.output {
+        Some(path) => std::fs::write(path, exported)
+            .map_err(|e| CliError::new(ErrorKind::Io, format!("cannot write {}: {}", path, e))),
+        None => {
+            let mut out = std::io::stdout().lock();
+            out.write_all(&exported)
+                .and_then(|()| out.flush())
+                .map_err(|e| CliError::new(ErrorKind::Io, format!("cannot export: {}", e)))
+        }
+    }
+}
+
+fn run_train_memory(
+    generator: &mut Generator,
+    args: &Cli,
+    cmd: &TrainMemoryCmd,
+) -> Result<(), CliError> {
+    use std::io::IsTerminal;
+    let io_error = |e: std::io::Error| CliError::new(ErrorKind::Io, format!("cannot train: {}", e));
+    let (passphrase, entropy) = generate(generator, args)?;
+    println!(
+        "memorize this passphrase ({:.2} bits):\n\n    {}\n",
+        entropy, passphrase
+    );
+    let warm_up = [Mask::None, Mask::Alternate, Mask::Initials];
+    let recall = std::iter::repeat_n(Mask::Full, cmd.rounds);
+    let mut input = std::io::stdin().lock();
+    let mut mistakes = 0;
+    for (round, mask) in warm_up.into_iter().chain(recall).enumerate() {
+        if round >= warm_up.len() {
+            let pause = cmd
+                .pause
+                .saturating_mul(1 << (round - warm_up.len()).min(16));
+            println!("next round in {}s...", pause);
+            std::thread::sleep(Duration::from_secs(pause));
+            if std::io::stdout().is_terminal() {
+                // clear the screen, so that the passphrase must come from memory.
+                print!("\x1b[2J\x1b[H");
+            }
+        }
+        loop {
+            print!(
+                "round {}, type it: {}\n> ",
+                round + 1,
+                mask.apply(&passphrase)
+            );
+            std::io::stdout().flush().map_err(io_error)?;
+            let mut line = String::new();
+            if input.read_line(&mut line).map_err(io_error)? == 0 {
+                return Err(CliError::new(
+                    ErrorKind::Interrupted,
+                    "training stopped before the end.",
+                ));
+            }
+            let typed = line.trim_end_matches(['\r', '\n']);
+            if typed == passphrase {
+                break;
+            }
+            mistakes += 1;
+            let first = typed
+                .chars()
+                .zip(passphrase.chars())
+                .take_while(|(a, b)| a == b)
+                .count();
+            println!(
+                "not quite, the first mistake is at character {}; it was:\n\n    {}\n",
+                first + 1,
+                passphrase
+            );
+        }
+    }
+    println!("well done, mistakes along the way: {}.", mistakes);
+    Ok(())
+}
+
+fn run_analyze(cmd: &AnalyzeCmd) -> Result<(), CliError> {
+    let catalog = Catalog::for_language(&cmd.language);
+    let mut generator = Generator::new();
+    let io_error =
+        |e: std::io::Error| CliError::new(ErrorKind::Io, format!("cannot analyze: {}", e));
+    let mut out = BufWriter::new(std::io::stdout());
+    writeln!(
+        out,
+        "{:>8}    {:>6}    {:>9}    strength",
+        "line", "length", "entropy"
+    )
+    .map_err(io_error)?;
+    for (i, line) in std::io::stdin().lock().lines().enumerate() {
+        let password = line.map_err(io_error)?;
+        for warning in analysis::portability_warnings(&password) {
+            eprintln!("warning: line {}: {}.", i + 1, catalog.warning(&warning));
+        }
+        let (bits, strength, notes) = if cmd.check {
+            let check = analysis::check_user_passphrase(&mut generator, &password, None);
+            let issues = check.issues.iter().map(|issue| catalog.issue(issue));
+            let suggestions = check.suggestions.iter().map(|s| catalog.suggestion(s));
+            let strengthened = analysis::suggest_strengthening(
+                &mut generator,
+                &password,
+                analysis::PASSPHRASE_TARGET_BITS,
+            );
+            let strengthened = strengthened.first().map(|s| catalog.strengthening(s));
+            let notes = issues.chain(suggestions).chain(strengthened).collect();
+            (check.bits, check.strength, notes)
+        } else {
+            let estimate = analysis::estimate(&password);
+            (estimate.bits, estimate.strength, Vec::new())
+        };
+        writeln!(
+            out,
+            "{:>8}    {:>6}    {:>9.2}    {}",
+            i + 1,
+            password.chars().count(),
+            bits,
+            catalog.strength(strength)
+        )
+        .map_err(io_error)?;
+        for note in notes {
+            writeln!(out, "{:>12}{}", "", note).map_err(io_error)?;
+        }
+    }
+    out.flush().map_err(io_error)
+}
+
+fn run_wordlist_audit(cmd: &WordlistAuditCmd) -> Result<(), CliError> {
+    const EXAMPLES: usize = 10;
+    let (list, invalid) = from_file(&cmd.file)
+        .map_err(|e| CliError::new(ErrorKind::Io, format!("cannot read {}: {}", cmd.file, e)))?;
+    if invalid > 0 {
+        eprintln!(
+            "warning: skipped {} lines of {} which are not valid UTF-8.",
+            invalid, cmd.file
+        );
+    }
+    let audit = audit(&list);
+    println!("words:              {}", audit.size);
+    println!("entropy per word:   {:.2} bits", audit.entropy_per_word);
+    println!("average length:     {:.2} characters", audit.average_length);
+    println!("duplicates:         {}", audit.duplicates.len());
+    for word in audit.duplicates.iter().take(EXAMPLES) {
+        println!("    {}", word);
+    }
+    println!("prefix violations:  {}", audit.prefix_violations.len());
+    for (word, longer) in audit.prefix_violations.iter().take(EXAMPLES) {
+        println!("    {} < {}", word, longer);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::random_suffix;
+
+    /// Runs the command line `rest`, as `cli_main` does.
+    fn run_line(rest: &[&str]) -> Result<(), CliError> {
+        let mut args = Cli::from_args(&["crypticli"], rest).unwrap();
+        expand_pattern(&mut args)?;
+        run(&args, &recorded_arguments(rest, &args.pattern), None)
+    }
+
+    #[test]
+    fn test_entropy_floor_of_story() {
+        let dir = std::env::temp_dir().join(format!("crypticli-cli-test-{}", random_suffix()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let output = dir.join("secrets.txt");
+        let output = output.to_str().unwrap();
+        // the floor is checked against the story pattern, not the default one: a header and
+        // the secret are written
+        run_line(&["--story", "--min-entropy", "35", "-n", "1", "-o", output]).unwrap();
+        let secrets = std::fs::read_to_string(output).unwrap();
+        assert_eq!(secrets.lines().count(), 2, "{}", secrets);
+        let error = run_line(&["--min-entropy", "35", "-o", output]).unwrap_err();
+        assert_eq!(error.kind, ErrorKind::EntropyFloor);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_entropy_ceiling_of_mobile() {
+        let dir = std::env::temp_dir().join(format!("crypticli-cli-test-{}", random_suffix()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let output = dir.join("secrets.txt");
+        let output = output.to_str().unwrap();
+        // the mobile pattern reaches floors which the default one cannot
+        run_line(&["--mobile", "--min-entropy", "50", "-n", "1", "-o", output]).unwrap();
+        let error = run_line(&["--min-entropy", "50", "-o", output]).unwrap_err();
+        assert!(error.message.contains("at most"), "{}", error.message);
+        // and the story pattern cannot reach floors which the default one can
+        let error = run_line(&["--story", "--min-entropy", "45", "-o", output]).unwrap_err();
+        assert!(error.message.contains("at most"), "{}", error.message);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,148 @@
+//! Error reporting and exit codes.
+use crate::json::json_string;
+use argh::FromArgValue;
+use std::fmt;
+
+/// Failure causes, each mapped to a stable exit code.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ErrorKind {
+    /// Invalid command line arguments or pattern.
+    Usage,
+    /// A generated secret did not meet `--min-entropy`.
+    EntropyFloor,
+    /// A file could not be read or written.
+    Io,
+    /// A remote service (e.g. vault) refused the request or could not be reached.
+    #[cfg_attr(not(feature = "vault"), allow(dead_code))]
+    Remote,
+    /// A check failed, e.g. a transcript did not match the challenge phrase or a secret was
+    /// not found in the history.
+    Mismatch,
+    /// No secret far enough from the others was found, see `--min-distance`.
+    Exhausted,
+    /// Generation was stopped with Ctrl-C.
+    Interrupted,
+}
+
+impl ErrorKind {
+    pub fn exit_code(self) -> i32 {
+        match self {
+            ErrorKind::Usage => 2,
+            ErrorKind::EntropyFloor => 3,
+            ErrorKind::Io => 4,
+            ErrorKind::Remote => 5,
+            ErrorKind::Mismatch => 6,
+            ErrorKind::Exhausted => 7,
+            ErrorKind::Interrupted => 130,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            ErrorKind::Usage => "usage",
+            ErrorKind::EntropyFloor => "entropy_floor",
+            ErrorKind::Io => "io",
+            ErrorKind::Remote => "remote",
+            ErrorKind::Mismatch => "mismatch",
+            ErrorKind::Exhausted => "exhausted",
+            ErrorKind::Interrupted => "interrupted",
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct CliError {
+    pub kind: ErrorKind,
+    pub message: String,
+}
+
+impl CliError {
+    pub fn new(kind: ErrorKind, message: impl Into<String>) -> CliError {
+        CliError {
+            kind,
+            message: message.into(),
+        }
+    }
+
+    /// Prints the error on standard error in the requested format.
+    pub fn report(&self, format: &ErrorFormat) {
+        match format {
+            ErrorFormat::Text() => eprintln!("{}", self),
+            ErrorFormat::Json() => eprintln!("{}", self.to_json()),
+        }
+    }
+
+    /// The error as a JSON object, with its exit code as a number.
+    fn to_json(&self) -> String {
+        format!(
+            "{{\"error\":{},\"code\":{},\"message\":{}}}",
+            json_string(self.kind.name()),
+            self.kind.exit_code(),
+            json_string(&self.message)
+        )
+    }
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl From<cryptirust::Error> for CliError {
+    fn from(e: cryptirust::Error) -> Self {
+        let kind = match e {
+            cryptirust::Error::WeakSecret { .. } | cryptirust::Error::EntropyUnreachable { .. } => {
+                ErrorKind::EntropyFloor
+            }
+            cryptirust::Error::BatchExhausted { .. }
+            | cryptirust::Error::AliasesExhausted { .. }
+            | cryptirust::Error::SecretsExhausted { .. } => ErrorKind::Exhausted,
+            cryptirust::Error::EmptyModel { .. }
+            | cryptirust::Error::NoDictionary
+            | cryptirust::Error::ElementFloorTooHigh { .. }
+            | cryptirust::Error::NumeralOutOfRange { .. }
+            | cryptirust::Error::EmptySymbols
+            | cryptirust::Error::EmptyDigits
+            | cryptirust::Error::ZeroWordLength
+            | cryptirust::Error::EmptyPlaceholder { .. }
+            | cryptirust::Error::PatternTooComplex { .. } => ErrorKind::Usage,
+        };
+        CliError::new(kind, e.to_string())
+    }
+}
+
+pub enum ErrorFormat {
+    Text(),
+    Json(),
+}
+
+impl FromArgValue for ErrorFormat {
+    fn from_arg_value(value: &str) -> Result<Self, String> {
+        match value {
+            "text" => Ok(ErrorFormat::Text()),
+            "json" => Ok(ErrorFormat::Json()),
+            _ => Err("unknown error format, use one of [text, json].".to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_report() {
+        let error = CliError::new(ErrorKind::Mismatch, "the \"phrases\" differ.");
+        assert_eq!(
+            error.to_json(),
+            r#"{"error":"mismatch","code":6,"message":"the \"phrases\" differ."}"#
+        );
+    }
+
+    #[test]
+    fn test_usage_errors() {
+        let e = cryptirust::Error::EmptyPlaceholder { offset: 4 };
+        assert_eq!(CliError::from(e).kind.exit_code(), 2);
+    }
+}
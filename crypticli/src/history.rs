@@ -0,0 +1,42 @@
+//! Encrypted history files for `--history` and `history-check`.
+//!
+//! The passphrase of the history is read from `CRYPTICLI_HISTORY_PASSPHRASE`, so that scripts
+//! can provide it without a prompt and it does not appear in the shell history. A missing file
+//! is an empty history; files are replaced atomically.
+use crate::error::{CliError, ErrorKind};
+use crate::output::AtomicFile;
+use cryptirust::history::History;
+use cryptirust::kdf::KdfParams;
+use std::io::Write;
+use std::path::Path;
+
+/// Opens the history at `path`, or starts an empty one if the file does not exist.
+pub fn open(path: &str) -> Result<History, CliError> {
+    let passphrase = std::env::var("CRYPTICLI_HISTORY_PASSPHRASE").map_err(|_| {
+        CliError::new(
+            ErrorKind::Usage,
+            "CRYPTICLI_HISTORY_PASSPHRASE is not set, cannot open the history.",
+        )
+    })?;
+    match std::fs::read(path) {
+        Ok(bytes) => History::open(&bytes, &passphrase)
+            .map_err(|e| CliError::new(ErrorKind::Io, format!("cannot open {}: {}", path, e))),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            History::new(&passphrase, KdfParams::RECOMMENDED)
+                .map_err(|e| CliError::new(ErrorKind::Usage, e.to_string()))
+        }
+        Err(e) => Err(CliError::new(
+            ErrorKind::Io,
+            format!("cannot read {}: {}", path, e),
+        )),
+    }
+}
+
+/// Writes `history` to `path`.
+pub fn save(path: &str, history: &History) -> Result<(), CliError> {
+    let io_error =
+        |e: std::io::Error| CliError::new(ErrorKind::Io, format!("cannot write {}: {}", path, e));
+    let mut file = AtomicFile::create(Path::new(path)).map_err(io_error)?;
+    file.write_all(&history.to_bytes()).map_err(io_error)?;
+    file.commit().map_err(io_error)
+}
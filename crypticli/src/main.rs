@@ -0,0 +1,238 @@
+//! Flexible password generator based on the [Cryptirust](https://docs.rs/cryptirust) library.
+//!
+//! # Installation
+//! ```bash
+//! > cargo install crypticli
+//! ```
+//!
+//! # Usage
+//! ```bash
+//! > crypticli --help
+//! ```
+//! outputs
+//! ```bash
+//!   Usage: crypticli [-p <pattern>] [--arg <arg...>] [--story] [-n <num>] [-d <depth>] [-s <style>] [--prefix-free] [--no-homophones] [--min-count <min-count>] [--exact-entropy] [--natural-lengths] [--date-safe] [--group-digits <group-digits>] [--layout <layout>] [--symbols <symbols>] [--digits <digits>] [--mobile] [--mix-languages] [--annotate] [--mnemonic] [--ascii] [--color] [--sort <sort>] [--sorted] [--temp-dir <temp-dir>] [--best-of <best-of>] [--confirm] [--watch <watch>] [--theme <theme>] [--format <format>] [--normalize <normalize>] [--newline <newline>] [--no-final-newline] [--min-distance <min-distance>] [--min-entropy <min-entropy>] [-o <output>] [--append] [--manifest <manifest>] [--seed <seed>] [--seen <seen>] [--demo] [--errors <errors>] [<command>] [<args>]
+//!   
+//!   Flexible password generator based on the Cryptirust library.
+//!   
+//!   Options:
+//!     -p, --pattern     string representing the desired structure of the generated
+//!                       passphrases, default is `w-c-s-d` (word-token-symbol-digit).
+//!     --arg             value of an arg placeholder of the pattern, as name=value,
+//!                       e.g. --arg site=dw.com for arg:site in braces; env
+//!                       placeholders take environment variables. Values are copied
+//!                       verbatim. Repeatable.
+//!     --story           generate grammatical mini-sentences instead
+//!                       (adjective-noun-verb-adjective-noun), easier to memorize;
+//!                       patterns can also use the adj, noun and verb elements in
+//!                       braces.
+//!     -n, --num         number of passphrases to generate, must be a positive
+//!                       integer; a seq element in braces in the pattern numbers
+//!                       them.
+//!     -d, --depth       depth of the markov model, 1...3 are reasonable values.
+//!     -s, --style       word style: eff (english), italian, russian (cyrillic), or
+//!                       cv (consonant-vowel pairs)
+//!     --prefix-free     drop the words which are prefixes of other words from the
+//!                       word list before training; the generated words are new and
+//!                       can still be prefixes of one another, keep separators.
+//!     --no-homophones   keep a single spelling of the words of the word list which
+//!                       sound alike (their, there), so that passphrases can be
+//!                       dictated reliably.
+//!     --min-count       drop the transitions of the model observed fewer than this
+//!                       many times in the word list, removing one-off noise.
+//!     --exact-entropy   report the exact entropy of each passphrase, accounting for
+//!                       the different ways the generator can produce the same text
+//!                       (e.g. words without separators); slower.
+//!     --natural-lengths draw the length of each word from the lengths of the words
+//!                       in the word list, instead of always generating about 8
+//!                       characters.
+//!     --date-safe       never emit groups of four digits looking like a year or a
+//!                       date, such as 1987 or 0412; the reported entropy accounts
+//!                       for the excluded values.
+//!     --group-digits    split runs of digits longer than this into hyphen separated
+//!                       groups, e.g. 123-456 for six digits with groups of 3.
+//!     --layout          keyboard layout: us, uk, de, it or mobile; symbols are
+//!                       limited to those easily typed on it.
+//!     --symbols         characters drawn by s in the pattern, e.g. for sites
+//!                       rejecting some symbols; overrides the symbols of --layout
+//!                       and --mobile.
+//!     --digits          characters drawn by d in the pattern, e.g. 23456789 to avoid
+//!                       the digits mistaken for letters.
+//!     --mobile          mobile preset: lowercase words, digits and symbols grouped
+//!                       so that they are quick to type on a phone; implies --layout
+//!                       mobile and overrides the pattern.
+//!     --mix-languages   mix words from the bundled natural language lists (english
+//!                       and italian) instead of using --style.
+//!     --annotate        print the language of every word next to each secret.
+//!     --mnemonic        print a sentence built around the words of each secret, as a
+//!                       memory aid; the words added to the sentence are not part of
+//!                       the secret.
+//!     --ascii           print the secrets transliterated to ASCII (e.g. ж as zh),
+//!                       followed by the original to memorize; warns when the
+//!                       transliteration can merge distinct secrets.
+//!     --color           color each part of the secrets by its entropy per character
+//!                       in the table output: green for 3 bits or more, yellow for 2
+//!                       bits or more, red below; only on a terminal, never in an
+//!                       --output file or a pipe.
+//!     --sort            sort the generated secrets: effort (fewest keystrokes first
+//!                       on the --layout keyboard, us by default).
+//!     --sorted          print the secrets in byte order, so that large batches
+//!                       compress well and are quick to deduplicate or compare;
+//!                       batches over a million secrets are sorted in chunks written
+//!                       to --temp-dir, which hold the secrets until the output is
+//!                       complete.
+//!     --temp-dir        directory of the temporary files of --sorted, in a private
+//!                       subdirectory removed at the end; default is the system
+//!                       temporary directory.
+//!     --best-of         generate this many candidates for each secret and keep the
+//!                       easiest to type (fewest keystrokes on the --layout
+//!                       keyboard); the reported entropy is lowered by log2 of the
+//!                       number of candidates to account for the choice.
+//!     --confirm         show every secret on standard error and ask to retype it
+//!                       before it is printed or stored, failing with exit code 6
+//!                       after three wrong attempts.
+//!     --watch           regenerate and print the secrets again at this interval
+//!                       (e.g. 5s, 500ms, 1m) until interrupted with Ctrl-C.
+//!     --theme           theme of the words: plain (default), alliteration (same
+//!                       first letter) or rhyme (same final token); the reported
+//!                       entropy accounts for the constraint.
+//!     --format          output format: table (default) or external-program (a single
+//!                       JSON object of strings, as expected by Terraform's external
+//!                       data source).
+//!     --normalize       unicode normalization form of the secrets: nfc (composed) or
+//!                       nfd (decomposed), for systems comparing them byte by byte;
+//!                       by default they are kept as generated.
+//!     --newline         line endings of the output: lf (default) or crlf.
+//!     --no-final-newline
+//!                       end the output without a line ending, e.g. for a single
+//!                       secret read verbatim by another program.
+//!     --min-distance    make every secret differ from all the others by at least
+//!                       this many characters (Levenshtein distance), so that codes
+//!                       cannot be mistaken for each other.
+//!     --min-entropy     refuse patterns which can yield secrets with less than this
+//!                       many bits of entropy, and such secrets.
+//!     -o, --output      write the secrets to this file instead of standard output,
+//!                       keeping them out of the terminal scrollback; the file gets
+//!                       0600 permissions and is replaced atomically once generation
+//!                       completes or is interrupted with Ctrl-C.
+//!     --append          append to the `--output` file instead of replacing it.
+//!     --manifest        write a JSON manifest of the run to this file: versions,
+//!                       model digest, depth, pattern, policy, counts and entropy
+//!                       statistics, without the secrets.
+//!     --seed            derive the secrets from the secret seed read from
+//!                       `CRYPTICLI_SEED` instead of the system random number
+//!                       generator, so that `regen` can reproduce them from the
+//!                       --manifest, which is required.
+//!     --seen            never output a secret recorded in this Bloom filter file by
+//!                       a previous run, and record the new ones; the filter rotates
+//!                       every million secrets and its key is read from
+//!                       `CRYPTICLI_SEEN_KEY`.
+//!     --demo            demo mode: reproducible, watermarked, zero-entropy outputs
+//!                       for docs and screenshots.
+//!     --errors          error output format: text (default) or json, see the exit
+//!                       codes in the documentation.
+//!     --help            display usage information
+//!   
+//!   Commands:
+//!     template          Fill the `cryptirust:<pattern>` placeholders of a template
+//!                       file with new secrets.
+//!     analyze           Estimate the strength of passwords read from standard input,
+//!                       one per line, and warn about those which other systems can
+//!                       silently truncate, trim or normalize.
+//!     wordlist-audit    Check a word list (one word per line) before adopting it for
+//!                       diceware passphrases.
+//!     challenge         Generate short challenge phrases for voice authentication,
+//!                       made of words which do not sound alike, or verify a speech
+//!                       transcript against one.
+//!     batch             Generate the secrets described by a CSV file of jobs with
+//!                       the columns label, pattern, count and optionally policy
+//!                       (e.g. `min-entropy=60 min-distance=3`), printing the jobs as
+//!                       CSV with one row per secret and the secret and entropy
+//!                       columns added.
+//!     plan              Design a pattern reaching a target entropy on average, with
+//!                       the given constraints, for the word list and settings chosen
+//!                       by the other options.
+//!     model-export      Export the markov model trained with the word list and depth
+//!                       chosen by the other options, as a Graphviz graph (dot), a
+//!                       JSON array of transitions with their counts (json) or the
+//!                       compact form loaded by Generator::from_model_bytes (binary);
+//!                       combine with --min-count to prune rare transitions first.
+//!     train-memory      Show a new passphrase, generated with the other options,
+//!                       then quiz you to retype it with less and less of it visible
+//!                       and longer and longer pauses, to memorize it before adopting
+//!                       it.
+//!     encode            Encode a UUID as a pronounceable alias, hexadecimal bytes as
+//!                       proquints or Bubble Babble, or a hexadecimal fingerprint as
+//!                       a few words to compare by voice.
+//!     decode            Decode a pronounceable alias to its UUID, or proquints or
+//!                       Bubble Babble to hexadecimal bytes.
+//!     compare           Compare two phrases word by word, e.g. fingerprint words
+//!                       read over the phone, ignoring case and punctuation; prints
+//!                       the differing words and exits with code 6 if the phrases
+//!                       differ.
+//!     regen             Reproduce the secrets of a run made with --seed and
+//!                       --manifest, from its manifest and the seed read from
+//!                       `CRYPTICLI_SEED`, e.g. to recover derived credentials; the
+//!                       model and the generation algorithm must not have changed
+//!                       since.
+//! ```
+//!
+//! # Example
+//! ```bash
+//! > crypticli -p w.w.w.w-20dd -n 10
+//! ```
+//! output:
+//! ```bash
+//!       n.     log2(guesses)     secret
+//!       1              67.87    glarean.seventail.judgines.passion-2049
+//!       2              69.71    baskettle.frustrep.banjohn.captivate-2036
+//!       3              71.35    pephant.matee.prodigan.patious-2088
+//!       4              65.86    smokedgi.extroving.banknote.juggling-2068
+//!       5              64.37    travesty.vetor.trifled.calmana-2002
+//!       6              66.85    showering.visorne.sprinked.delirical-2009
+//!       7              62.35    ranked.neglected.removing.requished-2024
+//!       8              71.86    landmine.nextinc.itablemis.droola-2004
+//!       9              59.82    mumbone.stoics.twitter.crawling-2014
+//!       10             65.83    dumpster.waferris.liability.unabaster-2098
+//!```
+//!
+//! # Exit codes
+//!
+//! | code | meaning |
+//! |------|---------|
+//! | 0    | success |
+//! | 2    | invalid arguments or pattern |
+//! | 3    | a secret did not meet `--min-entropy` |
+//! | 4    | a file could not be read or written |
+//! | 5    | a remote service (e.g. vault) refused the request |
+//! | 6    | `challenge --verify`: the transcript does not match the phrase |
+//! | 6    | `--confirm`: the secret was not retyped correctly |
+//! | 6    | `compare`: the phrases differ |
+//! | 6    | `regen`: the seed, the model or the generation algorithm differ from the manifest |
+//! | 6    | `history-check`: the secret is not in the history |
+//! | 7    | no secret at `--min-distance` from the others, or missing from `--seen`, could be found |
+//! | 130  | interrupted with Ctrl-C, the secrets generated so far are kept |
+//!
+//! With `--errors json` failures are reported on standard error as a single JSON object, e.g.
+//! `{"error":"entropy_floor","code":3,"message":"..."}`.
+//!
+//! # License
+//!
+//! Cryptirust is licensed under the MIT License.
+//!
+mod cli;
+mod csv;
+mod error;
+#[cfg(feature = "history")]
+mod history;
+mod interrupt;
+mod json;
+mod output;
+mod seen;
+mod sort;
+#[cfg(feature = "vault")]
+mod vault;
+
+fn main() {
+    cli::cli_main();
+}
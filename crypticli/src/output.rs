@@ -0,0 +1,265 @@
+//! Destinations of the generated secrets.
+use rand::rngs::OsRng;
+use rand::RngCore;
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+/// Random names tried for a temporary file or directory before giving up.
+pub const NAME_ATTEMPTS: usize = 16;
+
+/// 16 random hexadecimal digits from the operating system, for the names of temporary files
+/// and directories, so that other users cannot predict them.
+pub fn random_suffix() -> String {
+    let mut bytes = [0; 8];
+    OsRng.fill_bytes(&mut bytes);
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Opens `path` with `options` for writing secrets, creating it readable and writable by the
+/// owner only.
+fn open_private(path: &Path, options: &mut OpenOptions) -> io::Result<File> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    options.open(path)
+}
+
+/// Opens `path` for appending, creating it with `0600` permissions when missing; the
+/// permissions of an existing file are left untouched.
+pub fn append_private(path: &Path) -> io::Result<BufWriter<File>> {
+    let file = open_private(path, OpenOptions::new().append(true).create(true))?;
+    Ok(BufWriter::new(file))
+}
+
+/// Creates `path`, or truncates it, with `0600` permissions when missing.
+pub fn create_private(path: &Path) -> io::Result<BufWriter<File>> {
+    let file = open_private(
+        path,
+        OpenOptions::new().write(true).truncate(true).create(true),
+    )?;
+    Ok(BufWriter::new(file))
+}
+
+/// A file written atomically: data goes to a temporary file in the same directory, which
+/// replaces the destination only when [`AtomicFile::commit`] is called, so readers never see a
+/// partially written file. The temporary file is removed if the writer is dropped uncommitted.
+///
+/// The temporary file has a random name and is created with `0600` permissions, failing
+/// rather than opening a file which already exists, so that other users can neither read the
+/// secrets nor plant a file or link in its place.
+pub struct AtomicFile {
+    path: PathBuf,
+    tmp_path: PathBuf,
+    writer: Option<BufWriter<File>>,
+}
+
+impl AtomicFile {
+    pub fn create(path: &Path) -> io::Result<AtomicFile> {
+        let name = path
+            .file_name()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "not a file path"))?;
+        let mut attempts = 0;
+        loop {
+            let mut tmp_name = std::ffi::OsString::from(".");
+            tmp_name.push(name);
+            tmp_name.push(format!(".{}.tmp", random_suffix()));
+            let tmp_path = path.with_file_name(tmp_name);
+            match open_private(&tmp_path, OpenOptions::new().write(true).create_new(true)) {
+                Ok(file) => {
+                    return Ok(AtomicFile {
+                        path: path.to_path_buf(),
+                        tmp_path,
+                        writer: Some(BufWriter::new(file)),
+                    })
+                }
+                Err(e) if e.kind() == io::ErrorKind::AlreadyExists && attempts < NAME_ATTEMPTS => {
+                    attempts += 1;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// Flushes the data to disk and moves it to the destination path; on failure the
+    /// temporary file is removed, so that no copy of the secrets is left behind.
+    pub fn commit(mut self) -> io::Result<()> {
+        let committed = self.sync_and_rename();
+        if committed.is_err() {
+            let _ = std::fs::remove_file(&self.tmp_path);
+        }
+        committed
+    }
+
+    fn sync_and_rename(&mut self) -> io::Result<()> {
+        if let Some(writer) = self.writer.take() {
+            let file = writer.into_inner().map_err(|e| e.into_error())?;
+            file.sync_all()?;
+        }
+        std::fs::rename(&self.tmp_path, &self.path)
+    }
+}
+
+impl Write for AtomicFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self.writer.as_mut() {
+            Some(writer) => writer.write(buf),
+            None => Ok(0),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self.writer.as_mut() {
+            Some(writer) => writer.flush(),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Drop for AtomicFile {
+    fn drop(&mut self) {
+        if self.writer.take().is_some() {
+            let _ = std::fs::remove_file(&self.tmp_path);
+        }
+    }
+}
+
+/// A writer translating the line endings written to `inner`, for `--newline` and
+/// `--no-final-newline`: every `\n` becomes `\r\n` with `crlf`, and without `final_newline`
+/// the last line ending is held back until more data follows, so the output ends without one.
+pub struct LineEndings<W: Write> {
+    inner: W,
+    crlf: bool,
+    final_newline: bool,
+    pending: bool,
+}
+
+impl<W: Write> LineEndings<W> {
+    pub fn new(inner: W, crlf: bool, final_newline: bool) -> LineEndings<W> {
+        LineEndings {
+            inner,
+            crlf,
+            final_newline,
+            pending: false,
+        }
+    }
+
+    fn write_newline(&mut self) -> io::Result<()> {
+        self.inner
+            .write_all(if self.crlf { b"\r\n" } else { b"\n" })
+    }
+}
+
+impl<W: Write> Write for LineEndings<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        for line in buf.split_inclusive(|&b| b == b'\n') {
+            if self.pending {
+                self.write_newline()?;
+                self.pending = false;
+            }
+            match line.strip_suffix(b"\n") {
+                Some(text) => {
+                    self.inner.write_all(text)?;
+                    if self.final_newline {
+                        self.write_newline()?;
+                    } else {
+                        self.pending = true;
+                    }
+                }
+                None => self.inner.write_all(line)?,
+            }
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Progress bar drawn on standard error, only when it is a terminal.
+pub struct ProgressBar {
+    enabled: bool,
+    last_percent: Option<usize>,
+}
+
+impl ProgressBar {
+    /// Creates a progress bar, shown only for batches of at least `min_total` items.
+    pub fn new(total: usize, min_total: usize) -> ProgressBar {
+        use std::io::IsTerminal;
+        ProgressBar {
+            enabled: total >= min_total && io::stderr().is_terminal(),
+            last_percent: None,
+        }
+    }
+
+    pub fn update(&mut self, done: usize, total: usize) {
+        if !self.enabled || total == 0 {
+            return;
+        }
+        let percent = done * 100 / total;
+        if self.last_percent == Some(percent) {
+            return;
+        }
+        self.last_percent = Some(percent);
+        let filled = percent / 4;
+        eprint!(
+            "\r[{}{}] {:3}% {}/{}",
+            "#".repeat(filled),
+            " ".repeat(25 - filled),
+            percent,
+            done,
+            total
+        );
+    }
+
+    pub fn finish(&mut self) {
+        if self.enabled && self.last_percent.is_some() {
+            eprintln!();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_atomic_file() {
+        let dir = std::env::temp_dir().join(format!("crypticli-atomic-test-{}", random_suffix()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("secrets.txt");
+        // concurrent writers get distinct temporary files
+        let mut first = AtomicFile::create(&path).unwrap();
+        let mut second = AtomicFile::create(&path).unwrap();
+        assert_ne!(first.tmp_path, second.tmp_path);
+        first.write_all(b"first\n").unwrap();
+        second.write_all(b"second\n").unwrap();
+        first.commit().unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "first\n");
+        let tmp_path = second.tmp_path.clone();
+        drop(second);
+        assert!(!tmp_path.exists());
+        // the temporary name is not derived from the process
+        let name = tmp_path.file_name().unwrap().to_string_lossy().into_owned();
+        assert!(!name.contains(&format!(".{}.", std::process::id())));
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+        // a failed commit removes the temporary file, a directory cannot be replaced by a file
+        let taken = dir.join("taken");
+        std::fs::create_dir_all(taken.join("inside")).unwrap();
+        let mut failing = AtomicFile::create(&taken).unwrap();
+        failing.write_all(b"secret\n").unwrap();
+        let tmp_path = failing.tmp_path.clone();
+        assert!(failing.commit().is_err());
+        assert!(!tmp_path.exists());
+        assert!(taken.is_dir());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,64 @@
+//! Bloom filter files for `--seen`, so that no run repeats a secret of the previous ones.
+//!
+//! The key of the filters is read from `CRYPTICLI_SEEN_KEY`, without which the files do not
+//! tell whether a guess was handed out. The current generation is kept at the given path and
+//! the previous one next to it with a `.prev` suffix; when the current one is full it replaces
+//! the previous one, so the filters never grow past twice [`CAPACITY`] secrets. A missing file
+//! is an empty filter; files are replaced atomically.
+use crate::error::{CliError, ErrorKind};
+use cryptirust::store::{BloomStore, RotatingStore};
+use std::path::{Path, PathBuf};
+
+/// Secrets recorded in a generation of filters.
+pub const CAPACITY: usize = 1_000_000;
+/// False positive rate of a full filter: the chance of drawing a fresh secret again.
+const FALSE_POSITIVE_RATE: f64 = 1e-6;
+
+/// Opens the filters at `path`, or starts empty ones if the files do not exist.
+pub fn open(path: &str) -> Result<RotatingStore, CliError> {
+    let key = std::env::var("CRYPTICLI_SEEN_KEY").map_err(|_| {
+        CliError::new(
+            ErrorKind::Usage,
+            "CRYPTICLI_SEEN_KEY is not set, cannot open the --seen filter.",
+        )
+    })?;
+    let current = match read(Path::new(path), key.as_bytes())? {
+        Some(current) => current,
+        None => BloomStore::with_capacity(key.as_bytes(), CAPACITY, FALSE_POSITIVE_RATE)
+            .map_err(|e| CliError::new(ErrorKind::Usage, e.to_string()))?,
+    };
+    let previous = read(&previous_path(path), key.as_bytes())?;
+    Ok(RotatingStore::new(current, previous))
+}
+
+/// Writes the filters of `store` to `path`, the previous generation first, so that an
+/// interrupted save never forgets a secret.
+pub fn save(path: &str, store: &RotatingStore) -> Result<(), CliError> {
+    let write = |path: &Path, filter: &BloomStore| {
+        filter.save(path).map_err(|e| {
+            CliError::new(
+                ErrorKind::Io,
+                format!("cannot write {}: {}", path.display(), e),
+            )
+        })
+    };
+    if let Some(previous) = store.previous() {
+        write(&previous_path(path), previous)?;
+    }
+    write(Path::new(path), store.current())
+}
+
+fn previous_path(path: &str) -> PathBuf {
+    PathBuf::from(format!("{}.prev", path))
+}
+
+fn read(path: &Path, key: &[u8]) -> Result<Option<BloomStore>, CliError> {
+    match BloomStore::open(path, key) {
+        Ok(filter) => Ok(Some(filter)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(CliError::new(
+            ErrorKind::Io,
+            format!("cannot read {}: {}", path.display(), e),
+        )),
+    }
+}
@@ -0,0 +1,303 @@
+//! External merge sort of the secrets for `--sorted`.
+//!
+//! Secrets are sorted in memory by chunks, of [`CHUNK_SECRETS`] for the command line; when a
+//! batch is larger, every
+//! full chunk is written sorted to a run file in a private temporary directory, and the runs
+//! are merged at the end. The run files hold the secrets in clear until the directory is
+//! removed, when the [`Sorted`] secrets are dropped.
+use crate::output::{create_private, random_suffix, NAME_ATTEMPTS};
+use cryptirust::registry::{Annotated, Span, TaggedWord};
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+
+/// Secrets sorted in memory before they are written to a run file, for the command line.
+pub const CHUNK_SECRETS: usize = 1 << 20;
+
+/// A private temporary directory, removed with its content when dropped.
+struct TempDir(PathBuf);
+
+impl TempDir {
+    /// Creates a new directory in `parent`, accessible by the owner only.
+    fn create(parent: &Path) -> io::Result<TempDir> {
+        let mut builder = std::fs::DirBuilder::new();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::DirBuilderExt;
+            builder.mode(0o700);
+        }
+        let mut attempts = 0;
+        loop {
+            let path = parent.join(format!("crypticli-sort-{}", random_suffix()));
+            // fails rather than reusing a directory created by someone else
+            match builder.create(&path) {
+                Ok(()) => return Ok(TempDir(path)),
+                Err(e) if e.kind() == io::ErrorKind::AlreadyExists && attempts < NAME_ATTEMPTS => {
+                    attempts += 1;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+/// Secrets being sorted, see the [module](self) documentation.
+pub struct ExternalSort {
+    parent: PathBuf,
+    chunk_secrets: usize,
+    dir: Option<TempDir>,
+    chunk: Vec<Annotated>,
+    runs: Vec<PathBuf>,
+}
+
+impl ExternalSort {
+    /// A sort keeping up to `chunk_secrets` secrets in memory, at least one, and writing its
+    /// run files, if any, in a new directory in `parent`.
+    pub fn new(parent: &Path, chunk_secrets: usize) -> ExternalSort {
+        ExternalSort {
+            parent: parent.to_path_buf(),
+            chunk_secrets: chunk_secrets.max(1),
+            dir: None,
+            chunk: Vec::new(),
+            runs: Vec::new(),
+        }
+    }
+
+    pub fn push(&mut self, secret: Annotated) -> io::Result<()> {
+        self.chunk.push(secret);
+        if self.chunk.len() >= self.chunk_secrets {
+            self.spill()?;
+        }
+        Ok(())
+    }
+
+    /// Writes the chunk, sorted, to a new run file.
+    fn spill(&mut self) -> io::Result<()> {
+        let dir = match &self.dir {
+            Some(dir) => dir,
+            None => self.dir.insert(TempDir::create(&self.parent)?),
+        };
+        let path = dir.0.join(format!("run-{}", self.runs.len()));
+        let mut out = create_private(&path)?;
+        sort_chunk(&mut self.chunk);
+        for secret in self.chunk.drain(..) {
+            write_record(&mut out, &secret)?;
+        }
+        out.flush()?;
+        self.runs.push(path);
+        Ok(())
+    }
+
+    /// All the secrets pushed, in byte order of their text.
+    pub fn finish(mut self) -> io::Result<Sorted> {
+        if self.runs.is_empty() {
+            sort_chunk(&mut self.chunk);
+            return Ok(Sorted {
+                chunk: std::mem::take(&mut self.chunk).into_iter(),
+                runs: Vec::new(),
+                heads: Vec::new(),
+                queue: BinaryHeap::new(),
+                _dir: None,
+            });
+        }
+        if !self.chunk.is_empty() {
+            self.spill()?;
+        }
+        let mut runs = Vec::with_capacity(self.runs.len());
+        for path in &self.runs {
+            runs.push(BufReader::new(File::open(path)?));
+        }
+        let mut sorted = Sorted {
+            chunk: Vec::new().into_iter(),
+            heads: (0..runs.len()).map(|_| None).collect(),
+            runs,
+            queue: BinaryHeap::new(),
+            _dir: self.dir.take(),
+        };
+        for run in 0..sorted.runs.len() {
+            sorted.advance(run)?;
+        }
+        Ok(sorted)
+    }
+}
+
+fn sort_chunk(chunk: &mut [Annotated]) {
+    chunk.sort_unstable_by(|a, b| a.text.cmp(&b.text));
+}
+
+/// The sorted secrets, merged from the run files when the batch did not fit in a chunk.
+pub struct Sorted {
+    chunk: std::vec::IntoIter<Annotated>,
+    runs: Vec<BufReader<File>>,
+    /// The next secret of every run.
+    heads: Vec<Option<Annotated>>,
+    /// The texts of the next secrets, with their run, smallest first.
+    queue: BinaryHeap<Reverse<(String, usize)>>,
+    _dir: Option<TempDir>,
+}
+
+impl Sorted {
+    /// Reads the next secret of `run`.
+    fn advance(&mut self, run: usize) -> io::Result<()> {
+        let head = read_record(&mut self.runs[run])?;
+        if let Some(secret) = &head {
+            self.queue.push(Reverse((secret.text.clone(), run)));
+        }
+        self.heads[run] = head;
+        Ok(())
+    }
+}
+
+impl Iterator for Sorted {
+    type Item = io::Result<Annotated>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(secret) = self.chunk.next() {
+            return Some(Ok(secret));
+        }
+        let Reverse((_, run)) = self.queue.pop()?;
+        let secret = self.heads[run].take()?;
+        Some(self.advance(run).map(|()| secret))
+    }
+}
+
+fn write_u64(out: &mut impl Write, n: u64) -> io::Result<()> {
+    out.write_all(&n.to_be_bytes())
+}
+
+fn write_str(out: &mut impl Write, s: &str) -> io::Result<()> {
+    write_u64(out, s.len() as u64)?;
+    out.write_all(s.as_bytes())
+}
+
+fn write_record(out: &mut impl Write, secret: &Annotated) -> io::Result<()> {
+    write_str(out, &secret.text)?;
+    write_u64(out, secret.entropy.to_bits())?;
+    write_u64(out, secret.words.len() as u64)?;
+    for word in &secret.words {
+        write_u64(out, word.range.start as u64)?;
+        write_u64(out, word.range.end as u64)?;
+        write_str(out, &word.language)?;
+    }
+    write_u64(out, secret.spans.len() as u64)?;
+    for span in &secret.spans {
+        write_u64(out, span.range.start as u64)?;
+        write_u64(out, span.range.end as u64)?;
+        write_u64(out, span.entropy.to_bits())?;
+    }
+    Ok(())
+}
+
+fn read_u64(input: &mut impl Read) -> io::Result<u64> {
+    let mut bytes = [0; 8];
+    input.read_exact(&mut bytes)?;
+    Ok(u64::from_be_bytes(bytes))
+}
+
+fn read_usize(input: &mut impl Read) -> io::Result<usize> {
+    usize::try_from(read_u64(input)?)
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "corrupted run file"))
+}
+
+fn read_str(input: &mut impl Read) -> io::Result<String> {
+    let mut bytes = vec![0; read_usize(input)?];
+    input.read_exact(&mut bytes)?;
+    String::from_utf8(bytes)
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "corrupted run file"))
+}
+
+/// Reads the next secret of a run file, `None` at its end.
+fn read_record(input: &mut BufReader<File>) -> io::Result<Option<Annotated>> {
+    if input.fill_buf()?.is_empty() {
+        return Ok(None);
+    }
+    let text = read_str(input)?;
+    let entropy = f64::from_bits(read_u64(input)?);
+    let mut words = Vec::new();
+    for _ in 0..read_u64(input)? {
+        let range = read_usize(input)?..read_usize(input)?;
+        let language = read_str(input)?;
+        words.push(TaggedWord { range, language });
+    }
+    let mut spans = Vec::new();
+    for _ in 0..read_u64(input)? {
+        let range = read_usize(input)?..read_usize(input)?;
+        let entropy = f64::from_bits(read_u64(input)?);
+        spans.push(Span { range, entropy });
+    }
+    Ok(Some(Annotated {
+        text,
+        entropy,
+        words,
+        spans,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn secret(text: &str) -> Annotated {
+        Annotated {
+            text: text.to_string(),
+            entropy: text.len() as f64 / 3.0,
+            words: vec![TaggedWord {
+                range: 0..text.len(),
+                language: "it".to_string(),
+            }],
+            spans: vec![Span {
+                range: 0..text.len(),
+                entropy: 0.25,
+            }],
+        }
+    }
+
+    /// Sorts `texts` in chunks of `chunk_secrets` in a fresh directory, returning the sorted
+    /// secrets and how many run files were written.
+    fn sort(texts: &[&str], chunk_secrets: usize) -> (Vec<Annotated>, usize) {
+        let parent = std::env::temp_dir().join(format!(
+            "crypticli-sort-test-{}-{}",
+            std::process::id(),
+            chunk_secrets
+        ));
+        std::fs::create_dir_all(&parent).unwrap();
+        let mut sort = ExternalSort::new(&parent, chunk_secrets);
+        for text in texts {
+            sort.push(secret(text)).unwrap();
+        }
+        let runs = sort.runs.len();
+        let sorted: Vec<Annotated> = sort.finish().unwrap().map(Result::unwrap).collect();
+        // the run files are removed with the sorted secrets
+        assert_eq!(std::fs::read_dir(&parent).unwrap().count(), 0);
+        std::fs::remove_dir(&parent).unwrap();
+        (sorted, runs)
+    }
+
+    #[test]
+    fn test_external_sort() {
+        let texts = [
+            "kiwi", "apple", "fig", "banana", "cherry", "apple", "date", "éclair", "", "grape",
+        ];
+        let mut expected: Vec<Annotated> = texts.iter().map(|t| secret(t)).collect();
+        expected.sort_by(|a, b| a.text.cmp(&b.text));
+
+        // in memory
+        let (sorted, runs) = sort(&texts, 100);
+        assert_eq!((sorted, runs), (expected.clone(), 0));
+        // spilled to full runs, and to a last partial run
+        for chunk_secrets in [1, 2, 3, 5] {
+            let (sorted, runs) = sort(&texts, chunk_secrets);
+            assert_eq!(runs, texts.len() / chunk_secrets);
+            assert_eq!(sorted, expected);
+        }
+        assert_eq!(sort(&[], 2), (Vec::new(), 0));
+    }
+}
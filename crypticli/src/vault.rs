@@ -0,0 +1,393 @@
+//! Minimal writer for the HashiCorp Vault HTTP API.
+//!
+//! Only the single request needed by `--vault-path` is implemented: a write of the generated
+//! secrets to a KV path, authenticated with the token found in `VAULT_TOKEN`. The server address
+//! is read from `VAULT_ADDR` (default `http://127.0.0.1:8200`). `https://` addresses are verified
+//! against the bundled web PKI roots; plain `http://` addresses are only accepted on the loopback
+//! interface, so that the token and the secrets never cross the network in clear text. IPv6
+//! addresses are written in brackets, e.g. `http://[::1]:8200`.
+use crate::json::json_object;
+use argh::FromArgValue;
+use std::net::{SocketAddr, ToSocketAddrs};
+use std::time::Duration;
+
+/// Time allowed to connect to vault.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Time allowed for the whole request, connection included.
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Version of the KV secrets engine mounted at the path, which sets the shape of the request.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum KvVersion {
+    /// The secret data is the body of the request.
+    V1(),
+    /// The secret data is wrapped in a `data` object, and the path has a `data/` segment after
+    /// the mount, e.g. `secret/data/app`.
+    V2(),
+}
+
+impl FromArgValue for KvVersion {
+    fn from_arg_value(value: &str) -> Result<Self, String> {
+        match value {
+            "1" => Ok(KvVersion::V1()),
+            "2" => Ok(KvVersion::V2()),
+            _ => Err("unknown KV version, use one of [1, 2].".to_string()),
+        }
+    }
+}
+
+/// Writes `fields` as the secret data stored at `path`, in a KV engine of the given version.
+///
+/// The path is the one of the HTTP API, so for KV version 2 mounts it must include the `data/`
+/// segment (e.g. `secret/data/app`).
+pub fn write_secret(
+    path: &str,
+    fields: &[(String, String)],
+    version: KvVersion,
+) -> Result<(), String> {
+    let addr = std::env::var("VAULT_ADDR").unwrap_or_else(|_| "http://127.0.0.1:8200".to_string());
+    let token = std::env::var("VAULT_TOKEN")
+        .map_err(|_| "VAULT_TOKEN is not set, cannot authenticate to vault.".to_string())?;
+    write_to(&addr, &token, path, fields, version, TIMEOUT)
+}
+
+/// Writes `fields` at `path` of the vault at `addr`, giving up after `timeout`.
+fn write_to(
+    addr: &str,
+    token: &str,
+    path: &str,
+    fields: &[(String, String)],
+    version: KvVersion,
+    timeout: Duration,
+) -> Result<(), String> {
+    if token.is_empty() || !token.chars().all(|c| c.is_ascii_graphic()) {
+        return Err("VAULT_TOKEN is not a valid vault token.".to_string());
+    }
+    let path = api_path(path, version)?;
+    let mut agent = ureq::AgentBuilder::new()
+        .timeout_connect(CONNECT_TIMEOUT.min(timeout))
+        .timeout(timeout)
+        // a redirect would send the token and the secrets to another address
+        .redirects(0);
+    let addr = addr.trim_end_matches('/');
+    let host = if let Some(host) = addr.strip_prefix("https://") {
+        split_host(host, 443)?;
+        host
+    } else if let Some(host) = addr.strip_prefix("http://") {
+        // connect to the addresses checked, not to those of a second lookup
+        let addresses = loopback_addresses(host)?;
+        agent = agent.resolver(move |_: &str| Ok(addresses.clone()));
+        host
+    } else {
+        return Err(format!(
+            "unsupported VAULT_ADDR `{}`, use an https:// or http:// address.",
+            addr
+        ));
+    };
+    let body = match version {
+        KvVersion::V1() => json_object(fields),
+        KvVersion::V2() => format!("{{\"data\":{}}}", json_object(fields)),
+    };
+    let response = agent
+        .build()
+        .post(&format!("{}/v1/{}", addr, path))
+        .set("X-Vault-Token", token)
+        .set("Content-Type", "application/json")
+        .send_string(&body);
+    match response {
+        Ok(response) if (200..300).contains(&response.status()) => Ok(()),
+        Ok(response) | Err(ureq::Error::Status(_, response)) => {
+            let status = response.status();
+            let detail = response.into_string().unwrap_or_default();
+            Err(format!(
+                "vault refused the write (status {}): {}",
+                status,
+                detail.trim()
+            ))
+        }
+        Err(ureq::Error::Transport(e)) => Err(format!("cannot reach vault at {}: {}", host, e)),
+    }
+}
+
+/// The path of the HTTP API for the KV path `path`, e.g. `secret/data/app`, with its segments
+/// percent-encoded.
+fn api_path(path: &str, version: KvVersion) -> Result<String, String> {
+    let invalid = || format!("invalid vault path `{}`.", path);
+    let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+    if segments
+        .iter()
+        .any(|s| s.is_empty() || *s == "." || *s == ".." || s.chars().any(char::is_control))
+    {
+        return Err(invalid());
+    }
+    if version == KvVersion::V2() && !segments[1..].contains(&"data") {
+        return Err(format!(
+            "the vault path `{}` has no data/ segment after the mount, which KV version 2 \
+             requires (e.g. secret/data/app); use --vault-kv 1 for KV version 1 mounts.",
+            path
+        ));
+    }
+    let mut encoded = Vec::new();
+    for segment in segments {
+        let mut out = String::new();
+        for byte in segment.bytes() {
+            match byte {
+                b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                    out.push(byte as char)
+                }
+                _ => out.push_str(&format!("%{:02X}", byte)),
+            }
+        }
+        encoded.push(out);
+    }
+    Ok(encoded.join("/"))
+}
+
+/// The host and port of `host`, e.g. `127.0.0.1:8200`, `[::1]:8200` or `localhost`, with the
+/// port defaulting to `default_port`.
+fn split_host(host: &str, default_port: u16) -> Result<(&str, u16), String> {
+    let invalid = || format!("invalid host `{}` in VAULT_ADDR.", host);
+    let (name, port) = match host.strip_prefix('[') {
+        // a bracketed IPv6 address, optionally followed by a port
+        Some(rest) => {
+            let (name, after) = rest.split_once(']').ok_or_else(invalid)?;
+            match after {
+                "" => (name, None),
+                _ => (name, Some(after.strip_prefix(':').ok_or_else(invalid)?)),
+            }
+        }
+        None => match host.split_once(':') {
+            Some((name, port)) => (name, Some(port)),
+            None => (host, None),
+        },
+    };
+    let port = match port {
+        Some(port) => port.parse().map_err(|_| invalid())?,
+        None => default_port,
+    };
+    if name.is_empty() || name.contains(['/', '@', '?', '#']) {
+        return Err(invalid());
+    }
+    Ok((name, port))
+}
+
+/// The addresses of `host`, which must all be on the loopback interface: the token is sent in
+/// clear text.
+fn loopback_addresses(host: &str) -> Result<Vec<SocketAddr>, String> {
+    let (name, port) = split_host(host, 80)?;
+    let addresses: Vec<SocketAddr> = (name, port)
+        .to_socket_addrs()
+        .map_err(|e| format!("cannot resolve vault host {}: {}", name, e))?
+        .collect();
+    if addresses.is_empty() || !addresses.iter().all(|a| a.ip().is_loopback()) {
+        return Err(format!(
+            "refusing to send VAULT_TOKEN over plain http to {}, which is not a loopback \
+             address; use an https:// address.",
+            host
+        ));
+    }
+    Ok(addresses)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::sync::mpsc;
+    use std::thread;
+
+    #[test]
+    fn test_vault_hosts() {
+        assert_eq!(split_host("127.0.0.1:8200", 80), Ok(("127.0.0.1", 8200)));
+        assert_eq!(split_host("localhost", 80), Ok(("localhost", 80)));
+        assert_eq!(
+            split_host("vault.example.com", 443),
+            Ok(("vault.example.com", 443))
+        );
+        assert_eq!(split_host("[::1]:8200", 80), Ok(("::1", 8200)));
+        assert_eq!(split_host("[::1]", 80), Ok(("::1", 80)));
+        for invalid in [
+            "",
+            "::1:8200",
+            "[::1",
+            "[::1]8200",
+            "host:port",
+            "a:1:2",
+            "h/x",
+            "user@host",
+        ] {
+            assert!(split_host(invalid, 80).is_err(), "{}", invalid);
+        }
+
+        let v4 = loopback_addresses("127.0.0.1:8200").unwrap();
+        assert_eq!(v4, vec!["127.0.0.1:8200".parse().unwrap()]);
+        let v6 = loopback_addresses("[::1]:8200").unwrap();
+        assert_eq!(v6, vec!["[::1]:8200".parse().unwrap()]);
+        for remote in ["10.0.0.1:8200", "[2001:db8::1]:8200", "192.0.2.7"] {
+            let error = loopback_addresses(remote).unwrap_err();
+            assert!(error.contains("not a loopback address"), "{}", error);
+        }
+    }
+
+    #[test]
+    fn test_vault_paths() {
+        assert_eq!(
+            api_path("/secret/data/app/", KvVersion::V2()),
+            Ok("secret/data/app".to_string())
+        );
+        assert_eq!(
+            api_path("kv/my app?x#y", KvVersion::V1()),
+            Ok("kv/my%20app%3Fx%23y".to_string())
+        );
+        for invalid in [
+            "",
+            "secret//app",
+            "secret/../sys",
+            "secret/./app",
+            "kv/a\r\nb",
+        ] {
+            assert!(api_path(invalid, KvVersion::V1()).is_err(), "{:?}", invalid);
+        }
+        // KV version 2 paths need their data/ segment
+        let error = api_path("secret/app", KvVersion::V2()).unwrap_err();
+        assert!(error.contains("--vault-kv 1"), "{}", error);
+        assert!(api_path("data/app", KvVersion::V2()).is_err());
+        assert!(api_path("secret/app", KvVersion::V1()).is_ok());
+    }
+
+    /// A request received by [`mock_vault`].
+    struct Request {
+        head: String,
+        body: String,
+    }
+
+    /// Answers a single request on a loopback port with `response`, or never when it is `None`,
+    /// and returns the address of the server and the request received.
+    fn mock_vault(response: Option<&'static str>) -> (String, mpsc::Receiver<Request>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = format!("http://{}", listener.local_addr().unwrap());
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut head = String::new();
+            let mut length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some((name, value)) = line.split_once(':') {
+                    if name.eq_ignore_ascii_case("content-length") {
+                        length = value.trim().parse().unwrap();
+                    }
+                }
+                if line == "\r\n" {
+                    break;
+                }
+                head.push_str(&line);
+            }
+            let mut body = vec![0; length];
+            reader.read_exact(&mut body).unwrap();
+            let body = String::from_utf8(body).unwrap();
+            sender.send(Request { head, body }).unwrap();
+            match response {
+                Some(response) => reader.get_mut().write_all(response.as_bytes()).unwrap(),
+                None => thread::sleep(Duration::from_secs(5)),
+            }
+        });
+        (addr, receiver)
+    }
+
+    const NO_CONTENT: &str = "HTTP/1.1 204 No Content\r\nConnection: close\r\n\r\n";
+
+    #[test]
+    fn test_vault_writes() {
+        let fields = vec![("secret".to_string(), "a\"b".to_string())];
+        let timeout = Duration::from_secs(10);
+
+        // KV version 2 wraps the secret data
+        let (addr, requests) = mock_vault(Some(NO_CONTENT));
+        let path = "secret/data/my app";
+        write_to(&addr, "s.token", path, &fields, KvVersion::V2(), timeout).unwrap();
+        let request = requests.recv().unwrap();
+        assert!(request
+            .head
+            .starts_with("POST /v1/secret/data/my%20app HTTP/1.1\r\n"));
+        assert!(request.head.contains("X-Vault-Token: s.token\r\n"));
+        assert_eq!(request.body, r#"{"data":{"secret":"a\"b"}}"#);
+
+        // KV version 1 does not
+        let (addr, requests) = mock_vault(Some(NO_CONTENT));
+        write_to(
+            &addr,
+            "s.token",
+            "kv/app",
+            &fields,
+            KvVersion::V1(),
+            timeout,
+        )
+        .unwrap();
+        let request = requests.recv().unwrap();
+        assert!(request.head.starts_with("POST /v1/kv/app HTTP/1.1\r\n"));
+        assert_eq!(request.body, r#"{"secret":"a\"b"}"#);
+
+        // refusals carry the status and the errors of vault
+        let (addr, _requests) = mock_vault(Some(
+            "HTTP/1.1 403 Forbidden\r\nContent-Length: 32\r\nConnection: close\r\n\r\n\
+             {\"errors\":[\"permission denied\"]}",
+        ));
+        let error = write_to(
+            &addr,
+            "s.token",
+            "kv/app",
+            &fields,
+            KvVersion::V1(),
+            timeout,
+        )
+        .unwrap_err();
+        assert!(error.contains("status 403"), "{}", error);
+        assert!(error.contains("permission denied"), "{}", error);
+
+        // redirects are not followed
+        let (addr, _requests) = mock_vault(Some(
+            "HTTP/1.1 307 Temporary Redirect\r\nLocation: http://192.0.2.7/\r\n\
+             Content-Length: 0\r\nConnection: close\r\n\r\n",
+        ));
+        let error = write_to(
+            &addr,
+            "s.token",
+            "kv/app",
+            &fields,
+            KvVersion::V1(),
+            timeout,
+        )
+        .unwrap_err();
+        assert!(error.contains("status 307"), "{}", error);
+
+        // a silent server is given up on
+        let (addr, _requests) = mock_vault(None);
+        let started = std::time::Instant::now();
+        let timeout = Duration::from_millis(200);
+        let error = write_to(
+            &addr,
+            "s.token",
+            "kv/app",
+            &fields,
+            KvVersion::V1(),
+            timeout,
+        )
+        .unwrap_err();
+        assert!(started.elapsed() < Duration::from_secs(4), "{}", error);
+
+        // tokens which would break the headers and unsupported addresses are refused unsent
+        for token in ["", "s.a\r\nX-Injected: 1", "s.a b"] {
+            let error =
+                write_to(&addr, token, "kv/app", &fields, KvVersion::V1(), timeout).unwrap_err();
+            assert!(error.contains("VAULT_TOKEN"), "{}", error);
+        }
+        for addr in ["ftp://127.0.0.1", "127.0.0.1:8200", "http://10.0.0.1:8200"] {
+            assert!(write_to(addr, "s.a", "kv/app", &fields, KvVersion::V1(), timeout).is_err());
+        }
+    }
+}
@@ -0,0 +1,131 @@
+//! The `--format external-program` contract, as seen by Terraform's external data source: the
+//! program receives the query as a JSON object on standard input, must print a single JSON
+//! object of string values on standard output, and fails the data source with a non-zero exit
+//! status.
+#![cfg(unix)]
+
+use std::process::Command;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Stands in for the external data source: it sends the query to crypticli and passes on its
+/// output and exit status.
+const SCRIPT: &str = r#"#!/bin/sh
+printf '{"name":"database"}' | "$CRYPTICLI" --format external-program "$@"
+"#;
+
+/// Runs the script with `args`, returning the exit status and the standard output.
+fn run_script(args: &[&str]) -> (i32, String) {
+    static RUNS: AtomicUsize = AtomicUsize::new(0);
+    let dir = std::env::temp_dir().join(format!("crypticli-external-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let script = dir.join(format!(
+        "data-source-{}.sh",
+        RUNS.fetch_add(1, Ordering::Relaxed)
+    ));
+    std::fs::write(&script, SCRIPT).unwrap();
+    let output = Command::new("sh")
+        .arg(&script)
+        .args(args)
+        .env("CRYPTICLI", env!("CARGO_BIN_EXE_crypticli"))
+        .output()
+        .unwrap();
+    std::fs::remove_file(&script).unwrap();
+    // the other tests may still be using the directory
+    let _ = std::fs::remove_dir(&dir);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    (output.status.code().unwrap(), stdout)
+}
+
+/// Parses a JSON object whose values are all strings, or `None` for any other document.
+fn parse_string_object(json: &str) -> Option<Vec<(String, String)>> {
+    fn string(chars: &mut std::iter::Peekable<std::str::Chars>) -> Option<String> {
+        if chars.next()? != '"' {
+            return None;
+        }
+        let mut out = String::new();
+        loop {
+            match chars.next()? {
+                '"' => return Some(out),
+                '\\' => match chars.next()? {
+                    'n' => out.push('\n'),
+                    'r' => out.push('\r'),
+                    't' => out.push('\t'),
+                    'u' => {
+                        let hex: String = chars.by_ref().take(4).collect();
+                        out.push(char::from_u32(u32::from_str_radix(&hex, 16).ok()?)?);
+                    }
+                    c @ ('"' | '\\' | '/') => out.push(c),
+                    _ => return None,
+                },
+                c if (c as u32) < 0x20 => return None,
+                c => out.push(c),
+            }
+        }
+    }
+    let mut chars = json.trim_end_matches('\n').chars().peekable();
+    let mut members = Vec::new();
+    if chars.next()? != '{' {
+        return None;
+    }
+    if chars.peek() == Some(&'}') {
+        chars.next();
+    } else {
+        loop {
+            let key = string(&mut chars)?;
+            if chars.next()? != ':' {
+                return None;
+            }
+            members.push((key, string(&mut chars)?));
+            match chars.next()? {
+                ',' => continue,
+                '}' => break,
+                _ => return None,
+            }
+        }
+    }
+    chars.next().is_none().then_some(members)
+}
+
+#[test]
+fn test_external_program_output() {
+    let (status, stdout) = run_script(&["-n", "2", "--annotate", "--mnemonic"]);
+    assert_eq!(status, 0, "{}", stdout);
+    let members = parse_string_object(&stdout).unwrap_or_else(|| panic!("{}", stdout));
+    let keys: Vec<&str> = members.iter().map(|(key, _)| key.as_str()).collect();
+    assert_eq!(
+        keys,
+        [
+            "secret_1",
+            "entropy_1",
+            "languages_1",
+            "mnemonic_1",
+            "secret_2",
+            "entropy_2",
+            "languages_2",
+            "mnemonic_2"
+        ]
+    );
+    for (key, value) in &members {
+        assert!(!value.is_empty(), "{}", key);
+        if key.starts_with("entropy") {
+            assert!(value.parse::<f64>().unwrap() > 0.0);
+        }
+    }
+
+    let (status, stdout) = run_script(&["--pattern", "dddd", "-n", "1"]);
+    assert_eq!(status, 0);
+    let members = parse_string_object(&stdout).unwrap();
+    assert_eq!(members[0].0, "secret");
+    assert!(members[0].1.chars().all(|c| c.is_ascii_digit()));
+    assert_eq!(members[1], ("entropy".to_string(), "13.29".to_string()));
+}
+
+#[test]
+fn test_external_program_failures() {
+    // the data source fails with the exit status of crypticli
+    let (status, stdout) = run_script(&["--min-entropy", "200"]);
+    assert_eq!(status, 3);
+    assert_eq!(stdout, "");
+    let (status, _) = run_script(&["--style", "klingon"]);
+    assert_eq!(status, 2);
+}
@@ -0,0 +1,11 @@
+#![no_main]
+//! Arbitrary patterns against the default model: `cargo +nightly fuzz run --features fuzz gen_from_pattern`.
+use cryptirust::Generator;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|pattern: &str| {
+    let mut generator = Generator::new();
+    generator.reseed(0);
+    let (_, entropy) = generator.gen_from_pattern(pattern);
+    assert!(entropy >= 0.0);
+});
@@ -0,0 +1,14 @@
+#![no_main]
+//! Arbitrary word lists and depths: `cargo +nightly fuzz run --features fuzz new_custom`.
+use cryptirust::{word_list, Generator};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: (u8, &[u8], &str)| {
+    let (depth, list, pattern) = input;
+    let (tokens, _) = word_list::from_bytes(list);
+    if let Some(mut generator) = Generator::new_custom(tokens, depth as usize) {
+        generator.reseed(0);
+        let (_, entropy) = generator.gen_from_pattern(pattern);
+        assert!(entropy >= 0.0);
+    }
+});
@@ -0,0 +1,121 @@
+//! ChaCha20-Poly1305 authenticated encryption (RFC 8439), used to seal histories, from the
+//! RustCrypto `chacha20poly1305` crate.
+//!
+//! Keys are 256 bits and nonces 96 bits; a nonce must never be reused with the same key.
+use chacha20poly1305::aead::AeadInPlace;
+use chacha20poly1305::{ChaCha20Poly1305, KeyInit, Tag};
+
+/// Length of a key.
+pub(crate) const KEY_LEN: usize = 32;
+/// Length of a nonce.
+pub(crate) const NONCE_LEN: usize = 12;
+/// Length of a tag.
+pub(crate) const TAG_LEN: usize = 16;
+
+/// Encrypts `data` in place under `key` and `nonce`, returning the tag authenticating it
+/// together with `aad`.
+pub(crate) fn seal(
+    key: &[u8; KEY_LEN],
+    nonce: &[u8; NONCE_LEN],
+    aad: &[u8],
+    data: &mut [u8],
+) -> [u8; TAG_LEN] {
+    ChaCha20Poly1305::new(key.into())
+        .encrypt_in_place_detached(nonce.into(), aad, data)
+        .expect("histories are far below the 256 GiB limit of a message")
+        .into()
+}
+
+/// Checks `tag` against `aad` and the encrypted `data`, and decrypts `data` in place when it
+/// matches; `false`, leaving `data` untouched, when it does not.
+pub(crate) fn open(
+    key: &[u8; KEY_LEN],
+    nonce: &[u8; NONCE_LEN],
+    aad: &[u8],
+    data: &mut [u8],
+    tag: &[u8],
+) -> bool {
+    if tag.len() != TAG_LEN {
+        return false;
+    }
+    ChaCha20Poly1305::new(key.into())
+        .decrypt_in_place_detached(nonce.into(), aad, data, Tag::from_slice(tag))
+        .is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::history::{History, HistoryError};
+    use crate::kdf::KdfParams;
+
+    #[test]
+    fn test_seal_and_open() {
+        let key = [7; KEY_LEN];
+        let nonce = [1; NONCE_LEN];
+        let plaintext = b"salted hashes of the secrets".to_vec();
+        let mut data = plaintext.clone();
+        let tag = seal(&key, &nonce, b"header", &mut data);
+        assert_ne!(data, plaintext);
+        let sealed = data.clone();
+        assert!(open(&key, &nonce, b"header", &mut data, &tag));
+        assert_eq!(data, plaintext);
+
+        // every nonce gives another ciphertext and tag, and only opens its own
+        let mut again = plaintext.clone();
+        let other = [2; NONCE_LEN];
+        let other_tag = seal(&key, &other, b"header", &mut again);
+        assert_ne!((&again, other_tag), (&sealed, tag));
+        assert!(!open(&key, &other, b"header", &mut sealed.clone(), &tag));
+        assert!(open(&key, &other, b"header", &mut again, &other_tag));
+
+        // empty messages are authenticated too
+        let tag = seal(&key, &nonce, b"header", &mut []);
+        assert!(open(&key, &nonce, b"header", &mut [], &tag));
+        assert!(!open(&key, &nonce, b"other", &mut [], &tag));
+    }
+
+    #[test]
+    fn test_tampering() {
+        let key = [7; KEY_LEN];
+        let nonce = [1; NONCE_LEN];
+        let mut sealed = b"salted hashes of the secrets".to_vec();
+        let tag = seal(&key, &nonce, b"header", &mut sealed);
+        // tampered ciphertexts, associated data, tags and keys are refused, leaving the data
+        // untouched
+        let mut flipped = sealed.clone();
+        flipped[3] ^= 0x20;
+        let mut data = flipped.clone();
+        assert!(!open(&key, &nonce, b"header", &mut data, &tag));
+        assert_eq!(data, flipped);
+        let mut data = sealed.clone();
+        assert!(!open(&key, &nonce, b"Header", &mut data, &tag));
+        assert!(!open(&key, &nonce, b"", &mut data, &tag));
+        assert!(!open(&[8; KEY_LEN], &nonce, b"header", &mut data, &tag));
+        let mut bad_tag = tag;
+        bad_tag[TAG_LEN - 1] ^= 1;
+        assert!(!open(&key, &nonce, b"header", &mut data, &bad_tag));
+        assert!(!open(
+            &key,
+            &nonce,
+            b"header",
+            &mut data,
+            &tag[..TAG_LEN - 1]
+        ));
+        assert!(!open(&key, &nonce, b"header", &mut data, &[]));
+        assert_eq!(data, sealed);
+
+        // histories report any of them as an authentication failure
+        let history = History::new("passphrase", KdfParams { iterations: 1000 }).unwrap();
+        let bytes = history.to_bytes();
+        assert!(History::open(&bytes, "passphrase").is_ok());
+        for at in [0x0b, bytes.len() - 1] {
+            let mut tampered = bytes.clone();
+            tampered[at] ^= 1;
+            assert_eq!(
+                History::open(&tampered, "passphrase").err(),
+                Some(HistoryError::Authentication)
+            );
+        }
+    }
+}
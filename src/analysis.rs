@@ -0,0 +1,1525 @@
+//! Strength analysis of secrets.
+//!
+//! # Secrets made of several components
+//!
+//! When a secret is split across channels (e.g. a passphrase sent by mail and a PIN sent by
+//! SMS) the strength of the whole depends on which components an attacker can observe. The
+//! helpers below compute the figures usually requested during audits from the entropies
+//! returned by [`Generator::gen_from_pattern`](crate::Generator::gen_from_pattern).
+//!
+//! ```rust
+//! use cryptirust::analysis;
+//!
+//! let passphrase = 52.3;
+//! let pin = 19.9;
+//! assert_eq!(analysis::combined_entropy(&[passphrase, pin]), passphrase + pin);
+//! // the attacker intercepted the SMS carrying the PIN
+//! assert_eq!(analysis::entropy_given_known(&[passphrase, pin], &[1]), passphrase);
+//! // whichever channel leaks, at least this much entropy is left
+//! assert!((analysis::worst_single_compromise(&[passphrase, pin]) - pin).abs() < 1e-9);
+//! ```
+//!
+//! The same question arises within a single secret, e.g. when the digits at the end of a
+//! passphrase were seen over a shoulder: [`residual_entropy`] computes the entropy left once
+//! some elements of the pattern are known.
+//!
+//! ```rust
+//! use cryptirust::{analysis, pattern::Pattern, Generator};
+//!
+//! let generator = Generator::new();
+//! let pattern = Pattern::parse("w-w-dd");
+//! let all = pattern.expected_entropy(&generator).unwrap();
+//! // elements 4 and 5 are the two digits
+//! let left = analysis::residual_entropy(&generator, &pattern, &[4, 5]).unwrap();
+//! assert!((all - left - 2.0 * 10f64.log2()).abs() < 1e-9);
+//! ```
+//!
+//! # Passwords of unknown origin
+//!
+//! The entropy of passwords which were not generated by this crate can only be estimated,
+//! [`estimate`] gives a quick estimate based on the character classes in use and on the
+//! dictionary words found, also when letters are written as digits or symbols.
+//!
+//! ```rust
+//! use cryptirust::analysis::{self, Strength};
+//!
+//! assert_eq!(analysis::estimate("aaaaaaaa").strength, Strength::VeryWeak);
+//! assert_eq!(analysis::estimate("k7#Qv9!mZ2xR").strength, Strength::Strong);
+//! // a dictionary word with a capital letter, two substitutions, a symbol and a digit
+//! assert!(analysis::estimate("Tr0ub4dor&3").strength <= Strength::Weak);
+//! ```
+//!
+//! A password is only as strong as what the receiving system keeps of it: bcrypt ignores
+//! everything past 72 bytes, forms trim surrounding spaces, and systems which normalize
+//! Unicode can turn two spellings of the same text into one. [`portability_warnings`] tells
+//! which of these can silently change a password.
+//!
+//! ```rust
+//! use cryptirust::analysis::{self, PortabilityWarning};
+//!
+//! assert_eq!(
+//!     analysis::portability_warnings(" cafe"),
+//!     [PortabilityWarning::SurroundingWhitespace]
+//! );
+//! assert_eq!(
+//!     analysis::portability_warnings("cafe\u{301}"),
+//!     [PortabilityWarning::Normalization]
+//! );
+//! ```
+//!
+//! [`Strength::name`] and [`PortabilityWarning::description`] are in English, the
+//! [`messages`](crate::messages) module has them in other languages.
+//!
+//! # Passphrases chosen by people
+//!
+//! [`check_user_passphrase`] reviews a passphrase typed by a person rather than generated:
+//! it looks for common and blocklisted passwords, dictionary words and letters the markov
+//! model finds likely, and when the passphrase falls short of [`PASSPHRASE_TARGET_BITS`] it
+//! generates the fix, random words to append or a whole new passphrase.
+//!
+//! ```rust
+//! use cryptirust::analysis::{self, PassphraseIssue, Strength};
+//! use cryptirust::Generator;
+//!
+//! let mut generator = Generator::new();
+//! let check = analysis::check_user_passphrase(&mut generator, "Password1!", None);
+//! assert_eq!(check.strength, Strength::VeryWeak);
+//! assert!(check.issues.contains(&PassphraseIssue::Blocklisted));
+//! for suggestion in &check.suggestions {
+//!     println!("{}", suggestion.description());
+//! }
+//! ```
+//!
+//! Rather than replacing a weak passphrase, [`suggest_strengthening`] keeps it and proposes
+//! random words, symbols or digits to add, each with the exact entropy it brings.
+//!
+//! ```rust
+//! use cryptirust::{analysis, Generator};
+//!
+//! let mut generator = Generator::new();
+//! for proposal in analysis::suggest_strengthening(&mut generator, "Summer2024!", 60.0) {
+//!     assert!(proposal.bits >= 60.0);
+//!     println!("{} (+{:.1} bits)", proposal.passphrase, proposal.added_bits);
+//! }
+//! ```
+//!
+//! # Typing cost
+//!
+//! Secrets typed on phones are a chore when they keep switching between letters, digits and
+//! symbols; [`typing_cost`] counts the taps needed, so entropy can be traded against thumb
+//! effort.
+//!
+//! ```rust
+//! use cryptirust::analysis;
+//!
+//! assert_eq!(analysis::typing_cost("abc 123"), 8);
+//! assert_eq!(analysis::typing_cost("a1b2c3"), 11);
+//! ```
+//!
+//! [`typing_effort`] extends the count to desktop layouts, where the effort comes from the
+//! shift and `AltGr` modifiers and from dead keys.
+//!
+//! ```rust
+//! use cryptirust::{analysis, layout::Layout};
+//!
+//! assert_eq!(analysis::typing_effort("a@b", Layout::Us), 4);
+//! assert_eq!(analysis::typing_effort("a@b", Layout::De), 4);
+//! assert_eq!(analysis::typing_effort("a^b", Layout::De), 4);
+//! assert_eq!(analysis::typing_effort("a^b", Layout::Us), 4);
+//! assert_eq!(analysis::typing_effort("a-b", Layout::Us), 3);
+//! ```
+//!
+//! # Guess numbers
+//!
+//! The entropy of a generated secret is the average number of guesses needed, in bits, by an
+//! attacker who knows the model. Attackers however try the most probable outputs first, and
+//! [`guess_rank`] estimates how many guesses they need for a given secret under that strategy.
+//!
+//! ```rust
+//! use cryptirust::{analysis, pattern::Pattern, Generator};
+//!
+//! let mut generator = Generator::new();
+//! let pattern = Pattern::parse("w-dd");
+//! let (secret, entropy) = generator.gen_from_parsed(&pattern);
+//! let rank = analysis::guess_rank(&mut generator, &pattern, &secret).unwrap();
+//! println!("{}: {:.1} bits of entropy, found after 2^{:.1} guesses", secret, entropy, rank);
+//! ```
+use crate::layout::Layout;
+use crate::messages::ENGLISH;
+use crate::normalize::{normalize, NormalizationForm};
+use crate::pattern::Pattern;
+use crate::store::HistoryStore;
+use crate::word_list;
+use crate::Generator;
+use rand::{CryptoRng, Rng, RngCore, SeedableRng};
+use std::collections::HashSet;
+use std::sync::OnceLock;
+
+/// Number of outputs sampled by [`guess_rank`].
+pub const GUESS_RANK_SAMPLES: usize = 2000;
+
+/// Seed of the samples drawn by [`guess_rank`], so that estimates are reproducible.
+const GUESS_RANK_SEED: u64 = 0x6E55;
+
+/// Estimated rank, as `log2`, of `password` in the outputs of `pattern` sorted from the most
+/// to the least probable: the number of guesses an attacker who knows the word list, the
+/// pattern and the settings of `generator` needs to find it, trying outputs best-first.
+///
+/// The rank is estimated by Monte Carlo sampling of the model (Dell'Amico and Filippone,
+/// 2015), which is unbiased and much cheaper than enumerating the outputs: each of the
+/// [`GUESS_RANK_SAMPLES`] outputs more probable than `password` stands for `1 / (n p)`
+/// outputs, `p` being its [exact](Generator::exact_entropy) probability. The samples use
+/// their own random number generator, of the same type as that of `generator` with a fixed
+/// seed and no buffered draws; the state of `generator`, its buffered draws, sequence position
+/// and metrics included, is left untouched.
+///
+/// Returns `None` when `password` cannot be generated from `pattern`, or when
+/// [`Generator::exact_entropy`] is not available with the settings of `generator`.
+pub fn guess_rank<R: RngCore + CryptoRng + SeedableRng>(
+    generator: &mut Generator<R>,
+    pattern: &Pattern,
+    password: &str,
+) -> Option<f64> {
+    let entropy = generator.exact_entropy(pattern, password)?;
+    let seeded = R::seed_from_u64(GUESS_RANK_SEED);
+    let rng = std::mem::replace(&mut generator.rng, seeded);
+    let transcript = generator.transcript.take();
+    let demo = std::mem::replace(&mut generator.demo, false);
+    let exact = std::mem::replace(&mut generator.exact_entropy, true);
+    let draws = std::mem::take(&mut generator.draws);
+    let sequence = std::mem::replace(&mut generator.sequence, 0);
+    let metrics = generator.metrics.take();
+    let mut rank = 0.0;
+    for _ in 0..GUESS_RANK_SAMPLES {
+        let (_, h) = generator.gen_from_parsed(pattern);
+        if h < entropy {
+            rank += h.exp2() / GUESS_RANK_SAMPLES as f64;
+        }
+    }
+    generator.rng = rng;
+    generator.transcript = transcript;
+    generator.demo = demo;
+    generator.exact_entropy = exact;
+    generator.draws = draws;
+    generator.sequence = sequence;
+    generator.metrics = metrics;
+    Some(f64::max(rank, 1.0).log2())
+}
+
+/// Entropy of a secret made of independently generated components, i.e. the sum of their
+/// entropies.
+pub fn combined_entropy(components: &[f64]) -> f64 {
+    components.iter().sum()
+}
+
+/// Entropy left to an attacker who knows the components at the indices in `known`.
+///
+/// Out of range indices are ignored.
+pub fn entropy_given_known(components: &[f64], known: &[usize]) -> f64 {
+    components
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| !known.contains(i))
+        .map(|(_, h)| h)
+        .sum()
+}
+
+/// Expected entropy of the secrets `generator` produces from `pattern`, left to an attacker
+/// who knows the output of the elements at the indices in `known`, indices into
+/// [`Pattern::segments`]. Out of range indices are ignored.
+///
+/// Each unknown element counts for its expected entropy given the elements before it, see
+/// [`Pattern::expected_entropy`]. When a known element also tells something about the
+/// elements before it, as a word tells about the end of the previous one when they are not
+/// separated, the attacker can do somewhat better than this figure.
+///
+/// Returns `None` when [`Pattern::expected_entropy`] does.
+pub fn residual_entropy<R: RngCore + CryptoRng>(
+    generator: &Generator<R>,
+    pattern: &Pattern,
+    known: &[usize],
+) -> Option<f64> {
+    let entropies = pattern.segment_entropies(generator)?;
+    Some(entropy_given_known(&entropies, known))
+}
+
+/// Entropy left in the worst case where exactly one component is compromised, i.e. the
+/// combined entropy minus that of the strongest component.
+///
+/// Returns `0.0` for secrets with fewer than two components.
+pub fn worst_single_compromise(components: &[f64]) -> f64 {
+    let strongest = components.iter().cloned().fold(0.0, f64::max);
+    (combined_entropy(components) - strongest).max(0.0)
+}
+
+/// Qualitative strength classes, with the usual entropy thresholds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Strength {
+    /// Less than 28 bits.
+    VeryWeak,
+    /// From 28 to 36 bits.
+    Weak,
+    /// From 36 to 60 bits.
+    Fair,
+    /// From 60 to 128 bits.
+    Strong,
+    /// 128 bits or more.
+    VeryStrong,
+}
+
+impl Strength {
+    /// Classifies an entropy expressed in bits.
+    pub fn from_bits(bits: f64) -> Strength {
+        if bits < 28.0 {
+            Strength::VeryWeak
+        } else if bits < 36.0 {
+            Strength::Weak
+        } else if bits < 60.0 {
+            Strength::Fair
+        } else if bits < 128.0 {
+            Strength::Strong
+        } else {
+            Strength::VeryStrong
+        }
+    }
+
+    /// Lowercase, human readable name of the class, in English; see
+    /// [`Catalog::strength`](crate::messages::Catalog::strength) for other languages.
+    pub fn name(&self) -> &'static str {
+        ENGLISH.strength(*self)
+    }
+}
+
+/// Entropy estimate of a password, see [`estimate`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Estimate {
+    /// Estimated entropy in bits.
+    pub bits: f64,
+    pub strength: Strength,
+}
+
+/// Base words of leaked passwords, detected by [`estimate`] along with the words of the
+/// [default list](word_list::default_list) and the [`COMMON_PASSWORDS`].
+const COMMON_WORDS: &[&str] = &[
+    "admin",
+    "america",
+    "angel",
+    "apple",
+    "arsenal",
+    "ashley",
+    "august",
+    "autumn",
+    "banana",
+    "batman",
+    "berlin",
+    "buster",
+    "butterfly",
+    "canada",
+    "charlie",
+    "chelsea",
+    "chocolate",
+    "computer",
+    "cookie",
+    "daniel",
+    "december",
+    "diamond",
+    "dragon",
+    "family",
+    "flower",
+    "forever",
+    "freedom",
+    "friday",
+    "ginger",
+    "golden",
+    "google",
+    "guest",
+    "hello",
+    "hockey",
+    "hunter",
+    "internet",
+    "january",
+    "jessica",
+    "jordan",
+    "killer",
+    "liverpool",
+    "login",
+    "london",
+    "love",
+    "lovely",
+    "matrix",
+    "michael",
+    "monday",
+    "monkey",
+    "mustang",
+    "naruto",
+    "november",
+    "october",
+    "orange",
+    "paris",
+    "pass",
+    "pepper",
+    "phoenix",
+    "pokemon",
+    "purple",
+    "ranger",
+    "root",
+    "samsung",
+    "secret",
+    "september",
+    "silver",
+    "soccer",
+    "spiderman",
+    "spring",
+    "summer",
+    "sunday",
+    "superman",
+    "thomas",
+    "tigger",
+    "troubador",
+    "troubadour",
+    "user",
+    "winter",
+    "word",
+];
+
+/// Shortest word detected by [`estimate`], in characters.
+const MIN_WORD_CHARS: usize = 4;
+
+/// Letters commonly written as digits or symbols, undone by [`estimate`]; `1` stands for
+/// both `i` and `l`.
+const LEET: &[(char, &str)] = &[
+    ('0', "o"),
+    ('1', "il"),
+    ('3', "e"),
+    ('4', "a"),
+    ('5', "s"),
+    ('7', "t"),
+    ('8', "b"),
+    ('9', "g"),
+    ('@', "a"),
+    ('$', "s"),
+    ('!', "i"),
+    ('+', "t"),
+];
+
+/// Characters separating the words of a passphrase, counted by [`estimate`] as
+/// [`SEPARATOR_BITS`] each rather than as a character of the pool.
+const SEPARATORS: &[char] = &[' ', '-', '.', '_'];
+
+/// Entropy of a separator in [`estimate`], in bits: people pick few of them and tend to use
+/// the same one throughout.
+const SEPARATOR_BITS: f64 = 1.0;
+
+/// Most spellings of a stretch of a password tried by [`estimate`], bounding the ambiguous
+/// substitutions.
+const MAX_SPELLINGS: usize = 16;
+
+/// Lowercase words detected by [`estimate`] and the length of the longest, built once.
+fn estimate_words() -> &'static (HashSet<String>, usize) {
+    static WORDS: OnceLock<(HashSet<String>, usize)> = OnceLock::new();
+    WORDS.get_or_init(|| {
+        let words: HashSet<String> = COMMON_WORDS
+            .iter()
+            .chain(COMMON_PASSWORDS)
+            .map(|word| word.to_string())
+            .chain(dictionary().iter().cloned())
+            .filter(|word| {
+                word.chars().count() >= MIN_WORD_CHARS && word.chars().all(char::is_alphabetic)
+            })
+            .collect();
+        let longest = words.iter().map(|word| word.chars().count()).max();
+        (words, longest.unwrap_or(0))
+    })
+}
+
+/// Entropy of `text` as a word of `words`, possibly capitalized and with letters written as
+/// [`LEET`] substitutions, or `None` when it is not one: a random word of the list, one bit
+/// for the capitalization and one bit for each substitution.
+fn word_bits(text: &[char], words: &HashSet<String>) -> Option<f64> {
+    if !text.iter().any(|c| c.is_alphabetic()) {
+        return None;
+    }
+    let mut spellings = vec![String::new()];
+    let mut substitutions = 0;
+    for &c in text {
+        if c.is_alphabetic() {
+            for spelling in &mut spellings {
+                spelling.extend(c.to_lowercase());
+            }
+            continue;
+        }
+        let (_, letters) = LEET.iter().find(|(leet, _)| *leet == c)?;
+        substitutions += 1;
+        let letters = if spellings.len() * letters.len() > MAX_SPELLINGS {
+            &letters[..1]
+        } else {
+            letters
+        };
+        spellings = spellings
+            .iter()
+            .flat_map(|spelling| letters.chars().map(move |l| format!("{}{}", spelling, l)))
+            .collect();
+    }
+    spellings
+        .iter()
+        .any(|spelling| words.contains(spelling))
+        .then(|| {
+            let capitalized = text.iter().any(|c| c.is_uppercase());
+            (words.len() as f64).log2() + f64::from(u8::from(capitalized)) + substitutions as f64
+        })
+}
+
+/// Lowest entropy of `chars` over the ways of splitting them into words and single
+/// characters, see [`estimate`]; `per_char` is the entropy of a character of the pool.
+fn lowest_bits(chars: &[char], per_char: f64) -> f64 {
+    let (words, longest) = estimate_words();
+    // lowest estimate of the first i characters
+    let mut best = vec![f64::INFINITY; chars.len() + 1];
+    best[0] = 0.0;
+    for i in 0..chars.len() {
+        let code = chars[i] as u32;
+        let predictable = i > 0 && {
+            let p = chars[i - 1] as u32;
+            p == code || p + 1 == code || code + 1 == p
+        };
+        let bits = best[i]
+            + if predictable {
+                1.0
+            } else if SEPARATORS.contains(&chars[i]) {
+                SEPARATOR_BITS
+            } else {
+                per_char
+            };
+        best[i + 1] = best[i + 1].min(bits);
+        // words never span a separator
+        let end = chars[i..]
+            .iter()
+            .position(|c| SEPARATORS.contains(c))
+            .map_or(chars.len(), |at| i + at);
+        for end in i + MIN_WORD_CHARS..=end.min(i + longest) {
+            if let Some(h) = word_bits(&chars[i..end], words) {
+                best[end] = best[end].min(best[i] + h);
+            }
+        }
+    }
+    best[chars.len()]
+}
+
+/// Estimates the entropy of a password of unknown origin.
+///
+/// Every character counts as a uniform choice among the character classes used by the
+/// password (lowercase, uppercase, digits, ASCII symbols, other characters), except characters
+/// repeating the previous one or continuing an ascending or descending run (`abc`, `321`),
+/// which count as a single bit. Spaces, hyphens, dots and underscores separate words: each
+/// counts as a single bit, none adds its class to the pool, and the password is also read
+/// without them, so that a common password spaced out stays weak. Words of four letters or more
+/// found in the [default list](word_list::default_list) or among the base words of leaked
+/// passwords count as a random word of those, plus a bit for capital letters and a bit for
+/// each letter written as a digit or a symbol (`Tr0ub4dor`): the password is split in the way
+/// giving the lowest estimate. People choose words far from uniformly, so the estimate remains
+/// an upper bound for human-chosen passwords; prefer the entropy reported by the generator for
+/// secrets generated by this crate.
+pub fn estimate(password: &str) -> Estimate {
+    let chars: Vec<char> = password.chars().collect();
+    let has = |class: fn(&char) -> bool| chars.iter().any(|c| !SEPARATORS.contains(c) && class(c));
+    let pool = [
+        (has(char::is_ascii_lowercase), 26),
+        (has(char::is_ascii_uppercase), 26),
+        (has(char::is_ascii_digit), 10),
+        (has(char::is_ascii_punctuation), 32),
+        (has(|c| !c.is_ascii() || c.is_ascii_whitespace()), 100),
+    ]
+    .iter()
+    .filter(|(used, _)| *used)
+    .map(|(_, size)| size)
+    .sum::<usize>();
+    let per_char = (pool.max(1) as f64).log2();
+    let mut bits = lowest_bits(&chars, per_char);
+    // the words may also be read across the separators, as in a common password spaced out
+    let joined: Vec<char> = chars
+        .iter()
+        .copied()
+        .filter(|c| !SEPARATORS.contains(c))
+        .collect();
+    if joined.len() < chars.len() {
+        let separators = (chars.len() - joined.len()) as f64;
+        bits = bits.min(lowest_bits(&joined, per_char) + separators * SEPARATOR_BITS);
+    }
+    Estimate {
+        bits,
+        strength: Strength::from_bits(bits),
+    }
+}
+
+/// Bytes of a password considered by bcrypt, which ignores the following ones.
+pub const BCRYPT_MAX_BYTES: usize = 72;
+
+/// Ways a password can be silently changed by the systems it is given to, see
+/// [`portability_warnings`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PortabilityWarning {
+    /// The password is longer than [`BCRYPT_MAX_BYTES`] bytes in UTF-8, so bcrypt hashes only
+    /// a prefix of it; characters outside ASCII take several bytes.
+    BcryptTruncation {
+        /// Length of the password in UTF-8, in bytes.
+        bytes: usize,
+    },
+    /// The password starts or ends with whitespace, which forms and terminals often trim.
+    SurroundingWhitespace,
+    /// The password has characters which Unicode normalization (NFC, NFD or NFKC) replaces,
+    /// such as accented letters, combining accents, ligatures or full-width forms, so that it
+    /// can be stored in another form than the one typed, or typed in another form than the one
+    /// stored.
+    Normalization,
+}
+
+impl PortabilityWarning {
+    /// Human readable description of the warning, in English; see
+    /// [`Catalog::warning`](crate::messages::Catalog::warning) for other languages.
+    pub fn description(&self) -> String {
+        ENGLISH.warning(self)
+    }
+}
+
+/// Whether NFKC replaces `c`, for the common compatibility characters; canonical
+/// normalization is checked with [`normalize`], this covers the usual blocks rather than the
+/// whole Unicode database.
+fn compatibility(c: char) -> bool {
+    matches!(c,
+        '\u{00A0}' // no-break space
+        | '\u{00AA}' | '\u{00B2}' | '\u{00B3}' | '\u{00B5}' | '\u{00B9}' | '\u{00BA}'
+        | '\u{00BC}'..='\u{00BE}'
+        | '\u{2000}'..='\u{200A}' // spaces of various widths
+        | '\u{2070}'..='\u{209F}' // superscripts and subscripts
+        | '\u{2100}'..='\u{214F}' // letterlike symbols
+        | '\u{2150}'..='\u{218F}' // number forms, roman numerals
+        | '\u{2460}'..='\u{24FF}' // enclosed alphanumerics
+        | '\u{3000}' // ideographic space
+        | '\u{FB00}'..='\u{FB4F}' // ligatures
+        | '\u{FF01}'..='\u{FFEE}' // full-width and half-width forms
+    )
+}
+
+/// Ways in which `password` can be silently changed by other systems, see
+/// [`PortabilityWarning`]; empty for plain ASCII passwords of at most
+/// [`BCRYPT_MAX_BYTES`] characters without surrounding spaces.
+pub fn portability_warnings(password: &str) -> Vec<PortabilityWarning> {
+    let mut warnings = Vec::new();
+    if password.len() > BCRYPT_MAX_BYTES {
+        warnings.push(PortabilityWarning::BcryptTruncation {
+            bytes: password.len(),
+        });
+    }
+    if password.trim() != password {
+        warnings.push(PortabilityWarning::SurroundingWhitespace);
+    }
+    let stable = |form| normalize(password, form) == password;
+    if !stable(NormalizationForm::Nfc)
+        || !stable(NormalizationForm::Nfd)
+        || password.chars().any(compatibility)
+    {
+        warnings.push(PortabilityWarning::Normalization);
+    }
+    warnings
+}
+
+/// Keyboard page of a phone keyboard holding a character.
+#[derive(Clone, Copy, PartialEq)]
+enum Page {
+    Letters,
+    Numbers,
+    Symbols,
+}
+
+/// Page holding `c`, `None` for the space bar which is on every page.
+fn page(c: char) -> Option<Page> {
+    if c == ' ' {
+        None
+    } else if c.is_ascii_digit() || c == '"' || c == '\'' || Layout::Mobile.symbols().contains(c) {
+        Some(Page::Numbers)
+    } else if c.is_ascii_punctuation() {
+        Some(Page::Symbols)
+    } else {
+        Some(Page::Letters)
+    }
+}
+
+/// Number of taps needed to type `password` on a phone keyboard.
+///
+/// Every character takes a tap, plus a tap on shift for uppercase letters and a long press
+/// (counted as a tap) for characters which are not plain ASCII. Digits and the symbols of
+/// [`Layout::Mobile`] sit on the first symbol page, one tap away from the letters; the other
+/// symbols sit on the second symbol page, one tap further. The space bar is on every page.
+pub fn typing_cost(password: &str) -> usize {
+    let mut current = Page::Letters;
+    let mut taps = 0;
+    for c in password.chars() {
+        let Some(target) = page(c) else {
+            taps += 1;
+            continue;
+        };
+        taps += match (current, target) {
+            (a, b) if a == b => 0,
+            (Page::Letters, Page::Symbols) => 2,
+            _ => 1,
+        };
+        current = target;
+        taps += 1;
+        if c.is_uppercase() || !c.is_ascii() {
+            taps += 1;
+        }
+    }
+    taps
+}
+
+/// Keystrokes needed to type `c` on a desktop `layout`, `None` when the layout has no key for
+/// it.
+fn keystrokes(c: char, layout: Layout) -> Option<usize> {
+    // characters typed without modifiers, with shift, with AltGr, and dead keys (followed by a
+    // space to type the character alone)
+    let (plain, shifted, altgr, dead) = match layout {
+        Layout::Us | Layout::Mobile => ("-=[]\\;',./`", "~!@#$%^&*()_+{}|:\"<>?", "", ""),
+        Layout::Uk => ("-=[];'#,./`\\", "!\"£$%^&*()_+{}:@~<>?|¬", "¦€", ""),
+        Layout::De => ("ßüöä+#-.,<", "!\"§$%&/()=?*'_:;>°", "@€{[]}\\~|²³µ", "^´`"),
+        Layout::It => ("'ìè+òàù-.,<\\", "|!\"£$%&/()=?^éç*°§_:;>", "@#[]{}€~", ""),
+    };
+    let unshifted = c.to_lowercase().next().unwrap_or(c);
+    let shift = unshifted != c && (unshifted.is_ascii_lowercase() || plain.contains(unshifted));
+    let count = if c == ' ' || c.is_ascii_lowercase() || c.is_ascii_digit() || plain.contains(c) {
+        1
+    } else if shift || shifted.contains(c) || altgr.contains(c) {
+        2
+    } else if c == '`' && dead.contains(c) {
+        // shift, then the dead key, then space
+        3
+    } else if dead.contains(c) {
+        2
+    } else {
+        return None;
+    };
+    Some(count)
+}
+
+/// Number of keystrokes needed to type `password` on `layout`, counting modifier presses.
+///
+/// On desktop layouts every character takes a keystroke, plus one for shift or `AltGr`; dead
+/// keys take an extra space. Characters without a key on the layout count as
+/// [`UNTYPABLE_EFFORT`] keystrokes, the cost of a compose sequence. For [`Layout::Mobile`]
+/// this is [`typing_cost`].
+pub fn typing_effort(password: &str, layout: Layout) -> usize {
+    if layout == Layout::Mobile {
+        return typing_cost(password);
+    }
+    password
+        .chars()
+        .map(|c| keystrokes(c, layout).unwrap_or(UNTYPABLE_EFFORT))
+        .sum()
+}
+
+/// Keystrokes charged by [`typing_effort`] for characters missing from the layout.
+pub const UNTYPABLE_EFFORT: usize = 4;
+
+/// Length under which [`check_user_passphrase`] reports a passphrase as too short, in
+/// characters.
+pub const MIN_PASSPHRASE_CHARS: usize = 12;
+
+/// Entropy reached by the suggestions of [`check_user_passphrase`], in bits: the lower bound
+/// of [`Strength::Strong`].
+pub const PASSPHRASE_TARGET_BITS: f64 = 60.0;
+
+/// Passwords found at the top of every leak, compared with the letters and digits of a
+/// passphrase in lowercase.
+const COMMON_PASSWORDS: &[&str] = &[
+    "123456",
+    "12345678",
+    "123456789",
+    "111111",
+    "abc123",
+    "admin",
+    "baseball",
+    "changeme",
+    "correcthorsebatterystaple",
+    "dragon",
+    "football",
+    "freedom",
+    "hunter2",
+    "iloveyou",
+    "letmein",
+    "master",
+    "monkey",
+    "opensesame",
+    "passw0rd",
+    "password",
+    "password1",
+    "princess",
+    "qwerty",
+    "qwertyuiop",
+    "secret",
+    "shadow",
+    "starwars",
+    "sunshine",
+    "superman",
+    "trustno1",
+    "welcome",
+    "whatever",
+];
+
+/// A weakness of a passphrase chosen by a person, see [`check_user_passphrase`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PassphraseIssue {
+    /// The passphrase is shorter than [`MIN_PASSPHRASE_CHARS`] characters.
+    TooShort {
+        /// Length of the passphrase, in characters.
+        chars: usize,
+    },
+    /// The passphrase is a common password, or is in the blocklist.
+    Blocklisted,
+    /// Words of the passphrase are in the dictionary, which attackers try before random
+    /// strings; each counts as a random word of the dictionary, although people rarely pick
+    /// them at random.
+    DictionaryWords {
+        /// The words, as written in the passphrase.
+        words: Vec<String>,
+    },
+    /// Letters of the passphrase which are not dictionary words follow sequences the markov
+    /// model finds likely, so they carry less entropy than random letters.
+    Predictable {
+        /// The runs of letters, as written in the passphrase.
+        words: Vec<String>,
+    },
+}
+
+impl PassphraseIssue {
+    /// Human readable description of the issue, in English; see
+    /// [`Catalog::issue`](crate::messages::Catalog::issue) for other languages.
+    pub fn description(&self) -> String {
+        ENGLISH.issue(self)
+    }
+}
+
+/// A way to strengthen a passphrase, with text generated for it, see
+/// [`check_user_passphrase`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum Suggestion {
+    /// Append random words to the passphrase.
+    AppendWords {
+        /// The words, separated by hyphens.
+        words: String,
+        /// Number of words.
+        count: usize,
+        /// Entropy of the words, in bits.
+        bits: f64,
+    },
+    /// Replace the passphrase with a generated one.
+    Replace {
+        /// The generated passphrase.
+        passphrase: String,
+        /// Entropy of the generated passphrase, in bits.
+        bits: f64,
+    },
+}
+
+impl Suggestion {
+    /// Human readable description of the suggestion, in English; see
+    /// [`Catalog::suggestion`](crate::messages::Catalog::suggestion) for other languages.
+    pub fn description(&self) -> String {
+        ENGLISH.suggestion(self)
+    }
+}
+
+/// Findings of [`check_user_passphrase`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct PassphraseCheck {
+    /// Estimated entropy of the passphrase, in bits; zero for blocklisted passphrases.
+    pub bits: f64,
+    pub strength: Strength,
+    pub issues: Vec<PassphraseIssue>,
+    /// Ways to reach [`PASSPHRASE_TARGET_BITS`], empty when the passphrase reaches them.
+    pub suggestions: Vec<Suggestion>,
+}
+
+/// Lowercase words of the [default list](word_list::default_list), built once.
+fn dictionary() -> &'static HashSet<String> {
+    static DICTIONARY: OnceLock<HashSet<String>> = OnceLock::new();
+    DICTIONARY.get_or_init(|| {
+        word_list::default_list()
+            .into_iter()
+            .map(|word| word.to_lowercase())
+            .collect()
+    })
+}
+
+/// Whether `passphrase` is one of [`COMMON_PASSWORDS`], by its letters and digits in
+/// lowercase.
+fn is_common(passphrase: &str) -> bool {
+    let squashed: String = passphrase
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect();
+    COMMON_PASSWORDS.contains(&squashed.as_str())
+}
+
+/// Splits `text` into runs of letters, of digits and of other characters.
+fn class_runs(text: &str) -> Vec<&str> {
+    let class = |c: char| (c.is_alphabetic(), c.is_numeric());
+    let mut runs = Vec::new();
+    let mut rest = text;
+    while let Some(first) = rest.chars().next() {
+        let len = rest
+            .find(|c: char| class(c) != class(first))
+            .unwrap_or(rest.len());
+        runs.push(&rest[..len]);
+        rest = &rest[len..];
+    }
+    runs
+}
+
+/// What [`score_run`] found in a run of characters.
+enum RunKind {
+    Dictionary,
+    Predictable,
+    Other,
+}
+
+/// Entropy of a run of [`class_runs`], as counted by [`check_user_passphrase`].
+fn score_run<R: RngCore + CryptoRng>(generator: &Generator<R>, run: &str) -> (f64, RunKind) {
+    let estimated = estimate(run).bits;
+    if !run.starts_with(char::is_alphabetic) {
+        return (estimated, RunKind::Other);
+    }
+    let dictionary = dictionary();
+    let lower = run.to_lowercase();
+    if dictionary.contains(&lower) {
+        return ((dictionary.len() as f64).log2(), RunKind::Dictionary);
+    }
+    match generator.token_run_entropy(&lower) {
+        Some(modeled) if modeled < estimated => (modeled, RunKind::Predictable),
+        _ => (estimated, RunKind::Other),
+    }
+}
+
+/// Checks a passphrase chosen by a person, e.g. on a sign-up form, and suggests how to
+/// strengthen it with text generated by `generator`.
+///
+/// The passphrase is split into runs of letters, of digits and of other characters. Runs of
+/// letters found in the [default word list](word_list::default_list) count as a random word of
+/// the list; other runs of letters count as the lower of their [`estimate`] and of their
+/// entropy as a run of markov tokens of `generator`, so that letters the model produces easily
+/// count for little; the other runs count as their [`estimate`], a single bit for a space,
+/// hyphen, dot or underscore between words. The passphrase counts for nothing when it is one
+/// of the most common passwords, compared by its letters and digits in lowercase, or when
+/// `blocklist`, e.g. a [`BloomStore`](crate::store::BloomStore) of leaked passwords, contains
+/// it.
+///
+/// Below [`PASSPHRASE_TARGET_BITS`] the check suggests random words to append, unless the
+/// passphrase is blocklisted, and a new passphrase, both reaching the target, see
+/// [`Generator::gen_with_min_entropy`]. The suggestions draw from `generator`.
+pub fn check_user_passphrase<R: RngCore + CryptoRng>(
+    generator: &mut Generator<R>,
+    passphrase: &str,
+    blocklist: Option<&dyn HistoryStore>,
+) -> PassphraseCheck {
+    let chars = passphrase.chars().count();
+    let blocklisted =
+        is_common(passphrase) || blocklist.is_some_and(|store| store.contains(passphrase));
+    let mut bits = 0.0;
+    let mut dictionary_words = Vec::new();
+    let mut predictable = Vec::new();
+    for run in class_runs(passphrase) {
+        let (run_bits, kind) = score_run(generator, run);
+        bits += run_bits;
+        match kind {
+            RunKind::Dictionary => dictionary_words.push(run.to_string()),
+            RunKind::Predictable => predictable.push(run.to_string()),
+            RunKind::Other => (),
+        }
+    }
+    if blocklisted {
+        bits = 0.0;
+    }
+
+    let mut issues = Vec::new();
+    if chars < MIN_PASSPHRASE_CHARS {
+        issues.push(PassphraseIssue::TooShort { chars });
+    }
+    if blocklisted {
+        issues.push(PassphraseIssue::Blocklisted);
+    }
+    if !dictionary_words.is_empty() {
+        issues.push(PassphraseIssue::DictionaryWords {
+            words: dictionary_words,
+        });
+    }
+    if !predictable.is_empty() {
+        issues.push(PassphraseIssue::Predictable { words: predictable });
+    }
+
+    let mut suggestions = Vec::new();
+    if bits < PASSPHRASE_TARGET_BITS {
+        let missing = PASSPHRASE_TARGET_BITS - bits;
+        if let (false, Ok((words, h))) =
+            (blocklisted, generator.gen_with_min_entropy("-w", missing))
+        {
+            suggestions.push(Suggestion::AppendWords {
+                count: words.matches('-').count(),
+                words: words[1..].to_string(),
+                bits: h,
+            });
+        }
+        if let Ok((new, h)) = generator.gen_with_min_entropy("-w", PASSPHRASE_TARGET_BITS) {
+            suggestions.push(Suggestion::Replace {
+                passphrase: new[1..].to_string(),
+                bits: h,
+            });
+        }
+    }
+    PassphraseCheck {
+        bits,
+        strength: Strength::from_bits(bits),
+        issues,
+        suggestions,
+    }
+}
+
+/// Most modifications of one kind proposed by [`suggest_strengthening`].
+pub const MAX_MODIFICATIONS: usize = 8;
+
+/// Widest digit group [`suggest_strengthening`] swaps a group of digits for.
+pub const MAX_DIGIT_GROUP: usize = 12;
+
+/// One random change to a passphrase proposed by [`suggest_strengthening`]; positions are
+/// byte offsets in the passphrase as modified by the changes before it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Modification {
+    /// A pseudo-word inserted where the character class changes, or at either end.
+    InsertWord { word: String, position: usize },
+    /// The last group of digits replaced with random digits, at least as many.
+    SwapDigits {
+        digits: String,
+        range: std::ops::Range<usize>,
+    },
+    /// A symbol inserted between two characters, or at either end.
+    AddSymbol { symbol: String, position: usize },
+}
+
+/// A strengthened version of a passphrase, see [`suggest_strengthening`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Strengthening {
+    /// The passphrase with the modifications applied.
+    pub passphrase: String,
+    pub modifications: Vec<Modification>,
+    /// Entropy of the modifications, in bits: what is left to an attacker who knows the
+    /// original passphrase.
+    pub added_bits: f64,
+    /// Estimated entropy of the strengthened passphrase, in bits, as counted by
+    /// [`check_user_passphrase`] for the original plus the entropy of the modifications.
+    pub bits: f64,
+}
+
+/// Entropy, in bits, of inserting `insert` into `text` at one of `positions` chosen
+/// uniformly: positions giving the same text count as one.
+pub(crate) fn position_entropy(text: &str, positions: &[usize], insert: &str) -> f64 {
+    let mut outputs: Vec<String> = positions
+        .iter()
+        .map(|&at| format!("{}{}{}", &text[..at], insert, &text[at..]))
+        .collect();
+    outputs.sort();
+    let n = outputs.len() as f64;
+    outputs
+        .chunk_by(|a, b| a == b)
+        .map(|same| {
+            let p = same.len() as f64 / n;
+            -p * p.log2()
+        })
+        .sum()
+}
+
+/// Draws the text of `pattern` from `generator` with its exact entropy, or the reported one
+/// when the exact figure is not available.
+fn draw<R: RngCore + CryptoRng>(generator: &mut Generator<R>, pattern: &Pattern) -> (String, f64) {
+    let (text, reported) = generator.gen_from_parsed(pattern);
+    let exact = generator.exact_entropy(pattern, &text);
+    (text, exact.unwrap_or(reported))
+}
+
+/// Proposes small random changes raising a passphrase chosen by a person to `target_bits`,
+/// so that sign-up flows can improve a weak passphrase rather than reject it, keeping what
+/// its owner chose. Each proposal uses one kind of change, repeated as needed: inserting
+/// pseudo-words of `generator` where the character class changes, swapping the last group of
+/// digits for a random one, wider when needed, or inserting symbols at random positions.
+///
+/// The entropy of each change is exact given the original passphrase: the
+/// [exact entropy](Generator::exact_entropy) of the drawn text plus that of its uniformly
+/// drawn position, counting positions which give the same text once. The strengthened
+/// passphrase therefore keeps [`Strengthening::added_bits`] even against an attacker who
+/// knows the original, e.g. from a leak.
+///
+/// Proposals are sorted by the number of characters they add, fewest first; kinds which
+/// cannot reach the target within [`MAX_MODIFICATIONS`] changes, or [`MAX_DIGIT_GROUP`]
+/// digits, are left out. Returns no proposal when the passphrase already reaches the target
+/// or in [demo mode](Generator::set_demo_mode).
+pub fn suggest_strengthening<R: RngCore + CryptoRng>(
+    generator: &mut Generator<R>,
+    passphrase: &str,
+    target_bits: f64,
+) -> Vec<Strengthening> {
+    let runs = class_runs(passphrase);
+    let base = if is_common(passphrase) {
+        0.0
+    } else {
+        runs.iter().map(|run| score_run(generator, run).0).sum()
+    };
+    if base >= target_bits || generator.demo {
+        return Vec::new();
+    }
+    let mut proposals = Vec::new();
+
+    let word = Pattern::parse("w");
+    let symbol = Pattern::parse("s");
+    for insert_words in [true, false] {
+        let mut text = passphrase.to_string();
+        let mut modifications = Vec::new();
+        let mut added = 0.0;
+        while base + added < target_bits && modifications.len() < MAX_MODIFICATIONS {
+            let (inserted, h) = draw(generator, if insert_words { &word } else { &symbol });
+            let positions: Vec<usize> = if insert_words {
+                let mut at = 0;
+                let mut boundaries = vec![0];
+                for run in class_runs(&text) {
+                    at += run.len();
+                    boundaries.push(at);
+                }
+                boundaries.dedup();
+                boundaries
+            } else {
+                (0..=text.len())
+                    .filter(|&i| text.is_char_boundary(i))
+                    .collect()
+            };
+            let position = positions[generator.rng.gen_range(0..positions.len())];
+            added += h + position_entropy(&text, &positions, &inserted);
+            text.insert_str(position, &inserted);
+            modifications.push(if insert_words {
+                Modification::InsertWord {
+                    word: inserted,
+                    position,
+                }
+            } else {
+                Modification::AddSymbol {
+                    symbol: inserted,
+                    position,
+                }
+            });
+        }
+        if base + added >= target_bits {
+            proposals.push(Strengthening {
+                passphrase: text,
+                modifications,
+                added_bits: added,
+                bits: base + added,
+            });
+        }
+    }
+
+    let mut start = 0;
+    let mut last_group = None;
+    for run in &runs {
+        if run.starts_with(|c: char| c.is_ascii_digit()) {
+            last_group = Some(start..start + run.len());
+        }
+        start += run.len();
+    }
+    if let Some(range) = last_group {
+        let kept = (base - estimate(&passphrase[range.clone()]).bits).max(0.0);
+        let width = (range.len()..=MAX_DIGIT_GROUP).find(|&width| {
+            let max = Pattern::parse(&"d".repeat(width)).max_entropy(generator);
+            max.is_some_and(|max| kept + max >= target_bits)
+        });
+        if let Some(width) = width {
+            let (digits, h) = draw(generator, &Pattern::parse(&"d".repeat(width)));
+            let mut text = passphrase.to_string();
+            text.replace_range(range.clone(), &digits);
+            // date-safe digits are not uniform, a draw can fall short of the maximum
+            if kept + h >= target_bits {
+                proposals.push(Strengthening {
+                    passphrase: text,
+                    modifications: vec![Modification::SwapDigits { digits, range }],
+                    added_bits: h,
+                    bits: kept + h,
+                });
+            }
+        }
+    }
+
+    proposals.sort_by_key(|proposal| proposal.passphrase.chars().count());
+    proposals
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metrics::Counters;
+    use crate::{dates, lookup_distribution};
+    use std::sync::Arc;
+
+    #[test]
+    fn test_estimate() {
+        // the xkcd password: an uncommon word, capitalized, with substitutions
+        let troubador = estimate("Tr0ub4dor&3");
+        assert!(troubador.strength <= Strength::Weak, "{:?}", troubador);
+        assert!(troubador.bits < estimate("Xq0vb4kzr&3").bits);
+        // each substitution and the capital letter cost a bit over the plain word
+        let plain = estimate("troubador").bits;
+        assert!((estimate("Troubador").bits - plain - 1.0).abs() < 1e-9);
+        assert!((estimate("tr0ub4dor").bits - plain - 2.0).abs() < 1e-9);
+        // 1 stands for both i and l
+        assert!(estimate("l1verpoo1").bits < estimate("x1vqrpzz1").bits);
+        // digits alone are never read as words
+        let digits = estimate("5937").bits;
+        assert!((digits - 4.0 * 10f64.log2()).abs() < 1e-9);
+        assert_eq!(estimate("k7#Qv9!mZ2xR").strength, Strength::Strong);
+        assert_eq!(estimate("aaaaaaaa").strength, Strength::VeryWeak);
+        assert_eq!(estimate("").bits, 0.0);
+        // separators cost a bit each, also around a common password spaced out
+        let joined = estimate("correcthorsebatterystaple").bits;
+        for spaced in [
+            "correct horse battery staple",
+            "Correct-Horse-Battery-Staple",
+            "correct.horse_battery staple",
+        ] {
+            let spaced = estimate(spaced);
+            assert_eq!(spaced.strength, Strength::VeryWeak, "{:?}", spaced);
+            assert!(spaced.bits <= joined + 4.0, "{:?}", spaced);
+        }
+        // a space is a separator, not a character of another class
+        assert!((estimate("troubador troubador").bits - 2.0 * plain - 1.0).abs() < 1e-9);
+        #[cfg(feature = "wordlist-eff")]
+        {
+            let words = estimate("correctlanternbatterystaple").bits;
+            for separated in [
+                "correct lantern battery staple",
+                "correct-lantern-battery-staple",
+            ] {
+                let separated = estimate(separated);
+                assert!(
+                    (separated.bits - words - 3.0).abs() < 1e-9,
+                    "{:?}",
+                    separated
+                );
+                assert_eq!(separated.strength, Strength::Fair);
+            }
+            let capitalized = estimate("Correct-Lantern-Battery-Staple").bits;
+            assert!((capitalized - words - 3.0 - 4.0).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_portability_warnings() {
+        assert!(portability_warnings("tilby-wonder-42").is_empty());
+        assert!(portability_warnings("caffe-ж").is_empty());
+        assert!(portability_warnings(&"w".repeat(BCRYPT_MAX_BYTES)).is_empty());
+        // 37 characters, but 74 bytes
+        assert_eq!(
+            portability_warnings(&"ж".repeat(37)),
+            [PortabilityWarning::BcryptTruncation { bytes: 74 }]
+        );
+        assert_eq!(
+            portability_warnings("secret\t"),
+            [PortabilityWarning::SurroundingWhitespace]
+        );
+        // composed letters change under NFD, decomposed ones under NFC
+        let decomposable = ["caffè", "ж\u{306}", "\u{439}", "e\u{301}", "\u{212b}"];
+        for normalized in decomposable
+            .into_iter()
+            .chain(["ﬁle", "ＡＢＣ", "x²", "a\u{a0}b"])
+        {
+            assert_eq!(
+                portability_warnings(normalized),
+                [PortabilityWarning::Normalization],
+                "{}",
+                normalized
+            );
+        }
+        assert_eq!(
+            portability_warnings(&format!(" {} ", "ﬁ".repeat(30))).len(),
+            3
+        );
+    }
+
+    #[test]
+    fn test_component_pooling() {
+        // no components, no entropy
+        assert_eq!(combined_entropy(&[]), 0.0);
+        assert_eq!(entropy_given_known(&[], &[0]), 0.0);
+        assert_eq!(worst_single_compromise(&[]), 0.0);
+
+        // a single component is lost with its compromise
+        assert_eq!(combined_entropy(&[40.0]), 40.0);
+        assert_eq!(entropy_given_known(&[40.0], &[]), 40.0);
+        assert_eq!(entropy_given_known(&[40.0], &[0]), 0.0);
+        assert_eq!(worst_single_compromise(&[40.0]), 0.0);
+
+        // known components are removed, once however often they are listed
+        let components = [13.0, 40.0, 20.0, 7.0];
+        assert_eq!(combined_entropy(&components), 80.0);
+        assert_eq!(entropy_given_known(&components, &[1]), 40.0);
+        assert_eq!(entropy_given_known(&components, &[3, 0, 3]), 60.0);
+        assert_eq!(entropy_given_known(&components, &[0, 1, 2, 3]), 0.0);
+        assert_eq!(entropy_given_known(&components, &[2, 9]), 60.0);
+
+        // the worst case loses the strongest component, wherever it is
+        assert_eq!(worst_single_compromise(&components), 40.0);
+        assert_eq!(worst_single_compromise(&[40.0, 13.0, 7.0]), 20.0);
+        assert_eq!(worst_single_compromise(&[7.0, 13.0, 40.0]), 20.0);
+        assert_eq!(worst_single_compromise(&[20.0, 20.0]), 20.0);
+    }
+
+    #[test]
+    fn test_residual_entropy() {
+        let mut gen = Generator::new_custom(word_list::cv::list(), 2).unwrap();
+        gen.set_date_safe_digits(true);
+        let pattern = Pattern::parse("w.dddd");
+        let all = pattern.expected_entropy(&gen).unwrap();
+        let residual = |known: &[usize]| residual_entropy(&gen, &pattern, known).unwrap();
+        assert!((residual(&[]) - all).abs() < 1e-9);
+        assert!(residual(&[0, 1, 2, 3, 4, 5, 99]).abs() < 1e-9);
+        assert!((residual(&[1]) - all).abs() < 1e-9);
+        let group = (dates::safe_groups() as f64).log2();
+        assert!((residual(&[2, 3]) - (all - group / 2.0)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_guess_rank() {
+        let mut gen = Generator::new_custom(word_list::cv::list(), 2).unwrap();
+        let (_, root) = lookup_distribution(&gen.jump_table, gen.depth, gen.locale, "").unwrap();
+        let mut entropies = root.entropies.clone();
+        entropies.sort_by(f64::total_cmp);
+        let pattern = Pattern::parse("c");
+        let token = root.tokens[root.entropies.len() / 2].clone();
+        let h = gen.exact_entropy(&pattern, &token).unwrap();
+        let exact = entropies.iter().filter(|&&e| e < h).count().max(1) as f64;
+        let estimate = guess_rank(&mut gen, &pattern, &token).unwrap();
+        assert!(
+            (estimate - exact.log2()).abs() < 0.5,
+            "{} {}",
+            estimate,
+            exact
+        );
+        let state = gen.rng_mut().clone();
+        assert_eq!(guess_rank(&mut gen, &Pattern::parse("dd"), "42"), Some(0.0));
+        assert_eq!(guess_rank(&mut gen, &pattern, "#"), None);
+        assert_eq!(gen.rng_mut().clone(), state);
+
+        // buffered draws, the sequence and the metrics of the generator are left untouched,
+        // and do not change the estimate
+        let counters = Arc::new(Counters::new());
+        let mut buffered = Generator::new_custom(word_list::cv::list(), 2).unwrap();
+        buffered.set_buffered_draws(true);
+        buffered.reseed(3);
+        buffered.set_metrics(Some(counters.clone()));
+        buffered.set_sequence(7);
+        let mut untouched = Generator::new_custom(word_list::cv::list(), 2).unwrap();
+        untouched.set_buffered_draws(true);
+        untouched.reseed(3);
+        untouched.set_sequence(7);
+        let seq = Pattern::parse("c-{seq}");
+        assert_eq!(
+            buffered.gen_from_parsed(&seq),
+            untouched.gen_from_parsed(&seq)
+        );
+        assert_eq!(guess_rank(&mut buffered, &pattern, &token), Some(estimate));
+        assert_eq!(counters.generated(), 1);
+        assert_eq!(
+            buffered.gen_from_parsed(&seq),
+            untouched.gen_from_parsed(&seq)
+        );
+        assert_eq!(counters.generated(), 2);
+    }
+
+    #[test]
+    fn test_suggest_strengthening() {
+        let mut gen = Generator::new();
+        gen.reseed(11);
+        // "Summer" counts as a common word, so twelve random digits stop short of 60 bits
+        let proposals = suggest_strengthening(&mut gen, "Summer2024!", 50.0);
+        assert_eq!(proposals.len(), 3);
+        let lengths: Vec<usize> = proposals
+            .iter()
+            .map(|p| p.passphrase.chars().count())
+            .collect();
+        assert!(lengths.windows(2).all(|w| w[0] <= w[1]));
+        for proposal in &proposals {
+            assert!(proposal.bits >= 50.0);
+            assert!(proposal.added_bits > 0.0);
+            assert!(!proposal.modifications.is_empty());
+            // replaying the modifications gives the proposal, keeping the original text
+            let mut text = "Summer2024!".to_string();
+            for modification in &proposal.modifications {
+                match modification {
+                    Modification::InsertWord { word, position } => text.insert_str(*position, word),
+                    Modification::AddSymbol { symbol, position } => {
+                        text.insert_str(*position, symbol)
+                    }
+                    Modification::SwapDigits { digits, range } => {
+                        assert_eq!(range, &(6..10));
+                        assert!(digits.len() >= 4);
+                        text.replace_range(range.clone(), digits)
+                    }
+                }
+            }
+            assert_eq!(text, proposal.passphrase);
+        }
+
+        // the only symbol, at one of 7 distinct positions
+        let mut gen = Generator::new();
+        gen.set_symbols("#").unwrap();
+        // a common password counts for nothing, so one symbol reaches the target
+        let proposals = suggest_strengthening(&mut gen, "123456", 1.0);
+        let symbols = proposals
+            .iter()
+            .find(|p| matches!(p.modifications[0], Modification::AddSymbol { .. }))
+            .unwrap();
+        assert_eq!(symbols.modifications.len(), 1);
+        assert!((symbols.added_bits - 7f64.log2()).abs() < 1e-9);
+        // inserting at either side of a symbol gives the same text, counted once
+        assert_eq!(
+            position_entropy("a#", &[0, 1, 2], "#"),
+            -(2.0 / 3.0 * (2f64 / 3.0).log2() + 1.0 / 3.0 * (1f64 / 3.0).log2())
+        );
+
+        assert!(suggest_strengthening(&mut gen, "xk7#Qp9!mZ2@vL4$wT8&", 60.0).is_empty());
+        gen.set_demo_mode(true);
+        assert!(suggest_strengthening(&mut gen, "kitty", 60.0).is_empty());
+    }
+
+    #[test]
+    fn test_user_passphrase_check() {
+        use crate::messages::{ENGLISH, FRENCH, GERMAN, ITALIAN, SPANISH};
+        use crate::store::{HistoryStore, MemoryStore};
+        let mut gen = Generator::new();
+        gen.reseed(7);
+
+        let check = check_user_passphrase(&mut gen, "Tr0ub4dor", None);
+        assert!(check
+            .issues
+            .contains(&PassphraseIssue::TooShort { chars: 9 }));
+        assert!(check.bits < PASSPHRASE_TARGET_BITS);
+
+        // common passwords count for nothing, whatever their casing and punctuation
+        let check = check_user_passphrase(&mut gen, "Pass-word1!", None);
+        assert_eq!(check.bits, 0.0);
+        assert_eq!(check.strength, Strength::VeryWeak);
+        assert!(check.issues.contains(&PassphraseIssue::Blocklisted));
+        // appending to a leaked password does not help, so only a replacement is suggested
+        assert!(matches!(
+            check.suggestions.as_slice(),
+            [Suggestion::Replace { .. }]
+        ));
+
+        // the xkcd passphrase, with its words separated
+        for spaced in [
+            "correct horse battery staple",
+            "Correct-Horse-Battery-Staple",
+        ] {
+            let check = check_user_passphrase(&mut gen, spaced, None);
+            assert_eq!(check.strength, Strength::VeryWeak);
+            assert!(check.issues.contains(&PassphraseIssue::Blocklisted));
+            assert!(!check.suggestions.is_empty());
+        }
+
+        let mut blocklist = MemoryStore::new();
+        blocklist.insert("zebra-quokka-77");
+        let check = check_user_passphrase(&mut gen, "zebra-quokka-77", Some(&blocklist));
+        assert!(check.issues.contains(&PassphraseIssue::Blocklisted));
+
+        // the dictionary is the default list, the EFF one with its feature
+        #[cfg(feature = "wordlist-eff")]
+        {
+            let check = check_user_passphrase(&mut gen, "Lantern mosaic 42", None);
+            let words = vec!["Lantern".to_string(), "mosaic".to_string()];
+            assert!(check
+                .issues
+                .contains(&PassphraseIssue::DictionaryWords { words }));
+            for suggestion in &check.suggestions {
+                match suggestion {
+                    Suggestion::AppendWords { words, count, bits } => {
+                        assert_eq!(words.split('-').count(), *count);
+                        assert!(check.bits + bits >= PASSPHRASE_TARGET_BITS);
+                        let longer = format!("Lantern mosaic 42-{}", words);
+                        let rechecked = check_user_passphrase(&mut gen, &longer, None);
+                        assert!(rechecked.bits > check.bits);
+                    }
+                    Suggestion::Replace { passphrase, bits } => {
+                        assert!(*bits >= PASSPHRASE_TARGET_BITS);
+                        assert!(!passphrase.starts_with('-'));
+                    }
+                }
+            }
+            assert_eq!(check.suggestions.len(), 2);
+
+            // separated dictionary words count as random words of the list, no more
+            let check = check_user_passphrase(&mut gen, "correct lantern battery staple", None);
+            assert!(check.bits < PASSPHRASE_TARGET_BITS);
+            assert!(check.strength < Strength::Strong);
+            assert!(check.issues.iter().any(|issue| matches!(
+                issue,
+                PassphraseIssue::DictionaryWords { words } if words.len() == 4
+            )));
+            assert_eq!(check.suggestions.len(), 2);
+        }
+
+        // letters the model produces easily count for less than random letters
+        let (text, _) = gen.gen_from_pattern("ccc");
+        let modeled = gen.token_run_entropy(&text).unwrap();
+        assert!(modeled < estimate(&text).bits);
+        let check = check_user_passphrase(&mut gen, &text, None);
+        assert!(check.issues.iter().any(|issue| matches!(
+            issue,
+            PassphraseIssue::Predictable { .. } | PassphraseIssue::DictionaryWords { .. }
+        )));
+
+        let check = check_user_passphrase(&mut gen, "xk7#Qp9!mZ2@vL4$wT8&", None);
+        assert!(check.issues.is_empty());
+        assert!(check.suggestions.is_empty());
+        assert!(check.strength >= Strength::Strong);
+
+        // demo mode cannot generate suggestions, the check still reports its findings
+        gen.set_demo_mode(true);
+        let check = check_user_passphrase(&mut gen, "letmein", None);
+        assert!(check.suggestions.is_empty());
+        assert!(!check.issues.is_empty());
+
+        let append = Suggestion::AppendWords {
+            words: "lantern-mosaic".to_string(),
+            count: 2,
+            bits: 30.0,
+        };
+        assert_eq!(
+            append.description(),
+            "append 2 random words: lantern-mosaic"
+        );
+        for catalog in [ENGLISH, ITALIAN, GERMAN, FRENCH, SPANISH] {
+            let texts = [
+                catalog.issue(&PassphraseIssue::TooShort { chars: 3 }),
+                catalog.issue(&PassphraseIssue::Blocklisted),
+                catalog.issue(&PassphraseIssue::DictionaryWords {
+                    words: vec!["a".to_string(), "b".to_string()],
+                }),
+                catalog.suggestion(&append),
+                catalog.suggestion(&Suggestion::AppendWords {
+                    words: "lantern".to_string(),
+                    count: 1,
+                    bits: 15.0,
+                }),
+                catalog.suggestion(&Suggestion::Replace {
+                    passphrase: "x-y".to_string(),
+                    bits: 60.0,
+                }),
+            ];
+            for text in texts {
+                assert!(!text.contains('{'), "{}", text);
+            }
+        }
+    }
+}
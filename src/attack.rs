@@ -0,0 +1,377 @@
+//! Best-first enumeration of the outputs of a pattern, for red-team evaluations.
+//!
+//! An attacker who knows the word list, the markov chain depth and the pattern can try the
+//! outputs of the generator from the most to the least probable. [`Generator::enumerate`]
+//! does exactly that, so that the number of guesses needed to find generated secrets can be
+//! measured directly instead of estimated, see also
+//! [`analysis::guess_rank`](crate::analysis::guess_rank).
+//!
+//! Outputs are ordered by the probability of the most probable sequence of choices producing
+//! them, and every output is returned once. Partial outputs are explored by the probability of
+//! their most probable completion, so that the next output is found quickly even when many
+//! partial outputs are more probable than it. The enumeration keeps a frontier of partial
+//! outputs in memory, bounded by [`Enumeration::set_max_frontier`]; bound the time by taking
+//! as many outputs as needed.
+//!
+//! ```rust
+//! use cryptirust::{pattern::Pattern, Generator};
+//!
+//! let generator = Generator::new();
+//! let pattern = Pattern::parse("w-dd");
+//! let guesses = generator.enumerate(&pattern).unwrap();
+//! for (guess, entropy) in guesses.take(5) {
+//!     println!("{} ({:.2} bits)", guess, entropy);
+//! }
+//! ```
+use crate::dates;
+use crate::pattern::{Pattern, Segment};
+use crate::theme::Theme;
+use crate::{uppercase_first_letter, Generator, WordLengths};
+use rand::{CryptoRng, RngCore};
+use rand_chacha::ChaCha8Rng;
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+/// Default maximum number of partial outputs kept by an [`Enumeration`].
+pub const DEFAULT_MAX_FRONTIER: usize = 1 << 20;
+
+/// Iterator over the outputs of a pattern with their entropy in bits, from the most to the
+/// least probable, see the [module](self) documentation.
+pub struct Enumeration<'a, R = ChaCha8Rng> {
+    generator: &'a Generator<R>,
+    segments: Vec<Segment>,
+    tokens_per_word: usize,
+    lengths: Option<&'a WordLengths>,
+    frontier: BinaryHeap<Node>,
+    seen: HashSet<String>,
+    /// Entropy of the most probable completion of a partial output, by segment, progress in
+    /// the word and context.
+    bounds: HashMap<(usize, usize, String), f64>,
+    max_frontier: usize,
+    pruned: usize,
+}
+
+/// A partial output: the text generated before `segment`, with the progress in the current
+/// word as in [`Generator::exact_entropy`]. Nodes are explored by increasing `priority`, the
+/// entropy of the most probable output extending them, so that outputs come out in order
+/// without expanding every partial output more probable than them.
+struct Node {
+    priority: f64,
+    entropy: f64,
+    segment: usize,
+    emitted: usize,
+    text: String,
+}
+
+/// A successor of a partial output: its probability, segment, progress and appended text.
+type Step = (f64, usize, usize, String);
+
+impl Node {
+    /// The priority, rounded so that the rounding errors of sums of entropies do not separate
+    /// nodes leading to equally probable outputs.
+    fn rank(&self) -> i64 {
+        (self.priority * 1e9).round() as i64
+    }
+}
+
+impl PartialEq for Node {
+    fn eq(&self, other: &Node) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Node {}
+
+impl PartialOrd for Node {
+    fn partial_cmp(&self, other: &Node) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Node {
+    /// The node with the lowest priority is the greatest; among equals the most advanced one,
+    /// then the text decides for a deterministic order.
+    fn cmp(&self, other: &Node) -> Ordering {
+        other
+            .rank()
+            .cmp(&self.rank())
+            .then_with(|| self.entropy.total_cmp(&other.entropy))
+            .then_with(|| other.text.cmp(&self.text))
+            .then_with(|| self.segment.cmp(&other.segment))
+            .then_with(|| self.emitted.cmp(&other.emitted))
+    }
+}
+
+impl<R: RngCore + CryptoRng> Generator<R> {
+    /// Enumerates the outputs of `pattern` from the most to the least probable, see the
+    /// [`attack`](crate::attack) module. Returns `None` when a [`Theme`] is set or when the
+    /// pattern has entropy floors (`w{>=12}`) or alternations (`[sd]`).
+    pub fn enumerate(&self, pattern: &Pattern) -> Option<Enumeration<'_, R>> {
+        if self.theme != Theme::Plain || pattern.has_floors() || pattern.has_choices() {
+            return None;
+        }
+        let mut enumeration = Enumeration {
+            generator: self,
+            segments: pattern.segments().to_vec(),
+            tokens_per_word: self.word_length.div_ceil(self.depth.max(1)),
+            lengths: self.empirical_lengths.then_some(&self.word_lengths),
+            frontier: BinaryHeap::new(),
+            seen: HashSet::new(),
+            bounds: HashMap::new(),
+            max_frontier: DEFAULT_MAX_FRONTIER,
+            pruned: 0,
+        };
+        let priority = enumeration.bound(0, 0, "");
+        if priority.is_finite() {
+            enumeration.frontier.push(Node {
+                priority,
+                entropy: 0.0,
+                segment: 0,
+                emitted: 0,
+                text: String::new(),
+            });
+        }
+        Some(enumeration)
+    }
+}
+
+impl<R: RngCore + CryptoRng> Enumeration<'_, R> {
+    /// Sets the maximum number of partial outputs kept in memory, [`DEFAULT_MAX_FRONTIER`] by
+    /// default. When the frontier grows beyond it the least probable partial outputs are
+    /// dropped, and the outputs extending them are never returned.
+    pub fn set_max_frontier(&mut self, max: usize) {
+        self.max_frontier = max.max(1);
+    }
+
+    /// Number of partial outputs dropped so far to respect the maximum frontier; while it is
+    /// zero the enumeration is exact.
+    pub fn pruned(&self) -> usize {
+        self.pruned
+    }
+
+    /// The last characters of `text` which the model looks at.
+    fn context<'t>(&self, text: &'t str) -> &'t str {
+        let depth = self.generator.depth.max(1);
+        let start = text
+            .char_indices()
+            .rev()
+            .nth(depth - 1)
+            .map_or(0, |(i, _)| i);
+        &text[start..]
+    }
+
+    /// Entropy of the most probable completion of the partial output `text`, infinite when
+    /// it cannot be completed.
+    fn bound(&mut self, segment: usize, emitted: usize, text: &str) -> f64 {
+        if segment >= self.segments.len() {
+            return 0.0;
+        }
+        let context = self.context(text).to_string();
+        let key = (segment, emitted, context);
+        if let Some(&h) = self.bounds.get(&key) {
+            return h;
+        }
+        let mut best = f64::INFINITY;
+        for (p, next, emitted, appended) in self.successors(segment, emitted, &key.2) {
+            let h = -p.log2() + self.bound(next, emitted, &format!("{}{}", key.2, appended));
+            best = best.min(h);
+        }
+        self.bounds.insert(key, best);
+        best
+    }
+
+    /// The tokens which can follow `prefix`, rendered and with their length in characters and
+    /// probability; with a `limit` only the tokens of at most `limit` characters, if any.
+    /// `None` when the model has no distribution for the prefix.
+    fn tokens(
+        &self,
+        prefix: &str,
+        capitalized: bool,
+        limit: Option<usize>,
+    ) -> Option<Vec<(String, usize, f64)>> {
+        let (_, tr) = self.generator.distribution(prefix)?;
+        let fitting = |l: usize| tr.total_where(|t| t.chars().count() <= l);
+        let limit = limit.filter(|&l| fitting(l) > 0);
+        let scale = limit.map_or(1.0, |l| tr.total as f64 / fitting(l) as f64);
+        let tokens = tr
+            .tokens
+            .iter()
+            .enumerate()
+            .filter(|(_, token)| limit.is_none_or(|l| token.chars().count() <= l))
+            .map(|(i, token)| {
+                let rendered = if capitalized {
+                    uppercase_first_letter(token, self.generator.locale)
+                } else {
+                    token.clone()
+                };
+                (rendered, token.chars().count(), tr.probability(i) * scale)
+            })
+            .collect();
+        Some(tokens)
+    }
+
+    /// Successors of a word with empirical length which still needs `remaining` characters.
+    fn word_rest(
+        &self,
+        segment: usize,
+        text: &str,
+        p: f64,
+        remaining: usize,
+        capitalized: bool,
+    ) -> Vec<Step> {
+        let Some(tokens) = self.tokens(text, capitalized, Some(remaining)) else {
+            return vec![(p, segment + 1, 0, String::new())];
+        };
+        tokens
+            .into_iter()
+            .map(|(token, len, q)| match remaining.saturating_sub(len) {
+                0 => (p * q, segment + 1, 0, token),
+                left => (p * q, segment, left, token),
+            })
+            .collect()
+    }
+
+    /// The ways to continue the partial output `text`, mirroring [`Generator::gen_annotated`].
+    fn successors(&self, segment: usize, emitted: usize, text: &str) -> Vec<Step> {
+        match self.segments[segment] {
+            Segment::Literal(ref literal) => vec![(1.0, segment + 1, 0, literal.clone())],
+            // refused by `enumerate`
+            Segment::Choice(_) => Vec::new(),
+            Segment::Sequence { start, width } => {
+                let number = self.generator.sequence_number(start, width);
+                vec![(1.0, segment + 1, 0, number)]
+            }
+            Segment::Digit
+                if self
+                    .generator
+                    .starts_date_safe_group(&self.segments, segment) =>
+            {
+                let p = 1.0 / dates::safe_groups() as f64;
+                (0..10usize.pow(dates::GROUP_LENGTH as u32))
+                    .map(|v| format!("{:0width$}", v, width = dates::GROUP_LENGTH))
+                    .filter(|group| !dates::is_date_like(group))
+                    .map(|group| {
+                        let group = self.generator.group_digits(&self.segments, segment, &group);
+                        (p, segment + dates::GROUP_LENGTH, 0, group)
+                    })
+                    .collect()
+            }
+            Segment::Symbol | Segment::Digit => {
+                let grouping = self.generator.digit_grouping.as_ref();
+                let sep = grouping
+                    .and_then(|g| g.separator_before(&self.segments, segment))
+                    .map(String::from)
+                    .unwrap_or_default();
+                let set = self.generator.char_set(&self.segments[segment]);
+                let p = 1.0 / set.len() as f64;
+                set.iter()
+                    .map(|c| (p, segment + 1, 0, format!("{}{}", sep, c)))
+                    .collect()
+            }
+            Segment::Dictionary { capitalized } => {
+                let n = self.generator.dictionary.len();
+                if n == 0 {
+                    return vec![(1.0, segment + 1, 0, String::new())];
+                }
+                (0..n)
+                    .map(|i| {
+                        let word = self.generator.dictionary_word(i, capitalized);
+                        (1.0 / n as f64, segment + 1, 0, word)
+                    })
+                    .collect()
+            }
+            #[cfg(feature = "wordlist-pos")]
+            Segment::Part(part) => {
+                let p = 1.0 / part.words().count() as f64;
+                part.words()
+                    .map(|word| (p, segment + 1, 0, word.to_string()))
+                    .collect()
+            }
+            Segment::Roman { .. } | Segment::Ordinal { .. } => {
+                let numerals = self.segments[segment].numerals().unwrap_or_default();
+                let p = 1.0 / numerals.len() as f64;
+                numerals
+                    .into_iter()
+                    .map(|numeral| (p, segment + 1, 0, numeral))
+                    .collect()
+            }
+            Segment::Token { capitalized, .. } => match self.tokens(text, capitalized, None) {
+                Some(tokens) => tokens
+                    .into_iter()
+                    .map(|(token, _, p)| (p, segment + 1, 0, token))
+                    .collect(),
+                None => vec![(1.0, segment + 1, 0, String::new())],
+            },
+            Segment::Word { capitalized, .. } if self.lengths.is_some() => {
+                if emitted == 0 {
+                    let lengths = self.lengths.unwrap();
+                    (1..lengths.counts.len())
+                        .filter(|&len| lengths.counts[len] > 0)
+                        .flat_map(|len| {
+                            let p = lengths.probability(len);
+                            self.word_rest(segment, text, p, len, capitalized)
+                        })
+                        .collect()
+                } else {
+                    self.word_rest(segment, text, 1.0, emitted, false)
+                }
+            }
+            Segment::Word { capitalized, .. } => {
+                let first = capitalized && emitted == 0;
+                match self.tokens(text, first, None) {
+                    Some(tokens) if emitted < self.tokens_per_word => tokens
+                        .into_iter()
+                        .map(|(token, _, p)| (p, segment, emitted + 1, token))
+                        .collect(),
+                    // the generator ends the word early when the model has no continuation
+                    _ => vec![(1.0, segment + 1, 0, String::new())],
+                }
+            }
+        }
+    }
+
+    /// Pushes the successors of `node`.
+    fn expand(&mut self, node: &Node) {
+        for (p, segment, emitted, appended) in
+            self.successors(node.segment, node.emitted, &node.text)
+        {
+            if p <= 0.0 {
+                continue;
+            }
+            let text = format!("{}{}", node.text, appended);
+            let entropy = node.entropy - p.log2();
+            let priority = entropy + self.bound(segment, emitted, &text);
+            if priority.is_finite() {
+                self.frontier.push(Node {
+                    priority,
+                    entropy,
+                    segment,
+                    emitted,
+                    text,
+                });
+            }
+        }
+        if self.frontier.len() > 2 * self.max_frontier {
+            let mut nodes = std::mem::take(&mut self.frontier).into_sorted_vec();
+            let dropped = nodes.len() - self.max_frontier;
+            nodes.drain(..dropped);
+            self.pruned += dropped;
+            self.frontier = BinaryHeap::from(nodes);
+        }
+    }
+}
+
+impl<R: RngCore + CryptoRng> Iterator for Enumeration<'_, R> {
+    type Item = (String, f64);
+
+    fn next(&mut self) -> Option<(String, f64)> {
+        while let Some(node) = self.frontier.pop() {
+            if node.segment < self.segments.len() {
+                self.expand(&node);
+            } else if self.seen.insert(node.text.clone()) {
+                return Some((node.text, node.entropy));
+            }
+        }
+        None
+    }
+}
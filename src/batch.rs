@@ -0,0 +1,394 @@
+//! Generation of many secrets at once, with progress reporting and cancellation.
+//!
+//! ```rust
+//! use cryptirust::Generator;
+//!
+//! let mut generator = Generator::new();
+//! let mut secrets = Vec::new();
+//! let generated = generator.gen_batch_with("w-w-dd", 1000, |secret, _entropy, progress| {
+//!     secrets.push(secret);
+//!     if progress.done % 100 == 0 {
+//!         eprintln!("{}/{}", progress.done, progress.total);
+//!     }
+//!     true // return false to stop early
+//! });
+//! assert_eq!(generated, 1000);
+//! ```
+//!
+//! Customer-visible codes must not be mistaken for each other: [`Generator::gen_batch_distinct`]
+//! rejects the secrets closer than a minimum [Levenshtein distance](levenshtein) to one already
+//! in the batch.
+//!
+//! ```rust
+//! use cryptirust::{batch, Generator};
+//!
+//! let mut generator = Generator::new();
+//! let codes = generator.gen_batch_distinct("cccc", 50, 3).unwrap();
+//! for (i, (a, _)) in codes.iter().enumerate() {
+//!     for (b, _) in &codes[i + 1..] {
+//!         assert!(batch::levenshtein(a, b) >= 3);
+//!     }
+//! }
+//! ```
+//!
+//! Bulk-provisioned credentials are traced by a `{seq}` element, which writes the number of
+//! each secret in the batch, counting from 1 or from the start given as `{seq:start}`,
+//! zero-padded to the digits of the start, e.g. `{seq:001}`. The number is not random and adds
+//! no entropy; outside batches it is set with [`Generator::set_sequence`].
+//!
+//! ```rust
+//! use cryptirust::Generator;
+//!
+//! let mut generator = Generator::new();
+//! let servers = generator.gen_batch("vm-{seq:01}-w-w", 12);
+//! assert!(servers[0].0.starts_with("vm-01-"));
+//! assert!(servers[11].0.starts_with("vm-12-"));
+//! ```
+//!
+//! # Distributed batches
+//!
+//! Workers sharing a secret seed generate disjoint portions of one deterministic batch without
+//! coordination: [`Generator::gen_indexed`] derives secret `i` from the seed and `i` alone,
+//! so any worker computes it identically, in any order, and the secrets of one portion tell
+//! nothing about those of another. Derived secrets can still collide by chance, with the
+//! probability given by [`collision_probability`], unless the pattern has a `{seq}` element,
+//! which writes a different number for every index.
+//!
+//! ```rust
+//! use cryptirust::Generator;
+//!
+//! let seed = b"batch seed shared by the workers";
+//! let mut worker = Generator::new();
+//! let mine: Vec<_> = (500..1000)
+//!     .map(|i| worker.gen_indexed(seed, "{seq}-w-w", i))
+//!     .collect();
+//! let mut other = Generator::new();
+//! assert_eq!(other.gen_indexed(seed, "{seq}-w-w", 742), mine[242]);
+//! ```
+//!
+//! # Streaming to a writer
+//!
+//! Jobs of millions of secrets are written straight to a file or a socket by
+//! [`Generator::write_batch`], one record at a time in the [`RecordFormat`] of its
+//! [`StreamOptions`], without ever holding the batch in memory. Records are buffered and
+//! flushed every [`StreamOptions::flush_every`] secrets, so that a reader following the output
+//! sees it grow in chunks.
+//!
+//! ```rust
+//! use cryptirust::batch::{RecordFormat, StreamOptions};
+//! use cryptirust::Generator;
+//!
+//! let mut generator = Generator::new();
+//! let mut out = Vec::new();
+//! let options = StreamOptions::new(RecordFormat::Csv);
+//! let written = generator.write_batch("{seq}-w-w", 1000, options, &mut out).unwrap();
+//! assert_eq!(written, 1000);
+//! let text = String::from_utf8(out).unwrap();
+//! assert!(text.starts_with("secret,entropy\n1-"));
+//! ```
+use crate::metrics::Policy;
+use crate::pattern::Pattern;
+use crate::sha256::HmacSha256;
+use crate::{json, Error, Generator};
+use rand::{CryptoRng, RngCore, SeedableRng};
+use std::io::{self, BufWriter, Write};
+
+/// Number of consecutive rejected candidates after which [`Generator::gen_batch_distinct`]
+/// gives up.
+pub const MAX_REJECTIONS: usize = 1000;
+
+/// Progress of a batch, passed to the callback of [`Generator::gen_batch_with`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Progress {
+    /// Number of secrets generated so far, including the current one.
+    pub done: usize,
+    /// Number of secrets requested.
+    pub total: usize,
+}
+
+impl Progress {
+    /// Completed fraction of the batch, between `0.0` and `1.0`.
+    pub fn fraction(&self) -> f64 {
+        if self.total == 0 {
+            1.0
+        } else {
+            self.done as f64 / self.total as f64
+        }
+    }
+}
+
+/// Number of secrets between flushes of [`Generator::write_batch`] unless
+/// [set](StreamOptions::flush_every).
+pub const DEFAULT_FLUSH_EVERY: usize = 10_000;
+
+/// Layout of the records written by [`Generator::write_batch`], one line per secret.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecordFormat {
+    /// The secret alone.
+    Plain,
+    /// The secret and its entropy in bits, separated by a tab.
+    Tsv,
+    /// CSV records of the secret and its entropy in bits, after a `secret,entropy` header.
+    Csv,
+    /// JSON objects `{"secret":...,"entropy":...}`, one per line.
+    JsonLines,
+}
+
+impl RecordFormat {
+    /// Writes the record of `secret` to `out`.
+    fn write(self, out: &mut impl Write, secret: &str, entropy: f64) -> io::Result<()> {
+        match self {
+            RecordFormat::Plain => writeln!(out, "{}", secret),
+            RecordFormat::Tsv => writeln!(out, "{}\t{:.2}", secret, entropy),
+            RecordFormat::Csv if secret.contains(['"', ',', '\n', '\r']) => {
+                writeln!(out, "\"{}\",{:.2}", secret.replace('"', "\"\""), entropy)
+            }
+            RecordFormat::Csv => writeln!(out, "{},{:.2}", secret, entropy),
+            RecordFormat::JsonLines => writeln!(
+                out,
+                "{{\"secret\":{},\"entropy\":{:.2}}}",
+                json::json_string(secret),
+                entropy
+            ),
+        }
+    }
+}
+
+/// How [`Generator::write_batch`] writes its records.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StreamOptions {
+    /// Layout of each record.
+    pub format: RecordFormat,
+    /// Number of secrets between flushes of the writer, at least 1.
+    pub flush_every: usize,
+}
+
+impl StreamOptions {
+    /// Records in `format`, flushed every [`DEFAULT_FLUSH_EVERY`] secrets.
+    pub fn new(format: RecordFormat) -> StreamOptions {
+        StreamOptions {
+            format,
+            flush_every: DEFAULT_FLUSH_EVERY,
+        }
+    }
+
+    /// Flushes the writer every `secrets` secrets instead, at least 1.
+    pub fn flush_every(self, secrets: usize) -> StreamOptions {
+        StreamOptions {
+            flush_every: secrets.max(1),
+            ..self
+        }
+    }
+}
+
+impl Default for StreamOptions {
+    fn default() -> Self {
+        StreamOptions::new(RecordFormat::Plain)
+    }
+}
+
+impl<R: RngCore + CryptoRng> Generator<R> {
+    /// Sets the position of the next secret in its batch, from 0, which the `{seq}` elements
+    /// of patterns write counting from their start, see the [module](crate::batch)
+    /// documentation. The batch methods set it for every secret; it is 0 by default.
+    pub fn set_sequence(&mut self, position: u64) {
+        self.sequence = position;
+    }
+
+    /// The text of a `{seq}` element counting from `start`, zero-padded to `width` digits.
+    pub(crate) fn sequence_number(&self, start: u64, width: usize) -> String {
+        format!(
+            "{:0width$}",
+            start.saturating_add(self.sequence),
+            width = width
+        )
+    }
+
+    /// Generates `count` secrets following `pattern`, see [`Generator::gen_from_pattern`].
+    pub fn gen_batch(&mut self, pattern: &str, count: usize) -> Vec<(String, f64)> {
+        let mut secrets = Vec::with_capacity(count);
+        self.gen_batch_with(pattern, count, |secret, entropy, _| {
+            secrets.push((secret, entropy));
+            true
+        });
+        secrets
+    }
+
+    /// Generates up to `count` secrets following `pattern`, handing each of them to `sink`
+    /// together with the progress of the batch, so that large batches can be streamed to their
+    /// destination instead of being collected in memory.
+    ///
+    /// The batch stops early, without generating further secrets, as soon as `sink` returns
+    /// `false`. Returns the number of secrets handed to `sink`.
+    pub fn gen_batch_with<F>(&mut self, pattern: &str, count: usize, mut sink: F) -> usize
+    where
+        F: FnMut(String, f64, Progress) -> bool,
+    {
+        let pattern = Pattern::parse(pattern);
+        for done in 1..=count {
+            self.sequence = done as u64 - 1;
+            let (secret, entropy) = self.gen_from_parsed(&pattern);
+            let progress = Progress { done, total: count };
+            if !sink(secret, entropy, progress) {
+                return done;
+            }
+        }
+        count
+    }
+
+    /// Generates `count` secrets following `pattern`, as [`Generator::gen_batch`] does, and
+    /// writes them to `out` as they are generated, see the
+    /// [module](crate::batch#streaming-to-a-writer) documentation. Only one secret is held in
+    /// memory at a time, whatever the size of the batch.
+    ///
+    /// The output is buffered and flushed every [`StreamOptions::flush_every`] secrets and at
+    /// the end of the batch. Returns the number of secrets written, or the first error of
+    /// `out`, which stops the batch; the secrets of the chunk being written may then be lost.
+    pub fn write_batch<W: Write>(
+        &mut self,
+        pattern: &str,
+        count: usize,
+        options: StreamOptions,
+        out: W,
+    ) -> io::Result<usize> {
+        let mut out = BufWriter::new(out);
+        if options.format == RecordFormat::Csv {
+            writeln!(out, "secret,entropy")?;
+        }
+        let flush_every = options.flush_every.max(1);
+        let mut error = None;
+        let written = self.gen_batch_with(pattern, count, |secret, entropy, progress| {
+            let result = options
+                .format
+                .write(&mut out, &secret, entropy)
+                .and_then(|()| {
+                    if progress.done.is_multiple_of(flush_every) {
+                        out.flush()
+                    } else {
+                        Ok(())
+                    }
+                });
+            result.map_err(|e| error = Some(e)).is_ok()
+        });
+        match error {
+            Some(e) => Err(e),
+            None => out.flush().map(|()| written),
+        }
+    }
+
+    /// Generates `count` secrets following `pattern`, each at [Levenshtein distance](levenshtein)
+    /// at least `min_distance` from all the others, by rejecting the candidates too close to
+    /// the secrets already accepted.
+    ///
+    /// Fails with [`Error::BatchExhausted`] when [`MAX_REJECTIONS`] candidates in a row are
+    /// rejected, i.e. when the pattern has too little room for the batch. The reported entropy
+    /// of each secret does not account for the rejections, which is accurate as long as the
+    /// batch is small compared with the number of possible secrets.
+    pub fn gen_batch_distinct(
+        &mut self,
+        pattern: &str,
+        count: usize,
+        min_distance: usize,
+    ) -> Result<Vec<(String, f64)>, Error> {
+        let pattern = Pattern::parse(pattern);
+        let mut secrets: Vec<(String, f64)> = Vec::with_capacity(count);
+        while secrets.len() < count {
+            let mut candidate = None;
+            self.sequence = secrets.len() as u64;
+            for _ in 0..MAX_REJECTIONS {
+                let (generated, entropy) = self.gen_from_parsed(&pattern);
+                if secrets
+                    .iter()
+                    .all(|(secret, _)| levenshtein(&generated, secret) >= min_distance)
+                {
+                    candidate = Some((generated, entropy));
+                    break;
+                }
+                self.report_resample(Policy::MinDistance);
+            }
+            match candidate {
+                Some(secret) => secrets.push(secret),
+                None => {
+                    return Err(Error::BatchExhausted {
+                        generated: secrets.len(),
+                        requested: count,
+                        min_distance,
+                    })
+                }
+            }
+        }
+        Ok(secrets)
+    }
+
+    /// Secret number `index`, counting from 0, of the deterministic batch of `pattern` derived
+    /// from `seed`, see the [module](crate::batch#distributed-batches) documentation.
+    ///
+    /// The random number generator is seeded with HMAC-SHA-256 of `index` under `seed`, so
+    /// every index draws from its own stream, and `{seq}` elements write `index` from their
+    /// start. The output depends only on `seed`, `pattern`, `index`, the model and the
+    /// settings of the generator, never on the other indices generated before; the random
+    /// state of the generator is left untouched. Use a secret, high-entropy `seed`: anyone
+    /// holding it can recompute the whole batch.
+    pub fn gen_indexed(&mut self, seed: &[u8], pattern: &str, index: u64) -> (String, f64)
+    where
+        R: SeedableRng,
+    {
+        let prf = HmacSha256::new(seed);
+        let mut derived = R::Seed::default();
+        for (block, chunk) in derived.as_mut().chunks_mut(32).enumerate() {
+            let mut message = index.to_be_bytes().to_vec();
+            message.extend_from_slice(&(block as u32).to_be_bytes());
+            chunk.copy_from_slice(&prf.mac(&message)[..chunk.len()]);
+        }
+        let rng = std::mem::replace(&mut self.rng, R::from_seed(derived));
+        let sequence = std::mem::replace(&mut self.sequence, index);
+        let draws = std::mem::take(&mut self.draws);
+        let secret = self.gen_from_pattern(pattern);
+        self.rng = rng;
+        self.sequence = sequence;
+        self.draws = draws;
+        secret
+    }
+}
+
+/// Probability that some of `count` secrets with `bits` bits of entropy each are equal, by
+/// the birthday bound.
+///
+/// `bits` must be the collision entropy of the secrets, the probability of two of them being
+/// equal being `2^-bits`: it is their entropy when they are uniform, e.g. digits, and
+/// [`Pattern::collision_entropy`](crate::pattern::Pattern::collision_entropy) otherwise.
+///
+/// ```rust
+/// use cryptirust::batch::collision_probability;
+///
+/// assert!(collision_probability(40.0, 1_000_000) < 0.4);
+/// assert!(collision_probability(64.0, 1_000_000) < 1e-7);
+/// ```
+pub fn collision_probability(bits: f64, count: u64) -> f64 {
+    let pairs = count as f64 * count.saturating_sub(1) as f64 / 2.0;
+    -(-pairs * (-bits).exp2()).exp_m1()
+}
+
+/// Levenshtein distance between `a` and `b`: the minimum number of characters to insert, delete
+/// or substitute to turn one into the other.
+///
+/// ```rust
+/// use cryptirust::batch::levenshtein;
+///
+/// assert_eq!(levenshtein("kitten", "sitting"), 3);
+/// assert_eq!(levenshtein("", "abc"), 3);
+/// ```
+pub fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, &cb) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(ca != cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+    row[b.len()]
+}
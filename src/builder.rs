@@ -0,0 +1,164 @@
+//! Step by step configuration of a [`Generator`].
+//!
+//! [`GeneratorBuilder`] gathers the choices made when a generator is created, the word list,
+//! the depth of the chain, the symbols and digits and the length of the words, and a seed for
+//! reproducible outputs, and checks them together in [`GeneratorBuilder::build`], before
+//! training the model: every invalid choice is reported as a [`BuildError`].
+//!
+//! ```rust
+//! use cryptirust::builder::GeneratorBuilder;
+//!
+//! let mut generator = GeneratorBuilder::new()
+//!     .depth(2)
+//!     .symbols("-_.")
+//!     .digits("23456789")
+//!     .word_length(10)
+//!     .rng_seed(42)
+//!     .build()
+//!     .unwrap();
+//! let (passphrase, entropy) = generator.gen_from_pattern("w-w-s-dd");
+//!
+//! assert!(GeneratorBuilder::new().digits("").build().is_err());
+//! ```
+use crate::{word_list, Generator};
+use std::fmt;
+
+/// Depth of the chain unless [set](GeneratorBuilder::depth), as in [`Generator::new`].
+pub const DEFAULT_DEPTH: usize = 3;
+
+/// A choice of a [`GeneratorBuilder`] which cannot build a generator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BuildError {
+    /// The word list has no word, once trimmed.
+    EmptyWordList,
+    /// The depth of the chain is zero.
+    ZeroDepth,
+    /// The symbol set is empty.
+    EmptySymbols,
+    /// The digit set is empty.
+    EmptyDigits,
+    /// The word length is zero.
+    ZeroWordLength,
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BuildError::EmptyWordList => write!(f, "the word list has no word"),
+            BuildError::ZeroDepth => write!(f, "the depth of the chain must be at least 1"),
+            BuildError::EmptySymbols => write!(f, "the symbol set is empty"),
+            BuildError::EmptyDigits => write!(f, "the digit set is empty"),
+            BuildError::ZeroWordLength => write!(f, "the word length must be at least 1"),
+        }
+    }
+}
+
+impl std::error::Error for BuildError {}
+
+/// Configuration of a [`Generator`], see the [module](self) documentation; choices left out
+/// keep the defaults of [`Generator::new`].
+#[derive(Debug, Clone)]
+pub struct GeneratorBuilder {
+    word_list: Option<Vec<String>>,
+    depth: usize,
+    symbols: Option<String>,
+    digits: Option<String>,
+    word_length: Option<usize>,
+    rng_seed: Option<u64>,
+}
+
+impl Default for GeneratorBuilder {
+    fn default() -> Self {
+        GeneratorBuilder::new()
+    }
+}
+
+impl GeneratorBuilder {
+    /// A builder with the default choices.
+    pub fn new() -> GeneratorBuilder {
+        GeneratorBuilder {
+            word_list: None,
+            depth: DEFAULT_DEPTH,
+            symbols: None,
+            digits: None,
+            word_length: None,
+            rng_seed: None,
+        }
+    }
+
+    /// Trains the generator on `tokens`, as [`Generator::new_custom`], instead of the
+    /// [default list](word_list::default_list).
+    pub fn wordlist(mut self, tokens: Vec<String>) -> GeneratorBuilder {
+        self.word_list = Some(tokens);
+        self
+    }
+
+    /// Sets the depth of the markov chain, [`DEFAULT_DEPTH`] by default.
+    pub fn depth(mut self, depth: usize) -> GeneratorBuilder {
+        self.depth = depth;
+        self
+    }
+
+    /// Sets the characters drawn by `s`, see [`Generator::set_symbols`].
+    pub fn symbols(mut self, symbols: &str) -> GeneratorBuilder {
+        self.symbols = Some(symbols.to_string());
+        self
+    }
+
+    /// Sets the characters drawn by `d`, see [`Generator::set_digits`].
+    pub fn digits(mut self, digits: &str) -> GeneratorBuilder {
+        self.digits = Some(digits.to_string());
+        self
+    }
+
+    /// Sets the target length of `w` words, see [`Generator::set_word_length`]; words come
+    /// out about that long, some shorter and some longer.
+    pub fn word_length(mut self, len: usize) -> GeneratorBuilder {
+        self.word_length = Some(len);
+        self
+    }
+
+    /// Seeds the generator, see [`Generator::reseed`]; never reuse a seed for secrets.
+    pub fn rng_seed(mut self, seed: u64) -> GeneratorBuilder {
+        self.rng_seed = Some(seed);
+        self
+    }
+
+    /// Checks the choices and trains the generator.
+    pub fn build(self) -> Result<Generator, BuildError> {
+        if self.depth == 0 {
+            return Err(BuildError::ZeroDepth);
+        }
+        if self.symbols.as_deref() == Some("") {
+            return Err(BuildError::EmptySymbols);
+        }
+        if self.digits.as_deref() == Some("") {
+            return Err(BuildError::EmptyDigits);
+        }
+        if self.word_length == Some(0) {
+            return Err(BuildError::ZeroWordLength);
+        }
+        let tokens = self.word_list.unwrap_or_else(word_list::default_list);
+        let mut generator =
+            Generator::new_custom(tokens, self.depth).ok_or(BuildError::EmptyWordList)?;
+        if let Some(symbols) = &self.symbols {
+            generator
+                .set_symbols(symbols)
+                .map_err(|_| BuildError::EmptySymbols)?;
+        }
+        if let Some(digits) = &self.digits {
+            generator
+                .set_digits(digits)
+                .map_err(|_| BuildError::EmptyDigits)?;
+        }
+        if let Some(len) = self.word_length {
+            generator
+                .set_word_length(len)
+                .map_err(|_| BuildError::ZeroWordLength)?;
+        }
+        if let Some(seed) = self.rng_seed {
+            generator.reseed(seed);
+        }
+        Ok(generator)
+    }
+}
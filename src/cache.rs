@@ -0,0 +1,137 @@
+//! Cache of parsed patterns, so that [`Generator::gen_from_pattern`] does not parse the same
+//! pattern strings over and over, and [`Generator::try_gen_from_pattern`] does not walk the
+//! chain states for their weakest secret at every call.
+use crate::expected::TooExpensive;
+use crate::pattern::{Pattern, Segment};
+use crate::Generator;
+use rand::{CryptoRng, RngCore};
+use std::collections::VecDeque;
+
+/// Least recently used cache of parsed patterns. Services use a handful of patterns, so the
+/// entries are kept in a short list ordered from the most to the least recently used.
+pub(crate) struct PatternCache {
+    capacity: usize,
+    entries: VecDeque<Entry>,
+}
+
+/// A parsed pattern, with the pattern string it is cached under.
+pub(crate) struct Entry {
+    key: String,
+    pub(crate) parsed: Pattern,
+    /// Entropy of the weakest secret of the pattern, once computed, with the sequence
+    /// position it was computed at when the pattern writes it.
+    weakest: Option<(Option<u64>, Bound)>,
+}
+
+/// Entropy of the weakest secret of a pattern, see [`Pattern::min_entropy`].
+type Bound = Result<Option<f64>, TooExpensive>;
+
+impl Entry {
+    /// Entropy of the weakest secret `generator` produces from the pattern, computed at the
+    /// first call and after every change of the settings it depends on.
+    pub(crate) fn weakest<R: RngCore + CryptoRng>(&mut self, generator: &Generator<R>) -> Bound {
+        let sequence = self
+            .parsed
+            .all_segments()
+            .iter()
+            .any(|segment| matches!(segment, Segment::Sequence { .. }))
+            .then_some(generator.sequence);
+        match self.weakest {
+            Some((at, bound)) if at == sequence => bound,
+            _ => {
+                let bound = self.parsed.min_entropy(generator);
+                self.weakest = Some((sequence, bound));
+                bound
+            }
+        }
+    }
+}
+
+impl PatternCache {
+    pub(crate) fn new(capacity: usize) -> PatternCache {
+        PatternCache {
+            capacity,
+            entries: VecDeque::new(),
+        }
+    }
+
+    /// Removes the entry of `pattern` from the cache, parsing it if it is not cached. Only a
+    /// miss allocates: a hit hands out the cached entry, key included.
+    pub(crate) fn take(&mut self, pattern: &str) -> Entry {
+        match self.entries.iter().position(|entry| entry.key == pattern) {
+            Some(i) => self.entries.remove(i).unwrap(),
+            None => Entry {
+                key: pattern.to_string(),
+                parsed: Pattern::parse(pattern),
+                weakest: None,
+            },
+        }
+    }
+
+    /// Puts back `entry` as the most recently used one, evicting the least recently used
+    /// entry when the cache is full.
+    pub(crate) fn put(&mut self, entry: Entry) {
+        if self.capacity == 0 {
+            return;
+        }
+        self.entries.truncate(self.capacity - 1);
+        self.entries.push_front(entry);
+    }
+
+    /// Discards the weakest secrets computed for the cached patterns, when the model or a
+    /// setting they depend on changes.
+    pub(crate) fn forget_bounds(&mut self) {
+        for entry in self.entries.iter_mut() {
+            entry.weakest = None;
+        }
+    }
+}
+
+impl<R: RngCore + CryptoRng> Generator<R> {
+    /// Sets the number of parsed patterns kept by [`Generator::gen_from_pattern`],
+    /// [`DEFAULT_PATTERN_CACHE_SIZE`](crate::DEFAULT_PATTERN_CACHE_SIZE) by default; `0`
+    /// disables the cache. When the cache is full the least recently used pattern is evicted.
+    ///
+    /// ```rust
+    /// use cryptirust::Generator;
+    /// let mut generator = Generator::new();
+    /// generator.set_pattern_cache_size(4);
+    /// for _ in 0..1000 {
+    ///     // parsed once, then served from the cache
+    ///     let (password, entropy) = generator.gen_from_pattern("w-w-w-dd");
+    /// }
+    /// ```
+    pub fn set_pattern_cache_size(&mut self, size: usize) {
+        self.patterns.capacity = size;
+        self.patterns.entries.truncate(size);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::word_list;
+
+    #[test]
+    fn test_pattern_cache() {
+        let mut gen = Generator::new_custom(word_list::cv::list(), 2).unwrap();
+        gen.set_pattern_cache_size(2);
+        for pattern in ["w", "dd", "w", "s"] {
+            gen.gen_from_pattern(pattern);
+        }
+        let cached: Vec<&str> = gen
+            .patterns
+            .entries
+            .iter()
+            .map(|entry| entry.key.as_str())
+            .collect();
+        assert_eq!(cached, ["s", "w"]);
+        gen.set_pattern_cache_size(0);
+        gen.gen_from_pattern("w");
+        assert!(gen.patterns.entries.is_empty());
+        gen.reseed(7);
+        let cached = gen.gen_from_pattern("w-d");
+        gen.reseed(7);
+        assert_eq!(gen.gen_from_parsed(&Pattern::parse("w-d")), cached);
+    }
+}
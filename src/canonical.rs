@@ -0,0 +1,111 @@
+//! Forgiving but safe comparison of user-entered passphrases.
+//!
+//! Passphrases dictated or typed back by users differ from the issued ones in ways which do
+//! not matter: surrounding spaces, letter case, accents typed as separate combining marks, or
+//! a different separator between words. [`canonicalize`] removes those differences and
+//! [`matches()`] compares the canonical forms with [`constant_time_eq`], so that the comparison
+//! time does not reveal how much of the passphrase was right.
+//!
+//! Canonicalization trims the passphrase, composes Latin letters followed by a combining
+//! accent into the precomposed letter, as Unicode NFC does for the letters of the bundled word
+//! lists, and lowercases it. With `ignore_separators` every character which is not a letter or
+//! a digit is dropped as well, which suits passphrases whose symbols are only separators.
+//!
+//! ```rust
+//! use cryptirust::canonical;
+//!
+//! assert!(canonical::matches("Dolce-Vita-42", " dolce-vita-42\n", false));
+//! assert!(canonical::matches("perché-sì", "perche\u{301}-si\u{300}", false));
+//! assert!(canonical::matches("dolce-vita-42", "dolce vita 42", true));
+//! assert!(!canonical::matches("dolce-vita-42", "dolce vita 42", false));
+//! ```
+
+/// Latin letters composed with a combining mark: the mark, then pairs of base letter and
+/// precomposed letter.
+const COMPOSITIONS: &[(char, &str)] = &[
+    // grave
+    ('\u{0300}', "AÀaàEÈeèIÌiìNǸnǹOÒoòUÙuùWẀwẁYỲyỳ"),
+    // acute
+    (
+        '\u{0301}',
+        "AÁaáCĆcćEÉeéGǴgǵIÍiíKḰkḱLĹlĺNŃnńOÓoóRŔrŕSŚsśUÚuúWẂwẃYÝyýZŹzź",
+    ),
+    // circumflex
+    (
+        '\u{0302}',
+        "AÂaâCĈcĉEÊeêGĜgĝHĤhĥIÎiîJĴjĵOÔoôSŜsŝUÛuûWŴwŵYŶyŷZẐzẑ",
+    ),
+    // tilde
+    ('\u{0303}', "AÃaãEẼeẽIĨiĩNÑnñOÕoõUŨuũYỸyỹ"),
+    // diaeresis
+    ('\u{0308}', "AÄaäEËeëHḦhḧIÏiïOÖoötẗUÜuüWẄwẅYŸyÿ"),
+    // ring above
+    ('\u{030a}', "AÅaåUŮuůwẘyẙ"),
+    // cedilla
+    ('\u{0327}', "CÇcçDḐdḑEȨeȩGĢgģHḨhḩKĶkķLĻlļNŅnņRŖrŗSŞsşTŢtţ"),
+    // caron
+    (
+        '\u{030c}',
+        "AǍaǎCČcčDĎdďEĚeěGǦgǧHȞhȟIǏiǐjǰKǨkǩLĽlľNŇnňOǑoǒRŘrřSŠsšTŤtťUǓuǔZŽzž",
+    ),
+];
+
+/// The precomposed form of `base` followed by the combining `mark`, if any.
+fn compose(base: char, mark: char) -> Option<char> {
+    let (_, pairs) = COMPOSITIONS.iter().find(|&&(m, _)| m == mark)?;
+    let mut pairs = pairs.chars();
+    while let (Some(b), Some(composed)) = (pairs.next(), pairs.next()) {
+        if b == base {
+            return Some(composed);
+        }
+    }
+    None
+}
+
+/// Canonical form of `passphrase`, see the [module](self) documentation.
+pub fn canonicalize(passphrase: &str, ignore_separators: bool) -> String {
+    let mut composed = String::with_capacity(passphrase.len());
+    let mut last: Option<char> = None;
+    for c in passphrase.trim().chars() {
+        match last.and_then(|base| compose(base, c)) {
+            Some(letter) => last = Some(letter),
+            None => {
+                composed.extend(last);
+                last = Some(c);
+            }
+        }
+    }
+    composed.extend(last);
+    composed
+        .to_lowercase()
+        .chars()
+        .filter(|c| !ignore_separators || c.is_alphanumeric())
+        .collect()
+}
+
+/// Whether the passphrase `entered` by a user matches the `expected` one once both are
+/// [canonicalized](canonicalize), compared in constant time.
+pub fn matches(expected: &str, entered: &str, ignore_separators: bool) -> bool {
+    constant_time_eq(
+        canonicalize(expected, ignore_separators).as_bytes(),
+        canonicalize(entered, ignore_separators).as_bytes(),
+    )
+}
+
+/// Compares two byte strings in a time which depends only on their lengths, not on the
+/// position of the first difference.
+///
+/// ```rust
+/// use cryptirust::canonical::constant_time_eq;
+///
+/// assert!(constant_time_eq(b"secret", b"secret"));
+/// assert!(!constant_time_eq(b"secret", b"secreT"));
+/// assert!(!constant_time_eq(b"secret", b"secrets"));
+/// ```
+pub fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let difference = a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y));
+    std::hint::black_box(difference) == 0
+}
@@ -0,0 +1,90 @@
+//! Language-specific capitalization.
+//!
+//! The default Unicode case mappings are wrong for a few languages: Turkish and Azerbaijani
+//! pair the dotted `i` with `İ` and the dotless `ı` with `I`, and German capitalizes `ß` as
+//! `ẞ` within a word rather than as `SS`. [`Generator::set_locale`](crate::Generator::set_locale)
+//! selects the rules used to capitalize `C` and `W` elements and to lowercase the generated
+//! text when looking up the model, so that lists in those languages capitalize correctly.
+//! Training lowercases word lists with the default rules: lowercase Turkish lists with
+//! [`Locale::lowercase`] before training them.
+//!
+//! ```rust
+//! use cryptirust::{casing::Locale, Generator};
+//!
+//! assert_eq!(Locale::Turkic.capitalize("istanbul"), "İstanbul");
+//! assert_eq!(Locale::Root.capitalize("istanbul"), "Istanbul");
+//! assert_eq!(Locale::German.uppercase("straße"), "STRAẞE");
+//! assert_eq!(Locale::from_language("tr-TR"), Locale::Turkic);
+//!
+//! let list = ["ılık", "ısı", "iğne", "inek"].map(|w| Locale::Turkic.lowercase(w)).to_vec();
+//! let mut generator = Generator::new_custom(list, 2).unwrap();
+//! generator.set_locale(Locale::Turkic);
+//! let (passphrase, entropy) = generator.gen_from_pattern("W-W");
+//! ```
+
+/// Case mapping rules, see the [module](self) documentation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Locale {
+    /// The default Unicode mappings.
+    #[default]
+    Root,
+    /// Turkish and Azerbaijani: `i` and `İ`, `ı` and `I` are case pairs.
+    Turkic,
+    /// German: `ß` capitalizes as `ẞ`.
+    German,
+}
+
+impl Locale {
+    /// The rules for a BCP 47 language tag such as `tr` or `de-AT`, [`Locale::Root`] for
+    /// languages without specific rules.
+    pub fn from_language(tag: &str) -> Locale {
+        let language = tag.split(['-', '_']).next().unwrap_or_default();
+        match language.to_ascii_lowercase().as_str() {
+            "tr" | "az" => Locale::Turkic,
+            "de" => Locale::German,
+            _ => Locale::Root,
+        }
+    }
+
+    /// `s` with its first character in uppercase.
+    pub fn capitalize(self, s: &str) -> String {
+        let mut chars = s.chars();
+        let Some(first) = chars.next() else {
+            return String::new();
+        };
+        let mut capitalized = String::with_capacity(s.len() + 2);
+        match (self, first) {
+            (Locale::Turkic, 'i') => capitalized.push('İ'),
+            (Locale::German, 'ß') => capitalized.push('ẞ'),
+            _ => capitalized.extend(first.to_uppercase()),
+        }
+        capitalized.push_str(chars.as_str());
+        capitalized
+    }
+
+    /// `s` in uppercase.
+    pub fn uppercase(self, s: &str) -> String {
+        match self {
+            Locale::Turkic => s.replace('i', "İ").to_uppercase(),
+            Locale::German => s.replace('ß', "ẞ").to_uppercase(),
+            Locale::Root => s.to_uppercase(),
+        }
+    }
+
+    /// `s` in lowercase.
+    pub fn lowercase(self, s: &str) -> String {
+        match self {
+            Locale::Turkic => s
+                .split_inclusive(['I', 'İ'])
+                .map(|part| match part.strip_suffix('I') {
+                    Some(rest) => rest.to_lowercase() + "ı",
+                    None => match part.strip_suffix('İ') {
+                        Some(rest) => rest.to_lowercase() + "i",
+                        None => part.to_lowercase(),
+                    },
+                })
+                .collect(),
+            _ => s.to_lowercase(),
+        }
+    }
+}
@@ -0,0 +1,91 @@
+//! Masked display of secrets and confirmation by retyping.
+//!
+//! Secrets copied by hand are often copied wrong, and a mistyped secret stored in a password
+//! manager locks its owner out. [`verify_typed`] asks the user to type the secret back before
+//! it is accepted or stored, catching transcription errors while the secret can still be
+//! shown again. [`Mask`] hides parts of a secret for display, e.g. in prompts helping users
+//! remember it.
+//!
+//! ```rust
+//! use cryptirust::confirm::{self, Mask};
+//!
+//! assert_eq!(Mask::Initials.apply("dolce-vita-42"), "d____-v___-4_");
+//!
+//! let mut typed = "dolce-vita-24\ndolce-vita-42\n".as_bytes();
+//! let mut shown = Vec::new();
+//! assert!(confirm::verify_typed("dolce-vita-42", &mut typed, &mut shown, 3).unwrap());
+//! ```
+use crate::canonical::constant_time_eq;
+use std::io::{self, BufRead, Write};
+
+/// Default number of attempts given to retype a secret.
+pub const DEFAULT_ATTEMPTS: usize = 3;
+
+/// How much of a secret to show.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mask {
+    /// All of it.
+    None,
+    /// Every other word or number.
+    Alternate,
+    /// The first character of every word or number.
+    Initials,
+    /// Only its length.
+    Full,
+}
+
+impl Mask {
+    /// `secret` with the hidden characters replaced by underscores; apart from
+    /// [`Mask::Full`] the characters between words and numbers stay visible.
+    pub fn apply(self, secret: &str) -> String {
+        let mut masked = String::with_capacity(secret.len());
+        let mut runs = 0;
+        let mut in_run = false;
+        for c in secret.chars() {
+            let starts_run = c.is_alphanumeric() && !in_run;
+            in_run = c.is_alphanumeric();
+            if starts_run {
+                runs += 1;
+            }
+            let visible = match self {
+                Mask::None => true,
+                _ if !in_run => self != Mask::Full,
+                Mask::Alternate => runs % 2 == 1,
+                Mask::Initials => starts_run,
+                Mask::Full => false,
+            };
+            masked.push(if visible { c } else { '_' });
+        }
+        masked
+    }
+}
+
+/// Prompts on `output` to retype `expected` and reads the answer from `input`, one line per
+/// attempt, up to `attempts` times. Returns whether one of the answers matched exactly, apart
+/// from the line ending; the end of `input` counts as a failure. The comparison takes the same
+/// time however much of an answer of the right length is right.
+pub fn verify_typed<R: BufRead, W: Write>(
+    expected: &str,
+    input: &mut R,
+    output: &mut W,
+    attempts: usize,
+) -> io::Result<bool> {
+    for attempt in 0..attempts {
+        if attempt > 0 {
+            writeln!(output, "it does not match, try again.")?;
+        }
+        write!(output, "retype the secret: ")?;
+        output.flush()?;
+        let mut line = String::new();
+        if input.read_line(&mut line)? == 0 {
+            writeln!(output)?;
+            return Ok(false);
+        }
+        let typed = line.trim_end_matches(['\r', '\n']);
+        if constant_time_eq(typed.as_bytes(), expected.as_bytes()) {
+            return Ok(true);
+        }
+    }
+    writeln!(output, "it does not match.")?;
+    Ok(false)
+}
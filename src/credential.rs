@@ -0,0 +1,129 @@
+//! Generation of complete account credentials in a single call.
+//!
+//! A [`CredentialSpec`] lists the pattern used for each field of a credential, every field is
+//! generated independently and reported together with its own entropy.
+//!
+//! ```rust
+//! use cryptirust::{credential::CredentialSpec, Generator};
+//!
+//! let mut generator = Generator::new();
+//! let credential = generator.gen_credential(&CredentialSpec::default());
+//! println!("user: {}", credential.username.value);
+//! println!("password: {}", credential.password.value);
+//! println!("pin: {}", credential.pin.value);
+//! println!("recovery: {}", credential.recovery_phrase.value);
+//! println!("combined entropy: {:.2} bits", credential.entropy());
+//! ```
+use crate::Generator;
+use rand::{CryptoRng, RngCore};
+
+/// Patterns used to generate each field of a [`Credential`].
+#[derive(Debug, Clone)]
+pub struct CredentialSpec {
+    pub username: String,
+    pub password: String,
+    pub pin: String,
+    pub recovery_phrase: String,
+}
+
+impl Default for CredentialSpec {
+    fn default() -> Self {
+        CredentialSpec {
+            username: String::from("wdd"),
+            password: String::from("W-w-w-dd-s"),
+            pin: String::from("dddddd"),
+            recovery_phrase: String::from("w w w w w w"),
+        }
+    }
+}
+
+/// A generated value together with its entropy.
+#[derive(Debug, Clone)]
+pub struct Field {
+    pub value: String,
+    pub entropy: f64,
+}
+
+/// A set of independently generated account secrets.
+#[derive(Debug, Clone)]
+pub struct Credential {
+    pub username: Field,
+    pub password: Field,
+    pub pin: Field,
+    pub recovery_phrase: Field,
+}
+
+impl Credential {
+    /// Combined entropy of all fields, since fields are generated independently it is the sum
+    /// of the entropies of the single fields.
+    ///
+    /// Usernames are usually not secret, subtract `username.entropy` when they are disclosed.
+    pub fn entropy(&self) -> f64 {
+        self.username.entropy
+            + self.password.entropy
+            + self.pin.entropy
+            + self.recovery_phrase.entropy
+    }
+}
+
+impl<R: RngCore + CryptoRng> Generator<R> {
+    /// Generates username, password, PIN and recovery phrase following `spec`.
+    pub fn gen_credential(&mut self, spec: &CredentialSpec) -> Credential {
+        let mut field = |pattern: &str| {
+            let (value, entropy) = self.gen_from_pattern(pattern);
+            Field { value, entropy }
+        };
+        Credential {
+            username: field(&spec.username),
+            password: field(&spec.password),
+            pin: field(&spec.pin),
+            recovery_phrase: field(&spec.recovery_phrase),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SYMBOLS;
+
+    #[test]
+    fn test_credential() {
+        let spec = CredentialSpec {
+            username: String::from("w.dd"),
+            password: String::from("W-w-w-dd-s"),
+            pin: String::from("dddd"),
+            recovery_phrase: String::from("w w w w w"),
+        };
+        let mut gen = Generator::new_with_seed(672);
+        let credential = gen.gen_credential(&spec);
+
+        // the fields are generated from their patterns, in order
+        let mut replay = Generator::new_with_seed(672);
+        for (field, pattern) in [
+            (&credential.username, &spec.username),
+            (&credential.password, &spec.password),
+            (&credential.pin, &spec.pin),
+            (&credential.recovery_phrase, &spec.recovery_phrase),
+        ] {
+            let (value, entropy) = replay.gen_from_pattern(pattern);
+            assert_eq!((&field.value, field.entropy), (&value, entropy));
+            assert!(field.entropy > 0.0, "{}", pattern);
+        }
+        assert_eq!(credential.username.value.matches('.').count(), 1);
+        let password = &credential.password.value;
+        assert!(password.starts_with(char::is_uppercase), "{}", password);
+        assert!(password.ends_with(|c| SYMBOLS.contains(c)), "{}", password);
+        assert!(credential.pin.value.chars().all(|c| c.is_ascii_digit()));
+        assert_eq!(credential.pin.value.len(), 4);
+        assert_eq!(credential.pin.entropy, 4.0 * 10f64.log2());
+        assert_eq!(credential.recovery_phrase.value.split(' ').count(), 5);
+
+        // the combined entropy is the sum of the entropies of the fields
+        let sum = credential.username.entropy
+            + credential.password.entropy
+            + credential.pin.entropy
+            + credential.recovery_phrase.entropy;
+        assert_eq!(credential.entropy(), sum);
+    }
+}
@@ -0,0 +1,72 @@
+//! Selection of secret-dependent items with memory accesses which do not depend on the
+//! selected item, for [`Generator::set_constant_time_sampling`].
+use crate::Generator;
+use rand::{CryptoRng, RngCore};
+
+impl<R: RngCore + CryptoRng> Generator<R> {
+    /// Enables or disables constant-time-ish sampling, for users worried about local side
+    /// channels, such as cache timing attacks from other tenants of a shared host.
+    ///
+    /// When enabled, symbols, digits and unconstrained markov chain tokens are selected by
+    /// scanning every candidate and combining them with masks, so that the memory accesses do
+    /// not depend on the outcome. The outputs are the same as with the default, faster
+    /// sampling. This is a best effort: the lookup of the distribution of the next token still
+    /// depends on the text generated so far, and draws constrained by
+    /// [themes](crate::theme) or [empirical word lengths](Generator::set_empirical_word_lengths)
+    /// keep the default sampling.
+    ///
+    /// ```rust
+    /// use cryptirust::Generator;
+    /// let mut generator = Generator::new();
+    /// generator.set_constant_time_sampling(true);
+    /// let (passphrase, entropy) = generator.gen_from_pattern("w-w-w-w-dd");
+    /// ```
+    pub fn set_constant_time_sampling(&mut self, enabled: bool) {
+        self.constant_time = enabled;
+    }
+}
+
+/// All ones when `a == b`, zero otherwise.
+fn mask(a: usize, b: usize) -> u64 {
+    ((a == b) as u64).wrapping_neg()
+}
+
+/// Number of the ascending `cumulative` counts which are at most `n`, i.e. the index of the
+/// token drawn by `n`.
+pub(crate) fn count_at_most(cumulative: &[usize], n: usize) -> usize {
+    cumulative.iter().map(|&c| (c <= n) as usize).sum()
+}
+
+/// Copy of `items[i]`, reading every byte of every item.
+pub(crate) fn select_string(items: &[String], i: usize) -> String {
+    let longest = items.iter().map(String::len).max().unwrap_or(0);
+    let mut bytes = vec![0u8; longest];
+    let mut len = 0;
+    for (j, item) in items.iter().enumerate() {
+        let m = mask(i, j);
+        for (k, byte) in bytes.iter_mut().enumerate() {
+            *byte |= m as u8 & item.as_bytes().get(k).copied().unwrap_or(0);
+        }
+        len |= m as usize & item.len();
+    }
+    bytes.truncate(len);
+    String::from_utf8(bytes).unwrap()
+}
+
+/// `items[i]`, reading every item.
+pub(crate) fn select_f64(items: &[f64], i: usize) -> f64 {
+    let bits = items
+        .iter()
+        .enumerate()
+        .fold(0, |acc, (j, x)| acc | (mask(i, j) & x.to_bits()));
+    f64::from_bits(bits)
+}
+
+/// `set[i]`, reading every character.
+pub(crate) fn select_char(set: &[char], i: usize) -> char {
+    let code = set
+        .iter()
+        .enumerate()
+        .fold(0, |acc, (j, &c)| acc | (mask(i, j) as u32 & c as u32));
+    char::from_u32(code).unwrap()
+}
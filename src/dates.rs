@@ -0,0 +1,101 @@
+//! Date-safe digit groups.
+//!
+//! Crackers try years and dates before other 4-digit numbers, so a group like `1987` or `0412`
+//! is much weaker than its nominal 13.3 bits. With [`Generator::set_date_safe_digits`] every
+//! group of exactly four consecutive `d` in a pattern is drawn uniformly among the values which
+//! do not look like a date, see [`is_date_like`], and the reported entropy is that of the
+//! remaining values.
+//!
+//! ```rust
+//! use cryptirust::{dates, Generator};
+//!
+//! let mut generator = Generator::new();
+//! generator.set_date_safe_digits(true);
+//! let (pin, entropy) = generator.gen_from_pattern("dddd");
+//! assert!(!dates::is_date_like(&pin));
+//! assert!((entropy - (dates::safe_groups() as f64).log2()).abs() < 1e-9);
+//! ```
+use crate::pattern::Segment;
+use crate::{Generator, DIGITS};
+use rand::{CryptoRng, RngCore};
+use std::sync::OnceLock;
+
+/// Number of consecutive digits forming a group.
+pub const GROUP_LENGTH: usize = 4;
+
+/// Whether a 4-digit group looks like a year between 1900 and 2099, or like a day of the year
+/// written as `MMDD` or `DDMM`.
+pub fn is_date_like(group: &str) -> bool {
+    if group.len() != GROUP_LENGTH || !group.bytes().all(|b| b.is_ascii_digit()) {
+        return false;
+    }
+    let (hi, lo) = (
+        group[..2].parse::<u32>().unwrap(),
+        group[2..].parse::<u32>().unwrap(),
+    );
+    hi == 19 || hi == 20 || is_day(hi, lo) || is_day(lo, hi)
+}
+
+/// Number of 4-digit groups which do not look like a date.
+pub fn safe_groups() -> usize {
+    static SAFE: OnceLock<usize> = OnceLock::new();
+    *SAFE.get_or_init(|| {
+        (0..10_000)
+            .filter(|v| !is_date_like(&format!("{:04}", v)))
+            .count()
+    })
+}
+
+/// Whether the `i`-th segment starts a run of exactly [`GROUP_LENGTH`] digits.
+pub(crate) fn starts_group(segments: &[Segment], i: usize) -> bool {
+    let digit = |j: usize| segments.get(j) == Some(&Segment::Digit);
+    (i == 0 || !digit(i - 1)) && (i..i + GROUP_LENGTH).all(digit) && !digit(i + GROUP_LENGTH)
+}
+
+fn is_day(month: u32, day: u32) -> bool {
+    let days = match month {
+        2 => 29,
+        4 | 6 | 9 | 11 => 30,
+        1..=12 => 31,
+        _ => return false,
+    };
+    (1..=days).contains(&day)
+}
+
+impl<R: RngCore + CryptoRng> Generator<R> {
+    /// Enables or disables date-safe digit groups, see the [module](self) documentation.
+    pub fn set_date_safe_digits(&mut self, enabled: bool) {
+        self.date_safe = enabled;
+        self.patterns.forget_bounds();
+    }
+
+    /// Whether the `i`-th segment starts a group drawn with [`Generator::gen_date_safe_group`]:
+    /// date-safe digits are enabled and `d` draws the decimal digits.
+    pub(crate) fn starts_date_safe_group(&self, segments: &[Segment], i: usize) -> bool {
+        self.date_safe
+            && self.digits.len() == DIGITS.len()
+            && self.digits.iter().all(|c| DIGITS.contains(c))
+            && starts_group(segments, i)
+    }
+
+    /// Draws a date-safe digit group, returning it with its entropy.
+    pub(crate) fn gen_date_safe_group(&mut self) -> (String, f64) {
+        let safe = safe_groups();
+        let (value, group) = loop {
+            let value: usize = self.below(10_000);
+            let group = format!("{:04}", value);
+            if !is_date_like(&group) {
+                break (value, group);
+            }
+        };
+        if let Some(t) = self.transcript.as_mut() {
+            // the rank of the group among the safe ones, as for the other distributions
+            let index = value
+                - (0..value)
+                    .filter(|v| is_date_like(&format!("{:04}", v)))
+                    .count();
+            t.record("dddd", index, 1.0 / safe as f64);
+        }
+        (group, (safe as f64).log2())
+    }
+}
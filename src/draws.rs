@@ -0,0 +1,132 @@
+//! Buffered random draws, for [`Generator::set_buffered_draws`].
+use crate::Generator;
+use rand::{CryptoRng, Rng, RngCore};
+
+/// Number of 32-bit words fetched from the random number generator at once.
+const BUFFER_WORDS: usize = 256;
+
+impl<R: RngCore + CryptoRng> Generator<R> {
+    /// Enables or disables buffered draws, for bulk generation.
+    ///
+    /// When enabled, the generator fetches random 32-bit words from its random number
+    /// generator a block at a time and consumes them for every draw, instead of calling the
+    /// random number generator once per draw. This pays off most with random number
+    /// generators which are expensive to call, such as [`rand::rngs::OsRng`]. A seeded
+    /// generator produces the same secrets either way, but with buffering its random number
+    /// generator runs ahead of the draws by up to a block, so [`Generator::rng`] no longer
+    /// reflects the draws made. Reseeding or replacing the random number generator discards
+    /// the buffered words.
+    ///
+    /// ```rust
+    /// use cryptirust::Generator;
+    /// let mut buffered = Generator::new_with_seed(7);
+    /// buffered.set_buffered_draws(true);
+    /// let mut plain = Generator::new_with_seed(7);
+    /// assert_eq!(
+    ///     buffered.gen_from_pattern("w-w-w-w-dd"),
+    ///     plain.gen_from_pattern("w-w-w-w-dd")
+    /// );
+    /// ```
+    pub fn set_buffered_draws(&mut self, enabled: bool) {
+        self.draws = if enabled {
+            DrawBuffer::with_capacity(BUFFER_WORDS)
+        } else {
+            DrawBuffer::default()
+        };
+    }
+
+    /// Draws uniformly below `n`, from the buffered words when buffering is enabled.
+    pub(crate) fn below(&mut self, n: usize) -> usize {
+        self.draws.below(&mut self.rng, n)
+    }
+}
+
+/// Random words fetched in advance; an empty buffer draws directly from the random number
+/// generator.
+#[derive(Debug, Default)]
+pub(crate) struct DrawBuffer {
+    words: Vec<u32>,
+    next: usize,
+}
+
+impl DrawBuffer {
+    fn with_capacity(len: usize) -> DrawBuffer {
+        DrawBuffer {
+            words: vec![0; len],
+            next: len,
+        }
+    }
+
+    /// Discards the buffered words, e.g. when the random number generator changes.
+    pub(crate) fn clear(&mut self) {
+        self.next = self.words.len();
+    }
+
+    /// Draws uniformly below `n`, which must not be zero. The draw consumes the words of the
+    /// stream as `rng.gen_range(0..n)` does, so buffering does not change the outputs.
+    pub(crate) fn below<R: RngCore>(&mut self, rng: &mut R, n: usize) -> usize {
+        assert!(n > 0, "cannot sample empty range");
+        if self.words.is_empty() {
+            return rng.gen_range(0..n);
+        }
+        // widening multiplication with rejection, as the uniform sampling of rand
+        let zone = (n << n.leading_zeros()).wrapping_sub(1);
+        loop {
+            let v = self.next_usize(rng);
+            let m = v as u128 * n as u128;
+            if m as usize <= zone {
+                return (m >> usize::BITS) as usize;
+            }
+        }
+    }
+
+    fn next_usize<R: RngCore>(&mut self, rng: &mut R) -> usize {
+        let lo = self.next_word(rng) as u64;
+        if usize::BITS == 32 {
+            return lo as usize;
+        }
+        let hi = self.next_word(rng) as u64;
+        (hi << 32 | lo) as usize
+    }
+
+    fn next_word<R: RngCore>(&mut self, rng: &mut R) -> u32 {
+        if self.next == self.words.len() {
+            rng.fill(&mut self.words[..]);
+            self.next = 0;
+        }
+        self.next += 1;
+        self.words[self.next - 1]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_buffered_draws() {
+        // buffering consumes the random stream as the plain draws do
+        let mut plain = Generator::new_with_seed(0x719);
+        let mut buffered = Generator::new_with_seed(0x719);
+        buffered.set_buffered_draws(true);
+        for gen in [&mut plain, &mut buffered] {
+            gen.set_empirical_word_lengths(true);
+            gen.set_date_safe_digits(true);
+        }
+        for pattern in ["w-w-w-w-dd", "cccc{>=14}", "[w|W].s.dddd"] {
+            assert_eq!(
+                plain.gen_batch(pattern, 300),
+                buffered.gen_batch(pattern, 300)
+            );
+        }
+        assert_eq!(plain.gen_challenge(4), buffered.gen_challenge(4));
+
+        // reseeding discards the buffered words
+        buffered.reseed(1);
+        plain.reseed(1);
+        assert_eq!(
+            plain.gen_from_pattern("w-w"),
+            buffered.gen_from_pattern("w-w")
+        );
+    }
+}
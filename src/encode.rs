@@ -0,0 +1,488 @@
+//! Reversible pronounceable encodings of binary identifiers.
+//!
+//! Unlike generated secrets, which are random, the encodings of this module map identifiers to
+//! pronounceable text and back without loss, so that support engineers can read an identifier
+//! aloud and look it up from what they heard.
+//!
+//! [`encode_uuid`] writes a UUID as four words of four tokens of the [`cv`](crate::word_list::cv)
+//! list and a three digit number, e.g. `quadepto-qebiadba-viekziaz-wuimojev-409`; [`decode`]
+//! turns the alias back into the UUID. The mapping is a bijection between UUIDs and the
+//! aliases produced by [`encode_uuid`]: the tokens are the digits of the UUID in base 190,
+//! the number its remainder modulo 1000. Decoding ignores case and rejects any other text.
+//!
+//! For interoperability with existing tools, arbitrary bytes are encoded with the standard
+//! pronounceable encodings: [proquints](encode_proquint), five letter syllables of 16 bits
+//! each, and [Bubble Babble](encode_bubble_babble), the checksummed encoding of SSH key
+//! fingerprints. Digests too long to read in full are shortened by [`humanize_digest`] to a
+//! few words, which can be compared but not decoded; [`compare_phrases`] shows which words of
+//! two phrases differ.
+//!
+//! ```rust
+//! use cryptirust::encode;
+//!
+//! let uuid = encode::parse_uuid("5f0c6a1e-8d55-4c57-9a53-0d6f1ef07c11").unwrap();
+//! let alias = encode::encode_uuid(uuid);
+//! assert_eq!(alias, "quadepto-qebiadba-viekziaz-wuimojev-409");
+//! assert_eq!(encode::decode(&alias), Ok(uuid));
+//! assert_eq!(encode::format_uuid(uuid), "5f0c6a1e-8d55-4c57-9a53-0d6f1ef07c11");
+//!
+//! assert_eq!(encode::encode_proquint(&[127, 0, 0, 1]).unwrap(), "lusab-babad");
+//! assert_eq!(encode::decode_bubble_babble("xexax").unwrap(), Vec::<u8>::new());
+//! ```
+use crate::sha256::sha256;
+use crate::word_list;
+use std::fmt;
+
+/// Number of words of a UUID alias.
+const ALIAS_WORDS: usize = 4;
+/// Number of tokens of every word of a UUID alias.
+const TOKENS_PER_WORD: usize = 4;
+/// Modulus of the number closing a UUID alias.
+const NUMBER_RADIX: u128 = 1000;
+
+/// Text which is not a valid encoding.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodeError {
+    /// What is wrong.
+    pub message: String,
+}
+
+impl DecodeError {
+    fn new(message: &str) -> DecodeError {
+        DecodeError {
+            message: message.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+/// A UUID in the hyphenated form, e.g. `5f0c6a1e-8d55-4c57-9a53-0d6f1ef07c11`, or as 32
+/// hexadecimal digits, in either case.
+pub fn parse_uuid(text: &str) -> Result<u128, DecodeError> {
+    let text = text.trim();
+    let hyphenated = text.len() == 36
+        && text
+            .char_indices()
+            .all(|(i, c)| (c == '-') == matches!(i, 8 | 13 | 18 | 23));
+    let digits: String = if hyphenated {
+        text.split('-').collect()
+    } else {
+        text.to_string()
+    };
+    if digits.len() != 32 || !digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return Err(DecodeError::new("not a UUID"));
+    }
+    u128::from_str_radix(&digits, 16).map_err(|_| DecodeError::new("not a UUID"))
+}
+
+/// `uuid` in the lowercase hyphenated form.
+pub fn format_uuid(uuid: u128) -> String {
+    let hex = format!("{:032x}", uuid);
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..]
+    )
+}
+
+/// The pronounceable alias of `uuid`, see the [module](self) documentation.
+pub fn encode_uuid(uuid: u128) -> String {
+    let tokens = word_list::cv::list();
+    let radix = tokens.len() as u128;
+    let number = uuid % NUMBER_RADIX;
+    let mut rest = uuid / NUMBER_RADIX;
+    let mut digits = [0; ALIAS_WORDS * TOKENS_PER_WORD];
+    for digit in digits.iter_mut().rev() {
+        *digit = (rest % radix) as usize;
+        rest /= radix;
+    }
+    let mut alias = String::with_capacity(44);
+    for word in digits.chunks(TOKENS_PER_WORD) {
+        for &digit in word {
+            alias.push_str(&tokens[digit]);
+        }
+        alias.push('-');
+    }
+    alias.push_str(&format!("{:03}", number));
+    alias
+}
+
+/// The UUID of an alias produced by [`encode_uuid`], see the [module](self) documentation.
+pub fn decode(alias: &str) -> Result<u128, DecodeError> {
+    let tokens = word_list::cv::list();
+    let radix = tokens.len() as u128;
+    let alias = alias.trim().to_ascii_lowercase();
+    let parts: Vec<&str> = alias.split('-').collect();
+    if parts.len() != ALIAS_WORDS + 1 {
+        return Err(DecodeError::new(&format!(
+            "expected {} words and a number separated by '-'",
+            ALIAS_WORDS
+        )));
+    }
+    let mut uuid: u128 = 0;
+    for word in &parts[..ALIAS_WORDS] {
+        let bytes = word.as_bytes();
+        if bytes.len() != 2 * TOKENS_PER_WORD || !word.is_ascii() {
+            return Err(DecodeError::new(&format!(
+                "'{}' is not a word of {} tokens",
+                word, TOKENS_PER_WORD
+            )));
+        }
+        for token in bytes.chunks(2) {
+            let digit = tokens
+                .iter()
+                .position(|t| t.as_bytes() == token)
+                .ok_or_else(|| {
+                    DecodeError::new(&format!(
+                        "'{}' is not a token",
+                        String::from_utf8_lossy(token)
+                    ))
+                })?;
+            uuid = uuid
+                .checked_mul(radix)
+                .and_then(|u| u.checked_add(digit as u128))
+                .ok_or_else(|| DecodeError::new("alias out of range"))?;
+        }
+    }
+    let number = parts[ALIAS_WORDS];
+    if number.len() != 3 || !number.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(DecodeError::new(&format!(
+            "'{}' is not a three digit number",
+            number
+        )));
+    }
+    uuid.checked_mul(NUMBER_RADIX)
+        .and_then(|u| u.checked_add(number.parse::<u128>().unwrap_or_default()))
+        .ok_or_else(|| DecodeError::new("alias out of range"))
+}
+
+/// `bytes` as lowercase hexadecimal digits.
+pub fn format_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// The bytes written as hexadecimal digits in `text`, in either case, optionally separated by
+/// `:` or whitespace as in key fingerprints.
+pub fn parse_hex(text: &str) -> Result<Vec<u8>, DecodeError> {
+    let digits: Vec<u8> = text
+        .bytes()
+        .filter(|&b| b != b':' && !b.is_ascii_whitespace())
+        .collect();
+    if !digits.len().is_multiple_of(2) {
+        return Err(DecodeError::new("odd number of hexadecimal digits"));
+    }
+    digits
+        .chunks(2)
+        .map(|pair| {
+            std::str::from_utf8(pair)
+                .ok()
+                .and_then(|pair| u8::from_str_radix(pair, 16).ok())
+                .ok_or_else(|| DecodeError::new("not a hexadecimal number"))
+        })
+        .collect()
+}
+
+/// Consonants of proquints, coding 4 bits each.
+const PROQUINT_CONSONANTS: &[u8; 16] = b"bdfghjklmnprstvz";
+/// Vowels of proquints, coding 2 bits each.
+const PROQUINT_VOWELS: &[u8; 4] = b"aiou";
+
+/// `bytes` as proquints, five letter syllables such as `lusab` coding 16 bits each, separated
+/// by `-`, after the proposal of D. S. Wilkerson; an IPv4 address is two proquints. Returns
+/// `None` if `bytes` has an odd length, which proquints cannot represent.
+pub fn encode_proquint(bytes: &[u8]) -> Option<String> {
+    if !bytes.len().is_multiple_of(2) {
+        return None;
+    }
+    let quints: Vec<String> = bytes
+        .chunks(2)
+        .map(|pair| {
+            let n = u16::from_be_bytes([pair[0], pair[1]]) as usize;
+            [
+                PROQUINT_CONSONANTS[n >> 12],
+                PROQUINT_VOWELS[(n >> 10) & 3],
+                PROQUINT_CONSONANTS[(n >> 6) & 15],
+                PROQUINT_VOWELS[(n >> 4) & 3],
+                PROQUINT_CONSONANTS[n & 15],
+            ]
+            .iter()
+            .map(|&b| b as char)
+            .collect()
+        })
+        .collect();
+    Some(quints.join("-"))
+}
+
+/// The bytes of proquints produced by [`encode_proquint`], ignoring case.
+pub fn decode_proquint(text: &str) -> Result<Vec<u8>, DecodeError> {
+    let text = text.trim().to_ascii_lowercase();
+    if text.is_empty() {
+        return Ok(Vec::new());
+    }
+    let mut bytes = Vec::new();
+    for quint in text.split('-') {
+        let letters = quint.as_bytes();
+        if letters.len() != 5 {
+            return Err(DecodeError::new(&format!("'{}' is not a proquint", quint)));
+        }
+        let mut n = 0;
+        for (i, &letter) in letters.iter().enumerate() {
+            let (alphabet, bits): (&[u8], u32) = if i % 2 == 0 {
+                (PROQUINT_CONSONANTS, 4)
+            } else {
+                (PROQUINT_VOWELS, 2)
+            };
+            let value = alphabet
+                .iter()
+                .position(|&b| b == letter)
+                .ok_or_else(|| DecodeError::new(&format!("'{}' is not a proquint", quint)))?;
+            n = (n << bits) | value as u16;
+        }
+        bytes.extend_from_slice(&n.to_be_bytes());
+    }
+    Ok(bytes)
+}
+
+/// Vowels of Bubble Babble.
+const BUBBLE_VOWELS: &[u8; 6] = b"aeiouy";
+/// Consonants of Bubble Babble; the last one only closes the encoding.
+const BUBBLE_CONSONANTS: &[u8; 17] = b"bcdfghklmnprstvzx";
+
+/// `bytes` in the Bubble Babble encoding of A. Huima, as printed by `ssh-keygen -B`, e.g.
+/// `xigak-nyryk-humil-bosek-sonax` for `Pineapple`; its checksum detects most typing mistakes.
+pub fn encode_bubble_babble(bytes: &[u8]) -> String {
+    let vowel = |i: usize| BUBBLE_VOWELS[i % 6] as char;
+    let consonant = |i: usize| BUBBLE_CONSONANTS[i] as char;
+    let mut text = String::from("x");
+    let mut seed = 1;
+    let mut pairs = bytes.chunks_exact(2);
+    for pair in &mut pairs {
+        let (a, b) = (pair[0] as usize, pair[1] as usize);
+        text.push(vowel((a >> 6) + seed));
+        text.push(consonant((a >> 2) & 15));
+        text.push(vowel((a & 3) + seed / 6));
+        text.push(consonant(b >> 4));
+        text.push('-');
+        text.push(consonant(b & 15));
+        seed = (seed * 5 + a * 7 + b) % 36;
+    }
+    match pairs.remainder() {
+        &[a] => {
+            let a = a as usize;
+            text.push(vowel((a >> 6) + seed));
+            text.push(consonant((a >> 2) & 15));
+            text.push(vowel((a & 3) + seed / 6));
+        }
+        _ => {
+            text.push(vowel(seed));
+            text.push('x');
+            text.push(vowel(seed / 6));
+        }
+    }
+    text.push('x');
+    text
+}
+
+/// The bytes of a Bubble Babble encoding produced by [`encode_bubble_babble`], ignoring case;
+/// fails if the checksum does not match.
+pub fn decode_bubble_babble(text: &str) -> Result<Vec<u8>, DecodeError> {
+    let text = text.trim().to_ascii_lowercase();
+    let letters = text.as_bytes();
+    let invalid = || DecodeError::new("not a Bubble Babble encoding");
+    let checksum = || DecodeError::new("wrong Bubble Babble checksum");
+    if letters.len() < 5
+        || letters[0] != b'x'
+        || letters[letters.len() - 1] != b'x'
+        || !(letters.len() - 5).is_multiple_of(6)
+    {
+        return Err(invalid());
+    }
+    let vowel = |b: u8| {
+        BUBBLE_VOWELS
+            .iter()
+            .position(|&v| v == b)
+            .ok_or_else(invalid)
+    };
+    let consonant = |b: u8| {
+        BUBBLE_CONSONANTS[..16]
+            .iter()
+            .position(|&c| c == b)
+            .ok_or_else(invalid)
+    };
+    // the high and low bits of a byte from its vowels, checked against the seed
+    let outer = |v: usize, offset: usize, max: usize| {
+        let bits = (v + 6 - offset % 6) % 6;
+        if bits < max {
+            Ok(bits)
+        } else {
+            Err(checksum())
+        }
+    };
+    let body = &letters[1..letters.len() - 1];
+    let mut bytes = Vec::new();
+    let mut seed = 1;
+    let mut groups = body.chunks(6);
+    let last = groups.next_back().unwrap_or_default();
+    for group in groups {
+        if group[4] != b'-' {
+            return Err(invalid());
+        }
+        let high = outer(vowel(group[0])?, seed, 4)?;
+        let middle = consonant(group[1])?;
+        let low = outer(vowel(group[2])?, seed / 6, 4)?;
+        let a = high << 6 | middle << 2 | low;
+        let b = consonant(group[3])? << 4 | consonant(group[5])?;
+        bytes.push(a as u8);
+        bytes.push(b as u8);
+        seed = (seed * 5 + a * 7 + b) % 36;
+    }
+    if last.len() != 3 {
+        return Err(invalid());
+    }
+    if last[1] == b'x' {
+        if vowel(last[0])? != seed % 6 || vowel(last[2])? != seed / 6 {
+            return Err(checksum());
+        }
+    } else {
+        let high = outer(vowel(last[0])?, seed, 4)?;
+        let low = outer(vowel(last[2])?, seed / 6, 4)?;
+        bytes.push((high << 6 | consonant(last[1])? << 2 | low) as u8);
+    }
+    Ok(bytes)
+}
+
+/// Number of distinct words of [`humanize_digest`], pairs of [`cv`](crate::word_list::cv)
+/// tokens.
+pub const DIGEST_WORDS: usize = 190 * 190;
+
+/// A fingerprint or other digest as `words` pronounceable words of four letters separated by
+/// `-`, for comparing digests by voice, e.g. `soca-teon-reif-agic` for the bytes of
+/// `Pineapple` and four words. Every word
+/// is chosen by hashing `bytes` with SHA-256, so that all bits of the digest affect all
+/// words; unlike the other encodings it cannot be decoded. Each word carries
+/// [`humanized_entropy`]`(1)` bits, and [`digest_collision_probability`] tells how likely two
+/// digests share the words.
+pub fn humanize_digest(bytes: &[u8], words: usize) -> String {
+    let tokens = word_list::cv::list();
+    let mut message = bytes.to_vec();
+    message.extend_from_slice(&[0; 4]);
+    let counter = message.len() - 4;
+    let mut phrase = Vec::with_capacity(words);
+    for i in 0..words {
+        message[counter..].copy_from_slice(&(i as u32).to_be_bytes());
+        let hash = sha256(&message);
+        let mut word = [0; 8];
+        word.copy_from_slice(&hash[..8]);
+        let index = (u64::from_be_bytes(word) % DIGEST_WORDS as u64) as usize;
+        phrase.push(tokens[index / tokens.len()].clone() + &tokens[index % tokens.len()]);
+    }
+    phrase.join("-")
+}
+
+/// Bits of the digest conveyed by `words` words of [`humanize_digest`]: an attacker must try
+/// about two to this power digests to match a given phrase.
+pub fn humanized_entropy(words: usize) -> f64 {
+    words as f64 * (DIGEST_WORDS as f64).log2()
+}
+
+/// Probability that some of `digests` distinct digests share the phrase of `words` words of
+/// [`humanize_digest`], by the birthday bound.
+pub fn digest_collision_probability(words: usize, digests: usize) -> f64 {
+    let pairs = digests as f64 * digests.saturating_sub(1) as f64 / 2.0;
+    -(-pairs * (-humanized_entropy(words)).exp2()).exp_m1()
+}
+
+/// A word of two phrases compared by [`compare_phrases`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WordDiff {
+    /// The word is in both phrases.
+    Same(String),
+    /// A word of the first phrase was replaced by one of the second.
+    Changed { first: String, second: String },
+    /// The word is only in the first phrase.
+    OnlyFirst(String),
+    /// The word is only in the second phrase.
+    OnlySecond(String),
+}
+
+impl WordDiff {
+    /// Whether the word differs between the phrases.
+    pub fn differs(&self) -> bool {
+        !matches!(self, WordDiff::Same(_))
+    }
+}
+
+/// The words of two phrases aligned on their longest common subsequence, e.g. phrases of
+/// [`humanize_digest`] read by two people, ignoring case and the punctuation between words;
+/// words removed from the first phrase and added to the second at the same place are paired
+/// as [`WordDiff::Changed`].
+///
+/// ```rust
+/// use cryptirust::encode::{compare_phrases, WordDiff};
+///
+/// let diff = compare_phrases("soca-teon-reif-agic", "Soca teon raif agic");
+/// assert_eq!(diff.iter().filter(|w| w.differs()).count(), 1);
+/// assert_eq!(
+///     diff[2],
+///     WordDiff::Changed { first: "reif".to_string(), second: "raif".to_string() }
+/// );
+/// ```
+pub fn compare_phrases(first: &str, second: &str) -> Vec<WordDiff> {
+    let words = |text: &str| -> Vec<String> {
+        text.split(|c: char| !c.is_alphanumeric())
+            .filter(|w| !w.is_empty())
+            .map(str::to_lowercase)
+            .collect()
+    };
+    let (a, b) = (words(first), words(second));
+    // common[i][j]: length of the longest common subsequence of a[i..] and b[j..]
+    let mut common = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            common[i][j] = if a[i] == b[j] {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+    let mut diff = Vec::new();
+    let (mut removed, mut added) = (Vec::new(), Vec::new());
+    let flush = |diff: &mut Vec<WordDiff>, removed: &mut Vec<String>, added: &mut Vec<String>| {
+        let mut added = added.drain(..);
+        for first in removed.drain(..) {
+            diff.push(match added.next() {
+                Some(second) => WordDiff::Changed { first, second },
+                None => WordDiff::OnlyFirst(first),
+            });
+        }
+        diff.extend(added.map(WordDiff::OnlySecond));
+    };
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() || j < b.len() {
+        if i < a.len() && j < b.len() && a[i] == b[j] {
+            flush(&mut diff, &mut removed, &mut added);
+            diff.push(WordDiff::Same(a[i].clone()));
+            i += 1;
+            j += 1;
+        } else if j == b.len() || (i < a.len() && common[i + 1][j] >= common[i][j + 1]) {
+            removed.push(a[i].clone());
+            i += 1;
+        } else {
+            added.push(b[j].clone());
+            j += 1;
+        }
+    }
+    flush(&mut diff, &mut removed, &mut added);
+    diff
+}
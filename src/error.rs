@@ -0,0 +1,126 @@
+use std::fmt;
+
+/// Errors reported by the fallible methods of [`Generator`](crate::Generator).
+#[derive(Debug, Clone, PartialEq)]
+pub enum Error {
+    /// A secret of the pattern, drawn or possible, has less entropy than the configured
+    /// floor, see [`Generator::set_entropy_floor`](crate::Generator::set_entropy_floor).
+    WeakSecret { entropy: f64, floor: f64 },
+    /// No secret far enough from the ones already generated was found within
+    /// [`MAX_REJECTIONS`](crate::batch::MAX_REJECTIONS) attempts, see
+    /// [`Generator::gen_batch_distinct`](crate::Generator::gen_batch_distinct).
+    BatchExhausted {
+        generated: usize,
+        requested: usize,
+        min_distance: usize,
+    },
+    /// Every alias derived for a label within [`MAX_REJECTIONS`](crate::batch::MAX_REJECTIONS)
+    /// attempts was already issued, see
+    /// [`NamespaceGenerator::alias`](crate::namespace::NamespaceGenerator::alias); the alias
+    /// pattern has too little entropy for the number of labels.
+    AliasesExhausted { issued: usize },
+    /// The units appended by
+    /// [`Generator::gen_with_min_entropy`](crate::Generator::gen_with_min_entropy) did not
+    /// reach the target, because they have no entropy or too little.
+    EntropyUnreachable { entropy: f64, target: f64 },
+    /// Every secret drawn within [`MAX_REJECTIONS`](crate::batch::MAX_REJECTIONS) attempts
+    /// was already in the store, see
+    /// [`Generator::gen_unique`](crate::Generator::gen_unique); the pattern has too little
+    /// entropy for the number of secrets stored.
+    SecretsExhausted { attempts: usize },
+    /// Pruning would leave no transition from the empty state, where words start, see
+    /// [`Generator::prune`](crate::Generator::prune).
+    EmptyModel { min_count: usize },
+    /// The pattern draws real words, `x` or `X`, and the generator has no
+    /// [dictionary](crate::Generator::set_dictionary).
+    NoDictionary,
+    /// An element of the pattern has an entropy floor, `w{>=bits}`, above
+    /// [`MAX_ELEMENT_FLOOR`](crate::pattern::MAX_ELEMENT_FLOOR).
+    ElementFloorTooHigh { bits: f64, max: f64 },
+    /// A `{roman}` or `{ordinal}` element of the pattern has a range going beyond the
+    /// supported numbers, 1 to `largest`, see [`numerals`](crate::numerals).
+    NumeralOutOfRange { min: u32, max: u32, largest: u32 },
+    /// The symbol set given to [`Generator::set_symbols`](crate::Generator::set_symbols) is
+    /// empty.
+    EmptySymbols,
+    /// The digit set given to [`Generator::set_digits`](crate::Generator::set_digits) is empty.
+    EmptyDigits,
+    /// The word length given to
+    /// [`Generator::set_word_length`](crate::Generator::set_word_length) is zero.
+    ZeroWordLength,
+    /// A placeholder of a [template](crate::template), `{{cryptirust:}}`, has no pattern;
+    /// `offset` is its position in the template, in bytes.
+    EmptyPlaceholder { offset: usize },
+    /// Finding the weakest secret of the pattern would take more than `max_steps` steps, see
+    /// [`MAX_WALK_STEPS`](crate::pattern::MAX_WALK_STEPS); the pattern is refused rather than
+    /// left unchecked.
+    PatternTooComplex { max_steps: usize },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::WeakSecret { entropy, floor } => write!(
+                f,
+                "secret with {:.2} bits of entropy, below the floor of {:.2} bits",
+                entropy, floor
+            ),
+            Error::BatchExhausted {
+                generated,
+                requested,
+                min_distance,
+            } => write!(
+                f,
+                "only {} of {} secrets at distance {} or more from each other could be generated",
+                generated, requested, min_distance
+            ),
+            Error::AliasesExhausted { issued } => write!(
+                f,
+                "no free alias found for the label, {} aliases are already issued",
+                issued
+            ),
+            Error::EntropyUnreachable { entropy, target } => write!(
+                f,
+                "the units reached {:.2} bits of entropy, short of the target of {:.2} bits",
+                entropy, target
+            ),
+            Error::SecretsExhausted { attempts } => write!(
+                f,
+                "all of {} secrets drawn in a row were already in the store",
+                attempts
+            ),
+            Error::EmptyModel { min_count } => write!(
+                f,
+                "no word starts with a transition observed {} times or more",
+                min_count
+            ),
+            Error::NoDictionary => write!(
+                f,
+                "the pattern draws real words and the generator has no dictionary"
+            ),
+            Error::ElementFloorTooHigh { bits, max } => write!(
+                f,
+                "an element has an entropy floor of {} bits, more than the limit of {}",
+                bits, max
+            ),
+            Error::NumeralOutOfRange { min, max, largest } => write!(
+                f,
+                "the numbers {} to {} go beyond the supported ones, 1 to {}",
+                min, max, largest
+            ),
+            Error::EmptySymbols => write!(f, "the symbol set is empty"),
+            Error::EmptyDigits => write!(f, "the digit set is empty"),
+            Error::ZeroWordLength => write!(f, "the word length must be at least 1"),
+            Error::EmptyPlaceholder { offset } => {
+                write!(f, "the placeholder at byte {} has no pattern", offset)
+            }
+            Error::PatternTooComplex { max_steps } => write!(
+                f,
+                "the weakest secret of the pattern cannot be found within {} steps",
+                max_steps
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
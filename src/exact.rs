@@ -0,0 +1,388 @@
+//! Exact entropy of generated secrets.
+//!
+//! The entropy reported by default is the surprisal of the sequence of random choices made by
+//! the generator. Different sequences of choices can however produce the same text: markov
+//! tokens have different lengths, and consecutive words written without separators (`www`)
+//! can be split in several ways. An attacker guesses texts, not choices, so in those cases the
+//! default figure overstates the strength of the secret.
+//!
+//! The exact entropy of a text is `-log2 P(text)`, where `P(text)` sums the probabilities of
+//! every sequence of choices producing it. It is never larger than the default figure.
+//!
+//! ```rust
+//! use cryptirust::{pattern::Pattern, Generator};
+//!
+//! let mut generator = Generator::new();
+//! let pattern = Pattern::parse("www");
+//! let (password, entropy) = generator.gen_from_parsed(&pattern);
+//! let exact = generator.exact_entropy(&pattern, &password).unwrap();
+//! assert!(exact <= entropy + 1e-9);
+//!
+//! // or report the exact figure directly
+//! generator.set_exact_entropy(true);
+//! let (password, entropy) = generator.gen_from_pattern("www");
+//! ```
+use crate::dates;
+use crate::pattern::{self, Pattern, Segment};
+use crate::theme::Theme;
+use crate::{uppercase_first_letter, Generator, WordLengths};
+use rand::{CryptoRng, RngCore};
+use std::collections::HashMap;
+
+impl<R: RngCore + CryptoRng> Generator<R> {
+    /// Enables or disables exact entropy accounting: when enabled the entropy returned by
+    /// [`Generator::gen_from_pattern`] is the exact entropy of the generated text, see
+    /// [`Generator::exact_entropy`], at the cost of a slower generation.
+    pub fn set_exact_entropy(&mut self, enabled: bool) {
+        self.exact_entropy = enabled;
+    }
+
+    /// Exact entropy of `password` as an output of `pattern`, i.e. `-log2` of the total
+    /// probability of all the ways the generator can produce it. Returns `None` when the
+    /// generator cannot produce `password` from `pattern`, when a [`Theme`] is set, when the
+    /// pattern has entropy floors (`w{>=12}`) or when its alternations (`[sd]`) resolve to
+    /// more than 1024 distinct patterns.
+    pub fn exact_entropy(&self, pattern: &Pattern, password: &str) -> Option<f64> {
+        if self.theme != Theme::Plain || pattern.has_floors() {
+            return None;
+        }
+        let p = outcomes(pattern)?
+            .iter()
+            .map(|(flat, p)| {
+                let mut search = Search {
+                    generator: self,
+                    segments: flat.segments(),
+                    password,
+                    tokens_per_word: self.word_length.div_ceil(self.depth.max(1)),
+                    lengths: self.empirical_lengths.then_some(&self.word_lengths),
+                    memo: HashMap::new(),
+                };
+                p * search.probability(0, 0, 0)
+            })
+            .sum::<f64>();
+        if p > 0.0 {
+            // a certain text has no entropy, not `-0.0` or less by rounding
+            Some((0.0 - p.log2()).max(0.0))
+        } else {
+            None
+        }
+    }
+
+    /// Entropy of `text` as the start of a run of markov tokens, i.e. `-log2` of the
+    /// probability that the tokens generated from an empty prefix begin with `text`, the last
+    /// one possibly extending past it. Unlike [`Generator::exact_entropy`] the text need not
+    /// end at a token boundary, so this scores arbitrary letters. Returns `None` when the model
+    /// cannot produce `text`.
+    pub(crate) fn token_run_entropy(&self, text: &str) -> Option<f64> {
+        let mut p = HashMap::from([(text.len(), 1.0)]);
+        let boundaries: Vec<usize> = text.char_indices().map(|(i, _)| i).collect();
+        for &pos in boundaries.iter().rev() {
+            let rest = &text[pos..];
+            let total = self.distribution(&text[..pos]).map_or(0.0, |(_, tr)| {
+                tr.tokens
+                    .iter()
+                    .enumerate()
+                    .map(|(i, token)| {
+                        if token.starts_with(rest) {
+                            tr.probability(i)
+                        } else if rest.starts_with(token.as_str()) && !token.is_empty() {
+                            tr.probability(i) * p.get(&(pos + token.len())).copied().unwrap_or(0.0)
+                        } else {
+                            0.0
+                        }
+                    })
+                    .sum()
+            });
+            p.insert(pos, total);
+        }
+        let p = p.get(&0).copied().unwrap_or(0.0);
+        (p > 0.0 && !text.is_empty()).then(|| (0.0 - p.log2()).max(0.0))
+    }
+}
+
+/// Most distinct patterns the alternations of a pattern may resolve to for
+/// [`Generator::exact_entropy`] to score it.
+const MAX_OUTCOMES: usize = 1024;
+
+/// The distinct patterns `pattern` resolves to, choosing an alternative of each alternation
+/// uniformly, with their probabilities; identical outcomes are merged. `None` past
+/// [`MAX_OUTCOMES`].
+pub(crate) fn outcomes(pattern: &Pattern) -> Option<Vec<(Pattern, f64)>> {
+    let mut resolved = vec![(Vec::new(), 1.0)];
+    for segment in pattern.segments() {
+        let Segment::Choice(alternatives) = segment else {
+            for (segments, _) in resolved.iter_mut() {
+                pattern::push_segment(segments, segment.clone());
+            }
+            continue;
+        };
+        let mut choices = Vec::new();
+        for alternative in alternatives {
+            for (flat, p) in outcomes(alternative)? {
+                merge(&mut choices, flat, p / alternatives.len() as f64)?;
+            }
+        }
+        let mut next = Vec::new();
+        for (prefix, p) in &resolved {
+            for (choice, q) in &choices {
+                let mut segments: Vec<Segment> = prefix.clone();
+                for segment in choice.segments() {
+                    pattern::push_segment(&mut segments, segment.clone());
+                }
+                merge(&mut next, segments, p * q)?;
+            }
+        }
+        resolved = next;
+    }
+    Some(
+        resolved
+            .into_iter()
+            .map(|(segments, p)| (Pattern::from_segments(segments), p))
+            .collect(),
+    )
+}
+
+/// Adds the outcome `item` with probability `p` to `outcomes`, merging it with an identical
+/// one; `None` when there are too many.
+fn merge<T: PartialEq>(outcomes: &mut Vec<(T, f64)>, item: T, p: f64) -> Option<()> {
+    if let Some((_, q)) = outcomes.iter_mut().find(|(other, _)| *other == item) {
+        *q += p;
+    } else if outcomes.len() < MAX_OUTCOMES {
+        outcomes.push((item, p));
+    } else {
+        return None;
+    }
+    Some(())
+}
+
+/// Memoized computation of the probability of producing the rest of the password, the state
+/// is the current segment, the progress in the current word and the byte offset in the
+/// password. The progress is the number of tokens already emitted, or with empirical word
+/// lengths the number of characters still to generate (`0` before the length is drawn).
+struct Search<'a, R> {
+    generator: &'a Generator<R>,
+    segments: &'a [Segment],
+    password: &'a str,
+    tokens_per_word: usize,
+    lengths: Option<&'a WordLengths>,
+    memo: HashMap<(usize, usize, usize), f64>,
+}
+
+impl<R: RngCore + CryptoRng> Search<'_, R> {
+    fn probability(&mut self, segment: usize, emitted: usize, pos: usize) -> f64 {
+        if let Some(&p) = self.memo.get(&(segment, emitted, pos)) {
+            return p;
+        }
+        let rest = &self.password[pos..];
+        let p = match self.segments.get(segment) {
+            None => {
+                if rest.is_empty() {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+            // refused by `exact_entropy`
+            Some(Segment::Choice(_)) => 0.0,
+            Some(Segment::Literal(text)) => {
+                if rest.starts_with(text.as_str()) {
+                    self.probability(segment + 1, 0, pos + text.len())
+                } else {
+                    0.0
+                }
+            }
+            Some(&Segment::Sequence { start, width }) => {
+                let number = self.generator.sequence_number(start, width);
+                if rest.starts_with(number.as_str()) {
+                    self.probability(segment + 1, 0, pos + number.len())
+                } else {
+                    0.0
+                }
+            }
+            Some(Segment::Digit)
+                if self
+                    .generator
+                    .starts_date_safe_group(self.segments, segment) =>
+            {
+                let grouping = self.generator.digit_grouping;
+                let mut group = String::new();
+                let mut at = pos;
+                for k in 0..dates::GROUP_LENGTH {
+                    if let Some(sep) =
+                        grouping.and_then(|g| g.separator_before(self.segments, segment + k))
+                    {
+                        if !self.password[at..].starts_with(sep) {
+                            return 0.0;
+                        }
+                        at += sep.len_utf8();
+                    }
+                    match self.password[at..].chars().next() {
+                        Some(c) if c.is_ascii_digit() => {
+                            group.push(c);
+                            at += 1;
+                        }
+                        _ => return 0.0,
+                    }
+                }
+                if dates::is_date_like(&group) {
+                    0.0
+                } else {
+                    self.probability(segment + dates::GROUP_LENGTH, 0, at)
+                        / dates::safe_groups() as f64
+                }
+            }
+            Some(Segment::Symbol) | Some(Segment::Digit) => {
+                let grouping = self.generator.digit_grouping.as_ref();
+                let (rest, pos) =
+                    match grouping.and_then(|g| g.separator_before(self.segments, segment)) {
+                        Some(sep) if rest.starts_with(sep) => {
+                            (&rest[sep.len_utf8()..], pos + sep.len_utf8())
+                        }
+                        Some(_) => return 0.0,
+                        None => (rest, pos),
+                    };
+                let set = self.generator.char_set(&self.segments[segment]);
+                match rest.chars().next() {
+                    Some(c) => {
+                        let n = set.len() as f64;
+                        let hits = set.iter().filter(|&&s| s == c).count() as f64;
+                        if hits > 0.0 {
+                            hits / n * self.probability(segment + 1, 0, pos + c.len_utf8())
+                        } else {
+                            0.0
+                        }
+                    }
+                    None => 0.0,
+                }
+            }
+            Some(&Segment::Dictionary { capitalized }) => {
+                let n = self.generator.dictionary.len();
+                if n == 0 {
+                    return self.probability(segment + 1, 0, pos);
+                }
+                let matches: Vec<usize> = (0..n)
+                    .map(|i| self.generator.dictionary_word(i, capitalized))
+                    .filter(|word| rest.starts_with(word.as_str()))
+                    .map(|word| word.len())
+                    .collect();
+                matches
+                    .into_iter()
+                    .map(|len| self.probability(segment + 1, 0, pos + len) / n as f64)
+                    .sum()
+            }
+            #[cfg(feature = "wordlist-pos")]
+            Some(Segment::Part(part)) => {
+                let n = part.words().count() as f64;
+                let matches: Vec<usize> = part
+                    .words()
+                    .filter(|w| rest.starts_with(w))
+                    .map(str::len)
+                    .collect();
+                matches
+                    .into_iter()
+                    .map(|len| self.probability(segment + 1, 0, pos + len) / n)
+                    .sum()
+            }
+            Some(numeral @ (Segment::Roman { .. } | Segment::Ordinal { .. })) => {
+                let numerals = numeral.numerals().unwrap_or_default();
+                let n = numerals.len() as f64;
+                numerals
+                    .iter()
+                    .filter(|numeral| rest.starts_with(numeral.as_str()))
+                    .map(|numeral| self.probability(segment + 1, 0, pos + numeral.len()) / n)
+                    .sum()
+            }
+            Some(&Segment::Token { capitalized, .. }) => self
+                .token(pos, capitalized, None, |s, next| {
+                    s.probability(segment + 1, 0, next)
+                })
+                .unwrap_or_else(|| self.probability(segment + 1, 0, pos)),
+            Some(&Segment::Word { capitalized, .. }) if self.lengths.is_some() => {
+                if emitted == 0 {
+                    let lengths = self.lengths.unwrap();
+                    (1..lengths.counts.len())
+                        .filter(|&len| lengths.counts[len] > 0)
+                        .map(|len| {
+                            lengths.probability(len)
+                                * self.word_rest(segment, len, pos, capitalized)
+                        })
+                        .sum()
+                } else {
+                    self.word_rest(segment, emitted, pos, false)
+                }
+            }
+            Some(&Segment::Word { capitalized, .. }) => {
+                if emitted >= self.tokens_per_word {
+                    self.probability(segment + 1, 0, pos)
+                } else {
+                    self.token(pos, capitalized && emitted == 0, None, |s, next| {
+                        s.probability(segment, emitted + 1, next)
+                    })
+                    // the generator ends the word early when the model has no continuation
+                    .unwrap_or_else(|| self.probability(segment + 1, 0, pos))
+                }
+            }
+        };
+        self.memo.insert((segment, emitted, pos), p);
+        p
+    }
+
+    /// Probability of completing a word with empirical length which still needs `remaining`
+    /// characters.
+    fn word_rest(
+        &mut self,
+        segment: usize,
+        remaining: usize,
+        pos: usize,
+        capitalized: bool,
+    ) -> f64 {
+        self.token(pos, capitalized, Some(remaining), |s, next| {
+            let len = s.password[pos..next].chars().count();
+            match remaining.saturating_sub(len) {
+                0 => s.probability(segment + 1, 0, next),
+                left => s.probability(segment, left, next),
+            }
+        })
+        .unwrap_or_else(|| self.probability(segment + 1, 0, pos))
+    }
+
+    /// Sums, over the tokens which can follow the password prefix ending at `pos` and match
+    /// the password, the probability of the token times `rest(next_pos)`. With a `limit` only
+    /// the tokens of at most `limit` characters are drawn, if any. Returns `None` when the
+    /// model has no distribution for the prefix.
+    fn token<F>(
+        &mut self,
+        pos: usize,
+        capitalized: bool,
+        limit: Option<usize>,
+        mut rest: F,
+    ) -> Option<f64>
+    where
+        F: FnMut(&mut Self, usize) -> f64,
+    {
+        let (_, tr) = self.generator.distribution(&self.password[..pos])?;
+        let fitting = |l: usize| tr.total_where(|t| t.chars().count() <= l);
+        let limit = limit.filter(|&l| fitting(l) > 0);
+        let scale = limit.map_or(1.0, |l| tr.total as f64 / fitting(l) as f64);
+        let mut candidates = Vec::new();
+        for (i, token) in tr.tokens.iter().enumerate() {
+            if limit.is_some_and(|l| token.chars().count() > l) {
+                continue;
+            }
+            let rendered = if capitalized {
+                uppercase_first_letter(token, self.generator.locale)
+            } else {
+                token.clone()
+            };
+            if self.password[pos..].starts_with(rendered.as_str()) {
+                candidates.push((tr.probability(i) * scale, pos + rendered.len()));
+            }
+        }
+        Some(
+            candidates
+                .into_iter()
+                .map(|(p, next)| p * rest(self, next))
+                .sum(),
+        )
+    }
+}
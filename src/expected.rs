@@ -0,0 +1,638 @@
+//! Expected entropy of a pattern, computed from the model instead of sampled.
+use crate::dates;
+use crate::exact;
+use crate::pattern::{Pattern, Segment, MAX_WALK_STEPS};
+use crate::theme::{admits, Constraint, Theme};
+use crate::{relax, uppercase_first_letter, Generator};
+use rand::{CryptoRng, RngCore};
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap};
+
+/// Probability of each markov chain context, i.e. of the last `depth` characters of the text.
+type Contexts = BTreeMap<String, f64>;
+
+/// Probability of each context together with the characters left in the word being generated.
+type States = BTreeMap<(String, usize), f64>;
+
+/// A walk through the chain states which would take more than [`MAX_WALK_STEPS`] steps.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct TooExpensive;
+
+impl Pattern {
+    /// Mean of the entropy reported for the secrets that `generator` produces from this pattern.
+    ///
+    /// The entropy reported with a secret is its realized surprisal, which varies from one
+    /// secret to the next, especially for `w` words whose tokens are drawn from the markov
+    /// chain. This method computes its expectation exactly, by propagating the distribution of
+    /// the chain state (the last `depth` characters of the text) through the pattern, so that
+    /// patterns can be compared without sampling.
+    ///
+    /// Returns `None` when a [theme](crate::theme) is set, when the pattern has entropy
+    /// floors (`w{>=12}`) or alternations (`[sd]`), when it draws real words (`x`) and the
+    /// generator has no [dictionary](Generator::set_dictionary), or when the walk through the
+    /// chain states would take more than [`MAX_WALK_STEPS`] steps. The walk visits every
+    /// context the text can end with, so its cost grows with the depth of the model and with
+    /// the number of words written without separators; it is shorter with depths up to 3 and
+    /// separated words, see [`MAX_WALK_STEPS`]. With
+    /// [exact entropy](Generator::set_exact_entropy) the reported entropies are lower on
+    /// average, since they merge the ways of producing the same text.
+    ///
+    /// ```rust
+    /// use cryptirust::{pattern::Pattern, Generator};
+    ///
+    /// let mut generator = Generator::new();
+    /// let pattern = Pattern::parse("w-w-dd");
+    /// let expected = pattern.expected_entropy(&generator).unwrap();
+    /// let mean = (0..2000)
+    ///     .map(|_| generator.gen_from_parsed(&pattern).1)
+    ///     .sum::<f64>()
+    ///     / 2000.0;
+    /// assert!((mean - expected).abs() < 1.0);
+    /// ```
+    pub fn expected_entropy<R: RngCore + CryptoRng>(
+        &self,
+        generator: &Generator<R>,
+    ) -> Option<f64> {
+        self.segment_entropies(generator)
+            .map(|entropies| entropies.iter().sum())
+    }
+
+    /// Expected entropy of every segment given the segments before it, whose sum is
+    /// [`Pattern::expected_entropy`]; the entropy of a date-safe group of digits is shared
+    /// evenly among its digits.
+    pub(crate) fn segment_entropies<R: RngCore + CryptoRng>(
+        &self,
+        generator: &Generator<R>,
+    ) -> Option<Vec<f64>> {
+        if generator.theme != Theme::Plain
+            || self.has_floors()
+            || self.has_choices()
+            || generator.lacks_dictionary(self)
+        {
+            return None;
+        }
+        if generator.demo {
+            return Some(vec![0.0; self.segments().len()]);
+        }
+        let mut walk = Walk::new(generator, Mode::Mean, MAX_WALK_STEPS);
+        let mut entropies = Vec::with_capacity(self.segments().len());
+        let mut before = 0.0;
+        self.walk(&mut walk, |walk, span| {
+            let h = (walk.entropy - before) / span as f64;
+            before = walk.entropy;
+            entropies.extend(std::iter::repeat_n(h, span));
+        })
+        .ok()?;
+        Some(entropies)
+    }
+
+    /// Collision (Rényi order 2) entropy of the secrets that `generator` produces from this
+    /// pattern: `-log2` of the probability that two secrets drawn independently are equal.
+    ///
+    /// Collisions are dominated by the most likely secrets, so this is the entropy to use in
+    /// the birthday bound, see [`collision_probability`](crate::batch::collision_probability);
+    /// it never exceeds [`Pattern::expected_entropy`], and is much lower for markov chain
+    /// words, whose common tokens make some words far likelier than others. Returns `None` in
+    /// the same cases as [`Pattern::expected_entropy`]. Like the reported entropy, it counts
+    /// the ways of drawing the same text as distinct secrets.
+    ///
+    /// ```rust
+    /// use cryptirust::{pattern::Pattern, Generator};
+    ///
+    /// let generator = Generator::new();
+    /// let digits = Pattern::parse("dd").collision_entropy(&generator).unwrap();
+    /// assert!((digits - 100f64.log2()).abs() < 1e-9);
+    /// let words = Pattern::parse("w-w");
+    /// assert!(words.collision_entropy(&generator) < words.expected_entropy(&generator));
+    /// ```
+    pub fn collision_entropy<R: RngCore + CryptoRng>(
+        &self,
+        generator: &Generator<R>,
+    ) -> Option<f64> {
+        if generator.theme != Theme::Plain
+            || self.has_floors()
+            || self.has_choices()
+            || generator.lacks_dictionary(self)
+        {
+            return None;
+        }
+        if generator.demo {
+            return Some(0.0);
+        }
+        let mut walk = Walk::new(generator, Mode::Collision, MAX_WALK_STEPS);
+        self.walk(&mut walk, |_, _| {}).ok()?;
+        Some(-walk.contexts.values().sum::<f64>().log2())
+    }
+
+    /// Largest entropy reported for a secret that `generator` produces from this pattern, see
+    /// [`Generator::max_achievable_entropy`].
+    pub(crate) fn max_entropy<R: RngCore + CryptoRng>(
+        &self,
+        generator: &Generator<R>,
+    ) -> Option<f64> {
+        if generator.theme != Theme::Plain
+            || self.has_floors()
+            || self.has_choices()
+            || generator.lacks_dictionary(self)
+        {
+            return None;
+        }
+        if generator.demo {
+            return Some(0.0);
+        }
+        let mut walk = Walk::new(generator, Mode::Max, MAX_WALK_STEPS);
+        self.walk(&mut walk, |_, _| {}).ok()?;
+        Some(walk.contexts.values().copied().fold(0.0, f64::max))
+    }
+
+    /// Smallest entropy reported for a secret that `generator` produces from this pattern
+    /// without [exact entropy](Generator::set_exact_entropy), see
+    /// [`Generator::min_achievable_entropy`]; the walks of all the outcomes of the
+    /// alternations share one budget of [`MAX_WALK_STEPS`] steps.
+    pub(crate) fn min_entropy<R: RngCore + CryptoRng>(
+        &self,
+        generator: &Generator<R>,
+    ) -> Result<Option<f64>, TooExpensive> {
+        let mut budget = MAX_WALK_STEPS;
+        self.min_entropy_within(generator, &mut budget)
+    }
+
+    /// [`Pattern::min_entropy`] taking at most `budget` steps, and leaving the steps not
+    /// taken in `budget`.
+    fn min_entropy_within<R: RngCore + CryptoRng>(
+        &self,
+        generator: &Generator<R>,
+        budget: &mut usize,
+    ) -> Result<Option<f64>, TooExpensive> {
+        if generator.theme != Theme::Plain || generator.lacks_dictionary(self) {
+            return Ok(None);
+        }
+        if generator.demo {
+            return Ok(Some(0.0));
+        }
+        if self.has_choices() {
+            let Some(outcomes) = exact::outcomes(self) else {
+                return Ok(None);
+            };
+            // each outcome costs at least the bits of its own probability
+            let mut min = f64::INFINITY;
+            for (flat, p) in outcomes {
+                match flat.min_entropy_within(generator, budget)? {
+                    Some(h) => min = min.min(h - p.log2()),
+                    None => return Ok(None),
+                }
+            }
+            return Ok(Some(min));
+        }
+        // a floor only resamples the weaker elements, and adds the bits of the resampling,
+        // so the walk ignoring the floors bounds the entropy of the floored pattern
+        let mut walk = Walk::new(generator, Mode::Min, *budget);
+        let walked = self.walk(&mut walk, |_, _| {});
+        *budget = walk.budget;
+        walked?;
+        Ok(Some(
+            walk.contexts
+                .values()
+                .copied()
+                .fold(f64::INFINITY, f64::min),
+        ))
+    }
+
+    /// Walks `walk` through the segments, calling `walked` after each segment with the number
+    /// of segments it spans, until its budget runs out.
+    fn walk<R: RngCore + CryptoRng>(
+        &self,
+        walk: &mut Walk<R>,
+        mut walked: impl FnMut(&Walk<R>, usize),
+    ) -> Result<(), TooExpensive> {
+        let generator = walk.generator;
+        let segments = self.segments();
+        let mut skip = 0;
+        for (i, segment) in segments.iter().enumerate() {
+            if skip > 0 {
+                skip -= 1;
+                continue;
+            }
+            match *segment {
+                Segment::Digit if generator.starts_date_safe_group(segments, i) => {
+                    let groups: Vec<String> = (0..10_000)
+                        .map(|v| format!("{:04}", v))
+                        .filter(|group| !dates::is_date_like(group))
+                        .collect();
+                    walk.uniform(groups.iter().map(String::as_str));
+                    skip = dates::GROUP_LENGTH - 1;
+                }
+                Segment::Symbol | Segment::Digit => {
+                    let grouping = generator.digit_grouping.as_ref();
+                    if let Some(sep) = grouping.and_then(|g| g.separator_before(segments, i)) {
+                        walk.append(&sep.to_string());
+                    }
+                    let symbols = generator.char_set(segment);
+                    let symbols: Vec<String> = symbols.iter().map(char::to_string).collect();
+                    walk.uniform(symbols.iter().map(String::as_str));
+                }
+                Segment::Literal(ref text) => walk.append(text),
+                Segment::Sequence { start, width } => {
+                    walk.append(&generator.sequence_number(start, width))
+                }
+                Segment::Token { capitalized, .. } => {
+                    let states = walk.states(|_| generator.depth);
+                    walk.tokens(states, capitalized, false);
+                }
+                Segment::Word { capitalized, .. } if generator.empirical_lengths => {
+                    let lengths = &generator.word_lengths;
+                    let mut states = States::new();
+                    for len in (0..lengths.counts.len()).filter(|&l| lengths.counts[l] > 0) {
+                        let p = lengths.probability(len);
+                        walk.entropy -= p * p.log2();
+                        for ((context, _), q) in walk.states(|_| len) {
+                            let value = walk.choice(q, p);
+                            let unreached = walk.unreached();
+                            walk.merge(states.entry((context, len)).or_insert(unreached), value);
+                        }
+                    }
+                    walk.tokens(states, capitalized, true);
+                }
+                Segment::Word { capitalized, .. } => {
+                    let states = walk.states(|_| generator.word_length);
+                    walk.tokens(states, capitalized, false);
+                }
+                Segment::Dictionary { capitalized } => {
+                    let words: Vec<String> = (0..generator.dictionary.len())
+                        .map(|i| generator.dictionary_word(i, capitalized))
+                        .collect();
+                    walk.uniform(words.iter().map(String::as_str));
+                }
+                #[cfg(feature = "wordlist-pos")]
+                Segment::Part(part) => walk.uniform(part.words()),
+                Segment::Roman { .. } | Segment::Ordinal { .. } => {
+                    let numerals = segment.numerals().unwrap_or_default();
+                    walk.uniform(numerals.iter().map(String::as_str));
+                }
+                // refused by the callers
+                Segment::Choice(_) => (),
+            }
+            if walk.exhausted {
+                return Err(TooExpensive);
+            }
+            walked(walk, skip + 1);
+        }
+        Ok(())
+    }
+}
+
+impl<R: RngCore + CryptoRng> Generator<R> {
+    /// Largest entropy, in bits, that a secret generated from `pattern` can be reported with:
+    /// the pattern can never meet a stronger policy with this generator, e.g. the default
+    /// pattern of a generator trained on a single word has no more entropy than its digits.
+    ///
+    /// The maximum is taken over the chain states like [`Pattern::expected_entropy`] takes
+    /// the mean, and is `None` in the same cases, when a [theme](crate::theme) is set, when
+    /// the pattern has entropy floors or alternations, when it draws real words and the
+    /// generator has no [dictionary](Generator::set_dictionary), or when the walk would take
+    /// more than [`MAX_WALK_STEPS`] steps.
+    ///
+    /// ```rust
+    /// use cryptirust::{pattern::Pattern, Generator};
+    ///
+    /// let generator = Generator::new_custom(vec!["cryptirust".to_string()], 3).unwrap();
+    /// let max = generator.max_achievable_entropy("w-w-dd").unwrap();
+    /// assert!((max - 100f64.log2()).abs() < 1e-9);
+    ///
+    /// let generator = Generator::new();
+    /// let expected = Pattern::parse("w-w-dd").expected_entropy(&generator).unwrap();
+    /// assert!(generator.max_achievable_entropy("w-w-dd").unwrap() > expected);
+    /// ```
+    pub fn max_achievable_entropy(&self, pattern: &str) -> Option<f64> {
+        Pattern::parse(pattern).max_entropy(self)
+    }
+
+    /// Smallest entropy, in bits, that a secret generated from `pattern` can be reported with,
+    /// whatever the random draws: the bound checked by [`Generator::try_gen_from_pattern`]
+    /// against the [entropy floor](Generator::set_entropy_floor), so that a pattern either
+    /// always meets the floor or is always refused.
+    ///
+    /// The minimum is taken over the chain states like [`Generator::max_achievable_entropy`]
+    /// takes the maximum, and over the outcomes of the alternations. It is `None` when a
+    /// [theme](crate::theme) is set, when the pattern draws real words and the generator has
+    /// no [dictionary](Generator::set_dictionary), when its alternations have more than
+    /// 1024 outcomes, or when the walks would take more than [`MAX_WALK_STEPS`] steps.
+    /// [Exact entropy](Generator::set_exact_entropy) can report less.
+    ///
+    /// ```rust
+    /// use cryptirust::Generator;
+    ///
+    /// let generator = Generator::new();
+    /// let min = generator.min_achievable_entropy("w-dd").unwrap();
+    /// assert!(min < generator.max_achievable_entropy("w-dd").unwrap());
+    /// assert!(min >= 100f64.log2());
+    /// assert_eq!(generator.min_achievable_entropy("[dd|ddd]"), Some(100f64.log2() + 1.0));
+    /// ```
+    pub fn min_achievable_entropy(&self, pattern: &str) -> Option<f64> {
+        Pattern::parse(pattern).min_entropy(self).ok().flatten()
+    }
+}
+
+/// What the contexts of a [`Walk`] hold.
+#[derive(Clone, Copy, PartialEq)]
+enum Mode {
+    /// The probability of the context.
+    Mean,
+    /// The sum of the squared probabilities of the texts leading to the context.
+    Collision,
+    /// The largest entropy, in bits, of the texts leading to the context.
+    Max,
+    /// The smallest entropy, in bits, of the texts leading to the context.
+    Min,
+}
+
+/// Distribution of the chain state while walking through a pattern.
+///
+/// When walking for the [`Mode::Max`] or [`Mode::Min`] entropy the contexts hold instead the
+/// largest or smallest entropy, in bits, of the texts leading to them.
+struct Walk<'a, R> {
+    generator: &'a Generator<R>,
+    contexts: Contexts,
+    /// Expected entropy of the segments walked so far.
+    entropy: f64,
+    mode: Mode,
+    /// Steps left, each the extension of a context by one outcome.
+    budget: usize,
+    /// Whether the walk ran out of steps, leaving the contexts incomplete.
+    exhausted: bool,
+    /// Whether a character never occurs in the states of the model, memoized.
+    foreign: RefCell<HashMap<char, bool>>,
+}
+
+impl<'a, R: RngCore + CryptoRng> Walk<'a, R> {
+    fn new(generator: &'a Generator<R>, mode: Mode, budget: usize) -> Walk<'a, R> {
+        let start = match mode {
+            Mode::Mean | Mode::Collision => 1.0,
+            Mode::Max | Mode::Min => 0.0,
+        };
+        Walk {
+            generator,
+            contexts: Contexts::from([(String::new(), start)]),
+            entropy: 0.0,
+            mode,
+            budget,
+            exhausted: false,
+            foreign: RefCell::new(HashMap::new()),
+        }
+    }
+
+    /// Takes `steps` steps from the budget, returning `false` once it is exhausted.
+    fn take(&mut self, steps: usize) -> bool {
+        match self.budget.checked_sub(steps) {
+            Some(left) if !self.exhausted => {
+                self.budget = left;
+                true
+            }
+            _ => {
+                self.exhausted = true;
+                false
+            }
+        }
+    }
+
+    /// Value of a state which no path reaches yet.
+    fn unreached(&self) -> f64 {
+        if self.mode == Mode::Min {
+            f64::INFINITY
+        } else {
+            0.0
+        }
+    }
+
+    /// Adds the `value` of a path to the value of the state it reaches.
+    fn merge(&self, state: &mut f64, value: f64) {
+        match self.mode {
+            Mode::Mean | Mode::Collision => *state += value,
+            Mode::Max => *state = state.max(value),
+            Mode::Min => *state = state.min(value),
+        }
+    }
+
+    /// Value of a path of value `p` followed by a choice of probability `q`.
+    fn choice(&self, p: f64, q: f64) -> f64 {
+        match self.mode {
+            Mode::Mean => p * q,
+            Mode::Collision => p * q * q,
+            Mode::Max | Mode::Min => p - q.log2(),
+        }
+    }
+
+    /// The context following `context` once `text` is appended. The context stops after the
+    /// last character which no state of the model holds, e.g. a separator: the states looked
+    /// up from then on never reach back past it, so contexts differing only before it are
+    /// merged into one.
+    fn tail(&self, context: &str, text: &str) -> String {
+        let joined = format!("{}{}", context, text);
+        let start = joined
+            .char_indices()
+            .rev()
+            .nth(self.generator.depth.saturating_sub(1))
+            .map_or(0, |(i, _)| i);
+        let tail = &joined[start..];
+        let start = tail
+            .char_indices()
+            .rev()
+            .find(|&(_, c)| self.is_foreign(c))
+            .map_or(0, |(i, c)| i + c.len_utf8());
+        tail[start..].to_string()
+    }
+
+    /// Whether no state of the model holds `c`, in any case.
+    fn is_foreign(&self, c: char) -> bool {
+        if c.is_alphabetic() {
+            return false;
+        }
+        *self.foreign.borrow_mut().entry(c).or_insert_with(|| {
+            !self
+                .generator
+                .jump_table
+                .keys()
+                .any(|state| state.contains(c))
+        })
+    }
+
+    /// Appends `text` in every context.
+    fn append(&mut self, text: &str) {
+        if !self.take(self.contexts.len()) {
+            return;
+        }
+        let mut contexts = Contexts::new();
+        for (context, &p) in &self.contexts {
+            self.merge(
+                contexts
+                    .entry(self.tail(context, text))
+                    .or_insert(self.unreached()),
+                p,
+            );
+        }
+        self.contexts = contexts;
+    }
+
+    /// Appends one of `outcomes`, chosen uniformly.
+    fn uniform<'t>(&mut self, outcomes: impl Iterator<Item = &'t str>) {
+        let outcomes: Vec<&str> = outcomes.collect();
+        if !self.take(outcomes.len().saturating_mul(self.contexts.len())) {
+            return;
+        }
+        let n = outcomes.len() as f64;
+        self.entropy += n.log2();
+        let mut all = self.unreached();
+        for &p in self.contexts.values() {
+            self.merge(&mut all, p);
+        }
+        let mut contexts = Contexts::new();
+        for outcome in outcomes {
+            if outcome.chars().count() >= self.generator.depth {
+                // the outcome replaces the whole context
+                let value = self.choice(all, 1.0 / n);
+                self.merge(
+                    contexts
+                        .entry(self.tail("", outcome))
+                        .or_insert(self.unreached()),
+                    value,
+                );
+                continue;
+            }
+            for (context, &p) in &self.contexts {
+                let value = self.choice(p, 1.0 / n);
+                self.merge(
+                    contexts
+                        .entry(self.tail(context, outcome))
+                        .or_insert(self.unreached()),
+                    value,
+                );
+            }
+        }
+        self.contexts = contexts;
+    }
+
+    /// The current contexts, each with the `remaining` characters of a new word.
+    fn states(&self, remaining: impl Fn(&str) -> usize) -> States {
+        self.contexts
+            .iter()
+            .map(|(context, &p)| ((context.clone(), remaining(context)), p))
+            .collect()
+    }
+
+    /// Draws tokens until no characters remain in any state, mirroring the generation of a
+    /// word: with `limited` lengths the tokens are restricted to the remaining characters and
+    /// count for their length, otherwise they count for `depth` characters.
+    fn tokens(&mut self, mut states: States, capitalized: bool, limited: bool) {
+        let mut done = Contexts::new();
+        let mut first = true;
+        while !states.is_empty() {
+            let mut next = States::new();
+            for ((context, remaining), p) in states {
+                let tr = match self.generator.distribution(&context) {
+                    Some((_, tr)) if remaining > 0 => tr,
+                    // the word is complete, or the model has no continuation
+                    _ => {
+                        self.merge(done.entry(context).or_insert(self.unreached()), p);
+                        continue;
+                    }
+                };
+                let constraints = if limited {
+                    vec![Constraint::MaxChars(remaining)]
+                } else {
+                    Vec::new()
+                };
+                let (constraints, total) = relax(tr, &constraints);
+                if !self.take(tr.tokens.len()) {
+                    return;
+                }
+                for (i, token) in tr.tokens.iter().enumerate() {
+                    if !admits(constraints, token) {
+                        continue;
+                    }
+                    let q = tr.frequency(i) as f64 / total as f64;
+                    self.entropy -= p * q * q.log2();
+                    let rendered = if capitalized && first {
+                        uppercase_first_letter(token, self.generator.locale)
+                    } else {
+                        token.clone()
+                    };
+                    let used = if limited {
+                        token.chars().count()
+                    } else {
+                        self.generator.depth
+                    };
+                    let state = (
+                        self.tail(&context, &rendered),
+                        remaining.saturating_sub(used),
+                    );
+                    let value = self.choice(p, q);
+                    self.merge(next.entry(state).or_insert(self.unreached()), value);
+                }
+            }
+            states = next;
+            first = false;
+        }
+        self.contexts = done;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{grouping, word_list};
+
+    #[test]
+    fn test_expected_entropy() {
+        let mut gen = Generator::new_custom(word_list::cv::list(), 2).unwrap();
+        gen.reseed(0xE4);
+        gen.set_date_safe_digits(true);
+        gen.set_digit_grouping(Some(grouping::DigitGrouping::new(3, '-')));
+        for (pattern, empirical) in [("W-cC-dddd", false), ("w.w.s.dddddd", true)] {
+            gen.set_empirical_word_lengths(empirical);
+            let pattern = Pattern::parse(pattern);
+            let expected = pattern.expected_entropy(&gen).unwrap();
+            let mean = (0..20_000)
+                .map(|_| gen.gen_from_parsed(&pattern).1)
+                .sum::<f64>()
+                / 20_000.0;
+            assert!((mean - expected).abs() < 0.1, "{} {}", mean, expected);
+        }
+        gen.set_theme(Theme::Rhyme);
+        assert_eq!(Pattern::parse("w-w").expected_entropy(&gen), None);
+    }
+
+    #[test]
+    fn test_walk_budget() {
+        let gen = Generator::new();
+        let steps = |pattern: &str| {
+            let mut budget = MAX_WALK_STEPS;
+            let min = Pattern::parse(pattern)
+                .min_entropy_within(&gen, &mut budget)
+                .unwrap()
+                .unwrap();
+            (min, MAX_WALK_STEPS - budget)
+        };
+        // after a separator the contexts merge, so separated words cost the same steps, and
+        // have the same bounds, as the first one
+        let (word, walked) = steps("w");
+        let (words, walked_words) = steps("w-w-w");
+        assert!((words - 3.0 * word).abs() < 1e-9);
+        assert!(walked_words < 4 * walked);
+        let max = gen.max_achievable_entropy("w").unwrap();
+        assert!((gen.max_achievable_entropy("w-w-w").unwrap() - 3.0 * max).abs() < 1e-9);
+
+        // a walk beyond its budget gives up, as do the walks of all the alternatives together
+        let mut budget = walked - 1;
+        let pattern = Pattern::parse("w");
+        assert_eq!(
+            pattern.min_entropy_within(&gen, &mut budget),
+            Err(TooExpensive)
+        );
+        let mut budget = walked + 1;
+        let pattern = Pattern::parse("[w|W]");
+        assert_eq!(
+            pattern.min_entropy_within(&gen, &mut budget),
+            Err(TooExpensive)
+        );
+    }
+}
@@ -0,0 +1,234 @@
+//! Entropy floors of single pattern elements, e.g. `w{>=12}`.
+//!
+//! An element with a floor is resampled until its own surprisal reaches the floor. Rejecting
+//! the weak outcomes makes the accepted ones more likely: an outcome of probability `p` is
+//! drawn with probability `p / (1 - q)`, where `q` is the probability of the rejected
+//! outcomes, so the reported entropy of the element is its surprisal plus `log2(1 - q)`.
+//!
+//! `q` is computed exactly by enumerating the outcomes below the floor, which cannot be more
+//! than `2^floor`; see [`MAX_ELEMENT_FLOOR`](crate::pattern::MAX_ELEMENT_FLOOR). When no
+//! outcome reaches the floor the element is generated as if it had none.
+//!
+//! The floor bounds the surprisal of the outcome under the model; because of the correction the
+//! reported entropy of the element can be lower than the floor when most outcomes are
+//! rejected.
+use crate::metrics::Policy;
+use crate::theme::{admits, Anchor};
+use crate::{relax, uppercase_first_letter, Generator, WordProgress};
+use rand::{CryptoRng, RngCore};
+
+/// Transcript state of the correction applied to an element with a floor.
+const FLOOR_STATE: &str = "#floor";
+
+impl<R: RngCore + CryptoRng> Generator<R> {
+    /// Same as `gen_word`, resampling until the entropy of the word reaches `floor`.
+    pub(crate) fn gen_word_at_least(
+        &mut self,
+        passphrase: &mut String,
+        capitalized: bool,
+        anchor: &mut Option<Anchor>,
+        floor: f64,
+    ) -> f64 {
+        let Some(weak) =
+            self.weak_word_probability(passphrase, capitalized, anchor.as_ref(), floor)
+        else {
+            return self.gen_word(passphrase, capitalized, anchor);
+        };
+        let start = passphrase.len();
+        let recorded = self.recorded();
+        let initial = anchor.clone();
+        loop {
+            let h = self.gen_word(passphrase, capitalized, anchor);
+            if h >= floor {
+                return h + self.record_floor(weak);
+            }
+            passphrase.truncate(start);
+            anchor.clone_from(&initial);
+            self.forget(recorded);
+            self.report_resample(Policy::ElementFloor);
+        }
+    }
+
+    /// Same as [`Generator::gen_next_token`], resampling until the entropy of the token reaches
+    /// `floor`.
+    pub(crate) fn gen_token_at_least(&mut self, seed: &str, floor: f64) -> Option<(String, f64)> {
+        let (_, tr) = self.distribution(seed)?;
+        let weak: f64 = (0..tr.tokens.len())
+            .filter(|&i| tr.entropies[i] < floor)
+            .map(|i| tr.probability(i))
+            .sum();
+        if tr.entropies.iter().all(|&h| h < floor) {
+            return self.gen_next_token(seed);
+        }
+        let recorded = self.recorded();
+        loop {
+            let (token, h) = self.gen_next_token(seed)?;
+            if h >= floor {
+                return Some((token, h + self.record_floor(weak)));
+            }
+            self.forget(recorded);
+            self.report_resample(Policy::ElementFloor);
+        }
+    }
+
+    /// Probability that the word following `passphrase` has less than `floor` bits of entropy,
+    /// or `None` when no word reaches the floor.
+    fn weak_word_probability(
+        &self,
+        passphrase: &str,
+        capitalized: bool,
+        anchor: Option<&Anchor>,
+        floor: f64,
+    ) -> Option<f64> {
+        let mut search = WeakWords {
+            generator: self,
+            capitalized,
+            anchor,
+            floor,
+            weak: 0.0,
+            strong: false,
+        };
+        let mut text = passphrase.to_string();
+        let start = text.len();
+        if self.empirical_lengths {
+            for len in
+                (0..self.word_lengths.counts.len()).filter(|&l| self.word_lengths.counts[l] > 0)
+            {
+                let h = -self.word_lengths.probability(len).log2();
+                search.visit(&mut text, WordProgress::new(start, len), h);
+            }
+        } else {
+            search.visit(&mut text, WordProgress::new(start, self.word_length), 0.0);
+        }
+        search.strong.then_some(search.weak)
+    }
+
+    /// Number of decisions recorded in the transcript, if any.
+    fn recorded(&self) -> usize {
+        self.transcript.as_ref().map_or(0, |t| t.decisions.len())
+    }
+
+    /// Drops the decisions of a rejected draw from the transcript.
+    fn forget(&mut self, recorded: usize) {
+        if let Some(t) = self.transcript.as_mut() {
+            t.decisions.truncate(recorded);
+        }
+    }
+
+    /// Records the correction for the rejected outcomes of probability `weak` and returns it.
+    fn record_floor(&mut self, weak: f64) -> f64 {
+        if let Some(t) = self.transcript.as_mut() {
+            t.record(FLOOR_STATE, 0, 1.0 / (1.0 - weak));
+        }
+        (1.0 - weak).log2()
+    }
+}
+
+/// Depth-first enumeration of the words below a floor, mirroring `Generator::gen_word`.
+struct WeakWords<'a, R> {
+    generator: &'a Generator<R>,
+    capitalized: bool,
+    anchor: Option<&'a Anchor>,
+    floor: f64,
+    /// Total probability of the complete words below the floor.
+    weak: f64,
+    /// Whether some word reaches the floor.
+    strong: bool,
+}
+
+impl<R: RngCore + CryptoRng> WeakWords<'_, R> {
+    /// Visits the completions of the word in progress at the end of `text`, which has entropy
+    /// `h` so far.
+    fn visit(&mut self, text: &mut String, mut progress: WordProgress, h: f64) {
+        if h >= self.floor {
+            self.strong = true;
+            return;
+        }
+        let generator = self.generator;
+        let next = generator
+            .word_constraints(text, &mut progress, self.anchor)
+            .and_then(|constraints| Some((constraints, generator.distribution(text)?.1)));
+        let Some((constraints, tr)) = next else {
+            self.weak += (-h).exp2();
+            return;
+        };
+        let (constraints, total) = relax(tr, &constraints);
+        let len = text.len();
+        for (i, token) in tr.tokens.iter().enumerate() {
+            if !admits(constraints, token) {
+                continue;
+            }
+            let p = tr.frequency(i) as f64 / total as f64;
+            if self.capitalized && progress.produced == 0 {
+                text.push_str(&uppercase_first_letter(token, generator.locale));
+            } else {
+                text.push_str(token);
+            }
+            let mut next = progress.clone();
+            generator.advance_word(&mut next, token);
+            self.visit(text, next, h - p.log2());
+            text.truncate(len);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pattern::{self, Pattern};
+    use crate::theme::Theme;
+    use crate::{limits, Error};
+
+    #[test]
+    fn test_element_floors() {
+        let mut gen = Generator::new();
+        gen.reseed(0xF100);
+        // fraction of words below the floor, as drawn without it
+        let below = (0..20_000)
+            .filter(|_| gen.gen_from_pattern("w").1 < 14.0)
+            .count() as f64
+            / 20_000.0;
+        gen.start_transcript();
+        let (_, h) = gen.gen_from_pattern("w{>=14}");
+        let transcript = gen.take_transcript().unwrap();
+        assert!((transcript.entropy() - h).abs() < 1e-9);
+        let correction = transcript.decisions.last().unwrap();
+        assert_eq!(correction.kind, "#floor");
+        let weak = 1.0 - 1.0 / correction.probability;
+        assert!((weak - below).abs() < 0.01, "{} {}", weak, below);
+
+        for (theme, empirical) in [(Theme::Plain, true), (Theme::Rhyme, false)] {
+            gen.set_theme(theme);
+            gen.set_empirical_word_lengths(empirical);
+            for _ in 0..50 {
+                gen.start_transcript();
+                let (_, h) = gen.gen_from_pattern("W{>=12}-w{>=12}-c{>=5}-w");
+                let recorded = gen.take_transcript().unwrap().entropy();
+                assert!((recorded - h).abs() < 1e-9);
+            }
+        }
+
+        // floors above the limit are kept as written and refused by the fallible methods
+        let high = Pattern::parse("w{>=8}-c{>=64}");
+        assert_eq!(high.highest_floor(), Some(64.0));
+        assert_eq!(Pattern::parse("w-c").highest_floor(), None);
+        assert_eq!(
+            gen.try_gen_from_pattern("w{>=8}-c{>=64}"),
+            Err(Error::ElementFloorTooHigh {
+                bits: 64.0,
+                max: pattern::MAX_ELEMENT_FLOOR
+            })
+        );
+        assert!(!matches!(
+            gen.try_gen_from_pattern("w{>=20}"),
+            Err(Error::ElementFloorTooHigh { .. })
+        ));
+        assert_eq!(
+            gen.parse_limited("[w|w{>=30}]", &limits::PatternLimits::default()),
+            Err(limits::LimitError::FloorTooHigh {
+                bits: 30.0,
+                max: pattern::MAX_ELEMENT_FLOOR
+            })
+        );
+    }
+}
@@ -0,0 +1,75 @@
+//! Grouping of long digit runs, for codes which are read aloud or typed from another screen.
+//!
+//! With [`Generator::set_digit_grouping`] a run of consecutive `d` longer than the group size
+//! gets a separator between groups, e.g. `dddddd` gives `123-456`, also within the
+//! [date-safe](crate::dates) groups of four digits. Separators are fixed, so the entropy is the
+//! same as without grouping.
+//!
+//! ```rust
+//! use cryptirust::{grouping::DigitGrouping, Generator};
+//!
+//! let mut generator = Generator::new();
+//! generator.set_digit_grouping(Some(DigitGrouping::new(3, '-')));
+//! let (code, entropy) = generator.gen_from_pattern("dddddd");
+//! assert_eq!(code.len(), 7);
+//! assert_eq!(&code[3..4], "-");
+//! ```
+use crate::pattern::Segment;
+use crate::Generator;
+use rand::{CryptoRng, RngCore};
+
+/// How to split long digit runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DigitGrouping {
+    /// Number of digits per group, at least 1.
+    pub size: usize,
+    /// Separator inserted between groups.
+    pub separator: char,
+}
+
+impl DigitGrouping {
+    /// Groups of `size` digits separated by `separator`.
+    pub fn new(size: usize, separator: char) -> DigitGrouping {
+        DigitGrouping {
+            size: size.max(1),
+            separator,
+        }
+    }
+
+    /// Separator to insert before the `i`-th segment, when it is a digit which starts a new
+    /// group of a run longer than the group size.
+    pub(crate) fn separator_before(&self, segments: &[Segment], i: usize) -> Option<char> {
+        let digit = |j: &Segment| *j == Segment::Digit;
+        if !segments.get(i).is_some_and(digit) {
+            return None;
+        }
+        let before = segments[..i].iter().rev().take_while(|s| digit(s)).count();
+        let after = segments[i..].iter().take_while(|s| digit(s)).count();
+        let run = before + after;
+        (run > self.size && before > 0 && before % self.size.max(1) == 0).then_some(self.separator)
+    }
+}
+
+impl<R: RngCore + CryptoRng> Generator<R> {
+    /// Sets how long digit runs are grouped, `None` to disable grouping (the default).
+    pub fn set_digit_grouping(&mut self, grouping: Option<DigitGrouping>) {
+        self.digit_grouping = grouping;
+        self.patterns.forget_bounds();
+    }
+
+    /// `digits` drawn together for the segments from the `start`-th on, such as a date-safe
+    /// group, with the separators of the digit grouping inserted between them.
+    pub(crate) fn group_digits(&self, segments: &[Segment], start: usize, digits: &str) -> String {
+        let Some(grouping) = self.digit_grouping.as_ref() else {
+            return digits.to_string();
+        };
+        let mut grouped = String::new();
+        for (k, c) in digits.chars().enumerate() {
+            if let Some(sep) = grouping.separator_before(segments, start + k) {
+                grouped.push(sep);
+            }
+            grouped.push(c);
+        }
+        grouped
+    }
+}
@@ -0,0 +1,115 @@
+//! Written hints for passphrases, with the entropy they give away.
+//!
+//! Users who keep a written reminder of a passphrase are better served by a hint than by the
+//! passphrase itself, but every hint tells an attacker something. [`initials`] derives the
+//! first letter of every word of a secret and measures how many bits of entropy it reveals,
+//! so that the hint can be weighed against the strength of the passphrase; [`Hint::warnings`]
+//! flags hints giving away too much.
+//!
+//! The revealed entropy is the information carried by the initials under the markov model of
+//! the generator: the initial of a word reveals `-log2(p)` bits, `p` being the probability of
+//! a word starting with that letter after the text before it. Words from the part-of-speech
+//! lists are measured with the markov model as well, which only approximates their choice.
+//!
+//! ```rust
+//! use cryptirust::{hint, pattern::Pattern, Generator};
+//!
+//! let mut generator = Generator::new();
+//! let secret = generator.gen_annotated(&Pattern::parse("w-w-w-w-w-dd"));
+//! let hint = hint::initials(&generator, &secret);
+//! assert_eq!(hint.text.chars().count(), 5);
+//! println!("hint {} leaves {:.2} of {:.2} bits", hint.text, hint.remaining, secret.entropy);
+//! for warning in &hint.warnings {
+//!     println!("warning: {}", warning);
+//! }
+//! ```
+use crate::registry::Annotated;
+use crate::Generator;
+use rand::{CryptoRng, RngCore};
+use std::fmt;
+
+/// Largest share of the entropy of a secret which a hint may reveal without a warning.
+pub const MAX_REVEALED_SHARE: f64 = 0.25;
+
+/// A hint and what it gives away, see the [module](self) documentation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Hint {
+    /// The hint.
+    pub text: String,
+    /// Entropy revealed by the hint, in bits.
+    pub revealed: f64,
+    /// Entropy of the secret left to an attacker who knows the hint, in bits.
+    pub remaining: f64,
+    /// Reasons not to write the hint down, if any.
+    pub warnings: Vec<HintWarning>,
+}
+
+/// A reason not to write a hint down.
+#[derive(Debug, Clone, PartialEq)]
+pub enum HintWarning {
+    /// The hint reveals more than [`MAX_REVEALED_SHARE`] of the entropy of the secret.
+    RevealsTooMuch { share: f64 },
+    /// The entropy left once the hint is known is below the
+    /// [entropy floor](Generator::set_entropy_floor) of the generator.
+    BelowFloor { remaining: f64, floor: f64 },
+}
+
+impl fmt::Display for HintWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HintWarning::RevealsTooMuch { share } => write!(
+                f,
+                "the hint reveals {:.0}% of the entropy of the secret",
+                share * 100.0
+            ),
+            HintWarning::BelowFloor { remaining, floor } => write!(
+                f,
+                "with the hint only {:.2} bits are left, below the floor of {:.2} bits",
+                remaining, floor
+            ),
+        }
+    }
+}
+
+/// The first letter of every word of `secret`, lowercase, with the entropy it reveals;
+/// `generator` must be the one which generated `secret`.
+pub fn initials<R: RngCore + CryptoRng>(generator: &Generator<R>, secret: &Annotated) -> Hint {
+    let mut text = String::new();
+    let mut revealed = 0.0;
+    for word in &secret.words {
+        let Some(initial) = secret.text[word.range.clone()].chars().next() else {
+            continue;
+        };
+        let initial = generator.locale.lowercase(&initial.to_string());
+        text.push_str(&initial);
+        if let Some((_, tr)) = generator.distribution(&secret.text[..word.range.start]) {
+            let p = (0..tr.tokens.len())
+                .filter(|&i| tr.tokens[i].starts_with(&initial))
+                .map(|i| tr.probability(i))
+                .sum::<f64>();
+            if p > 0.0 {
+                revealed -= p.log2();
+            }
+        }
+    }
+    let revealed = revealed.min(secret.entropy);
+    let remaining = secret.entropy - revealed;
+    let mut warnings = Vec::new();
+    if secret.entropy > 0.0 && revealed > MAX_REVEALED_SHARE * secret.entropy {
+        warnings.push(HintWarning::RevealsTooMuch {
+            share: revealed / secret.entropy,
+        });
+    }
+    if remaining < generator.entropy_floor {
+        warnings.push(HintWarning::BelowFloor {
+            remaining,
+            floor: generator.entropy_floor,
+        });
+    }
+    Hint {
+        text,
+        revealed,
+        remaining,
+        warnings,
+    }
+}
@@ -0,0 +1,246 @@
+//! Encrypted history of generated secrets.
+//!
+//! Provisioning tools are sometimes asked whether a secret found in the wild was ever generated
+//! by them. A [`History`] answers without storing the secrets: it keeps a salted hash of every
+//! recorded secret, HMAC-SHA-256 under a key derived from a passphrase with a random salt per
+//! entry, and is serialized encrypted and authenticated under the same passphrase, so that the
+//! file reveals neither the hashes nor, beyond its size, the number of secrets to those without
+//! the passphrase. This module requires the `history` feature.
+//!
+//! ```rust
+//! use cryptirust::history::History;
+//! use cryptirust::kdf::KdfParams;
+//!
+//! let params = KdfParams { iterations: 1000 };
+//! let mut history = History::new("correct-horse-battery", params).unwrap();
+//! history.record("tilby-wonder-42");
+//! let bytes = history.to_bytes();
+//!
+//! let history = History::open(&bytes, "correct-horse-battery").unwrap();
+//! assert!(history.contains("tilby-wonder-42"));
+//! assert!(!history.contains("tilby-wonder-43"));
+//! assert!(History::open(&bytes, "wrong passphrase").is_err());
+//! ```
+//!
+//! # Format
+//!
+//! A history is the magic `CRHIST`, the version byte `2`, the PBKDF2 iterations as a
+//! big-endian `u32`, the 16 byte PBKDF2 salt, a 12 byte nonce drawn at every serialization, the
+//! encrypted entries, 48 bytes each (the 16 byte salt of the entry and its 32 byte hash), and a
+//! 16 byte tag. The key derived with [`kdf::derive_key`] is split with HMAC-SHA-256 into a
+//! hashing key and an encryption key; the entries are sealed with ChaCha20-Poly1305 (RFC 8439)
+//! under the encryption key and the nonce, authenticating the header as associated data.
+//!
+//! The iterations must be between [`MIN_ITERATIONS`] and [`MAX_ITERATIONS`], so that a
+//! history can neither be created with a trivial work factor nor make [`History::open`] spin
+//! for hours on a crafted file.
+use crate::aead::{self, KEY_LEN, NONCE_LEN, TAG_LEN};
+use crate::kdf::{self, KdfParams};
+use crate::sha256::HmacSha256;
+use crate::store::HistoryStore;
+use rand::rngs::OsRng;
+use rand::RngCore;
+use std::fmt;
+
+/// Fewest PBKDF2 iterations of a history.
+pub const MIN_ITERATIONS: u32 = 1_000;
+/// Most PBKDF2 iterations of a history.
+pub const MAX_ITERATIONS: u32 = 10_000_000;
+
+/// First bytes of a serialized history.
+const MAGIC: &[u8] = b"CRHIST";
+/// Version of the format.
+const VERSION: u8 = 2;
+const SALT_LEN: usize = 16;
+const HASH_LEN: usize = 32;
+const ENTRY_LEN: usize = SALT_LEN + HASH_LEN;
+const HEADER_LEN: usize = MAGIC.len() + 1 + 4 + SALT_LEN + NONCE_LEN;
+
+/// A history which could not be opened.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HistoryError {
+    /// The data is not a serialized history.
+    NotAHistory,
+    /// The history was written by another version of the format.
+    UnsupportedVersion(u8),
+    /// The PBKDF2 iterations are outside [`MIN_ITERATIONS`]`..=`[`MAX_ITERATIONS`].
+    Iterations(u32),
+    /// The tag does not match: the passphrase is wrong or the data was modified.
+    Authentication,
+}
+
+impl fmt::Display for HistoryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HistoryError::NotAHistory => write!(f, "not a history file"),
+            HistoryError::UnsupportedVersion(version) => {
+                write!(f, "unsupported history version {}", version)
+            }
+            HistoryError::Iterations(iterations) => write!(
+                f,
+                "{} iterations, histories need between {} and {}",
+                iterations, MIN_ITERATIONS, MAX_ITERATIONS
+            ),
+            HistoryError::Authentication => {
+                write!(f, "wrong passphrase, or the history was modified")
+            }
+        }
+    }
+}
+
+impl std::error::Error for HistoryError {}
+
+/// Salted hashes of the recorded secrets, see the [module](self) documentation.
+pub struct History {
+    params: KdfParams,
+    salt: [u8; SALT_LEN],
+    keys: Keys,
+    entries: Vec<[u8; ENTRY_LEN]>,
+}
+
+/// Keys derived from the passphrase.
+struct Keys {
+    hash: HmacSha256,
+    encrypt: [u8; KEY_LEN],
+}
+
+impl Keys {
+    fn derive(passphrase: &str, salt: &[u8], params: &KdfParams) -> Result<Keys, HistoryError> {
+        check_params(params)?;
+        let key = kdf::derive_key(passphrase, salt, params)
+            .map_err(|_| HistoryError::Iterations(params.iterations))?;
+        let split = HmacSha256::new(&key);
+        Ok(Keys {
+            hash: HmacSha256::new(&split.mac(b"hash")),
+            encrypt: split.mac(b"encrypt"),
+        })
+    }
+}
+
+/// Checks that `params` are within the bounds of the [format](self#format).
+fn check_params(params: &KdfParams) -> Result<(), HistoryError> {
+    if (MIN_ITERATIONS..=MAX_ITERATIONS).contains(&params.iterations) {
+        Ok(())
+    } else {
+        Err(HistoryError::Iterations(params.iterations))
+    }
+}
+
+impl History {
+    /// An empty history protected by `passphrase`, stretched with `params`; fails when the
+    /// iterations are out of [bounds](self#format).
+    pub fn new(passphrase: &str, params: KdfParams) -> Result<History, HistoryError> {
+        let mut salt = [0; SALT_LEN];
+        OsRng.fill_bytes(&mut salt);
+        Ok(History {
+            keys: Keys::derive(passphrase, &salt, &params)?,
+            params,
+            salt,
+            entries: Vec::new(),
+        })
+    }
+
+    /// Opens a history serialized by [`History::to_bytes`].
+    pub fn open(bytes: &[u8], passphrase: &str) -> Result<History, HistoryError> {
+        let body_len = bytes
+            .len()
+            .checked_sub(HEADER_LEN + TAG_LEN)
+            .filter(|len| len.is_multiple_of(ENTRY_LEN))
+            .ok_or(HistoryError::NotAHistory)?;
+        if !bytes.starts_with(MAGIC) {
+            return Err(HistoryError::NotAHistory);
+        }
+        let version = bytes[MAGIC.len()];
+        if version != VERSION {
+            return Err(HistoryError::UnsupportedVersion(version));
+        }
+        let at = MAGIC.len() + 1;
+        let iterations = u32::from_be_bytes(bytes[at..at + 4].try_into().unwrap());
+        let salt: [u8; SALT_LEN] = bytes[at + 4..at + 4 + SALT_LEN].try_into().unwrap();
+        let nonce: [u8; NONCE_LEN] = bytes[HEADER_LEN - NONCE_LEN..HEADER_LEN]
+            .try_into()
+            .unwrap();
+        let params = KdfParams { iterations };
+        let keys = Keys::derive(passphrase, &salt, &params)?;
+        let (header, rest) = bytes.split_at(HEADER_LEN);
+        let (body, tag) = rest.split_at(body_len);
+        let mut body = body.to_vec();
+        if !aead::open(&keys.encrypt, &nonce, header, &mut body, tag) {
+            return Err(HistoryError::Authentication);
+        }
+        let entries = body
+            .chunks_exact(ENTRY_LEN)
+            .map(|entry| entry.try_into().unwrap())
+            .collect();
+        Ok(History {
+            params,
+            salt,
+            keys,
+            entries,
+        })
+    }
+
+    /// Number of recorded secrets.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether no secret was recorded.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Records the salted hash of `secret`.
+    pub fn record(&mut self, secret: &str) {
+        let mut entry = [0; ENTRY_LEN];
+        OsRng.fill_bytes(&mut entry[..SALT_LEN]);
+        let hash = self.hash(&entry[..SALT_LEN], secret);
+        entry[SALT_LEN..].copy_from_slice(&hash);
+        self.entries.push(entry);
+    }
+
+    /// Whether `secret` was recorded; every entry is hashed with its own salt, so the time
+    /// taken grows with the number of entries.
+    pub fn contains(&self, secret: &str) -> bool {
+        self.entries
+            .iter()
+            .any(|entry| self.hash(&entry[..SALT_LEN], secret) == entry[SALT_LEN..])
+    }
+
+    /// The history encrypted and authenticated, with a fresh nonce, see the [format](self#format).
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut nonce = [0; NONCE_LEN];
+        OsRng.fill_bytes(&mut nonce);
+        let mut bytes = Vec::with_capacity(HEADER_LEN + self.entries.len() * ENTRY_LEN + TAG_LEN);
+        bytes.extend_from_slice(MAGIC);
+        bytes.push(VERSION);
+        bytes.extend_from_slice(&self.params.iterations.to_be_bytes());
+        bytes.extend_from_slice(&self.salt);
+        bytes.extend_from_slice(&nonce);
+        let mut body = self.entries.concat();
+        let tag = aead::seal(&self.keys.encrypt, &nonce, &bytes, &mut body);
+        bytes.extend_from_slice(&body);
+        bytes.extend_from_slice(&tag);
+        bytes
+    }
+
+    fn hash(&self, salt: &[u8], secret: &str) -> [u8; HASH_LEN] {
+        let mut message = salt.to_vec();
+        message.extend_from_slice(secret.as_bytes());
+        self.keys.hash.mac(&message)
+    }
+}
+
+impl HistoryStore for History {
+    fn contains(&self, secret: &str) -> bool {
+        History::contains(self, secret)
+    }
+
+    fn insert(&mut self, secret: &str) -> bool {
+        let missing = !History::contains(self, secret);
+        if missing {
+            self.record(secret);
+        }
+        missing
+    }
+}
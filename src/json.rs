@@ -0,0 +1,302 @@
+//! Tiny JSON writer used by the machine-readable outputs of the library, and the reader of
+//! the documents it writes and of those it imports, [manifests](crate::manifest) and
+//! [tables of counts](crate::ngram).
+
+/// Deepest nesting of arrays and objects accepted by [`parse`], far beyond that of the
+/// documents read, so that hostile input cannot exhaust the stack.
+pub(crate) const MAX_DEPTH: usize = 32;
+
+/// Serializes `s` as a quoted JSON string.
+pub fn json_string(s: &str) -> String {
+    let mut out = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// A parsed JSON value; objects keep the order of their members.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Value {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Value>),
+    Object(Vec<(String, Value)>),
+}
+
+impl Value {
+    /// The member `name` of an object.
+    pub(crate) fn get(&self, name: &str) -> Option<&Value> {
+        match self {
+            Value::Object(members) => members.iter().find(|(n, _)| n == name).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_f64(&self) -> Option<f64> {
+        match self {
+            Value::Number(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    /// A non-negative integer small enough to be exact as a JSON number, below 2^53.
+    pub(crate) fn as_count(&self) -> Option<usize> {
+        self.as_f64()
+            .filter(|n| *n >= 0.0 && *n < (1u64 << 53) as f64 && n.fract() == 0.0)
+            .and_then(|n| usize::try_from(n as u64).ok())
+    }
+}
+
+/// The first error found in a JSON document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ParseError {
+    pub(crate) message: &'static str,
+    /// Byte offset of the error in the document.
+    pub(crate) pos: usize,
+}
+
+impl ParseError {
+    /// Line of the error in `text`, counting from 1.
+    pub(crate) fn line(&self, text: &str) -> usize {
+        line_at(text, self.pos)
+    }
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} at byte {}", self.message, self.pos)
+    }
+}
+
+/// Line of the byte offset `pos` in `text`, counting from 1.
+pub(crate) fn line_at(text: &str, pos: usize) -> usize {
+    text.as_bytes()[..pos.min(text.len())]
+        .iter()
+        .filter(|&&b| b == b'\n')
+        .count()
+        + 1
+}
+
+/// Parses a JSON document.
+pub(crate) fn parse(text: &str) -> Result<Value, ParseError> {
+    let mut parser = Parser { text, pos: 0 };
+    let value = parser.value(0)?;
+    parser.end()?;
+    Ok(value)
+}
+
+/// Parses a JSON document which must be an array, returning its items with the byte offset
+/// where each starts, so that errors found in an item can point at it.
+pub(crate) fn parse_array(text: &str) -> Result<Vec<(usize, Value)>, ParseError> {
+    let mut parser = Parser { text, pos: 0 };
+    if parser.peek() != Some('[') {
+        return Err(parser.error("expected '['"));
+    }
+    parser.pos += 1;
+    let mut items = Vec::new();
+    if parser.peek() == Some(']') {
+        parser.pos += 1;
+    } else {
+        loop {
+            parser.peek();
+            let start = parser.pos;
+            items.push((start, parser.value(1)?));
+            match parser.peek() {
+                Some(',') => parser.pos += 1,
+                Some(']') => {
+                    parser.pos += 1;
+                    break;
+                }
+                _ => return Err(parser.error("expected ',' or ']'")),
+            }
+        }
+    }
+    parser.end()?;
+    Ok(items)
+}
+
+struct Parser<'a> {
+    text: &'a str,
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn error(&self, message: &'static str) -> ParseError {
+        ParseError {
+            message,
+            pos: self.pos,
+        }
+    }
+
+    fn end(&mut self) -> Result<(), ParseError> {
+        match self.peek() {
+            Some(_) => Err(self.error("trailing characters")),
+            None => Ok(()),
+        }
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        let rest = &self.text[self.pos..];
+        self.pos += rest.len() - rest.trim_start().len();
+        self.text[self.pos..].chars().next()
+    }
+
+    fn eat(&mut self, literal: &str) -> bool {
+        let found = self.text[self.pos..].starts_with(literal);
+        if found {
+            self.pos += literal.len();
+        }
+        found
+    }
+
+    /// A value nested in `depth` arrays and objects.
+    fn value(&mut self, depth: usize) -> Result<Value, ParseError> {
+        let open = self.peek();
+        if matches!(open, Some('{' | '[')) && depth >= MAX_DEPTH {
+            return Err(self.error("too deeply nested"));
+        }
+        match open {
+            Some('{') => {
+                self.pos += 1;
+                let mut members = Vec::new();
+                if self.peek() == Some('}') {
+                    self.pos += 1;
+                    return Ok(Value::Object(members));
+                }
+                loop {
+                    if self.peek() != Some('"') {
+                        return Err(self.error("expected a member name"));
+                    }
+                    let name = self.string()?;
+                    if self.peek() != Some(':') {
+                        return Err(self.error("expected ':'"));
+                    }
+                    self.pos += 1;
+                    members.push((name, self.value(depth + 1)?));
+                    match self.peek() {
+                        Some(',') => self.pos += 1,
+                        Some('}') => {
+                            self.pos += 1;
+                            return Ok(Value::Object(members));
+                        }
+                        _ => return Err(self.error("expected ',' or '}'")),
+                    }
+                }
+            }
+            Some('[') => {
+                self.pos += 1;
+                let mut items = Vec::new();
+                if self.peek() == Some(']') {
+                    self.pos += 1;
+                    return Ok(Value::Array(items));
+                }
+                loop {
+                    items.push(self.value(depth + 1)?);
+                    match self.peek() {
+                        Some(',') => self.pos += 1,
+                        Some(']') => {
+                            self.pos += 1;
+                            return Ok(Value::Array(items));
+                        }
+                        _ => return Err(self.error("expected ',' or ']'")),
+                    }
+                }
+            }
+            Some('"') => self.string().map(Value::String),
+            _ if self.eat("null") => Ok(Value::Null),
+            _ if self.eat("true") => Ok(Value::Bool(true)),
+            _ if self.eat("false") => Ok(Value::Bool(false)),
+            _ => {
+                let rest = &self.text[self.pos..];
+                let len = rest
+                    .find(|c: char| !(c.is_ascii_digit() || "+-.eE".contains(c)))
+                    .unwrap_or(rest.len());
+                let number = rest[..len]
+                    .parse()
+                    .map_err(|_| self.error("expected a value"))?;
+                self.pos += len;
+                Ok(Value::Number(number))
+            }
+        }
+    }
+
+    /// A string, starting at its opening quote.
+    fn string(&mut self) -> Result<String, ParseError> {
+        self.pos += 1;
+        let mut out = String::new();
+        let mut chars = self.text[self.pos..].char_indices();
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '"' => {
+                    self.pos += i + 1;
+                    return Ok(out);
+                }
+                '\\' => {
+                    let escaped = match chars.next().map(|(_, e)| e) {
+                        Some('"') => '"',
+                        Some('\\') => '\\',
+                        Some('/') => '/',
+                        Some('n') => '\n',
+                        Some('r') => '\r',
+                        Some('t') => '\t',
+                        Some('b') => '\u{8}',
+                        Some('f') => '\u{c}',
+                        Some('u') => {
+                            let unit = hex_unit(&mut chars);
+                            let c = match unit {
+                                // a high surrogate must be followed by an escaped low one
+                                Some(high @ 0xD800..=0xDBFF) => {
+                                    let low = match (chars.next(), chars.next()) {
+                                        (Some((_, '\\')), Some((_, 'u'))) => hex_unit(&mut chars),
+                                        _ => None,
+                                    };
+                                    low.filter(|low| (0xDC00..=0xDFFF).contains(low))
+                                        .map(|low| {
+                                            0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00)
+                                        })
+                                }
+                                unit => unit,
+                            };
+                            match c.and_then(char::from_u32) {
+                                Some(c) => c,
+                                None => return Err(self.error("invalid \\u escape")),
+                            }
+                        }
+                        _ => return Err(self.error("invalid escape")),
+                    };
+                    out.push(escaped);
+                }
+                c => out.push(c),
+            }
+        }
+        Err(self.error("unterminated string"))
+    }
+}
+
+/// The 4 hexadecimal digits of a `\u` escape, as a UTF-16 code unit.
+fn hex_unit(chars: &mut std::str::CharIndices<'_>) -> Option<u32> {
+    let hex: String = chars.by_ref().take(4).map(|(_, h)| h).collect();
+    if hex.len() != 4 || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    u32::from_str_radix(&hex, 16).ok()
+}
@@ -0,0 +1,422 @@
+//! From a generated passphrase to an encryption key.
+//!
+//! [`derive_key`] stretches a passphrase into a 256-bit key with PBKDF2-HMAC-SHA-256
+//! (RFC 8018), and [`derive_key_argon2id`] with the memory-hard Argon2id (RFC 9106), so that
+//! applications can go from the passphrase to the key of their encryption library in one call.
+//! Both functions are those of the RustCrypto `pbkdf2` and `argon2` crates, with parameters
+//! checked against the bounds below. The salt must be unique per key, e.g. 16 random bytes stored next to the
+//! ciphertext. Argon2id is preferred for new keys: its memory cost makes guessing on GPUs and
+//! dedicated hardware much more expensive. This module requires the `kdf` feature, enabled by
+//! default.
+//!
+//! ```rust
+//! use cryptirust::{kdf, Generator};
+//!
+//! let mut generator = Generator::new();
+//! let (passphrase, _) = generator.gen_from_pattern("w-w-w-w-w");
+//! let salt = b"per-file random salt";
+//! let params = kdf::KdfParams { iterations: 1000 };
+//! let key = kdf::derive_key(&passphrase, salt, &params).unwrap();
+//! assert_eq!(key, kdf::derive_key(&passphrase, salt, &params).unwrap());
+//!
+//! let params = kdf::Argon2Params { memory_kib: 64, iterations: 1, parallelism: 1 };
+//! let key = kdf::derive_key_argon2id(&passphrase, salt, &params).unwrap();
+//!
+//! // parameters without work are refused
+//! let params = kdf::KdfParams { iterations: 0 };
+//! assert!(kdf::derive_key(&passphrase, salt, &params).is_err());
+//! ```
+use crate::sha256::HmacSha256;
+use argon2::{Algorithm, Argon2, AssociatedData, ParamsBuilder, Version};
+use rand::rngs::OsRng;
+use rand::{RngCore, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use sha2::Sha256;
+use std::fmt;
+
+/// Most Argon2 lanes, as allowed by the RFC.
+pub const MAX_ARGON2_LANES: u32 = (1 << 24) - 1;
+/// Most Argon2 memory, 4 GiB in KiB, so that a parameter typo does not exhaust the memory of
+/// the host.
+pub const MAX_ARGON2_MEMORY_KIB: u32 = 4 << 20;
+/// Shortest Argon2 salt, as required by the RFC.
+pub const MIN_ARGON2_SALT: usize = 8;
+
+/// Parameters refused by the key derivation functions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KdfError {
+    /// No iterations: the key would cost nothing to guess.
+    NoIterations,
+    /// Argon2 lanes outside `1..=`[`MAX_ARGON2_LANES`].
+    Parallelism(u32),
+    /// Argon2 memory below 8 KiB per lane or above [`MAX_ARGON2_MEMORY_KIB`].
+    Memory(u32),
+    /// Argon2 salt shorter than [`MIN_ARGON2_SALT`] bytes.
+    ShortSalt(usize),
+    /// Argon2 input, password, secret, associated data or output, of a length outside the
+    /// bounds of the RFC.
+    Length,
+}
+
+impl fmt::Display for KdfError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KdfError::NoIterations => write!(f, "the key derivation needs at least one iteration"),
+            KdfError::Parallelism(lanes) => write!(
+                f,
+                "{} Argon2 lanes, between 1 and {} are allowed",
+                lanes, MAX_ARGON2_LANES
+            ),
+            KdfError::Memory(kib) => write!(
+                f,
+                "{} KiB of Argon2 memory, at least 8 KiB per lane and at most {} KiB are allowed",
+                kib, MAX_ARGON2_MEMORY_KIB
+            ),
+            KdfError::ShortSalt(len) => write!(
+                f,
+                "{} byte Argon2 salt, at least {} bytes are needed",
+                len, MIN_ARGON2_SALT
+            ),
+            KdfError::Length => write!(
+                f,
+                "an Argon2 input or output has a length outside the bounds of RFC 9106"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for KdfError {}
+
+/// Parameters of [`derive_key`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KdfParams {
+    /// Number of PBKDF2 iterations.
+    pub iterations: u32,
+}
+
+impl KdfParams {
+    /// Recommended parameters: 600000 iterations, as advised by OWASP for PBKDF2-HMAC-SHA-256.
+    pub const RECOMMENDED: KdfParams = KdfParams {
+        iterations: 600_000,
+    };
+}
+
+impl Default for KdfParams {
+    fn default() -> Self {
+        KdfParams::RECOMMENDED
+    }
+}
+
+/// Parameters of [`derive_key_argon2id`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Argon2Params {
+    /// Memory used, in KiB; rounded down to a multiple of 4 KiB per lane.
+    pub memory_kib: u32,
+    /// Number of passes over the memory.
+    pub iterations: u32,
+    /// Number of lanes; they are filled one after the other, so this does not add threads.
+    pub parallelism: u32,
+}
+
+impl Argon2Params {
+    /// Recommended parameters: 19 MiB of memory, 2 passes and 1 lane, as advised by OWASP for
+    /// Argon2id.
+    pub const RECOMMENDED: Argon2Params = Argon2Params {
+        memory_kib: 19 * 1024,
+        iterations: 2,
+        parallelism: 1,
+    };
+
+    /// Checks the parameters against the bounds of the RFC and [`MAX_ARGON2_MEMORY_KIB`].
+    pub fn check(&self) -> Result<(), KdfError> {
+        if self.iterations == 0 {
+            return Err(KdfError::NoIterations);
+        }
+        if !(1..=MAX_ARGON2_LANES).contains(&self.parallelism) {
+            return Err(KdfError::Parallelism(self.parallelism));
+        }
+        if self.memory_kib / 8 < self.parallelism || self.memory_kib > MAX_ARGON2_MEMORY_KIB {
+            return Err(KdfError::Memory(self.memory_kib));
+        }
+        Ok(())
+    }
+}
+
+impl Default for Argon2Params {
+    fn default() -> Self {
+        Argon2Params::RECOMMENDED
+    }
+}
+
+/// Derives a 256-bit key from `passphrase` and `salt` with PBKDF2, see the [module](self)
+/// documentation; fails without iterations.
+pub fn derive_key(passphrase: &str, salt: &[u8], params: &KdfParams) -> Result<[u8; 32], KdfError> {
+    let mut key = [0; 32];
+    pbkdf2_hmac_sha256(passphrase.as_bytes(), salt, params.iterations, &mut key)?;
+    Ok(key)
+}
+
+/// Derives a 256-bit key from `passphrase` and `salt` with Argon2id, see the [module](self)
+/// documentation; fails when the parameters are out of [bounds](Argon2Params::check) or the
+/// salt is shorter than [`MIN_ARGON2_SALT`] bytes.
+///
+/// ```rust
+/// use cryptirust::kdf::{derive_key_argon2id, Argon2Params};
+///
+/// let params = Argon2Params { memory_kib: 64, iterations: 2, parallelism: 1 };
+/// let key = derive_key_argon2id("password", b"somesalt", &params).unwrap();
+/// assert_eq!(key[..4], [0x16, 0xa1, 0xa4, 0x98]);
+/// ```
+pub fn derive_key_argon2id(
+    passphrase: &str,
+    salt: &[u8],
+    params: &Argon2Params,
+) -> Result<[u8; 32], KdfError> {
+    let mut key = [0; 32];
+    argon2id(passphrase.as_bytes(), salt, &[], &[], params, &mut key)?;
+    Ok(key)
+}
+
+/// 16 random bytes from the operating system, a salt for [`derive_key`].
+pub fn random_salt() -> [u8; 16] {
+    let mut salt = [0; 16];
+    OsRng.fill_bytes(&mut salt);
+    salt
+}
+
+/// PBKDF2-HMAC-SHA-256 of `password` and `salt`, filling `output`; fails without iterations.
+///
+/// ```rust
+/// use cryptirust::kdf;
+///
+/// let mut output = [0; 32];
+/// kdf::pbkdf2_hmac_sha256(b"password", b"salt", 2, &mut output).unwrap();
+/// assert_eq!(
+///     output[..4],
+///     [0xae, 0x4d, 0x0c, 0x95],
+/// );
+/// ```
+pub fn pbkdf2_hmac_sha256(
+    password: &[u8],
+    salt: &[u8],
+    iterations: u32,
+    output: &mut [u8],
+) -> Result<(), KdfError> {
+    if iterations == 0 {
+        return Err(KdfError::NoIterations);
+    }
+    pbkdf2::pbkdf2_hmac::<Sha256>(password, salt, iterations, output);
+    Ok(())
+}
+
+/// Argon2id of `password` and `salt`, with the optional `secret` and associated data `ad` of
+/// the RFC, filling `output`.
+pub(crate) fn argon2id(
+    password: &[u8],
+    salt: &[u8],
+    secret: &[u8],
+    ad: &[u8],
+    params: &Argon2Params,
+    output: &mut [u8],
+) -> Result<(), KdfError> {
+    params.check()?;
+    if salt.len() < MIN_ARGON2_SALT {
+        return Err(KdfError::ShortSalt(salt.len()));
+    }
+    let refused = |e| match e {
+        argon2::Error::MemoryTooLittle | argon2::Error::MemoryTooMuch => {
+            KdfError::Memory(params.memory_kib)
+        }
+        argon2::Error::ThreadsTooFew | argon2::Error::ThreadsTooMany => {
+            KdfError::Parallelism(params.parallelism)
+        }
+        argon2::Error::TimeTooSmall => KdfError::NoIterations,
+        argon2::Error::SaltTooShort => KdfError::ShortSalt(salt.len()),
+        _ => KdfError::Length,
+    };
+    let mut builder = ParamsBuilder::new();
+    builder
+        .m_cost(params.memory_kib)
+        .t_cost(params.iterations)
+        .p_cost(params.parallelism)
+        .output_len(output.len());
+    if !ad.is_empty() {
+        builder.data(AssociatedData::new(ad).map_err(refused)?);
+    }
+    let params = builder.build().map_err(refused)?;
+    Argon2::new_with_secret(secret, Algorithm::Argon2id, Version::V0x13, params)
+        .map_err(refused)?
+        .hash_password_into(password, salt, output)
+        .map_err(refused)
+}
+
+/// Seed of the random number generator of a run derived from a secret, so that whoever knows
+/// the secret can reproduce the run, e.g. to recover derived credentials.
+///
+/// The key derived from the secret with [`derive_key`] is split with HMAC-SHA-256 into the
+/// seed and a [check value](SecretSeed::check), which can be stored next to the salt to
+/// recognize the secret without revealing the seed.
+///
+/// ```rust
+/// use cryptirust::kdf::{KdfParams, SecretSeed};
+/// use cryptirust::Generator;
+///
+/// let params = KdfParams { iterations: 1000 };
+/// let seed = SecretSeed::derive("recovery phrase", b"per-run salt", &params).unwrap();
+/// let mut generator = Generator::new();
+/// generator.set_rng(seed.rng());
+/// let (first, _) = generator.gen_from_pattern("w-w-w-dd");
+///
+/// let again = SecretSeed::derive("recovery phrase", b"per-run salt", &params).unwrap();
+/// assert_eq!(again.check(), seed.check());
+/// generator.set_rng(again.rng());
+/// assert_eq!(generator.gen_from_pattern("w-w-w-dd").0, first);
+/// ```
+pub struct SecretSeed {
+    key: HmacSha256,
+}
+
+impl SecretSeed {
+    /// Derives the seed of `secret` and `salt`; fails without iterations.
+    pub fn derive(secret: &str, salt: &[u8], params: &KdfParams) -> Result<SecretSeed, KdfError> {
+        Ok(SecretSeed {
+            key: HmacSha256::new(&derive_key(secret, salt, params)?),
+        })
+    }
+
+    /// A value telling whether two seeds were derived from the same secret and salt.
+    pub fn check(&self) -> [u8; 32] {
+        self.key.mac(b"check")
+    }
+
+    /// A random number generator seeded with the seed, for
+    /// [`Generator::set_rng`](crate::Generator::set_rng).
+    pub fn rng(&self) -> ChaCha8Rng {
+        ChaCha8Rng::from_seed(self.key.mac(b"seed"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kdf_params() {
+        assert_eq!(KdfParams::default(), KdfParams::RECOMMENDED);
+        assert_eq!(Argon2Params::default(), Argon2Params::RECOMMENDED);
+        assert_eq!(Argon2Params::RECOMMENDED.check(), Ok(()));
+
+        // PBKDF2 refuses keys which cost nothing, whatever their length
+        let none = KdfParams { iterations: 0 };
+        assert_eq!(derive_key("p", b"salt", &none), Err(KdfError::NoIterations));
+        assert_eq!(
+            pbkdf2_hmac_sha256(b"p", b"salt", 0, &mut [0; 64]),
+            Err(KdfError::NoIterations)
+        );
+        assert!(SecretSeed::derive("p", b"salt", &none).is_err());
+
+        // Argon2 bounds: a pass, 1 to 2^24 - 1 lanes, and 8 KiB per lane up to 4 GiB
+        let params = |memory_kib, iterations, parallelism| Argon2Params {
+            memory_kib,
+            iterations,
+            parallelism,
+        };
+        let accepted = [
+            params(8, 1, 1),
+            params(16, 1, 2),
+            params(MAX_ARGON2_MEMORY_KIB, 1, 1),
+            params(MAX_ARGON2_MEMORY_KIB, 1, MAX_ARGON2_LANES >> 8),
+        ];
+        for params in accepted {
+            assert_eq!(params.check(), Ok(()), "{:?}", params);
+        }
+        let refused = [
+            (params(64, 0, 1), KdfError::NoIterations),
+            (params(64, 1, 0), KdfError::Parallelism(0)),
+            (
+                params(u32::MAX, 1, MAX_ARGON2_LANES + 1),
+                KdfError::Parallelism(MAX_ARGON2_LANES + 1),
+            ),
+            (params(7, 1, 1), KdfError::Memory(7)),
+            (params(15, 1, 2), KdfError::Memory(15)),
+            (
+                params(MAX_ARGON2_MEMORY_KIB + 1, 1, 1),
+                KdfError::Memory(MAX_ARGON2_MEMORY_KIB + 1),
+            ),
+        ];
+        for (params, error) in refused {
+            assert_eq!(params.check(), Err(error));
+            // refused before any memory is allocated
+            assert_eq!(derive_key_argon2id("p", b"somesalt", &params), Err(error));
+        }
+    }
+
+    #[test]
+    fn test_argon2_inputs() {
+        let params = Argon2Params {
+            memory_kib: 64,
+            iterations: 1,
+            parallelism: 1,
+        };
+        assert_eq!(
+            derive_key_argon2id("p", &[0; MIN_ARGON2_SALT - 1], &params),
+            Err(KdfError::ShortSalt(MIN_ARGON2_SALT - 1))
+        );
+        assert!(derive_key_argon2id("p", &[0; MIN_ARGON2_SALT], &params).is_ok());
+        // lengths refused by the RFC map to Length: associated data above 32 bytes, outputs
+        // below 4 bytes
+        let mut output = [0; 32];
+        assert_eq!(
+            argon2id(b"p", b"somesalt", &[], &[0; 33], &params, &mut output),
+            Err(KdfError::Length)
+        );
+        assert_eq!(
+            argon2id(b"p", b"somesalt", &[], &[], &params, &mut [0; 3]),
+            Err(KdfError::Length)
+        );
+        // the secret and the associated data change the key
+        let plain = derive_key_argon2id("p", b"somesalt", &params).unwrap();
+        argon2id(b"p", b"somesalt", &[], &[], &params, &mut output).unwrap();
+        assert_eq!(output, plain);
+        argon2id(b"p", b"somesalt", b"pepper", &[], &params, &mut output).unwrap();
+        assert_ne!(output, plain);
+        argon2id(b"p", b"somesalt", &[], b"context", &params, &mut output).unwrap();
+        assert_ne!(output, plain);
+    }
+
+    #[test]
+    fn test_derived_keys() {
+        // keys depend on the passphrase, the salt and the cost
+        let params = KdfParams { iterations: 2 };
+        let key = derive_key("passphrase", b"salt", &params).unwrap();
+        assert_eq!(derive_key("passphrase", b"salt", &params), Ok(key));
+        assert_ne!(derive_key("passphrase", b"SALT", &params), Ok(key));
+        assert_ne!(derive_key("Passphrase", b"salt", &params), Ok(key));
+        assert_ne!(
+            derive_key("passphrase", b"salt", &KdfParams { iterations: 3 }),
+            Ok(key)
+        );
+        let argon2 = Argon2Params {
+            memory_kib: 64,
+            iterations: 1,
+            parallelism: 1,
+        };
+        let more = Argon2Params {
+            memory_kib: 128,
+            ..argon2
+        };
+        assert_ne!(
+            derive_key_argon2id("passphrase", b"saltsalt", &argon2),
+            derive_key_argon2id("passphrase", b"saltsalt", &more)
+        );
+        assert_ne!(random_salt(), random_salt());
+
+        // the check value of a seed tells seeds apart without revealing their streams
+        let seed = SecretSeed::derive("recovery", b"salt", &params).unwrap();
+        let other = SecretSeed::derive("recovery", b"pepper", &params).unwrap();
+        assert_ne!(seed.check(), other.check());
+        assert_ne!(seed.rng().next_u64(), other.rng().next_u64());
+        assert_eq!(seed.rng().next_u64(), seed.rng().next_u64());
+        assert_ne!(seed.check()[..8], seed.rng().next_u64().to_le_bytes());
+    }
+}
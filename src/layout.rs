@@ -0,0 +1,94 @@
+//! Keyboard layout aware symbol sets.
+//!
+//! The default symbols drawn by the `s` pattern element are easy to type on a US keyboard, but
+//! some of them need dead keys or `AltGr` combinations elsewhere, and most of them hide behind
+//! a second symbol page on phones. [`Generator::set_layout`] restricts `s` to the symbols which
+//! are directly reachable on the chosen layout; the reported entropy follows the size of the
+//! set.
+//!
+//! ```rust
+//! use cryptirust::{layout::Layout, Generator};
+//!
+//! let mut generator = Generator::new();
+//! generator.set_layout(Layout::De);
+//! let (password, entropy) = generator.gen_from_pattern("w-s-w");
+//! assert!(!password.contains('@'));
+//! ```
+//!
+//! # Mobile preset
+//!
+//! [`MOBILE_PATTERN`] together with [`Layout::Mobile`] generates secrets which are quick to
+//! type on a phone: lowercase words separated by spaces, followed by digits and a symbol which
+//! share the first symbol page. [`analysis::typing_cost`](crate::analysis::typing_cost)
+//! measures the taps needed, to compare against other patterns.
+//!
+//! ```rust
+//! use cryptirust::{analysis, layout, Generator};
+//!
+//! let mut generator = Generator::new();
+//! generator.set_layout(layout::Layout::Mobile);
+//! let (password, entropy) = generator.gen_from_pattern(layout::MOBILE_PATTERN);
+//! println!("{} ({:.1} bits, {} taps)", password, entropy, analysis::typing_cost(&password));
+//! ```
+use crate::Generator;
+use rand::{CryptoRng, RngCore};
+
+/// Pattern of the mobile preset: four lowercase words, two digits and a symbol.
+pub const MOBILE_PATTERN: &str = "w w w w dds";
+
+/// A physical or virtual keyboard layout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Layout {
+    /// US QWERTY.
+    Us,
+    /// UK QWERTY.
+    Uk,
+    /// German QWERTZ: no `@`, `^` or brackets, which need `AltGr` or dead keys.
+    De,
+    /// Italian QWERTY: no `@`, `#` or brackets, which need `AltGr`.
+    It,
+    /// Phone keyboards: only the first symbol page, without quotes, which phones may replace
+    /// with typographic quotes.
+    Mobile,
+}
+
+impl Layout {
+    /// Every layout.
+    pub const ALL: [Layout; 5] = [
+        Layout::Us,
+        Layout::Uk,
+        Layout::De,
+        Layout::It,
+        Layout::Mobile,
+    ];
+
+    /// The symbols drawn by `s` on this layout.
+    pub fn symbols(&self) -> &'static str {
+        match self {
+            Layout::Us => "!@#$%^&*-_=+?",
+            Layout::Uk => "!\"$%^&*-_=+@#?",
+            Layout::De => "!\"$%&/()=?+*#-_.,",
+            Layout::It => "!\"$%&/()=?^+*-_.,",
+            Layout::Mobile => "-/:;()$&@.,?!",
+        }
+    }
+
+    /// Short name of the layout, e.g. `de`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Layout::Us => "us",
+            Layout::Uk => "uk",
+            Layout::De => "de",
+            Layout::It => "it",
+            Layout::Mobile => "mobile",
+        }
+    }
+}
+
+impl<R: RngCore + CryptoRng> Generator<R> {
+    /// Restricts the symbols drawn by `s` to those easily typed on `layout`.
+    pub fn set_layout(&mut self, layout: Layout) {
+        self.symbols = layout.symbols().chars().collect();
+        self.patterns.forget_bounds();
+    }
+}
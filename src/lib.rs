@@ -8,7 +8,7 @@
 //! - **Entropy Calculation**: Automatically calculates and returns the entropy of each generated password, helping you gauge its strength.
 //! - **Custom Token Support**: Define custom token sets and adjust the depth of the Markov chain model for even greater control over password structure.
 //! - **Pattern Flexibility**: Generate passphrases, pseudo-words, and custom patterns that can include symbols, numbers, and more.
-//! - **CLI**: most functions of cryptirust are easily accessible from [Crypticli](`crypticli::cli`).
+//! - **CLI**: most functions of cryptirust are easily accessible from the [crypticli](https://crates.io/crates/crypticli) binary crate.
 //!
 //! ## Quick start
 //!
@@ -20,8 +20,20 @@
 //! - **`C`**: Uppercase token.
 //! - **`w`**: Lowercase word.
 //! - **`W`**: Uppercase word.
+//! - **`x`**, **`X`**: Real word of the word list, lowercase or capitalized, see
+//!   [`Generator::set_dictionary`].
 //! - **`s`**: Symbol.
 //! - **`d`**: Digit.
+//! - **`{adj}`**, **`{noun}`**, **`{verb}`**: Word from a part-of-speech list, see [story].
+//! - **`{roman}`**, **`{ordinal}`**: Roman numeral or ordinal word, optionally in a range such
+//!   as `{roman:1-12}`, see [numerals].
+//! - **`{seq}`**: Number of the secret in a batch, not random, e.g. `{seq:001}` to count from
+//!   `001`, see [batch].
+//! - **`{>=bits}`** after `c`, `C`, `w` or `W`: Minimum entropy of that element, e.g. `w{>=12}`.
+//! - **`{n}`** after an element or a group in parentheses: Repetition, e.g. `d{6}` or
+//!   `(w-){3}w`, see [pattern].
+//! - **`[...]`**: One of the alternatives separated by `|`, or of the elements, e.g. `[w|c]` or
+//!   `[sd]`, see [pattern].
 //! - **`\`**: Escape next character.
 //!
 //! ```rust
@@ -41,23 +53,126 @@
 //! use cryptirust::*;
 //!
 //! fn main() {
-//!     let mut generator = Generator::new_custom(word_list::eff::list(), 2).unwrap();
-//!     let (passphrase, entropy) = generator.gen_from_pattern("w.w.w.w");;
+//!     let mut generator = Generator::new_custom(word_list::default_list(), 2).unwrap();
+//!     let (passphrase, entropy) = generator.gen_from_pattern("w.w.w.w");
 //!     println!("Generated passphrase: {}", passphrase);
 //!     println!("Entropy: {:.2} bits", entropy);
 //! }
 //! ```
 //!
+//! ## Cargo features
+//!
+//! - **`wordlist-eff`** (default): the EFF word list, used by [`Generator::new`].
+//! - **`wordlist-italian`** (default): the Italian word list.
+//! - **`wordlist-russian`** (default): a Russian word list in Cyrillic script, see [script].
+//! - **`wordlist-pos`** (default): part-of-speech tagged lists for [story] passphrases.
+//! - **`kdf`** (default): key derivation from passphrases, see [kdf].
+//! - **`transliterate`** (default): ASCII transliteration of secrets, see [translit].
+//! - **`history`**: an encrypted history of the salted hashes of generated secrets, see the
+//!   `history` module.
+//! - **`sqlite`**: a store of generated secrets in a SQLite database, `store::SqliteStore`,
+//!   see [store].
+//!
+//! With `default-features = false` only the small, generated [`word_list::cv`] list is bundled
+//! and [`Generator::new`] uses it.
+//!
 //! ## License
 //!
 //! Cryptirust is licensed under the MIT License.
 //!
+use cache::PatternCache;
+use casing::Locale;
+use draws::DrawBuffer;
+pub use error::Error;
+use grouping::DigitGrouping;
+use metrics::Metrics;
+use pattern::{Pattern, Segment};
 use rand::prelude::*;
 use rand_chacha::ChaCha8Rng;
-use std::collections::HashMap;
-pub mod crypticli;
+use registry::{Annotated, Span, TaggedWord};
+use std::collections::{BTreeMap, HashSet};
+use std::sync::Arc;
+use std::time::Instant;
+use theme::{admits, Anchor, Constraint, Theme, RHYME_LENGTH, RHYME_RETRIES};
+use transcript::Transcript;
+#[cfg(feature = "history")]
+mod aead;
+pub mod analysis;
+pub mod attack;
+pub mod batch;
+pub mod builder;
+mod cache;
+pub mod canonical;
+pub mod casing;
+pub mod confirm;
+pub mod credential;
+mod ct;
+pub mod dates;
+mod draws;
+pub mod encode;
+mod error;
+mod exact;
+mod expected;
+mod floor;
+pub mod grouping;
+pub mod hint;
+#[cfg(feature = "history")]
+pub mod history;
+mod json;
+#[cfg(feature = "kdf")]
+pub mod kdf;
+pub mod layout;
+pub mod limits;
+pub mod manifest;
+pub mod messages;
+pub mod metrics;
+pub mod model;
+pub mod namespace;
+pub mod ngram;
+pub mod nonblocking;
+pub mod normalize;
+pub mod numerals;
+pub mod passphrase;
+pub mod pattern;
+pub mod plan;
+pub mod pool;
+pub mod registry;
+pub mod script;
+mod sha256;
+pub mod store;
+#[cfg(feature = "wordlist-pos")]
+pub mod story;
+pub mod target;
+pub mod template;
+pub mod test_vectors;
+pub mod theme;
+pub mod throttle;
+pub mod transcript;
+#[cfg(feature = "transliterate")]
+pub mod translit;
+pub mod untrusted;
+pub mod voice;
 pub mod word_list;
 
+/// Default minimum entropy, in bits, accepted by [`Generator::try_gen_from_pattern`].
+pub const DEFAULT_ENTROPY_FLOOR: f64 = 40.0;
+
+/// Default number of parsed patterns cached by a generator, see
+/// [`Generator::set_pattern_cache_size`].
+pub const DEFAULT_PATTERN_CACHE_SIZE: usize = 16;
+
+/// Characters drawn by the `s` pattern element, unless a [`layout`] is chosen.
+const SYMBOLS: &str = "@#!$%&=?^+-*\"";
+/// Characters drawn by the `d` pattern element, unless [set](Generator::set_digits) otherwise.
+const DIGITS: &[char] = &['0', '9', '8', '7', '6', '5', '4', '3', '2', '1'];
+/// Length of a `w` word, reached by generating `ceil(8 / depth)` tokens,
+/// unless [set](Generator::set_word_length) otherwise.
+const WORD_LENGTH: usize = 8;
+
+/// Prefix marking the outputs of a generator in demo mode, see [`Generator::set_demo_mode`].
+pub const DEMO_WATERMARK: &str = "DEMO-";
+const DEMO_SEED: u64 = 0xDE30;
+
 /// `Generator` is the core struct responsible for generating customizable, pronounceable passwords.
 ///
 /// It allows generating passwords or passphrases based on wordlists, patterns, and custom token
@@ -89,6 +204,25 @@ pub mod word_list;
 /// and complexity. For faster generation of more complex (but less pronounceable) passwords,
 /// use `Generator::new_he()`, which lowers the depth to 2.
 ///
+/// Generators are `Send` and `Sync`; async code can generate large batches without blocking
+/// its executor through [`nonblocking::AsyncGenerator`].
+///
+/// # Random number generator
+///
+/// Generators draw from a [`ChaCha8Rng`] seeded by the operating system. Deployments which
+/// must use another cryptographically secure generator, e.g. a FIPS-validated or hardware
+/// one, move the model and settings of a generator onto it with [`Generator::with_rng`]: the
+/// type parameter `R` accepts any [`RngCore`] + [`CryptoRng`]. [`Generator::reseed`] and
+/// [demo mode](Generator::set_demo_mode) also need it to be [`SeedableRng`].
+///
+/// ```rust
+/// use cryptirust::Generator;
+/// use rand::rngs::OsRng;
+///
+/// let mut generator: Generator<OsRng> = Generator::new().with_rng(OsRng);
+/// let (passphrase, entropy) = generator.gen_from_pattern("w-w-w-w");
+/// ```
+///
 /// # Customization
 ///
 /// You can provide your own token sets and control the Markov chain depth using `new_custom()`.
@@ -105,10 +239,31 @@ pub mod word_list;
 /// let (password, entropy) = generator.gen_from_pattern("w.w.w.w");
 /// println!("Custom passphrase: {}", password);
 /// ```
-pub struct Generator {
-    pub rng: ChaCha8Rng,
+pub struct Generator<R = ChaCha8Rng> {
+    rng: R,
     depth: usize,
-    jump_table: HashMap<String, Distribution>,
+    jump_table: BTreeMap<String, Distribution>,
+    transcript: Option<Transcript>,
+    demo: bool,
+    entropy_floor: f64,
+    exact_entropy: bool,
+    word_lengths: WordLengths,
+    empirical_lengths: bool,
+    theme: Theme,
+    date_safe: bool,
+    symbols: Vec<char>,
+    digits: Vec<char>,
+    word_length: usize,
+    digit_grouping: Option<DigitGrouping>,
+    language: String,
+    patterns: PatternCache,
+    constant_time: bool,
+    metrics: Option<Arc<dyn Metrics>>,
+    locale: Locale,
+    sequence: u64,
+    word_list: Option<String>,
+    dictionary: Vec<String>,
+    draws: DrawBuffer,
 }
 impl Default for Generator {
     fn default() -> Self {
@@ -127,34 +282,272 @@ struct Distribution {
     total: usize,
 }
 
+impl Distribution {
+    /// Builds the distribution of tokens observed with the given frequencies.
+    fn from_frequencies<I: IntoIterator<Item = (String, usize)>>(frequencies: I) -> Distribution {
+        let frequencies: Vec<(String, usize)> = frequencies.into_iter().collect();
+        let total: usize = frequencies.iter().map(|(_, freq)| freq).sum();
+        let mut counts = Vec::new();
+        let mut tokens = Vec::new();
+        let mut entropies = Vec::new();
+        let mut cum = 0;
+
+        for (token, freq) in frequencies {
+            let p = freq as f64 / total as f64;
+            cum += freq;
+            entropies.push(-p.log2());
+            counts.push(cum);
+            tokens.push(token);
+        }
+
+        Distribution {
+            tokens,
+            entropies,
+            counts,
+            total,
+        }
+    }
+
+    /// Draws a token with `draws`, returning its index, text and entropy. With
+    /// `constant_time` the memory accesses do not depend on the drawn token, otherwise it is
+    /// found by bisection of the cumulative counts.
+    fn draw<R: RngCore>(
+        &self,
+        draws: &mut DrawBuffer,
+        rng: &mut R,
+        constant_time: bool,
+    ) -> (usize, String, f64) {
+        let n = draws.below(rng, self.total);
+        if constant_time {
+            let i = ct::count_at_most(&self.counts, n);
+            let token = ct::select_string(&self.tokens, i);
+            return (i, token, ct::select_f64(&self.entropies, i));
+        }
+        let i = self.counts.partition_point(|&c| c <= n);
+        (i, self.tokens[i].clone(), self.entropies[i])
+    }
+
+    /// Number of occurrences of the `i`-th token.
+    fn frequency(&self, i: usize) -> usize {
+        let below = if i > 0 { self.counts[i - 1] } else { 0 };
+        self.counts[i] - below
+    }
+
+    /// Probability of the `i`-th token.
+    fn probability(&self, i: usize) -> f64 {
+        self.frequency(i) as f64 / self.total as f64
+    }
+
+    /// Total number of occurrences of the tokens accepted by `filter`.
+    fn total_where<F: Fn(&str) -> bool>(&self, filter: F) -> usize {
+        (0..self.tokens.len())
+            .filter(|&i| filter(&self.tokens[i]))
+            .map(|i| self.frequency(i))
+            .sum()
+    }
+}
+
+/// Number of words of each length, in characters, in the training list.
+#[derive(Debug)]
+struct WordLengths {
+    counts: Vec<usize>,
+    total: usize,
+}
+
+impl WordLengths {
+    fn from_tokens(tokens: &[String]) -> WordLengths {
+        let mut counts = Vec::new();
+        for token in tokens {
+            let len = token.trim().chars().count();
+            if len == 0 {
+                continue;
+            }
+            if counts.len() <= len {
+                counts.resize(len + 1, 0);
+            }
+            counts[len] += 1;
+        }
+        let total = counts.iter().sum();
+        WordLengths { counts, total }
+    }
+
+    /// Probability of a word of `len` characters.
+    fn probability(&self, len: usize) -> f64 {
+        self.counts
+            .get(len)
+            .map_or(0.0, |&c| c as f64 / self.total as f64)
+    }
+}
+
+/// Progress of a `w` word being generated.
+#[derive(Clone)]
+struct WordProgress {
+    /// Byte offset of the word in the passphrase.
+    start: usize,
+    /// Target length of the word, in characters.
+    len: usize,
+    /// Characters produced so far, counted as `depth` per token for fixed lengths.
+    produced: usize,
+    /// Tokens added past the target length to reach a rhyme.
+    retries: usize,
+}
+
+impl WordProgress {
+    fn new(start: usize, len: usize) -> WordProgress {
+        WordProgress {
+            start,
+            len,
+            produced: 0,
+            retries: 0,
+        }
+    }
+}
+
 impl Generator {
     /// Creates a new generator with a custom token set and a specified Markov chain depth.
+    ///
+    /// Returns `None` when no token remains after trimming. Degenerate lists are fine: a list
+    /// of one word gives words without entropy, and a list written with a single character,
+    /// whose texts differ only by their length, enables
+    /// [exact entropy](Generator::set_exact_entropy), since the default figure would count
+    /// every split of a text into tokens as a different secret.
     pub fn new_custom(tokens: Vec<String>, depth: usize) -> Option<Generator> {
         let depth = depth.max(1);
-        let rng = ChaCha8Rng::from_entropy();
+        let fingerprint = word_list::fingerprint(&tokens);
+        let word_lengths = WordLengths::from_tokens(&tokens);
+        let dictionary = dictionary_from_tokens(&tokens);
         let transition_matrix = transition_matrix_from_tokens(tokens, depth);
-        if transition_matrix.len() == 0 {
+        if transition_matrix.is_empty() {
             return None;
         }
         let jump_table = jump_table_from_transition_matrix(transition_matrix);
-        if jump_table.len() == 0 {
+        // words shorter than the depth give no longer states: record the depth reached
+        let depth = max_depth(&jump_table);
+        let mut generator = Generator::from_jump_table(jump_table, word_lengths, depth)?;
+        generator.word_list = Some(fingerprint);
+        generator.dictionary = dictionary;
+        Some(generator)
+    }
+
+    /// Creates a generator from a trained model of the given depth, `None` if the model is
+    /// empty or the depth is zero.
+    fn from_jump_table(
+        jump_table: BTreeMap<String, Distribution>,
+        word_lengths: WordLengths,
+        depth: usize,
+    ) -> Option<Generator> {
+        if jump_table.is_empty() || depth == 0 {
             return None;
         }
+        let rng = ChaCha8Rng::from_entropy();
+        let mut alphabet = jump_table
+            .values()
+            .flat_map(|d| d.tokens.iter())
+            .flat_map(|token| token.chars());
+        let first = alphabet.next();
+        let single_character = alphabet.all(|c| Some(c) == first);
         Some(Generator {
-            rng: rng,
-            depth: max_depth(&jump_table),
-            jump_table: jump_table,
+            rng,
+            depth,
+            jump_table,
+            transcript: None,
+            demo: false,
+            entropy_floor: DEFAULT_ENTROPY_FLOOR,
+            exact_entropy: single_character,
+            word_lengths,
+            empirical_lengths: false,
+            theme: Theme::Plain,
+            date_safe: false,
+            symbols: SYMBOLS.chars().collect(),
+            digits: DIGITS.to_vec(),
+            word_length: WORD_LENGTH,
+            digit_grouping: None,
+            language: String::new(),
+            patterns: PatternCache::new(DEFAULT_PATTERN_CACHE_SIZE),
+            constant_time: false,
+            metrics: None,
+            locale: Locale::Root,
+            sequence: 0,
+            word_list: None,
+            dictionary: Vec::new(),
+            draws: DrawBuffer::default(),
         })
     }
 
     /// Creates a new generator using the default wordlist (EFF's word list) with a Markov chain depth of 3.
+    ///
+    /// When the `wordlist-eff` feature is disabled the default wordlist is [`word_list::cv`].
     pub fn new() -> Generator {
-        Generator::new_custom(word_list::eff::list(), 3).unwrap()
+        Generator::new_custom(word_list::default_list(), 3).unwrap()
     }
 
     /// Similar to `new()`, but uses a Markov chain depth of 2 for quicker password generation at the expense of phonetic fidelity.
     pub fn new_he() -> Generator {
-        Generator::new_custom(word_list::eff::list(), 2).unwrap()
+        Generator::new_custom(word_list::default_list(), 2).unwrap()
+    }
+
+    /// Like [`Generator::new`], with the random number generator seeded deterministically
+    /// from `seed`, see [`Generator::reseed`]: every generator created with the same seed
+    /// produces the same outputs, in the same order, for the same calls and settings. Meant for
+    /// test suites and reproducible derivations; never reuse a seed for secrets.
+    ///
+    /// ```rust
+    /// use cryptirust::Generator;
+    ///
+    /// let mut a = Generator::new_with_seed(42);
+    /// let mut b = Generator::new_with_seed(42);
+    /// assert_eq!(a.gen_from_pattern("w-w-dd"), b.gen_from_pattern("w-w-dd"));
+    /// ```
+    pub fn new_with_seed(seed: u64) -> Generator {
+        let mut generator = Generator::new();
+        generator.reseed(seed);
+        generator
+    }
+
+    /// Like [`Generator::new_custom`], with the random number generator seeded
+    /// deterministically from `seed`, as [`Generator::new_with_seed`]. The outputs depend on
+    /// the tokens and their order only through the trained model, which is stored in sorted
+    /// order, so they are the same across runs and platforms.
+    pub fn new_custom_with_seed(tokens: Vec<String>, depth: usize, seed: u64) -> Option<Generator> {
+        let mut generator = Generator::new_custom(tokens, depth)?;
+        generator.reseed(seed);
+        Some(generator)
+    }
+}
+
+impl<R: RngCore + CryptoRng> Generator<R> {
+    /// Moves the model and the settings of the generator to a new one drawing from `rng`,
+    /// e.g. a hardware or FIPS-validated random number generator, see the
+    /// [`Generator`] documentation.
+    pub fn with_rng<S: RngCore + CryptoRng>(self, rng: S) -> Generator<S> {
+        let mut draws = self.draws;
+        draws.clear();
+        Generator {
+            rng,
+            depth: self.depth,
+            jump_table: self.jump_table,
+            transcript: self.transcript,
+            demo: self.demo,
+            entropy_floor: self.entropy_floor,
+            exact_entropy: self.exact_entropy,
+            word_lengths: self.word_lengths,
+            empirical_lengths: self.empirical_lengths,
+            theme: self.theme,
+            date_safe: self.date_safe,
+            symbols: self.symbols,
+            digits: self.digits,
+            word_length: self.word_length,
+            digit_grouping: self.digit_grouping,
+            language: self.language,
+            patterns: self.patterns,
+            constant_time: self.constant_time,
+            metrics: self.metrics,
+            locale: self.locale,
+            sequence: self.sequence,
+            word_list: self.word_list,
+            dictionary: self.dictionary,
+            draws,
+        }
     }
 
     /// Generates a password based on a given pattern, while calculating its entropy.
@@ -162,12 +555,19 @@ impl Generator {
     /// The pattern string defines how the password is structured, where different
     /// characters in the pattern correspond to different token types:
     ///
-    /// * `'s'` - Inserts a symbol from the predefined symbol set (`@#!$%&=?^+-*"`).
+    /// * `'s'` - Inserts a symbol from the predefined symbol set (`@#!$%&=?^+-*"`), or from
+    ///   the set of the [`layout`] chosen with [`Generator::set_layout`].
     /// * `'d'` - Inserts a digit from the set `0-9`.
     /// * `'c'` - Generates a token using the markov chain.
     /// * `'C'` - Generates a token, capitalized.
     /// * `'w'` - Generates a word using the markov chain.
     /// * `'W'` - Generates a word, capitalized.
+    /// * `'x'` - Inserts a real word of the word list, see [`Generator::set_dictionary`].
+    /// * `'X'` - Inserts a real word, capitalized.
+    ///
+    /// A token or word followed by `{>=bits}`, e.g. `w{>=12}`, is resampled until its own
+    /// entropy reaches `bits`, and its reported entropy accounts for the resampling, see
+    /// [`pattern`].
     ///
     /// Additionally, any literal character (e.g., `.` or `!`) can be inserted into the
     /// pattern, which will be directly appended to the password as is.
@@ -179,7 +579,7 @@ impl Generator {
     /// # Parameters
     ///
     /// * `pattern`: A string that defines the structure of the generated password. Each character
-    /// in the string corresponds to a token type, symbol, or literal.
+    ///   in the string corresponds to a token type, symbol, or literal.
     ///
     /// # Returns
     ///
@@ -187,7 +587,7 @@ impl Generator {
     ///
     /// * `String`: The generated password based on the given pattern.
     /// * `f64`: The estimated entropy of the generated password, calculated using the log base 2 of the number
-    /// of possible outcomes for each token.
+    ///   of possible outcomes for each token.
     ///
     /// # Example
     ///
@@ -201,66 +601,405 @@ impl Generator {
     /// In this example, the pattern `"wWsdC"` would generate a password such as `"hunkindEreso2"Mus"`,
     /// with its corresponding entropy value.
     ///
+    /// # Panics
+    ///
+    /// Never: every string is a valid pattern, and generation works with any model built by
+    /// [`Generator::new_custom`], whatever the word list or depth. Fuzz targets checking this
+    /// guarantee live in the `fuzz` directory of the repository.
+    ///
     /// # Performance
     ///
     /// The performance of this method depends on the length of the input pattern and
     /// the complexity of tokens defined in the jump table. Deeper chain depths or longer
-    /// patterns may result in higher processing time.
+    /// patterns may result in higher processing time. Parsed patterns are cached, see
+    /// [`Generator::set_pattern_cache_size`].
     pub fn gen_from_pattern(&mut self, pattern: &str) -> (String, f64) {
+        let cached = self.patterns.take(pattern);
+        let generated = self.gen_from_parsed(&cached.parsed);
+        self.patterns.put(cached);
+        generated
+    }
+
+    /// Generates a password from an already parsed [`Pattern`], see
+    /// [`Generator::gen_from_pattern`].
+    pub fn gen_from_parsed(&mut self, pattern: &Pattern) -> (String, f64) {
+        let annotated = self.gen_annotated(pattern);
+        (annotated.text, annotated.entropy)
+    }
+
+    /// Same as [`Generator::gen_from_parsed`], but also reports where each word of the output
+    /// is, tagged with the language set by [`Generator::set_language`], and the entropy of the
+    /// output of every pattern element; the spans leave out the entropy of the choices of
+    /// [alternations](crate::pattern), e.g. `[sd]`.
+    ///
+    /// ```rust
+    /// use cryptirust::{pattern::Pattern, Generator};
+    ///
+    /// let mut generator = Generator::new();
+    /// let secret = generator.gen_annotated(&Pattern::parse("w-w-dd"));
+    /// let total: f64 = secret.spans.iter().map(|span| span.entropy).sum();
+    /// assert!((total - secret.entropy).abs() < 1e-9);
+    /// for span in &secret.spans {
+    ///     println!("{:>8.2} bits  {}", span.entropy, &secret.text[span.range.clone()]);
+    /// }
+    /// ```
+    pub fn gen_annotated(&mut self, pattern: &Pattern) -> Annotated {
+        let mut secret = self.gen_unmarked(pattern);
+        if self.demo {
+            secret.watermark();
+        }
+        secret
+    }
+
+    /// Same as [`Generator::gen_annotated`], without the [`DEMO_WATERMARK`] of demo mode, for
+    /// callers assembling a secret from several parts and marking it once.
+    pub(crate) fn gen_unmarked(&mut self, pattern: &Pattern) -> Annotated {
+        let started = self.metrics.as_ref().map(|_| Instant::now());
+        let original = pattern;
+        let resolved;
+        let (pattern, choices) = if pattern.has_choices() {
+            let (flat, h) = self.resolve_choices(pattern);
+            resolved = flat;
+            (&resolved, h)
+        } else {
+            (pattern, 0.0)
+        };
         let mut passphrase = String::new();
-        let mut entropy = 0.0;
-        let mut iter = pattern.chars().peekable();
+        let mut entropy = choices;
+        let mut anchor = None;
+        let mut skip = 0;
+        let mut words = Vec::new();
+        let mut spans = Vec::new();
 
-        loop {
-            let cs = iter.next();
-            if let Some(c) = cs {
-                if c == '\\' {
-                    if let Some(cn) = iter.next() {
-                        passphrase.push(cn);
-                    }
-                    continue;
+        for (i, segment) in pattern.segments().iter().enumerate() {
+            if skip > 0 {
+                skip -= 1;
+                continue;
+            }
+            let start = passphrase.len();
+            let before = entropy;
+            match *segment {
+                Segment::Digit if self.starts_date_safe_group(pattern.segments(), i) => {
+                    let (group, h) = self.gen_date_safe_group();
+                    passphrase.push_str(&self.group_digits(pattern.segments(), i, &group));
+                    entropy += h;
+                    skip = dates::GROUP_LENGTH - 1;
                 }
-                match c {
-                    'w' | 'W' => {
-                        let mut nlen = 0;
-                        while nlen < 8 {
-                            let (mut tok, h) = self.gen_next_token(&passphrase).unwrap();
-                            if c == 'W' && nlen == 0 {
-                                tok = uppercase_first_letter(&tok);
-                            }
-                            passphrase.push_str(&tok);
-                            entropy += h;
-                            nlen += self.depth;
+                Segment::Word {
+                    capitalized,
+                    min_entropy,
+                } => {
+                    entropy += match min_entropy {
+                        Some(floor) => {
+                            let floor = floor.min(pattern::MAX_ELEMENT_FLOOR);
+                            self.gen_word_at_least(&mut passphrase, capitalized, &mut anchor, floor)
                         }
+                        None => self.gen_word(&mut passphrase, capitalized, &mut anchor),
+                    };
+                }
+                Segment::Symbol | Segment::Digit => {
+                    let grouping = self.digit_grouping.as_ref();
+                    if let Some(sep) =
+                        grouping.and_then(|g| g.separator_before(pattern.segments(), i))
+                    {
+                        passphrase.push(sep);
                     }
-                    's' | 'd' => {
-                        let symbols = if c == 's' {
-                            "@#!$%&=?^+-*\""
+                    let symbols = if *segment == Segment::Symbol {
+                        &self.symbols
+                    } else {
+                        &self.digits
+                    };
+                    let n = symbols.len();
+                    let d = self.draws.below(&mut self.rng, n);
+                    let c = if self.constant_time {
+                        ct::select_char(symbols, d)
+                    } else {
+                        symbols[d]
+                    };
+                    if let Some(t) = self.transcript.as_mut() {
+                        let kind = if *segment == Segment::Symbol {
+                            "symbol"
                         } else {
-                            "0987654321"
+                            "digit"
                         };
-                        let d = self.rng.gen_range(0..symbols.len());
-                        passphrase.push(symbols.chars().nth(d).unwrap());
-                        entropy += (symbols.len() as f64).log2();
+                        t.record(kind, d, 1.0 / n as f64);
                     }
-                    'c' | 'C' => {
-                        let (mut tok, h) = self.gen_next_token(&passphrase).unwrap();
-                        if c == 'C' {
-                            tok = uppercase_first_letter(&tok);
-                        }
-                        passphrase.push_str(&tok);
-                        entropy += h;
+                    passphrase.push(c);
+                    entropy += (n as f64).log2();
+                }
+                Segment::Token {
+                    capitalized,
+                    min_entropy,
+                } => {
+                    let token = match min_entropy {
+                        Some(floor) => self
+                            .gen_token_at_least(&passphrase, floor.min(pattern::MAX_ELEMENT_FLOOR)),
+                        None => self.gen_next_token(&passphrase),
+                    };
+                    let Some((mut tok, h)) = token else {
+                        continue;
+                    };
+                    if capitalized {
+                        tok = uppercase_first_letter(&tok, self.locale);
                     }
-                    _ => {
-                        passphrase.push(c);
+                    passphrase.push_str(&tok);
+                    entropy += h;
+                }
+                Segment::Literal(ref text) => {
+                    passphrase.push_str(text);
+                }
+                Segment::Dictionary { capitalized } => {
+                    let n = self.dictionary.len();
+                    if n == 0 {
+                        continue;
+                    }
+                    let i = self.below(n);
+                    if let Some(t) = self.transcript.as_mut() {
+                        t.record("dictionary", i, 1.0 / n as f64);
                     }
+                    passphrase.push_str(&self.dictionary_word(i, capitalized));
+                    entropy += (n as f64).log2();
                 }
-            } else {
-                break;
+                #[cfg(feature = "wordlist-pos")]
+                Segment::Part(part) => {
+                    let n = part.words().count();
+                    let i = self.below(n);
+                    if let Some(t) = self.transcript.as_mut() {
+                        t.record(part.name(), i, 1.0 / n as f64);
+                    }
+                    passphrase.push_str(part.words().nth(i).unwrap());
+                    entropy += (n as f64).log2();
+                }
+                Segment::Roman { .. } | Segment::Ordinal { .. } => {
+                    let numerals = segment.numerals().unwrap_or_default();
+                    let n = numerals.len();
+                    let i = self.below(n);
+                    if let Some(t) = self.transcript.as_mut() {
+                        let name = match segment {
+                            Segment::Roman { .. } => "roman",
+                            _ => "ordinal",
+                        };
+                        t.record(name, i, 1.0 / n as f64);
+                    }
+                    passphrase.push_str(&numerals[i]);
+                    entropy += (n as f64).log2();
+                }
+                Segment::Sequence { start, width } => {
+                    passphrase.push_str(&self.sequence_number(start, width));
+                }
+                // resolved above
+                Segment::Choice(_) => (),
+            }
+            if segment.is_word() && passphrase.len() > start {
+                words.push(TaggedWord {
+                    range: start..passphrase.len(),
+                    language: self.language.clone(),
+                });
+            }
+            if passphrase.len() > start {
+                spans.push(Span {
+                    range: start..passphrase.len(),
+                    entropy: entropy - before,
+                });
+            }
+        }
+
+        if self.exact_entropy {
+            // alternatives producing the same text are merged by the exact figure
+            entropy = self
+                .exact_entropy(original, &passphrase)
+                .or_else(|| {
+                    self.exact_entropy(pattern, &passphrase)
+                        .map(|h| h + choices)
+                })
+                .unwrap_or(entropy);
+        }
+        if self.demo {
+            for span in spans.iter_mut() {
+                span.entropy = 0.0;
+            }
+            entropy = 0.0;
+        }
+        if let (Some(metrics), Some(started)) = (&self.metrics, started) {
+            metrics.secret_generated(entropy, started.elapsed());
+        }
+        Annotated {
+            text: passphrase,
+            entropy,
+            words,
+            spans,
+        }
+    }
+
+    /// `pattern` with every alternation replaced by one of its alternatives, drawn uniformly,
+    /// and the entropy of the choices. Identical alternatives, `[s|s]`, are one outcome drawn
+    /// with a larger probability, so they are not counted twice.
+    pub(crate) fn resolve_choices(&mut self, pattern: &Pattern) -> (Pattern, f64) {
+        let mut segments = Vec::with_capacity(pattern.segments().len());
+        let mut entropy = 0.0;
+        for segment in pattern.segments() {
+            let Segment::Choice(alternatives) = segment else {
+                pattern::push_segment(&mut segments, segment.clone());
+                continue;
+            };
+            let n = alternatives.len();
+            let i = self.below(n);
+            let same = alternatives
+                .iter()
+                .filter(|&alternative| *alternative == alternatives[i])
+                .count();
+            if let Some(t) = self.transcript.as_mut() {
+                t.record("choice", i, same as f64 / n as f64);
             }
+            let (alternative, h) = self.resolve_choices(&alternatives[i]);
+            for segment in alternative.segments() {
+                pattern::push_segment(&mut segments, segment.clone());
+            }
+            entropy += (n as f64 / same as f64).log2() + h;
+        }
+        (Pattern::from_segments(segments), entropy)
+    }
+
+    /// Sets the language label attached to the words generated by
+    /// [`Generator::gen_annotated`], e.g. `en`; empty by default.
+    pub fn set_language(&mut self, label: &str) {
+        self.language = label.to_string();
+    }
+
+    /// Sets the case mapping rules used to capitalize words and to look up the model, see
+    /// [`casing`]; [`Locale::Root`] by default.
+    pub fn set_locale(&mut self, locale: Locale) {
+        self.locale = locale;
+        self.patterns.forget_bounds();
+    }
+
+    /// Same as [`Generator::gen_from_pattern`], but refuses to issue secrets whose entropy is
+    /// below the floor set with [`Generator::set_entropy_floor`] (default
+    /// [`DEFAULT_ENTROPY_FLOOR`] bits), and patterns drawing real words when the generator has
+    /// no [dictionary](Generator::set_dictionary).
+    ///
+    /// The floor applies to every secret the pattern can produce, not only to the one drawn:
+    /// a pattern whose [weakest secret](Generator::min_achievable_entropy) falls below the
+    /// floor is refused before drawing, even if most of its secrets would pass, so that
+    /// retrying cannot turn a weak pattern into an accepted one. The secret drawn is checked
+    /// as well, which covers the patterns without a bound, e.g. with a
+    /// [theme], and [exact entropy](Generator::set_exact_entropy). The check
+    /// fails closed: a NaN floor or entropy refuses every secret, and a pattern whose weakest
+    /// secret would take more than [`pattern::MAX_WALK_STEPS`] steps to find is refused with
+    /// [`Error::PatternTooComplex`]. The weakest secret is computed once per pattern and
+    /// kept in the [pattern cache](Generator::set_pattern_cache_size) until the model or the
+    /// settings it depends on change.
+    ///
+    /// Integrations should prefer this method, so that a misconfigured pattern or model fails
+    /// loudly instead of silently producing weak credentials.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cryptirust::{Error, Generator};
+    /// let mut gen = Generator::new();
+    /// assert!(matches!(gen.try_gen_from_pattern("dddd"), Err(Error::WeakSecret { .. })));
+    /// assert!(gen.try_gen_from_pattern("w-w-w-w-dd").is_ok());
+    /// ```
+    pub fn try_gen_from_pattern(&mut self, pattern: &str) -> Result<(String, f64), Error> {
+        let mut cached = self.patterns.take(pattern);
+        let lacks_dictionary = self.lacks_dictionary(&cached.parsed);
+        let highest_floor = cached.parsed.highest_floor();
+        let numeral_out_of_range = cached.parsed.numeral_out_of_range();
+        let weakest = cached.weakest(self);
+        self.patterns.put(cached);
+        if lacks_dictionary {
+            return Err(Error::NoDictionary);
         }
+        if let Some(bits) = highest_floor.filter(|&bits| bits > pattern::MAX_ELEMENT_FLOOR) {
+            return Err(Error::ElementFloorTooHigh {
+                bits,
+                max: pattern::MAX_ELEMENT_FLOOR,
+            });
+        }
+        if let Some((min, max, largest)) = numeral_out_of_range {
+            return Err(Error::NumeralOutOfRange { min, max, largest });
+        }
+        let weakest = weakest.map_err(|_| Error::PatternTooComplex {
+            max_steps: pattern::MAX_WALK_STEPS,
+        })?;
+        let floor = self.entropy_floor;
+        let weak = |entropy: f64| entropy.is_nan() || floor.is_nan() || entropy < floor;
+        if let Some(entropy) = weakest.filter(|&h| weak(h)) {
+            return Err(Error::WeakSecret { entropy, floor });
+        }
+        let (passphrase, entropy) = self.gen_from_pattern(pattern);
+        if weak(entropy) {
+            return Err(Error::WeakSecret { entropy, floor });
+        }
+        Ok((passphrase, entropy))
+    }
+
+    /// Sets the minimum entropy, in bits, accepted by [`Generator::try_gen_from_pattern`] for
+    /// every secret of a pattern; use `0.0` to disable the check.
+    pub fn set_entropy_floor(&mut self, bits: f64) {
+        self.entropy_floor = bits;
+    }
+
+    /// Reseeds the internal random number generator deterministically.
+    ///
+    /// Two generators built from the same tokens and reseeded with the same `seed` produce the
+    /// same sequence of outputs, across runs and platforms since the model is stored in sorted
+    /// order; this is meant for tests and reproducible derivations, never reuse a seed for
+    /// secrets.
+    pub fn reseed(&mut self, seed: u64)
+    where
+        R: SeedableRng,
+    {
+        self.rng = R::seed_from_u64(seed);
+        self.draws.clear();
+    }
+
+    /// Replaces the internal random number generator, with one of the same type; see
+    /// [`Generator::with_rng`] to change the type.
+    pub fn set_rng(&mut self, rng: R) {
+        self.rng = rng;
+        self.draws.clear();
+    }
+
+    /// The internal random number generator.
+    pub fn rng(&self) -> &R {
+        &self.rng
+    }
+
+    /// Gives mutable access to the internal random number generator, e.g. to share it with
+    /// other sampling code.
+    pub fn rng_mut(&mut self) -> &mut R {
+        &mut self.rng
+    }
 
-        (passphrase, entropy)
+    /// Enables or disables demo mode.
+    ///
+    /// In demo mode the generator is reseeded with a fixed, public seed and every output is
+    /// prefixed with [`DEMO_WATERMARK`], so documentation, screenshots and test fixtures show
+    /// realistic-looking secrets which are obviously not meant to be used. Since the sequence of
+    /// outputs is public, the reported entropy is always `0.0`. Disabling demo mode reseeds the
+    /// generator from the operating system.
+    ///
+    /// ```rust
+    /// use cryptirust::Generator;
+    /// let mut generator = Generator::new();
+    /// generator.set_demo_mode(true);
+    /// let (password, entropy) = generator.gen_from_pattern("w-w-dd");
+    /// assert!(password.starts_with(cryptirust::DEMO_WATERMARK));
+    /// assert_eq!(entropy, 0.0);
+    /// ```
+    pub fn set_demo_mode(&mut self, enabled: bool)
+    where
+        R: SeedableRng,
+    {
+        self.demo = enabled;
+        self.patterns.forget_bounds();
+        if enabled {
+            self.reseed(DEMO_SEED);
+        } else {
+            self.set_rng(R::from_entropy());
+        }
     }
 
     /// Generates the next token in a sequence, based on the current seed and internal state.
@@ -277,119 +1016,515 @@ impl Generator {
     /// This example demonstrates how to generate the next token in a sequence starting with
     /// the seed `"he"`. The method returns both the token and its associated entropy.
     pub fn gen_next_token(&mut self, seed: &str) -> Option<(String, f64)> {
-        let sl = seed[seed.len().saturating_sub(self.depth)..].to_lowercase();
-        let mut tok = sl.as_str();
-        loop {
-            if let Some(tr) = self.jump_table.get(tok) {
-                let n = self.rng.gen_range(0..tr.total);
-                for (i, v) in tr.counts.iter().enumerate() {
-                    if n < *v {
-                        return Some((tr.tokens[i].clone(), tr.entropies[i]));
-                    }
-                }
-                return None;
-            }
-            if tok.len() == 0 {
-                return None;
-            }
-            tok = &tok[1..];
+        let (_, tr) = lookup_distribution(&self.jump_table, self.depth, self.locale, seed)?;
+        let (i, token, entropy) = tr.draw(&mut self.draws, &mut self.rng, self.constant_time);
+        if let Some(t) = self.transcript.as_mut() {
+            t.record("token", i, tr.probability(i));
         }
+        Some((token, entropy))
     }
-}
 
-fn max_depth(jump_table: &HashMap<String, Distribution>) -> usize {
-    let mut t_depth = 0;
-    for (k, v) in jump_table.iter() {
-        t_depth = t_depth.max(k.len());
-        for c in v.tokens.iter() {
-            t_depth = t_depth.max(c.len());
+    /// Enables or disables empirical word lengths.
+    ///
+    /// By default a `w` word is made of `ceil(8 / depth)` tokens. When enabled, the length of
+    /// each word is instead drawn from the distribution of word lengths in the training list,
+    /// and tokens are drawn among those fitting in the remaining length, so generated words
+    /// look like the words of the list. The entropy of the length choice is included in the
+    /// reported entropy. Generators [imported](Generator::from_ngram_counts) from a table of
+    /// counts have no word lengths and ignore this setting.
+    ///
+    /// ```rust
+    /// use cryptirust::Generator;
+    /// let mut generator = Generator::new();
+    /// generator.set_empirical_word_lengths(true);
+    /// let (passphrase, entropy) = generator.gen_from_pattern("w-w-w-w");
+    /// ```
+    pub fn set_empirical_word_lengths(&mut self, enabled: bool) {
+        self.empirical_lengths = enabled && self.word_lengths.total > 0;
+        self.patterns.forget_bounds();
+    }
+
+    /// Sets the length of a `w` word, 8 characters by default: a word is made of
+    /// `ceil(len / depth)` tokens, so that generated words are about `len` characters long.
+    /// Ignored with [empirical word lengths](Generator::set_empirical_word_lengths).
+    ///
+    /// ```rust
+    /// use cryptirust::Generator;
+    /// let mut generator = Generator::new();
+    /// generator.set_word_length(12).unwrap();
+    /// let (passphrase, entropy) = generator.gen_from_pattern("w-w-w");
+    /// assert!(generator.set_word_length(0).is_err());
+    /// ```
+    pub fn set_word_length(&mut self, len: usize) -> Result<(), Error> {
+        if len == 0 {
+            return Err(Error::ZeroWordLength);
         }
+        self.word_length = len;
+        self.patterns.forget_bounds();
+        Ok(())
     }
-    t_depth
-}
 
-fn transition_matrix_from_tokens(
-    tokens: Vec<String>,
-    depth: usize,
-) -> HashMap<String, HashMap<String, usize>> {
-    let mut transition_matrix: HashMap<String, HashMap<String, usize>> = HashMap::new();
+    /// Draws the length of a word from the training list distribution, or `None` when the
+    /// generator has no word lengths.
+    fn gen_word_length(&mut self) -> Option<(usize, f64)> {
+        if self.word_lengths.total == 0 {
+            return None;
+        }
+        let n = self.below(self.word_lengths.total);
+        let mut cum = 0;
+        let mut len = 0;
+        for (l, &c) in self.word_lengths.counts.iter().enumerate() {
+            cum += c;
+            if n < cum {
+                len = l;
+                break;
+            }
+        }
+        let p = self.word_lengths.probability(len);
+        if let Some(t) = self.transcript.as_mut() {
+            t.record("#length", len, p);
+        }
+        Some((len, -p.log2()))
+    }
 
-    let mut put = |str: String, r: String| {
-        transition_matrix
-            .entry(str)
-            .or_default()
-            .entry(r)
-            .and_modify(|count| *count += 1)
-            .or_insert(1);
-    };
+    /// Appends a `w` word to `passphrase` and returns its entropy. The first word of a themed
+    /// passphrase sets the `anchor` constraining the following ones.
+    fn gen_word(
+        &mut self,
+        passphrase: &mut String,
+        capitalized: bool,
+        anchor: &mut Option<Anchor>,
+    ) -> f64 {
+        let mut entropy = 0.0;
+        let drawn = self
+            .empirical_lengths
+            .then(|| self.gen_word_length())
+            .flatten();
+        let len = match drawn {
+            Some((len, h)) => {
+                entropy += h;
+                len
+            }
+            None => self.word_length,
+        };
+        let start = passphrase.len();
+        let mut progress = WordProgress::new(start, len);
+        while let Some(constraints) =
+            self.word_constraints(passphrase, &mut progress, anchor.as_ref())
+        {
+            let Some((tok, h)) = self.gen_token_where(passphrase, &constraints) else {
+                break;
+            };
+            if capitalized && progress.produced == 0 {
+                passphrase.push_str(&uppercase_first_letter(&tok, self.locale));
+            } else {
+                passphrase.push_str(&tok);
+            }
+            entropy += h;
+            self.advance_word(&mut progress, &tok);
+        }
+        if anchor.is_none() && self.theme != Theme::Plain {
+            let word: Vec<char> = self
+                .locale
+                .lowercase(&passphrase[start..])
+                .chars()
+                .collect();
+            if let Some(&initial) = word.first() {
+                *anchor = Some(Anchor {
+                    initial,
+                    rhyme: word[word.len().saturating_sub(RHYME_LENGTH)..]
+                        .iter()
+                        .collect(),
+                });
+            }
+        }
+        entropy
+    }
 
-    for raw_w in tokens.iter() {
-        let sl = raw_w.trim().to_lowercase();
-        if sl.len() == 0 {
-            continue;
+    /// Constraints on the next token of the word in progress at the end of `passphrase`, or
+    /// `None` when the word is complete.
+    fn word_constraints(
+        &self,
+        passphrase: &str,
+        progress: &mut WordProgress,
+        anchor: Option<&Anchor>,
+    ) -> Option<Vec<Constraint>> {
+        let remaining = progress.len.saturating_sub(progress.produced);
+        let rhyming = match (self.theme, anchor) {
+            (Theme::Rhyme, Some(a)) => Some(a.rhyme.as_str()),
+            _ => None,
+        };
+        if remaining == 0 {
+            // a rhyming word may take a few more tokens to reach its rhyme
+            let rhymed = rhyming.is_none_or(|r| passphrase[progress.start..].ends_with(r));
+            if rhymed || progress.retries == RHYME_RETRIES {
+                return None;
+            }
+            progress.retries += 1;
         }
-        let sb: Vec<char> = sl.chars().collect::<Vec<char>>();
-        for i in 0..sb.len() {
-            let from = sb[i.saturating_sub(depth)..i].into_iter().collect();
-            let to = sb[i..(i + depth).min(sb.len())].into_iter().collect();
-            if to == "" || from == to {
-                continue;
+        let ending = remaining <= self.depth;
+        let mut constraints = Vec::new();
+        match rhyming {
+            Some(rhyme) if ending => {
+                let tokens = self.rhyming_tokens(passphrase, rhyme);
+                if !tokens.is_empty() {
+                    constraints.push(Constraint::OneOf(tokens));
+                }
             }
-            put(from, to);
+            _ if self.empirical_lengths => constraints.push(Constraint::MaxChars(remaining)),
+            _ => {}
+        }
+        if let (Theme::Alliteration, Some(a), 0) = (self.theme, anchor, progress.produced) {
+            constraints.push(Constraint::StartsWith(a.initial));
         }
+        Some(constraints)
     }
-    transition_matrix
-}
 
-fn jump_table_from_transition_matrix(
-    transition_matrix: HashMap<String, HashMap<String, usize>>,
-) -> HashMap<String, Distribution> {
-    let mut dist_trans_matrix = HashMap::new();
-    for (k, rfreq) in transition_matrix.into_iter() {
-        let total: usize = rfreq.values().sum();
-        let mut counts = Vec::new();
-        let mut tokens = Vec::new();
-        let mut entropies = Vec::new();
-        let mut cum = 0;
+    /// Accounts for `token` having been appended to the word in progress.
+    fn advance_word(&self, progress: &mut WordProgress, token: &str) {
+        progress.produced += if self.empirical_lengths {
+            token.chars().count()
+        } else {
+            self.depth
+        };
+    }
 
-        for (token, &freq) in rfreq.iter() {
-            let p = freq as f64 / total as f64;
-            cum += freq;
-            entropies.push(-p.log2());
-            counts.push(cum);
-            tokens.push(token.clone());
+    /// Tokens which can follow `seed` and make it end with `rhyme`, either directly or, when
+    /// none does, through one more token.
+    fn rhyming_tokens(&self, seed: &str, rhyme: &str) -> Vec<String> {
+        let Some((_, tr)) = self.distribution(seed) else {
+            return Vec::new();
+        };
+        let text = self.locale.lowercase(seed);
+        let rhymes = |s: &str, t: &str| format!("{}{}", s, t).ends_with(rhyme);
+        let finishing: Vec<String> = tr
+            .tokens
+            .iter()
+            .filter(|t| rhymes(&text, t))
+            .cloned()
+            .collect();
+        if !finishing.is_empty() {
+            return finishing;
         }
+        tr.tokens
+            .iter()
+            .filter(|t| {
+                let next = format!("{}{}", text, t);
+                self.distribution(&next)
+                    .is_some_and(|(_, u)| u.tokens.iter().any(|u| rhymes(&next, u)))
+            })
+            .cloned()
+            .collect()
+    }
 
-        dist_trans_matrix.insert(
-            k,
-            Distribution {
-                tokens,
-                entropies,
-                counts,
-                total,
-            },
-        );
+    /// Same as [`Generator::gen_next_token`], but only draws among the tokens satisfying the
+    /// `constraints`, renormalizing their probabilities. When no token satisfies them the
+    /// constraints are relaxed starting from the last one.
+    fn gen_token_where(&mut self, seed: &str, constraints: &[Constraint]) -> Option<(String, f64)> {
+        let (_, tr) = lookup_distribution(&self.jump_table, self.depth, self.locale, seed)?;
+        let (constraints, total) = relax(tr, constraints);
+        if constraints.is_empty() {
+            let (i, token, entropy) = tr.draw(&mut self.draws, &mut self.rng, self.constant_time);
+            if let Some(t) = self.transcript.as_mut() {
+                t.record("token", i, tr.probability(i));
+            }
+            return Some((token, entropy));
+        }
+        let mut n = self.draws.below(&mut self.rng, total);
+        for (i, token) in tr.tokens.iter().enumerate() {
+            if !admits(constraints, token) {
+                continue;
+            }
+            let freq = tr.frequency(i);
+            if n < freq {
+                let p = freq as f64 / total as f64;
+                if let Some(t) = self.transcript.as_mut() {
+                    t.record("token", i, p);
+                }
+                return Some((token.clone(), -p.log2()));
+            }
+            n -= freq;
+        }
+        None
     }
-    dist_trans_matrix
-}
 
-fn uppercase_first_letter(s: &str) -> String {
-    let mut c = s.chars();
-    match c.next() {
-        None => String::new(),
-        Some(f) => f.to_uppercase().collect::<String>() + c.as_str(),
+    /// The characters drawn by a symbol or digit `segment`.
+    fn char_set(&self, segment: &Segment) -> &[char] {
+        if *segment == Segment::Symbol {
+            &self.symbols
+        } else {
+            &self.digits
+        }
     }
-}
+
+    /// Sets the characters drawn by the `s` pattern element, uniformly; repeated characters
+    /// are drawn as one. Any Unicode characters can be used, see also [`Generator::set_layout`].
+    /// An empty `symbols` is refused with [`Error::EmptySymbols`], keeping the current symbols.
+    ///
+    /// ```rust
+    /// use cryptirust::Generator;
+    /// let mut generator = Generator::new();
+    /// generator.set_symbols("€£¥§").unwrap();
+    /// let (symbol, entropy) = generator.gen_from_pattern("s");
+    /// assert!("€£¥§".contains(&symbol));
+    /// assert_eq!(entropy, 2.0);
+    /// assert!(generator.set_symbols("").is_err());
+    /// ```
+    pub fn set_symbols(&mut self, symbols: &str) -> Result<(), Error> {
+        if symbols.is_empty() {
+            return Err(Error::EmptySymbols);
+        }
+        self.symbols = distinct_chars(symbols);
+        self.patterns.forget_bounds();
+        Ok(())
+    }
+
+    /// Sets the real words drawn, uniformly, by the `x` and `X` pattern elements, which add
+    /// `log2` of their number to the entropy. Words are trimmed, and blank and repeated words
+    /// are dropped, so that every word is a distinct outcome; they are not lowercased.
+    ///
+    /// Generators trained with [`Generator::new_custom`] draw from their word list,
+    /// lowercased, and [saved models](crate::model) keep their words, while those imported
+    /// from [transition counts](crate::ngram) have none until they are set here. Without words
+    /// the fallible methods, [`Generator::try_gen_from_pattern`] and
+    /// [`Generator::parse_limited`], refuse the `x` element and the entropy estimates are
+    /// `None`; [`Generator::gen_from_pattern`] produces nothing for it.
+    ///
+    /// ```rust
+    /// use cryptirust::Generator;
+    ///
+    /// let mut generator = Generator::new();
+    /// generator.set_dictionary(vec!["alpha".into(), "bravo".into(), "alpha".into()]);
+    /// let (password, entropy) = generator.gen_from_pattern("x-X-w");
+    /// assert!(password.starts_with("alpha-") || password.starts_with("bravo-"));
+    /// assert_eq!(generator.dictionary().len(), 2);
+    /// ```
+    pub fn set_dictionary(&mut self, words: Vec<String>) {
+        let mut seen = HashSet::new();
+        self.dictionary = words
+            .into_iter()
+            .map(|word| word.trim().to_string())
+            .filter(|word| !word.is_empty() && seen.insert(word.clone()))
+            .collect();
+        self.patterns.forget_bounds();
+    }
+
+    /// The real words drawn by the `x` pattern element, see [`Generator::set_dictionary`].
+    pub fn dictionary(&self) -> &[String] {
+        &self.dictionary
+    }
+
+    /// Whether `pattern` draws real words while the generator has none to draw.
+    pub(crate) fn lacks_dictionary(&self, pattern: &Pattern) -> bool {
+        self.dictionary.is_empty() && pattern.has_dictionary_words()
+    }
+
+    /// Word `i` of the dictionary, capitalized if asked.
+    pub(crate) fn dictionary_word(&self, i: usize, capitalized: bool) -> String {
+        if capitalized {
+            uppercase_first_letter(&self.dictionary[i], self.locale)
+        } else {
+            self.dictionary[i].clone()
+        }
+    }
+
+    /// Sets the characters drawn by the `d` pattern element, uniformly, like
+    /// [`Generator::set_symbols`]; e.g. `"23456789"` avoids the digits mistaken for letters.
+    /// [Date-safe groups](crate::dates) only apply to the ten decimal digits. An empty
+    /// `digits` is refused with [`Error::EmptyDigits`], keeping the current digits.
+    ///
+    /// ```rust
+    /// use cryptirust::Generator;
+    /// let mut generator = Generator::new();
+    /// generator.set_digits("01").unwrap();
+    /// let (bits, entropy) = generator.gen_from_pattern("dddd");
+    /// assert!(bits.chars().all(|c| c == '0' || c == '1'));
+    /// assert_eq!(entropy, 4.0);
+    /// ```
+    pub fn set_digits(&mut self, digits: &str) -> Result<(), Error> {
+        if digits.is_empty() {
+            return Err(Error::EmptyDigits);
+        }
+        self.digits = distinct_chars(digits);
+        self.patterns.forget_bounds();
+        Ok(())
+    }
+
+    /// Finds the distribution of the token following `seed`, see [`lookup_distribution`].
+    fn distribution(&self, seed: &str) -> Option<(&str, &Distribution)> {
+        lookup_distribution(&self.jump_table, self.depth, self.locale, seed)
+    }
+
+    /// Starts recording the random decisions taken by the generator into a new [`Transcript`],
+    /// discarding any previous recording.
+    pub fn start_transcript(&mut self) {
+        self.transcript = Some(Transcript::default());
+    }
+
+    /// Stops recording and returns the decisions recorded since [`Generator::start_transcript`].
+    pub fn take_transcript(&mut self) -> Option<Transcript> {
+        self.transcript.take()
+    }
+}
+
+/// The characters of `text`, each once, in order of first appearance.
+fn distinct_chars(text: &str) -> Vec<char> {
+    let mut chars = Vec::new();
+    for c in text.chars() {
+        if !chars.contains(&c) {
+            chars.push(c);
+        }
+    }
+    chars
+}
+
+/// The `constraints` which apply when drawing from `tr`, relaxed starting from the last one
+/// until some token satisfies them, together with the total frequency of the admitted tokens.
+fn relax<'c>(tr: &Distribution, constraints: &'c [Constraint]) -> (&'c [Constraint], usize) {
+    if constraints.is_empty() {
+        return (constraints, tr.total);
+    }
+    let mut active = constraints.len();
+    loop {
+        let total = tr.total_where(|t| admits(&constraints[..active], t));
+        if total > 0 || active == 0 {
+            return (&constraints[..active], total);
+        }
+        active -= 1;
+    }
+}
+
+/// Finds the distribution of the token following `seed`, backing off to shorter contexts when
+/// the last `depth` characters of the seed were never observed. Returns the matched state
+/// together with its distribution.
+fn lookup_distribution<'a>(
+    jump_table: &'a BTreeMap<String, Distribution>,
+    depth: usize,
+    locale: Locale,
+    seed: &str,
+) -> Option<(&'a str, &'a Distribution)> {
+    let start = seed
+        .char_indices()
+        .rev()
+        .nth(depth.saturating_sub(1))
+        .map_or(0, |(i, _)| i);
+    let context = &seed[start..];
+    let lowered;
+    let mut tok = if !context.is_ascii() || context.bytes().any(|b| b.is_ascii_uppercase()) {
+        lowered = locale.lowercase(context);
+        lowered.as_str()
+    } else {
+        context
+    };
+    loop {
+        if let Some((state, tr)) = jump_table.get_key_value(tok) {
+            return Some((state.as_str(), tr));
+        }
+        if tok.is_empty() {
+            return None;
+        }
+        tok = &tok[tok.chars().next().map_or(0, char::len_utf8)..];
+    }
+}
+
+fn max_depth(jump_table: &BTreeMap<String, Distribution>) -> usize {
+    let mut t_depth = 0;
+    for (k, v) in jump_table.iter() {
+        t_depth = t_depth.max(k.chars().count());
+        for c in v.tokens.iter() {
+            t_depth = t_depth.max(c.chars().count());
+        }
+    }
+    t_depth
+}
+
+/// Whether `words` can be the dictionary of a generator: trimmed, not blank and distinct.
+fn is_dictionary(words: &[String]) -> bool {
+    let mut seen = HashSet::new();
+    words
+        .iter()
+        .all(|word| !word.is_empty() && word.trim() == word && seen.insert(word.as_str()))
+}
+
+/// The distinct words of `tokens`, trimmed and lowercased as for training, in order.
+fn dictionary_from_tokens(tokens: &[String]) -> Vec<String> {
+    let mut seen = HashSet::new();
+    tokens
+        .iter()
+        .map(|word| word.trim().to_lowercase())
+        .filter(|word| !word.is_empty() && seen.insert(word.clone()))
+        .collect()
+}
+
+fn transition_matrix_from_tokens(
+    tokens: Vec<String>,
+    depth: usize,
+) -> BTreeMap<String, BTreeMap<String, usize>> {
+    let mut transition_matrix: BTreeMap<String, BTreeMap<String, usize>> = BTreeMap::new();
+
+    let mut put = |str: String, r: String| {
+        transition_matrix
+            .entry(str)
+            .or_default()
+            .entry(r)
+            .and_modify(|count| *count += 1)
+            .or_insert(1);
+    };
+
+    for raw_w in tokens.iter() {
+        let sl = raw_w.trim().to_lowercase();
+        if sl.is_empty() {
+            continue;
+        }
+        let sb: Vec<char> = sl.chars().collect::<Vec<char>>();
+        for i in 0..sb.len() {
+            let from: String = sb[i.saturating_sub(depth)..i].iter().collect();
+            let to: String = sb[i..i.saturating_add(depth).min(sb.len())]
+                .iter()
+                .collect();
+            if to.is_empty() || from == to {
+                continue;
+            }
+            put(from, to);
+        }
+    }
+    transition_matrix
+}
+
+fn jump_table_from_transition_matrix(
+    transition_matrix: BTreeMap<String, BTreeMap<String, usize>>,
+) -> BTreeMap<String, Distribution> {
+    transition_matrix
+        .into_iter()
+        .map(|(k, rfreq)| (k, Distribution::from_frequencies(rfreq)))
+        .collect()
+}
+
+fn uppercase_first_letter(s: &str, locale: Locale) -> String {
+    locale.capitalize(s)
+}
 
 #[cfg(test)]
 mod tests {
+    use std::collections::{HashMap, HashSet};
     use word_list::debug;
 
     use super::*;
 
+    /// A list, available without features, whose transitions are observed several times
+    /// each, and a few of them once, so that pruning keeps part of the model.
+    fn repeated_list() -> Vec<String> {
+        [
+            "banana", "bandana", "cabana", "canal", "panama", "lanai", "nanny", "anagram",
+        ]
+        .iter()
+        .map(|w| w.to_string())
+        .collect()
+    }
+
     fn certify(pattern: &str) -> bool {
         let mut gen = Generator::new_custom(debug::list(), 2).unwrap();
-        gen.rng = ChaCha8Rng::seed_from_u64(0x5792CBF); //fix seed for reproducible results
+        gen.reseed(0x5792CBF); //fix seed for reproducible results
         let mut hist = HashMap::<String, usize>::new();
         let mut tot_h: f64 = 0.0;
         let mut tot_c: f64 = 1e-16;
@@ -401,12 +1536,12 @@ mod tests {
                 let (pw, h) = gen.gen_from_pattern(pattern);
                 tot_h += h;
                 tot_c += 1.0;
-                let v = hist.get(&pw).or(Some(&0)).unwrap();
+                let v = hist.get(&pw).unwrap_or(&0);
                 hist.insert(pw, v + 1);
             }
             q += q / 16;
             let avg_h = tot_h / tot_c;
-            let mut entropy = 0.0 as f64;
+            let mut entropy = 0.0_f64;
             for (_, &v) in hist.iter() {
                 let p = v as f64 / tot_c;
                 entropy += -p * p.log2();
@@ -442,4 +1577,2602 @@ mod tests {
         assert!(certify("sdc"));
         assert!(certify("literal"));
     }
+
+    #[test]
+    fn test_sha256_vectors() {
+        use sha256::{sha256, HmacSha256};
+        let hex = |bytes: &[u8]| -> String { bytes.iter().map(|b| format!("{:02x}", b)).collect() };
+        assert_eq!(
+            hex(&sha256(b"abc")),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_eq!(
+            hex(&sha256(&[b'a'; 1_000])),
+            "41edece42d63e8d9bf515a9ba6932e1c20cbc9f5a5d134645adb5db1b9737ea3"
+        );
+        // RFC 4231, test cases 2 and 6
+        assert_eq!(
+            hex(&HmacSha256::new(b"Jefe").mac(b"what do ya want for nothing?")),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+        assert_eq!(
+            hex(&HmacSha256::new(&[0xaa; 131])
+                .mac(b"Test Using Larger Than Block-Size Key - Hash Key First")),
+            "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54"
+        );
+    }
+
+    #[cfg(feature = "history")]
+    #[test]
+    fn test_history() {
+        use history::{History, HistoryError};
+        let params = kdf::KdfParams {
+            iterations: history::MIN_ITERATIONS,
+        };
+        let mut history = History::new("passphrase", params).unwrap();
+        assert!(history.is_empty());
+        for secret in ["first-1", "second-2", "first-1"] {
+            history.record(secret);
+        }
+        assert_eq!(history.len(), 3);
+        let bytes = history.to_bytes();
+        assert_eq!(bytes.len(), 6 + 1 + 4 + 16 + 12 + 3 * 48 + 16);
+        assert_ne!(history.to_bytes(), bytes);
+
+        let mut opened = History::open(&bytes, "passphrase").unwrap();
+        assert_eq!(opened.len(), 3);
+        assert!(opened.contains("first-1") && opened.contains("second-2"));
+        assert!(!opened.contains("first-2") && !opened.contains(""));
+        opened.record("third-3");
+        let reopened = History::open(&opened.to_bytes(), "passphrase").unwrap();
+        assert!(reopened.contains("third-3") && reopened.contains("second-2"));
+
+        // as a store, a history records the secrets handed out by gen_unique
+        let mut gen = Generator::new();
+        let (secret, _) = gen.gen_unique("w-dd", &mut opened).unwrap();
+        assert!(opened.contains(&secret) && opened.len() == 5);
+        assert!(!store::HistoryStore::insert(&mut opened, "first-1"));
+
+        assert_eq!(
+            History::open(&bytes, "other").err(),
+            Some(HistoryError::Authentication)
+        );
+        let mut tampered = bytes.clone();
+        tampered[60] ^= 1;
+        assert_eq!(
+            History::open(&tampered, "passphrase").err(),
+            Some(HistoryError::Authentication)
+        );
+        assert_eq!(
+            History::open(&bytes[..bytes.len() - 1], "passphrase").err(),
+            Some(HistoryError::NotAHistory)
+        );
+        let mut newer = bytes.clone();
+        newer[6] = 3;
+        assert_eq!(
+            History::open(&newer, "passphrase").err(),
+            Some(HistoryError::UnsupportedVersion(3))
+        );
+        // the work factor is bounded, when creating and when opening
+        for iterations in [0, history::MIN_ITERATIONS - 1, history::MAX_ITERATIONS + 1] {
+            let params = kdf::KdfParams { iterations };
+            assert_eq!(
+                History::new("passphrase", params).err(),
+                Some(HistoryError::Iterations(iterations))
+            );
+            let mut crafted = bytes.clone();
+            crafted[7..11].copy_from_slice(&iterations.to_be_bytes());
+            assert_eq!(
+                History::open(&crafted, "passphrase").err(),
+                Some(HistoryError::Iterations(iterations))
+            );
+        }
+        let mut header = bytes.clone();
+        header[11] ^= 1;
+        assert_eq!(
+            History::open(&header, "passphrase").err(),
+            Some(HistoryError::Authentication)
+        );
+        // the file reveals neither the secrets nor their hashes
+        assert!(!bytes.windows(7).any(|w| w == b"first-1"));
+    }
+
+    #[test]
+    fn test_voice_list_has_no_homophones() {
+        let list = word_list::voice::list();
+        let (cleaned, dropped) = word_list::filter_homophones(&list);
+        assert_eq!(dropped, 0);
+        assert_eq!(cleaned.len(), list.len());
+    }
+
+    #[test]
+    fn test_homophone_groups() {
+        let mut seen = HashSet::new();
+        for group in word_list::homophones::GROUPS.split(';') {
+            let words: Vec<String> = group.split(',').map(|w| w.to_string()).collect();
+            assert!(words.len() >= 2, "{group}");
+            for word in &words {
+                assert!(
+                    !word.is_empty() && word.trim() == word && word.to_lowercase() == *word,
+                    "{group}"
+                );
+                assert!(seen.insert(word.clone()), "{word} is in two groups");
+            }
+            // only the most common spelling survives, whichever spellings the list holds
+            let (cleaned, dropped) = word_list::filter_homophones(&words);
+            assert_eq!(cleaned, vec![words[0].clone()], "{group}");
+            assert_eq!(dropped, words.len() - 1);
+            for later in &words[1..] {
+                let pair = [words[0].clone(), later.clone()];
+                assert_eq!(
+                    word_list::filter_homophones(&pair).0,
+                    vec![words[0].clone()]
+                );
+                let alone = [later.clone()];
+                assert_eq!(word_list::filter_homophones(&alone).1, 0);
+            }
+        }
+        // pairs which do not sound alike are not grouped
+        for pair in [
+            ["close", "clothes"],
+            ["ferry", "fairy"],
+            ["desert", "dessert"],
+            ["rot", "wrought"],
+            ["wind", "wined"],
+        ] {
+            let list: Vec<String> = pair.iter().map(|w| w.to_string()).collect();
+            assert_eq!(word_list::filter_homophones(&list).1, 0, "{pair:?}");
+        }
+    }
+
+    #[test]
+    fn test_prefix_free_list_splits_uniquely() {
+        let list: Vec<String> = ["a", "ab", "abc", "b", "ba", "bab", "c", "ca", "cab", "cb"]
+            .iter()
+            .map(|w| w.to_string())
+            .collect();
+        let (cleaned, dropped) = word_list::remove_prefixes(&list);
+        assert_eq!(cleaned, vec!["abc", "bab", "cab", "cb"]);
+        assert_eq!(dropped, 6);
+        // every concatenation of up to three words, without separators, is a different text
+        let mut texts = HashSet::new();
+        let mut total = 0;
+        for a in &cleaned {
+            for b in &cleaned {
+                texts.insert(format!("{}{}", a, b));
+                for c in &cleaned {
+                    texts.insert(format!("{}{}{}", a, b, c));
+                    total += 1;
+                }
+                total += 1;
+            }
+        }
+        assert_eq!(texts.len(), total);
+    }
+
+    #[test]
+    fn test_deterministic_training() {
+        let outputs = || {
+            let mut gen = Generator::new();
+            gen.reseed(0xC0FFEE);
+            gen.gen_batch("w-c-s-d", 50)
+        };
+        assert_eq!(outputs(), outputs());
+    }
+
+    #[test]
+    fn test_seeded_constructors() {
+        let mut reseeded = Generator::new();
+        reseeded.reseed(0xC0FFEE);
+        let mut seeded = Generator::new_with_seed(0xC0FFEE);
+        assert_eq!(
+            seeded.gen_batch("w-c-s-d", 50),
+            reseeded.gen_batch("w-c-s-d", 50)
+        );
+        let mut first = Generator::new_with_seed(0xC0FFEE);
+        let mut other = Generator::new_with_seed(0xC0FFEF);
+        assert_ne!(
+            other.gen_batch("w-c-s-d", 50),
+            first.gen_batch("w-c-s-d", 50)
+        );
+
+        // the order of the tokens does not matter, only the trained model
+        let mut list = debug::list();
+        let mut a = Generator::new_custom_with_seed(list.clone(), 2, 7).unwrap();
+        list.reverse();
+        let mut b = Generator::new_custom_with_seed(list, 2, 7).unwrap();
+        assert_eq!(a.gen_batch("W-w-dd", 20), b.gen_batch("W-w-dd", 20));
+        assert!(Generator::new_custom_with_seed(vec![" ".to_string()], 2, 7).is_none());
+    }
+
+    #[test]
+    fn test_gen_indexed() {
+        let seed = b"shared batch seed";
+        let mut a = Generator::new();
+        let forward: Vec<_> = (0..50).map(|i| a.gen_indexed(seed, "w-w-dd", i)).collect();
+        let mut b = Generator::new();
+        let backward: Vec<_> = (0..50)
+            .rev()
+            .map(|i| b.gen_indexed(seed, "w-w-dd", i))
+            .collect();
+        assert!(forward.iter().eq(backward.iter().rev()));
+        let distinct: HashSet<&String> = forward.iter().map(|(s, _)| s).collect();
+        assert_eq!(distinct.len(), 50);
+        assert_ne!(a.gen_indexed(b"other seed", "w-w-dd", 7), forward[7]);
+
+        // the random state and the position in the batch of the generator are kept
+        let mut plain = Generator::new_with_seed(3);
+        let mut indexed = Generator::new_with_seed(3);
+        plain.set_sequence(4);
+        indexed.set_sequence(4);
+        indexed.gen_indexed(seed, "w", 9);
+        assert_eq!(
+            indexed.gen_from_pattern("w-{seq}"),
+            plain.gen_from_pattern("w-{seq}")
+        );
+
+        // {seq} writes the index, so indices never share a secret
+        let (secret, _) = a.gen_indexed(seed, "{seq:100}-c", 23);
+        assert!(secret.starts_with("123-"));
+        let tiny = Generator::new_custom(vec!["a".to_string()], 1).unwrap();
+        let mut tiny = tiny.with_rng(rand_chacha::ChaCha20Rng::seed_from_u64(0));
+        let codes: HashSet<String> = (0..20)
+            .map(|i| tiny.gen_indexed(seed, "{seq}:w", i).0)
+            .collect();
+        assert_eq!(codes.len(), 20);
+
+        assert_eq!(batch::collision_probability(10.0, 1), 0.0);
+        assert!(batch::collision_probability(10.0, 2000) > 0.99);
+    }
+
+    #[test]
+    fn test_dictionary_words() {
+        let list = ["apple", "banana", "Cherry", "apple", " date "];
+        let mut gen = Generator::new_custom(list.map(String::from).to_vec(), 2).unwrap();
+        assert_eq!(gen.dictionary(), ["apple", "banana", "cherry", "date"]);
+        for _ in 0..50 {
+            let (word, entropy) = gen.gen_from_pattern("x");
+            assert!(gen.dictionary().contains(&word));
+            assert_eq!(entropy, 2.0);
+            let (word, _) = gen.gen_from_pattern("X");
+            assert!(["Apple", "Banana", "Cherry", "Date"].contains(&word.as_str()));
+        }
+        let secret = gen.gen_annotated(&Pattern::parse("x-w-dd"));
+        assert_eq!(secret.words.len(), 2);
+        assert!(gen
+            .dictionary()
+            .contains(&secret.text[secret.words[0].range.clone()].into()));
+
+        let pattern = Pattern::parse("X-x");
+        assert_eq!(gen.exact_entropy(&pattern, "Date-apple"), Some(4.0));
+        assert_eq!(gen.exact_entropy(&pattern, "date-apple"), None);
+        assert_eq!(pattern.expected_entropy(&gen), Some(4.0));
+        assert_eq!(gen.enumerate(&pattern).unwrap().count(), 16);
+
+        // words which are prefixes of each other make some texts ambiguous
+        gen.set_dictionary(vec!["a".into(), "ab".into(), "b".into(), "ba".into()]);
+        let exact = gen.exact_entropy(&Pattern::parse("xx"), "aba").unwrap();
+        assert!((exact - (16.0f64 / 2.0).log2()).abs() < 1e-9);
+
+        // saved models keep their words, which count in the digest
+        let loaded = Generator::from_model_bytes(&gen.to_model_bytes()).unwrap();
+        assert_eq!(loaded.dictionary(), gen.dictionary());
+        let loaded = Generator::from_flat_model(&gen.to_flat_model().unwrap()).unwrap();
+        assert_eq!(loaded.dictionary(), gen.dictionary());
+        let fingerprint = gen.word_list_fingerprint().unwrap();
+        let loaded = Generator::from_model_json(&gen.model_json(), fingerprint).unwrap();
+        assert_eq!(loaded.dictionary(), gen.dictionary());
+        let digest = gen.model_digest();
+        gen.set_dictionary(vec!["a".into(), "ab".into()]);
+        assert_ne!(gen.model_digest(), digest);
+
+        // models imported from counts have no words: x is refused until they are set
+        let mut loaded = Generator::from_ngram_counts(
+            gen.transitions()
+                .map(|(s, t, c)| (s.to_string(), t.to_string(), c)),
+        )
+        .unwrap();
+        assert!(loaded.dictionary().is_empty());
+        let (secret, entropy) = loaded.gen_from_pattern("x-dd");
+        assert_eq!((secret.len(), entropy), (3, 2.0 * 10f64.log2()));
+        assert_eq!(loaded.try_gen_from_pattern("w-x"), Err(Error::NoDictionary));
+        let limits = limits::PatternLimits::default();
+        assert_eq!(
+            loaded.parse_limited("w[x|d]", &limits).err(),
+            Some(limits::LimitError::NoDictionary)
+        );
+        assert_eq!(Pattern::parse("x").expected_entropy(&loaded), None);
+        assert_eq!(loaded.max_achievable_entropy("dX"), None);
+        loaded.set_dictionary(vec![" kiwi".into(), String::new(), "kiwi".into()]);
+        assert_eq!(loaded.gen_from_pattern("X"), ("Kiwi".to_string(), 0.0));
+        assert!(loaded.parse_limited("w[x|d]", &limits).is_ok());
+    }
+
+    #[test]
+    fn test_gen_passphrase() {
+        use passphrase::{Case, PassphraseSpec, SpecError};
+
+        let mut generator = Generator::new_with_seed(11);
+        let spec = PassphraseSpec {
+            words: 4,
+            separator: String::from("."),
+            case: Case::Title,
+            suffix_digits: 3,
+        };
+        let (passphrase, entropy) = generator.gen_passphrase(&spec).unwrap();
+        let expected = Generator::new_with_seed(11).gen_from_pattern("W.W.W.W.ddd");
+        assert_eq!((passphrase, entropy), expected);
+
+        let upper = PassphraseSpec {
+            case: Case::Upper,
+            ..PassphraseSpec::default()
+        };
+        let (passphrase, entropy) = Generator::new_with_seed(2).gen_passphrase(&upper).unwrap();
+        let lower = Generator::new_with_seed(2).gen_from_pattern("w-w-w-w-w");
+        assert_eq!(passphrase, lower.0.to_uppercase());
+        assert_eq!(entropy, lower.1);
+
+        // title case marks the boundaries without a separator
+        let camel = PassphraseSpec {
+            separator: String::new(),
+            case: Case::Title,
+            ..PassphraseSpec::default()
+        };
+        let (passphrase, _) = generator.gen_passphrase(&camel).unwrap();
+        assert_eq!(passphrase.chars().filter(|c| c.is_uppercase()).count(), 5);
+
+        let invalid = |words, separator: &str| PassphraseSpec {
+            words,
+            separator: separator.to_string(),
+            ..PassphraseSpec::default()
+        };
+        assert_eq!(invalid(0, "-").validate(), Err(SpecError::NoWords));
+        assert_eq!(
+            invalid(65, "-").validate(),
+            Err(SpecError::TooManyWords { words: 65 })
+        );
+        assert_eq!(
+            generator.gen_passphrase(&invalid(3, " and ")),
+            Err(SpecError::AmbiguousSeparator)
+        );
+        assert!(invalid(1, "").validate().is_ok());
+        let digits = PassphraseSpec {
+            suffix_digits: 65,
+            ..PassphraseSpec::default()
+        };
+        assert_eq!(
+            digits.validate(),
+            Err(SpecError::TooManyDigits { digits: 65 })
+        );
+
+        // uppercase must not merge characters of the model
+        let turkish = vec!["ılık".to_string(), "iğne".to_string()];
+        let mut generator = Generator::new_custom(turkish, 2).unwrap();
+        assert_eq!(
+            generator.gen_passphrase(&upper),
+            Err(SpecError::AmbiguousUppercase)
+        );
+        generator.set_locale(casing::Locale::Turkic);
+        let (passphrase, _) = generator.gen_passphrase(&upper).unwrap();
+        assert!(passphrase.contains('I') || passphrase.contains('İ'));
+        let mut generator = Generator::new_custom(vec!["straße".to_string()], 2).unwrap();
+        assert_eq!(
+            generator.gen_passphrase(&upper),
+            Err(SpecError::AmbiguousUppercase)
+        );
+        generator.set_locale(casing::Locale::German);
+        assert!(generator.gen_passphrase(&upper).is_ok());
+    }
+
+    #[test]
+    fn test_write_batch() {
+        use batch::{RecordFormat, StreamOptions};
+
+        /// Counts the flushes and fails after `limit` bytes.
+        struct Sink {
+            bytes: Vec<u8>,
+            flushes: usize,
+            limit: usize,
+        }
+        impl std::io::Write for Sink {
+            fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+                if self.bytes.len() + buf.len() > self.limit {
+                    return Err(std::io::ErrorKind::WriteZero.into());
+                }
+                self.bytes.extend_from_slice(buf);
+                Ok(buf.len())
+            }
+            fn flush(&mut self) -> std::io::Result<()> {
+                self.flushes += 1;
+                Ok(())
+            }
+        }
+
+        let mut streamed = Generator::new_with_seed(5);
+        let mut out = Vec::new();
+        let options = StreamOptions::new(RecordFormat::Tsv);
+        assert_eq!(
+            streamed.write_batch("w,s", 300, options, &mut out).unwrap(),
+            300
+        );
+        let expected = Generator::new_with_seed(5).gen_batch("w,s", 300);
+        let text = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 300);
+        for (line, (secret, entropy)) in lines.iter().zip(&expected) {
+            assert_eq!(*line, format!("{}\t{:.2}", secret, entropy));
+        }
+
+        let mut out = Vec::new();
+        let options = StreamOptions::new(RecordFormat::Csv);
+        streamed.write_batch("w,w", 5, options, &mut out).unwrap();
+        let records = String::from_utf8(out).unwrap();
+        let records: Vec<&str> = records.lines().collect();
+        assert_eq!(records[0], "secret,entropy");
+        assert!(records[1..].iter().all(|r| r.starts_with('"')));
+
+        let mut out = Vec::new();
+        let options = StreamOptions::new(RecordFormat::JsonLines);
+        streamed.write_batch("\"w\"", 3, options, &mut out).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert!(text.lines().all(|l| l.starts_with("{\"secret\":\"\\\"")));
+
+        let mut sink = Sink {
+            bytes: Vec::new(),
+            flushes: 0,
+            limit: usize::MAX,
+        };
+        let options = StreamOptions::default().flush_every(100);
+        assert_eq!(
+            streamed
+                .write_batch("ddd", 1050, options, &mut sink)
+                .unwrap(),
+            1050
+        );
+        assert_eq!(sink.bytes.len(), 1050 * 4);
+        // ten full chunks and the end of the batch
+        assert_eq!(sink.flushes, 11);
+
+        let mut sink = Sink {
+            bytes: Vec::new(),
+            flushes: 0,
+            limit: 40_000,
+        };
+        let result = streamed.write_batch("ddd", 20_000, StreamOptions::default(), &mut sink);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_custom_rng() {
+        use rand::rngs::OsRng;
+        use rand_chacha::ChaCha20Rng;
+        let mut os = Generator::new().with_rng(OsRng);
+        let (passphrase, entropy) = os.gen_from_pattern("w-w-dd");
+        assert!(entropy > 0.0);
+        let pattern = Pattern::parse("w-w-dd");
+        assert!(os.exact_entropy(&pattern, &passphrase).is_some());
+        let check = analysis::check_user_passphrase(&mut os, "kitty", None);
+        assert!(!check.suggestions.is_empty());
+
+        // settings move with the model, and seedable generators are reproducible
+        let mut gen = Generator::new_with_seed(1);
+        gen.set_symbols("#").unwrap();
+        let mut a = gen.with_rng(ChaCha20Rng::seed_from_u64(9));
+        let mut b = Generator::new().with_rng(ChaCha20Rng::seed_from_u64(0));
+        b.set_symbols("#").unwrap();
+        b.reseed(9);
+        assert_eq!(a.gen_batch("w-s-d", 20), b.gen_batch("w-s-d", 20));
+        assert!(a.gen_from_pattern("s").0 == "#");
+        let rank = analysis::guess_rank(&mut a, &Pattern::parse("w"), "zebra");
+        assert_eq!(
+            rank,
+            analysis::guess_rank(&mut b, &Pattern::parse("w"), "zebra")
+        );
+        a.set_demo_mode(true);
+        assert!(a.gen_from_pattern("w").0.starts_with(DEMO_WATERMARK));
+
+        // secrets assembled from parts in several languages are marked once, at the start
+        let mut languages = registry::Registry::new();
+        for label in ["a", "b"] {
+            let mut gen = Generator::new_custom(word_list::cv::list(), 2).unwrap();
+            gen.set_demo_mode(true);
+            languages.add(label, gen);
+        }
+        let secret = languages.gen_annotated(&Pattern::parse("w-w-dd-w"));
+        assert!(secret.text.starts_with(DEMO_WATERMARK));
+        assert_eq!(secret.text.matches(DEMO_WATERMARK).count(), 1);
+        assert_eq!(secret.entropy, 0.0);
+        let covered: usize = secret.spans.iter().map(|s| s.range.len()).sum();
+        assert_eq!(covered, secret.text.len() - DEMO_WATERMARK.len());
+        assert_eq!(secret.spans.last().unwrap().range.end, secret.text.len());
+    }
+
+    #[test]
+    fn test_empirical_word_lengths() {
+        let mut gen = Generator::new_custom(debug::list(), 2).unwrap();
+        gen.set_empirical_word_lengths(true);
+        gen.start_transcript();
+        for pattern in ["w", "W.w", "ww"] {
+            let pattern = Pattern::parse(pattern);
+            for _ in 0..200 {
+                let (pw, h) = gen.gen_from_parsed(&pattern);
+                let recorded = gen.take_transcript().unwrap().entropy();
+                gen.start_transcript();
+                assert!((recorded - h).abs() < 1e-9);
+                let exact = gen.exact_entropy(&pattern, &pw).unwrap();
+                assert!(exact <= h + 1e-9, "{} {} {}", pw, exact, h);
+            }
+        }
+    }
+
+    #[test]
+    fn test_transcript_keeps_no_text() {
+        let mut gen = Generator::new();
+        gen.reseed(0x7A);
+        gen.set_date_safe_digits(true);
+        gen.start_transcript();
+        let (secret, h) = gen.gen_from_pattern("w-s-dddd");
+        let transcript = gen.take_transcript().unwrap();
+        assert!((transcript.entropy() - h).abs() < 1e-9);
+        let kinds: HashSet<&str> = transcript.decisions.iter().map(|d| d.kind).collect();
+        assert_eq!(kinds, HashSet::from(["token", "symbol", "dddd"]));
+        let json = transcript.to_json();
+        let word = secret.split('-').next().unwrap();
+        assert!(!json.contains(word), "{} {}", secret, json);
+        // digit groups are recorded by their rank among the date-safe groups
+        let value: usize = secret[secret.len() - 4..].parse().unwrap();
+        let rank = (0..value)
+            .filter(|v| !dates::is_date_like(&format!("{:04}", v)))
+            .count();
+        assert_eq!(transcript.decisions.last().unwrap().index, rank);
+    }
+
+    #[test]
+    fn test_themes() {
+        let mut gen = Generator::new();
+        gen.reseed(0x7E3E);
+        let mut rhymes = 0;
+        for (theme, empirical) in [(Theme::Alliteration, false), (Theme::Rhyme, true)] {
+            gen.set_theme(theme);
+            gen.set_empirical_word_lengths(empirical);
+            for _ in 0..200 {
+                gen.start_transcript();
+                let (pw, h) = gen.gen_from_pattern("w-w-w");
+                let recorded = gen.take_transcript().unwrap().entropy();
+                assert!((recorded - h).abs() < 1e-9);
+                let words: Vec<&str> = pw.split('-').collect();
+                match theme {
+                    Theme::Alliteration => assert!(words.iter().all(|w| w[..1] == words[0][..1])),
+                    _ => {
+                        rhymes += words
+                            .iter()
+                            .all(|w| w.ends_with(&words[0][words[0].len() - 2..]))
+                            as usize
+                    }
+                }
+            }
+        }
+        assert!(rhymes > 150, "{}", rhymes);
+    }
+
+    #[test]
+    fn test_date_safe_digit_grouping() {
+        let mut gen = Generator::new_custom(word_list::cv::list(), 2).unwrap();
+        gen.reseed(0xDA7E);
+        gen.set_date_safe_digits(true);
+        gen.set_digit_grouping(Some(grouping::DigitGrouping::new(2, '-')));
+        let group = (dates::safe_groups() as f64).log2();
+        let pattern = Pattern::parse("dddd");
+        for _ in 0..200 {
+            let (pin, h) = gen.gen_from_parsed(&pattern);
+            assert_eq!(pin.len(), 5, "{}", pin);
+            assert_eq!(&pin[2..3], "-", "{}", pin);
+            assert!(!dates::is_date_like(&pin.replace('-', "")), "{}", pin);
+            assert!((h - group).abs() < 1e-9);
+            let exact = gen.exact_entropy(&pattern, &pin).unwrap();
+            assert!((exact - group).abs() < 1e-9, "{} {}", pin, exact);
+        }
+        assert_eq!(gen.exact_entropy(&pattern, "7251"), None);
+        let guesses: Vec<(String, f64)> = gen.enumerate(&pattern).unwrap().collect();
+        assert_eq!(guesses.len(), dates::safe_groups());
+        assert!(guesses.iter().all(|(pin, _)| &pin[2..3] == "-"));
+    }
+
+    #[test]
+    fn test_plan_pattern() {
+        use plan::{PlanConstraints, MAX_PLAN_BITS};
+
+        let gen = Generator::new();
+        let expected = |pattern: &str| Pattern::parse(pattern).expected_entropy(&gen).unwrap();
+        let free = PlanConstraints::default();
+        let pattern = gen.plan_pattern(50.0, &free).unwrap();
+        assert!(pattern.chars().all(|c| c == 'w' || c == '-'));
+        assert!(expected(&pattern) >= 50.0);
+        assert_eq!(gen.plan_pattern(0.0, &free).unwrap(), "");
+
+        let constraints = PlanConstraints {
+            max_length: Some(20),
+            digit: true,
+            symbol: true,
+            uppercase: true,
+        };
+        let pattern = gen.plan_pattern(60.0, &constraints).unwrap();
+        assert!(pattern.contains('d') && pattern.contains('s') && pattern.contains(['W', 'C']));
+        assert!(expected(&pattern) >= 60.0);
+        assert!(gen.max_length(&Pattern::parse(&pattern)) <= 20);
+        let tight = PlanConstraints {
+            max_length: Some(3),
+            ..PlanConstraints::default()
+        };
+        assert_eq!(gen.plan_pattern(100.0, &tight), None);
+
+        // targets no bounded pattern reaches are refused instead of searched forever
+        let unbounded = PlanConstraints {
+            max_length: Some(usize::MAX),
+            ..PlanConstraints::default()
+        };
+        for target in [
+            f64::NAN,
+            f64::INFINITY,
+            f64::NEG_INFINITY,
+            -1.0,
+            MAX_PLAN_BITS + 1.0,
+            1e12,
+        ] {
+            assert_eq!(gen.plan_pattern(target, &free), None, "{target}");
+            assert_eq!(gen.plan_pattern(target, &unbounded), None, "{target}");
+        }
+    }
+
+    #[test]
+    fn test_min_entropy_target() {
+        let mut gen = Generator::new_custom(word_list::cv::list(), 2).unwrap();
+        gen.reseed(0x753);
+        let (secret, entropy) = gen.gen_with_min_entropy("-W", 60.0).unwrap();
+        assert!(entropy >= 60.0);
+        // the units are the secrets of the unit pattern, in order
+        gen.reseed(0x753);
+        let mut expected = (String::new(), 0.0);
+        while expected.1 < 60.0 {
+            let (text, h) = gen.gen_from_pattern("-W");
+            expected.0 += &text;
+            expected.1 += h;
+        }
+        assert_eq!((secret, entropy), expected);
+        assert_eq!(
+            gen.gen_with_min_entropy("d", 0.0).unwrap(),
+            (String::new(), 0.0)
+        );
+
+        assert_eq!(
+            gen.gen_with_min_entropy("-", 10.0),
+            Err(Error::EntropyUnreachable {
+                entropy: 0.0,
+                target: 10.0
+            })
+        );
+        gen.set_demo_mode(true);
+        assert!(gen.gen_with_min_entropy("w", 10.0).is_err());
+        gen.set_demo_mode(false);
+        let tiny = target::MAX_UNITS as f64 * 10f64.log2();
+        assert!(gen.gen_with_min_entropy("d", tiny + 1.0).is_err());
+    }
+
+    #[test]
+    fn test_history_stores() {
+        use store::{BloomStore, HistoryStore, MemoryStore, RotatingStore, StoreError};
+        let mut gen = Generator::new_custom(word_list::cv::list(), 2).unwrap();
+        // 10 possible secrets, all handed out once
+        let mut memory = MemoryStore::new();
+        let mut bloom = BloomStore::with_capacity(b"key", 10, 1e-9).unwrap();
+        for store in [&mut memory as &mut dyn HistoryStore, &mut bloom] {
+            let mut secrets: Vec<String> = (0..10)
+                .map(|_| gen.gen_unique("d", store).unwrap().0)
+                .collect();
+            secrets.sort();
+            assert_eq!(secrets.concat(), "0123456789");
+            assert_eq!(
+                gen.gen_unique("d", store),
+                Err(Error::SecretsExhausted {
+                    attempts: batch::MAX_REJECTIONS
+                })
+            );
+        }
+        assert_eq!(memory.len(), 10);
+        assert_eq!(bloom.inserted(), 10);
+
+        let bytes = bloom.to_bytes();
+        let restored = BloomStore::from_bytes(&bytes, b"key").unwrap();
+        assert!(restored.contains("7") && !restored.contains("77"));
+        let other_key = BloomStore::from_bytes(&bytes, b"other key").unwrap();
+        assert!(!(0..10).all(|d| other_key.contains(&d.to_string())));
+        assert_eq!(
+            BloomStore::from_bytes(&bytes[..10], b"key").err(),
+            Some(StoreError::NotAFilter)
+        );
+
+        // sizes and rates out of range are refused, not clamped
+        for rate in [0.0, -1.0, 0.6, f64::NAN] {
+            assert!(matches!(
+                BloomStore::with_capacity(b"key", 10, rate).err(),
+                Some(StoreError::FalsePositiveRate(_))
+            ));
+        }
+        for capacity in [0, usize::MAX] {
+            assert_eq!(
+                BloomStore::with_capacity(b"key", capacity, 1e-6).err(),
+                Some(StoreError::Capacity(capacity))
+            );
+        }
+
+        // batches draw against a store, within and across batches
+        let mut seen = MemoryStore::new();
+        let first = gen.gen_batch_unique("d", 6, &mut seen).unwrap();
+        let distinct: HashSet<&String> = first.iter().map(|(s, _)| s).collect();
+        assert_eq!((distinct.len(), seen.len()), (6, 6));
+        assert_eq!(
+            gen.gen_batch_unique("d", 5, &mut seen),
+            Err(Error::SecretsExhausted {
+                attempts: batch::MAX_REJECTIONS
+            })
+        );
+        assert!((0..10).all(|d| seen.contains(&d.to_string())));
+
+        // a rotating store forgets a generation at a time, never the current one
+        let filter = BloomStore::with_capacity(b"key", 8, 1e-3).unwrap();
+        let mut rotating = RotatingStore::new(filter, None);
+        let mut rotated_at = None;
+        for i in 0..100 {
+            assert!(rotating.insert(&format!("s{}", i)));
+            if rotated_at.is_none() && rotating.previous().is_some() {
+                rotated_at = Some(i);
+            }
+        }
+        let rotated_at = rotated_at.unwrap();
+        assert!((4..=16).contains(&rotated_at));
+        assert!(rotating.contains("s99") && !rotating.contains("s0"));
+        assert!(!rotating.current().is_full());
+
+        // saving replaces the file whole, without leaving temporary files behind
+        let dir = std::env::temp_dir().join(format!("cryptirust-bloom-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("seen.bloom");
+        std::fs::write(&path, b"stale").unwrap();
+        bloom.save(&path).unwrap();
+        assert!(BloomStore::open(&path, b"key").unwrap().contains("7"));
+        assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 1);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[test]
+    fn test_sqlite_store() {
+        use store::{HistoryStore, SqliteStore};
+        let mut gen = Generator::new_custom(word_list::cv::list(), 2).unwrap();
+        let dir = std::env::temp_dir().join(format!("cryptirust-sqlite-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("seen.sqlite");
+        let mut store = SqliteStore::open(&path, b"key").unwrap();
+        assert!(store.is_empty().unwrap());
+        let mut secrets: Vec<String> = (0..10)
+            .map(|_| gen.gen_unique("d", &mut store).unwrap().0)
+            .collect();
+        secrets.sort();
+        assert_eq!(secrets.concat(), "0123456789");
+        assert_eq!(
+            gen.gen_unique("d", &mut store),
+            Err(Error::SecretsExhausted {
+                attempts: batch::MAX_REJECTIONS
+            })
+        );
+        assert_eq!(store.len().unwrap(), 10);
+        drop(store);
+
+        // the secrets survive the process, hashed under the key
+        let mut reopened = SqliteStore::open(&path, b"key").unwrap();
+        assert!(reopened.contains("7") && !reopened.contains("77"));
+        assert!(!reopened.insert("7"));
+        assert!(reopened.insert("77"));
+        let other_key = SqliteStore::open(&path, b"other key").unwrap();
+        assert!(!other_key.contains("7"));
+        drop((reopened, other_key));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_enumerate() {
+        let mut gen = Generator::new_custom(word_list::cv::list(), 2).unwrap();
+        let (_, root) = lookup_distribution(&gen.jump_table, gen.depth, gen.locale, "").unwrap();
+        let tokens = root.tokens.len();
+        let all: Vec<(String, f64)> = gen.enumerate(&Pattern::parse("c")).unwrap().collect();
+        assert_eq!(all.len(), tokens);
+        assert!(all.windows(2).all(|w| w[0].1 <= w[1].1));
+        assert_eq!(gen.enumerate(&Pattern::parse("dd")).unwrap().count(), 100);
+        for empirical in [false, true] {
+            gen.set_empirical_word_lengths(empirical);
+            let pattern = Pattern::parse("Ww-d");
+            let guesses: Vec<(String, f64)> = gen.enumerate(&pattern).unwrap().take(500).collect();
+            assert!(guesses.windows(2).all(|w| w[0].1 <= w[1].1));
+            for (guess, entropy) in &guesses {
+                assert!(gen.exact_entropy(&pattern, guess).unwrap() <= entropy + 1e-9);
+            }
+        }
+        let pattern = Pattern::parse("ww");
+        let mut bounded = gen.enumerate(&pattern).unwrap();
+        bounded.set_max_frontier(10);
+        let first: Vec<(String, f64)> = bounded.by_ref().take(5).collect();
+        assert!(bounded.pruned() > 0);
+        let exact: Vec<(String, f64)> = gen.enumerate(&pattern).unwrap().take(5).collect();
+        assert_eq!(first, exact);
+        gen.set_theme(Theme::Rhyme);
+        assert!(gen.enumerate(&Pattern::parse("w-w")).is_none());
+    }
+
+    #[test]
+    fn test_salt_model() {
+        let salted = |salt: &[u8]| {
+            let mut gen = Generator::new_custom(word_list::cv::list(), 2).unwrap();
+            gen.salt_model(salt);
+            gen.reseed(0x5A17);
+            gen.gen_from_pattern("w-w-w-w")
+        };
+        assert_eq!(salted(b"one"), salted(b"one"));
+        assert_ne!(salted(b"one"), salted(b"two"));
+        let mut gen = Generator::new_custom(word_list::cv::list(), 2).unwrap();
+        let before = gen.model_stats();
+        let impact = gen.salt_model(b"one");
+        assert_eq!(gen.model_stats().transitions, before.transitions);
+        assert!(impact.divergence > 0.0 && impact.after < impact.before);
+        gen.start_transcript();
+        let (_, entropy) = gen.gen_from_pattern("w-w-w-w");
+        assert!((gen.take_transcript().unwrap().entropy() - entropy).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_template() {
+        use template::render;
+        let mut gen = Generator::new_custom(word_list::cv::list(), 2).unwrap();
+        gen.reseed(0x671);
+        let rendered = render(
+            &mut gen,
+            "user: {{cryptirust:w}}\npin: {{cryptirust:d{6}}}.",
+        )
+        .unwrap();
+        gen.reseed(0x671);
+        let (word, h) = gen.gen_from_pattern("w");
+        let (pin, d) = gen.gen_from_pattern("d{6}");
+        assert_eq!(rendered.text, format!("user: {}\npin: {}.", word, pin));
+        assert_eq!(rendered.entropies, vec![h, d]);
+        assert!((d - 6.0 * 10f64.log2()).abs() < 1e-9);
+
+        // without placeholders, or with unterminated ones, the text is copied verbatim
+        for text in ["", "plain {{text}}", "{{cryptirust:w-w", "{{cryptirust:d}"] {
+            let rendered = render(&mut gen, text).unwrap();
+            assert_eq!(rendered.text, text);
+            assert!(rendered.entropies.is_empty());
+        }
+        let rendered = render(&mut gen, "{{cryptirust:d}} {{cryptirust:dd").unwrap();
+        assert_eq!(rendered.text.len(), "1 {{cryptirust:dd".len());
+        assert!(rendered.text.ends_with(" {{cryptirust:dd"));
+        assert_eq!(rendered.entropies.len(), 1);
+        // placeholders are not nested, the first closing braces end the outer one
+        let rendered = render(&mut gen, "{{cryptirust:{{cryptirust:d}}").unwrap();
+        assert_eq!(rendered.entropies.len(), 1);
+        assert!(!rendered.text.ends_with("}}"));
+        // a placeholder without a pattern is refused
+        assert_eq!(
+            render(&mut gen, "pin: {{cryptirust:d}} {{cryptirust:}}").err(),
+            Some(Error::EmptyPlaceholder { offset: 22 })
+        );
+    }
+
+    #[test]
+    fn test_model_export() {
+        let gen = Generator::new_custom(vec!["ab".to_string(), "ac".to_string()], 1).unwrap();
+        let transitions: Vec<_> = gen.transitions().collect();
+        assert_eq!(transitions, [("", "a", 2), ("a", "b", 1), ("a", "c", 1)]);
+        assert_eq!(
+            gen.model_json(),
+            format!(
+                "[\n{{\"word_list\":\"{}\"}},\n\
+                 {{\"dictionary\":[\"ab\",\"ac\"]}},\n\
+                 {{\"state\":\"\",\"next\":\"a\",\"count\":2}},\n\
+                 {{\"state\":\"a\",\"next\":\"b\",\"count\":1}},\n\
+                 {{\"state\":\"a\",\"next\":\"c\",\"count\":1}}\n]\n",
+                word_list::fingerprint(&["ab".to_string(), "ac".to_string()])
+            )
+        );
+        let dot = gen.model_dot();
+        assert!(dot.contains("s0 [label=\"start\"];"));
+        assert!(dot.contains("s0 -> s1 [label=\"a 1.000\"];"));
+        assert!(dot.contains("s1 -> s0 [label=\"b 0.500\"];"));
+        assert_eq!(gen.model_stats().transitions, dot.matches("->").count());
+
+        // quotes and backslashes are escaped for DOT, other characters kept as they are
+        let gen = Generator::new_custom(vec!["q\"\\\tn".to_string()], 1).unwrap();
+        let dot = gen.model_dot();
+        assert!(dot.contains("[label=\"\\\"\"];"), "{}", dot);
+        assert!(dot.contains("[label=\"\\\\\"];"), "{}", dot);
+        assert!(dot.contains("[label=\"\t\"];"), "{}", dot);
+        assert!(dot.contains("[label=\"n 1.000\"];"), "{}", dot);
+        assert!(!dot.contains("\\t") && !dot.contains("\\u"), "{}", dot);
+    }
+
+    #[test]
+    fn test_model_bytes() {
+        use model::ModelError;
+        let mut gen = Generator::new_custom(repeated_list(), 3).unwrap();
+        gen.prune(2).unwrap();
+        gen.salt_model(b"salt");
+        let bytes = gen.to_model_bytes();
+        let mut loaded = Generator::from_model_bytes(&bytes).unwrap();
+        assert_eq!(loaded.model_digest(), gen.model_digest());
+        assert_eq!(loaded.model_stats(), gen.model_stats());
+        assert_eq!(loaded.word_list_fingerprint(), gen.word_list_fingerprint());
+        assert_eq!(loaded.to_model_bytes(), bytes);
+        // the word lengths survive, unlike in the JSON form
+        gen.set_empirical_word_lengths(true);
+        loaded.set_empirical_word_lengths(true);
+        gen.reseed(5);
+        loaded.reseed(5);
+        for _ in 0..20 {
+            assert_eq!(
+                loaded.gen_from_pattern("W-w-dd"),
+                gen.gen_from_pattern("W-w-dd")
+            );
+        }
+
+        // models imported from transition counts record no word list
+        let imported = Generator::from_ngram_counts(vec![
+            ("".to_string(), "ab".to_string(), 3),
+            ("b".to_string(), "c".to_string(), 1),
+        ])
+        .unwrap();
+        let reloaded = Generator::from_model_bytes(&imported.to_model_bytes()).unwrap();
+        assert_eq!(reloaded.word_list_fingerprint(), None);
+        assert_eq!(reloaded.model_json(), imported.model_json());
+
+        // the depth is recorded, not guessed from the longest state
+        let mut deep = gen.to_model_bytes();
+        deep[8..12].copy_from_slice(&8u32.to_be_bytes());
+        assert_eq!(Generator::from_model_bytes(&deep).unwrap().depth, 8);
+        assert_eq!(
+            Generator::from_model_bytes(&bytes).unwrap().depth,
+            gen.depth
+        );
+
+        // states and tokens recorded twice are rejected, not overwritten
+        let distinct = Generator::from_ngram_counts(vec![
+            ("".to_string(), "x".to_string(), 1),
+            ("".to_string(), "y".to_string(), 1),
+            ("a".to_string(), "x".to_string(), 1),
+            ("b".to_string(), "x".to_string(), 2),
+        ])
+        .unwrap()
+        .to_model_bytes();
+        let replace = |from: &[u8], to: &[u8]| {
+            let at = distinct
+                .windows(from.len())
+                .position(|w| w == from)
+                .unwrap();
+            let mut bytes = distinct.clone();
+            bytes[at..at + to.len()].copy_from_slice(to);
+            Generator::from_model_bytes(&bytes).err()
+        };
+        assert_eq!(
+            replace(b"\0\0\0\x01b", b"\0\0\0\x01a"),
+            Some(ModelError::NotAModel)
+        );
+        assert_eq!(
+            replace(b"\0\0\0\x01y", b"\0\0\0\x01x"),
+            Some(ModelError::NotAModel)
+        );
+        assert!(replace(b"\0\0\0\x01b", b"\0\0\0\x01c").is_none());
+
+        assert_eq!(
+            Generator::from_model_bytes(b"").err(),
+            Some(ModelError::NotAModel)
+        );
+        assert_eq!(
+            Generator::from_model_bytes(&bytes[..bytes.len() - 1]).err(),
+            Some(ModelError::NotAModel)
+        );
+        let mut trailing = bytes.clone();
+        trailing.push(0);
+        assert_eq!(
+            Generator::from_model_bytes(&trailing).err(),
+            Some(ModelError::NotAModel)
+        );
+        let mut older = bytes.clone();
+        older[7] = 1;
+        assert_eq!(
+            Generator::from_model_bytes(&older).err(),
+            Some(ModelError::UnsupportedVersion(1))
+        );
+        let mut shallow = bytes.clone();
+        shallow[8..12].copy_from_slice(&[0; 4]);
+        assert_eq!(
+            Generator::from_model_bytes(&shallow).err(),
+            Some(ModelError::NotAModel)
+        );
+        // every truncation fails cleanly, never panics
+        for len in 0..bytes.len() {
+            assert!(Generator::from_model_bytes(&bytes[..len]).is_err());
+        }
+    }
+
+    #[test]
+    fn test_flat_model() {
+        use model::ModelError;
+        let mut gen = Generator::new_custom(word_list::cv::list(), 2).unwrap();
+        let flat = gen.to_flat_model().unwrap();
+        assert_eq!(flat.states(), gen.model_stats().states);
+        assert_eq!(flat.counts.len(), gen.model_stats().transitions);
+        assert_eq!(flat.state(0), "");
+        for s in 0..flat.states() {
+            let row = flat.row(s);
+            let total = flat.cumulative[row.end - 1];
+            assert_eq!(flat.counts[row.clone()].iter().sum::<u64>(), total);
+            let p: f32 = flat.probabilities[row].iter().sum();
+            assert!((p - 1.0).abs() < 1e-4);
+        }
+        let transitions: Vec<(String, String, usize)> = (0..flat.states())
+            .flat_map(|s| flat.row(s).map(move |t| (s, t)))
+            .map(|(s, t)| {
+                let token = flat.token(flat.next_token[t] as usize);
+                (
+                    flat.state(s).to_string(),
+                    token.to_string(),
+                    flat.counts[t] as usize,
+                )
+            })
+            .collect();
+        let expected: Vec<(String, String, usize)> = gen
+            .transitions()
+            .map(|(s, t, c)| (s.to_string(), t.to_string(), c))
+            .collect();
+        assert_eq!(transitions, expected);
+
+        let mut loaded = Generator::from_flat_model(&flat).unwrap();
+        assert_eq!(loaded.model_digest(), gen.model_digest());
+        assert_eq!(loaded.to_model_bytes(), gen.to_model_bytes());
+        gen.reseed(9);
+        loaded.reseed(9);
+        assert_eq!(
+            loaded.gen_from_pattern("w-c-w"),
+            gen.gen_from_pattern("w-c-w")
+        );
+
+        // pruned, salted and imported models survive as well
+        let mut pruned = Generator::new_custom(repeated_list(), 3).unwrap();
+        pruned.prune(2).unwrap();
+        pruned.salt_model(b"salt");
+        let reloaded = Generator::from_flat_model(&pruned.to_flat_model().unwrap()).unwrap();
+        assert_eq!(reloaded.to_model_bytes(), pruned.to_model_bytes());
+        let imported = Generator::from_ngram_counts(vec![
+            ("".to_string(), "ab".to_string(), 3),
+            ("b".to_string(), "c".to_string(), 1),
+        ])
+        .unwrap();
+        let flat = imported.to_flat_model().unwrap();
+        // the generator falls back to shorter contexts, down to the empty state
+        assert_eq!(flat.next_state, [1, 0]);
+        let reloaded = Generator::from_flat_model(&flat).unwrap();
+        assert_eq!(reloaded.model_json(), imported.model_json());
+
+        // states with an empty row are dropped, unless a transition leads to them
+        let mut empty_row = flat.clone();
+        empty_row.state_bytes.push(b'c');
+        empty_row.state_offsets.push(2);
+        empty_row.row_offsets.push(2);
+        let reloaded = Generator::from_flat_model(&empty_row).unwrap();
+        assert_eq!(reloaded.to_flat_model().unwrap(), flat);
+        empty_row.next_state[1] = 2;
+        assert_eq!(
+            Generator::from_flat_model(&empty_row).err(),
+            Some(ModelError::NotAModel)
+        );
+
+        // inconsistent arrays are refused without panicking
+        let corrupt = |edit: &dyn Fn(&mut model::FlatModel)| {
+            let mut bad = flat.clone();
+            edit(&mut bad);
+            Generator::from_flat_model(&bad).err()
+        };
+        let not_a_model = Some(ModelError::NotAModel);
+        assert_eq!(corrupt(&|f| f.cumulative[0] += 1), not_a_model);
+        assert_eq!(corrupt(&|f| f.counts[1] = 0), not_a_model);
+        assert_eq!(corrupt(&|f| f.next_token[0] = 99), not_a_model);
+        assert_eq!(corrupt(&|f| f.next_state[0] = 0), not_a_model);
+        assert_eq!(corrupt(&|f| f.row_offsets.truncate(1)), not_a_model);
+        assert_eq!(corrupt(&|f| f.state_offsets[1] = 100), not_a_model);
+        assert_eq!(corrupt(&|f| f.token_bytes[0] = 0xff), not_a_model);
+        assert_eq!(corrupt(&|f| f.depth = 0), not_a_model);
+        assert_eq!(
+            corrupt(&|f| f.word_lengths = vec![u64::MAX; 2]),
+            not_a_model
+        );
+        assert_eq!(corrupt(&|f| f.counts[0] = u64::MAX), not_a_model);
+        assert_eq!(corrupt(&|f| f.next_state[0] = -2), not_a_model);
+        // a state repeated is not silently merged
+        assert_eq!(
+            corrupt(&|f| {
+                f.state_bytes.clear();
+                f.state_offsets[2] = 0;
+            }),
+            not_a_model
+        );
+    }
+
+    #[test]
+    fn test_confirm() {
+        use confirm::{verify_typed, Mask};
+        let secret = "Dolce-vita-42!x";
+        assert_eq!(Mask::None.apply(secret), secret);
+        assert_eq!(Mask::Alternate.apply(secret), "Dolce-____-42!_");
+        assert_eq!(Mask::Initials.apply(secret), "D____-v___-4_!x");
+        assert_eq!(Mask::Full.apply(secret), "_".repeat(15));
+        let verify = |typed: &str, attempts| {
+            let mut prompts = Vec::new();
+            let ok = verify_typed(secret, &mut typed.as_bytes(), &mut prompts, attempts).unwrap();
+            (
+                ok,
+                String::from_utf8(prompts)
+                    .unwrap()
+                    .matches("retype")
+                    .count(),
+            )
+        };
+        assert_eq!(verify("Dolce-vita-42!x\r\n", 3), (true, 1));
+        assert_eq!(verify("dolce-vita-42!x\nDolce-vita-42!x", 3), (true, 2));
+        assert_eq!(verify("a\nb\nc\nDolce-vita-42!x\n", 3), (false, 3));
+        assert_eq!(verify(" Dolce-vita-42!x\n", 1), (false, 1));
+        assert_eq!(verify("", 3), (false, 1));
+    }
+
+    #[test]
+    fn test_hint_initials() {
+        let list = ["apple", "apricot", "banana", "cherry"]
+            .map(String::from)
+            .to_vec();
+        let mut gen = Generator::new_custom(list, 8).unwrap();
+        gen.set_entropy_floor(1.0);
+        let secret = gen.gen_annotated(&Pattern::parse("C-c"));
+        let hint = hint::initials(&gen, &secret);
+        // every token is one of the four words, two of them starting with a
+        let expected: f64 = hint
+            .text
+            .chars()
+            .map(|c| if c == 'a' { 1.0 } else { 2.0 })
+            .sum();
+        assert_eq!(
+            hint.text,
+            secret
+                .text
+                .to_lowercase()
+                .split('-')
+                .map(|w| &w[..1])
+                .collect::<String>()
+        );
+        assert!((hint.revealed - expected).abs() < 1e-9);
+        assert!((hint.remaining + hint.revealed - secret.entropy).abs() < 1e-9);
+        assert!(hint.warnings.contains(&hint::HintWarning::RevealsTooMuch {
+            share: hint.revealed / secret.entropy
+        }));
+        let mut gen = Generator::new_custom(word_list::cv::list(), 2).unwrap();
+        let secret = gen.gen_annotated(&Pattern::parse("dddddd"));
+        let hint = hint::initials(&gen, &secret);
+        assert_eq!((hint.text.as_str(), hint.revealed), ("", 0.0));
+        assert!(hint.warnings.contains(&hint::HintWarning::BelowFloor {
+            remaining: secret.entropy,
+            floor: DEFAULT_ENTROPY_FLOOR
+        }));
+    }
+
+    #[cfg(feature = "wordlist-pos")]
+    #[test]
+    fn test_mnemonic_sentence() {
+        use story::mnemonic_sentence;
+        let mnemonic = mnemonic_sentence(&["shy", "otter", "juggles", "ripe", "mango"]);
+        assert_eq!(mnemonic.sentence, "the shy otter juggles a ripe mango");
+        assert_eq!(mnemonic.template, "the <1> <2> <3> a <4> <5>");
+        let mnemonic = mnemonic_sentence(&["Juggles", "greets", "shy", "otter", "mango", "ripe"]);
+        assert_eq!(
+            mnemonic.sentence,
+            "someone juggles and greets the shy otter meets a mango follows a ripe one"
+        );
+        assert_eq!(
+            mnemonic_sentence(&["zorbix", "otter"]).template,
+            "the <1> meets an <2>"
+        );
+        assert_eq!(mnemonic_sentence(&[]).sentence, "");
+        let mut gen = Generator::new();
+        let (passphrase, _) = gen.gen_from_pattern(story::LONG);
+        let words: Vec<&str> = passphrase.split('-').collect();
+        let mnemonic = mnemonic_sentence(&words);
+        assert_eq!(mnemonic.sentence.split(' ').count(), 7);
+        let mut filled = mnemonic.template.clone();
+        for (i, word) in words.iter().enumerate() {
+            filled = filled.replace(&format!("<{}>", i + 1), word);
+        }
+        assert_eq!(filled, mnemonic.sentence);
+    }
+
+    #[test]
+    fn test_locale_casing() {
+        use casing::Locale;
+        assert_eq!(Locale::Turkic.capitalize("ırmak"), "Irmak");
+        assert_eq!(Locale::Turkic.lowercase("IRMAK İĞNE"), "ırmak iğne");
+        assert_eq!(Locale::German.capitalize("ßeta"), "ẞeta");
+        assert_eq!(Locale::Root.capitalize("ßeta"), "SSeta");
+        assert_eq!(Locale::Root.capitalize(""), "");
+        assert_eq!(Locale::from_language("AZ"), Locale::Turkic);
+        assert_eq!(Locale::from_language("de_CH"), Locale::German);
+        assert_eq!(Locale::from_language("it"), Locale::Root);
+        let list = ["ılık", "ısı", "ıtır", "iğne", "inek", "irmik"]
+            .map(String::from)
+            .to_vec();
+        let mut gen = Generator::new_custom(list, 1).unwrap();
+        gen.set_locale(Locale::Turkic);
+        for seed in 0..20 {
+            gen.reseed(seed);
+            let (lower, h_lower) = gen.gen_from_pattern("w-c");
+            gen.reseed(seed);
+            let (upper, h_upper) = gen.gen_from_pattern("W-C");
+            assert_eq!(Locale::Turkic.lowercase(&upper), lower);
+            assert!(upper.starts_with(['I', 'İ']), "{}", upper);
+            assert!((h_upper - h_lower).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_non_latin_scripts() {
+        let lists = [
+            vec!["كتاب", "قلم", "بيت", "شمس", "قمر"],
+            vec!["שלום", "בית", "ספר", "ילד", "מים"],
+            vec!["θάλασσα", "ήλιος", "σπίτι", "νερό", "βιβλίο"],
+            vec!["кошка", "собака", "молоко", "дерево", "ёлка"],
+        ];
+        for list in lists {
+            let list: Vec<String> = list.into_iter().map(String::from).collect();
+            let mut gen = Generator::new_custom(list, 2).unwrap();
+            gen.set_exact_entropy(true);
+            for pattern in ["W·ж·w-dd", "C-c-s"] {
+                gen.reseed(7);
+                let (upper, h) = gen.gen_from_pattern(pattern);
+                gen.reseed(7);
+                let (lower, _) = gen.gen_from_pattern(&pattern.to_lowercase());
+                assert_eq!(upper.to_lowercase(), lower);
+                let exact = gen.exact_entropy(&Pattern::parse(pattern), &upper).unwrap();
+                assert!(exact >= h - 1e-9, "{} {} {}", upper, exact, h);
+            }
+        }
+        assert!(script::is_rtl('ש') && script::is_rtl('ك') && !script::is_rtl('ж'));
+        let shown = script::isolate_rtl("ab-שלום-בית-12-קמ");
+        assert_eq!(
+            shown,
+            "ab-\u{2067}שלום\u{2069}-\u{2067}בית\u{2069}-12-\u{2067}קמ\u{2069}"
+        );
+        assert_eq!(script::strip_isolates(&shown), "ab-שלום-בית-12-קמ");
+    }
+
+    #[cfg(feature = "transliterate")]
+    #[test]
+    fn test_transliteration() {
+        use translit::transliterate;
+        assert_eq!(transliterate("Щука-Эхо-ёж"), "Shhuka-Ehxo-yozh");
+        assert_eq!(
+            transliterate("Straße-perché-Θάλασσα"),
+            "Strasse-perche-Thalassa"
+        );
+        assert_eq!(transliterate("漢字-42"), "漢字-42");
+        let injective = |list: &[&str], pattern: &str| {
+            let list = list.iter().map(|w| w.to_string()).collect();
+            Generator::new_custom(list, 2)
+                .unwrap()
+                .transliteration_is_injective(&Pattern::parse(pattern))
+        };
+        assert!(injective(&["кошка", "бобровь", "съел", "шум"], "W-w-dd"));
+        assert!(injective(&["abc", "def"], "W-w-dd"));
+        assert!(!injective(&["perche", "perché"], "w-w"));
+        assert!(!injective(&["ήλιος", "ώρα"], "w-w"));
+        // literal x is also the transliteration of х
+        assert!(!injective(&["хлеб", "ёж"], "w \\x w"));
+        // с followed by the literal h and ш both become sh
+        assert!(!injective(&["сом", "шум"], "whw"));
+    }
+
+    #[test]
+    fn test_encode_uuid() {
+        let uuid = encode::parse_uuid("5F0C6A1E8D554C579A530D6F1EF07C11").unwrap();
+        assert_eq!(
+            encode::format_uuid(uuid),
+            "5f0c6a1e-8d55-4c57-9a53-0d6f1ef07c11"
+        );
+        let mut rng = ChaCha8Rng::seed_from_u64(0x737);
+        for uuid in [0, 1, 999, 1000, u128::MAX, u128::MAX - 1]
+            .into_iter()
+            .chain((0..500).map(|_| rng.gen()))
+        {
+            let alias = encode::encode_uuid(uuid);
+            assert_eq!(encode::decode(&alias), Ok(uuid));
+            assert_eq!(encode::decode(&alias.to_uppercase()), Ok(uuid));
+        }
+        assert_eq!(
+            encode::encode_uuid(0),
+            "qaqaqaqa-qaqaqaqa-qaqaqaqa-qaqaqaqa-000"
+        );
+        for invalid in [
+            "",
+            "qaqaqaqa-qaqaqaqa-qaqaqaqa-qaqaqaqa",
+            "qaqaqaqa-qaqaqaqa-qaqaqaqa-qaqaqaqa-00",
+            "qaqaqaqa-qaqaqaqa-qaqaqaqa-qaqaqaqa-0000",
+            "qaqaqaqa-qaqaqaqa-qaqaqaqa-qaqaqaq-000",
+            "qaqaqaqa-qaqaqaqa-qaqaqaqa-qaqaqaqy-000",
+            "umumumum-umumumum-umumumum-umumumum-999",
+            "qaqaqaqa-qaqaqaqa-qaqaqaqa-qaqaqaqä-000",
+        ] {
+            assert!(encode::decode(invalid).is_err(), "{}", invalid);
+        }
+        assert!(encode::parse_uuid("5f0c6a1e-8d554c57-9a53-0d6f1ef07c11").is_err());
+    }
+
+    #[test]
+    fn test_encode_bytes() {
+        use encode::*;
+        assert_eq!(encode_proquint(&[127, 0, 0, 1]).unwrap(), "lusab-babad");
+        assert_eq!(encode_proquint(&[63, 84, 220, 193]).unwrap(), "gutih-tugad");
+        assert_eq!(encode_proquint(&[1]), None);
+        assert_eq!(decode_proquint("Lusab-Babad").unwrap(), [127, 0, 0, 1]);
+        assert!(decode_proquint("lusab-babe").is_err());
+        assert!(decode_proquint("lusab-babax").is_err());
+        for (bytes, encoded) in [
+            ("", "xexax"),
+            ("1234567890", "xesef-disof-gytuf-katof-movif-baxux"),
+            ("Pineapple", "xigak-nyryk-humil-bosek-sonax"),
+        ] {
+            assert_eq!(encode_bubble_babble(bytes.as_bytes()), encoded);
+            assert_eq!(decode_bubble_babble(encoded).unwrap(), bytes.as_bytes());
+        }
+        assert!(decode_bubble_babble("xigak-nyryk-humil-bosek-sonux").is_err());
+        assert!(decode_bubble_babble("xigak-nyryk-humil-bosek").is_err());
+        let mut rng = ChaCha8Rng::seed_from_u64(0x738);
+        for len in 0..40 {
+            let bytes: Vec<u8> = (0..len).map(|_| rng.gen()).collect();
+            assert_eq!(
+                decode_bubble_babble(&encode_bubble_babble(&bytes)).unwrap(),
+                bytes
+            );
+            if let Some(quints) = encode_proquint(&bytes) {
+                assert_eq!(decode_proquint(&quints).unwrap(), bytes);
+            }
+        }
+        assert_eq!(parse_hex("DE:ad be01").unwrap(), [0xde, 0xad, 0xbe, 0x01]);
+        assert_eq!(format_hex(&[0xde, 0xad, 0x01]), "dead01");
+        assert!(parse_hex("abc").is_err());
+    }
+
+    #[test]
+    fn test_humanize_digest() {
+        use encode::*;
+        let phrase = humanize_digest(b"Pineapple", 4);
+        assert_eq!(phrase, "soca-teon-reif-agic");
+        assert_eq!(humanize_digest(b"Pineapple", 6)[..phrase.len()], phrase);
+        assert_ne!(humanize_digest(b"Pineapplf", 4), phrase);
+        assert_eq!(humanize_digest(&[], 0), "");
+        assert!((humanized_entropy(4) - 60.5).abs() < 0.1);
+        assert_eq!(digest_collision_probability(4, 1), 0.0);
+        let p = digest_collision_probability(2, 36100);
+        assert!(p > 0.3 && p < 0.5, "{}", p);
+    }
+
+    #[test]
+    fn test_compare_phrases() {
+        use encode::{compare_phrases, WordDiff};
+        let same = |w: &str| WordDiff::Same(w.to_string());
+        let phrase = "soca-teon-reif-agic";
+        assert!(compare_phrases(phrase, "SOCA, teon; reif agic")
+            .iter()
+            .all(|w| !w.differs()));
+        assert_eq!(
+            compare_phrases(phrase, "soca reif agic"),
+            [
+                same("soca"),
+                WordDiff::OnlyFirst("teon".to_string()),
+                same("reif"),
+                same("agic")
+            ]
+        );
+        assert_eq!(
+            compare_phrases(phrase, "soca tean raif agic extra"),
+            [
+                same("soca"),
+                WordDiff::Changed {
+                    first: "teon".to_string(),
+                    second: "tean".to_string()
+                },
+                WordDiff::Changed {
+                    first: "reif".to_string(),
+                    second: "raif".to_string()
+                },
+                same("agic"),
+                WordDiff::OnlySecond("extra".to_string())
+            ]
+        );
+        assert!(compare_phrases("", "").is_empty());
+    }
+
+    #[test]
+    fn test_throttle() {
+        use std::time::Duration;
+        use throttle::Throttle;
+        let secs = Duration::from_secs;
+        let start = Instant::now();
+        let mut bucket = Throttle::new(2, secs(10));
+        bucket.set_lockouts(Vec::new());
+        assert_eq!(bucket.check(start, || false), Ok(false));
+        assert_eq!(bucket.check(start + secs(1), || false), Ok(false));
+        assert_eq!(
+            bucket.attempt(start + secs(4)).unwrap_err().retry_after,
+            secs(6)
+        );
+        assert!(bucket.attempt(start + secs(10)).is_ok());
+        assert_eq!(
+            bucket.attempt(start + secs(19)).unwrap_err().retry_after,
+            secs(1)
+        );
+        assert!(bucket.attempt(start + secs(20)).is_ok());
+        assert_eq!(bucket.failures(), 2);
+
+        let mut lockouts = Throttle::new(1, Duration::ZERO);
+        lockouts.set_lockouts(vec![secs(1), secs(2)]);
+        let mut now = start;
+        for lockout in [1, 2, 2] {
+            assert_eq!(lockouts.check(now, || false), Ok(false));
+            assert_eq!(
+                lockouts.attempt(now).unwrap_err().retry_after,
+                secs(lockout)
+            );
+            now += secs(lockout);
+        }
+        let mut called = false;
+        assert!(lockouts
+            .check(now, || {
+                called = true;
+                true
+            })
+            .unwrap());
+        assert!(called);
+        assert_eq!(lockouts.failures(), 0);
+        assert_eq!(lockouts.check(now, || false), Ok(false));
+        assert_eq!(lockouts.attempt(now).unwrap_err().retry_after, secs(1));
+    }
+
+    #[test]
+    fn test_numerals() {
+        use numerals::{ordinal, roman};
+        let romans: Vec<String> = (1..=numerals::MAX_ROMAN)
+            .map(|n| roman(n).unwrap())
+            .collect();
+        assert_eq!(romans[..9].join(" "), "I II III IV V VI VII VIII IX");
+        assert_eq!(romans[3998], "MMMCMXCIX");
+        assert_eq!(
+            romans
+                .iter()
+                .collect::<std::collections::HashSet<_>>()
+                .len(),
+            3999
+        );
+        assert_eq!((roman(0), roman(4000)), (None, None));
+        assert_eq!(ordinal(12).unwrap(), "twelfth");
+        assert_eq!(ordinal(40).unwrap(), "fortieth");
+        assert_eq!(ordinal(99).unwrap(), "ninety-ninth");
+        assert_eq!((ordinal(0), ordinal(100)), (None, None));
+
+        assert_eq!(
+            Pattern::parse("{roman:0-5000}{ordinal}{roman:3-2}{roman:4000-5000}").segments(),
+            &[
+                Segment::Roman { min: 0, max: 5000 },
+                Segment::Ordinal { min: 1, max: 99 },
+                Segment::Literal("{roman:3-2}{roman:4000-5000}".to_string()),
+            ]
+        );
+        // bounds beyond the supported numbers are refused by the fallible methods
+        let mut generator = Generator::new();
+        let wide = Pattern::parse("{ordinal:1-9}-[w|{ordinal:0-120}]");
+        assert_eq!(wide.numeral_out_of_range(), Some((0, 120, 99)));
+        assert_eq!(
+            Pattern::parse("{roman}-{ordinal:2-9}").numeral_out_of_range(),
+            None
+        );
+        assert_eq!(
+            generator.try_gen_from_pattern("{roman:1-5000}"),
+            Err(Error::NumeralOutOfRange {
+                min: 1,
+                max: 5000,
+                largest: 3999
+            })
+        );
+        assert_eq!(
+            generator.parse_limited("{ordinal:0-9}", &limits::PatternLimits::default()),
+            Err(limits::LimitError::NumeralOutOfRange {
+                min: 0,
+                max: 9,
+                largest: 99
+            })
+        );
+        // and lowered when generating
+        let (_, entropy) = generator.gen_from_pattern("{ordinal:90-150}");
+        assert!((entropy - 10f64.log2()).abs() < 1e-9);
+        let pattern = Pattern::parse("{roman:1-8}-{ordinal:1-4}");
+        let (text, entropy) = generator.gen_from_parsed(&pattern);
+        assert_eq!(entropy, 5.0);
+        assert_eq!(pattern.expected_entropy(&generator), Some(5.0));
+        assert!((generator.exact_entropy(&pattern, &text).unwrap() - 5.0).abs() < 1e-9);
+        // I, II and III can be read in several ways when written together
+        let adjacent = Pattern::parse("{roman:1-3}{roman:1-3}");
+        let exact = generator.exact_entropy(&adjacent, "III").unwrap();
+        assert!((exact - (9.0f64 / 2.0).log2()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_sequence() {
+        assert_eq!(
+            Pattern::parse("{seq}{seq:007}").segments(),
+            &[
+                Segment::Sequence { start: 1, width: 1 },
+                Segment::Sequence { start: 7, width: 3 },
+            ]
+        );
+        let mut generator = Generator::new();
+        generator.reseed(0x5E9);
+        let batch = generator.gen_batch("w-{seq:98}", 3);
+        let numbers: Vec<&str> = batch.iter().map(|(s, _)| &s[s.len() - 3..]).collect();
+        assert_eq!(numbers, ["-98", "-99", "100"]);
+        let plain = generator.gen_from_pattern("w").1;
+        assert!(batch.iter().all(|(_, h)| (h - plain).abs() < 8.0));
+
+        let pattern = Pattern::parse("\\srv{seq:01}d");
+        generator.set_sequence(4);
+        let (text, entropy) = generator.gen_from_parsed(&pattern);
+        assert!(text.starts_with("srv05"));
+        assert!((entropy - 10f64.log2()).abs() < 1e-9);
+        assert!((generator.exact_entropy(&pattern, &text).unwrap() - entropy).abs() < 1e-9);
+        assert_eq!(pattern.expected_entropy(&generator), Some(entropy));
+
+        let codes = generator.gen_batch_distinct("{seq}-cc", 5, 1).unwrap();
+        for (i, (code, _)) in codes.iter().enumerate() {
+            assert!(code.starts_with(&format!("{}-", i + 1)));
+        }
+    }
+
+    #[test]
+    fn test_expand_variables() {
+        use pattern::{expand_variables, MissingVariable};
+        let lookup = |source: &str, name: &str| match (source, name) {
+            ("arg", "site") => Some("w{seq}\\".to_string()),
+            ("env", "TEAM") => Some("ops".to_string()),
+            _ => None,
+        };
+        let expanded = expand_variables("{env:TEAM}-{arg:site}-w-\\{arg:site}-{adj}", lookup);
+        assert_eq!(
+            expanded.unwrap(),
+            "op\\s-\\w\\{\\seq}\\\\-w-\\{arg:site}-{adj}"
+        );
+        let mut generator = Generator::new();
+        let pattern = expand_variables("{arg:site}{env:TEAM}", lookup).unwrap();
+        assert_eq!(
+            generator.gen_from_pattern(&pattern),
+            ("w{seq}\\ops".to_string(), 0.0)
+        );
+        assert_eq!(
+            expand_variables("a{env:HOME}{arg:missing}", |s, _| (s == "env")
+                .then(String::new)),
+            Err(MissingVariable {
+                source: "arg".to_string(),
+                name: "missing".to_string()
+            })
+        );
+        assert_eq!(
+            expand_variables("é{env:}", |_, _| Some("ü".into())).unwrap(),
+            "éü"
+        );
+    }
+
+    #[test]
+    fn test_manifest() {
+        use manifest::Manifest;
+        let mut generator = Generator::new_custom(word_list::cv::list(), 2).unwrap();
+        let digest = generator.model_digest();
+        assert_eq!(digest.len(), 64);
+        assert_eq!(generator.model_digest(), digest);
+        let other = Generator::new_custom(vec!["cryptirust".to_string()], 2).unwrap();
+        assert_ne!(other.model_digest(), digest);
+
+        let mut manifest = Manifest::new(&generator, "w \"quoted\"", 4);
+        assert_eq!(
+            manifest.to_json(),
+            format!(
+                "{{\"crate_version\":\"{}\",\"algorithm_version\":2,\"model_digest\":\"{}\",\
+                 \"word_list\":\"{}\",\"depth\":2,\"pattern\":\"w \\\"quoted\\\"\",\"policy\":{{}},\"arguments\":[],\
+                 \"seeding\":null,\"requested\":4,\"generated\":0,\"entropy\":null}}",
+                manifest::CRATE_VERSION,
+                digest,
+                word_list::fingerprint(&word_list::cv::list())
+            )
+        );
+        manifest.add_policy("min-distance", "3");
+        for entropy in [10.0, 20.0, 33.0] {
+            manifest.record(entropy);
+        }
+        let json = manifest.to_json();
+        assert!(json.contains("\"policy\":{\"min-distance\":\"3\"}"));
+        assert!(json.ends_with(
+            "\"generated\":3,\"entropy\":{\"min\":10.00,\"mean\":21.00,\"max\":33.00}}"
+        ));
+        assert!(Manifest::from_json(&json).unwrap().seeding.is_none());
+
+        // seeded runs record what is needed to reproduce them, and parse back
+        manifest.arguments = vec!["-n".to_string(), "4".to_string(), "--seed".to_string()];
+        manifest.seeding = Some(manifest::Seeding {
+            salt: "00ff".to_string(),
+            iterations: 1000,
+            check: "\"ab\"".to_string(),
+        });
+        let json = manifest.to_json();
+        assert!(json.contains(
+            "\"arguments\":[\"-n\",\"4\",\"--seed\"],\
+             \"seeding\":{\"salt\":\"00ff\",\"iterations\":1000,\"check\":\"\\\"ab\\\"\"}"
+        ));
+        let parsed = Manifest::from_json(&json).unwrap();
+        assert!(parsed.entropies.is_empty());
+        assert_eq!(
+            parsed,
+            Manifest {
+                entropies: Vec::new(),
+                ..manifest.clone()
+            }
+        );
+        assert!(Manifest::from_json("{\"depth\":2}").is_err());
+        assert!(Manifest::from_json(&"[".repeat(1 << 20)).is_err());
+        assert!(Manifest::from_json(&json.replace("1000", "\"1000\"")).is_err());
+        // every transition of cv is observed once: pruning would leave no word
+        assert_eq!(generator.prune(2), Err(Error::EmptyModel { min_count: 2 }));
+        assert_eq!(generator.model_digest(), digest);
+        let mut pruned = Generator::new_custom(repeated_list(), 2).unwrap();
+        let digest = pruned.model_digest();
+        let stats = pruned.prune(2).unwrap();
+        assert!(stats.after.transitions < stats.before.transitions);
+        assert_ne!(pruned.model_digest(), digest);
+    }
+
+    #[test]
+    fn test_namespace() {
+        use namespace::NamespaceGenerator;
+        let namespace = |key: &[u8]| {
+            let generator = Generator::new_custom(word_list::cv::list(), 3).unwrap();
+            NamespaceGenerator::with_generator(key, generator)
+        };
+        let mut first = namespace(b"key");
+        let mut second = namespace(b"key");
+        let mut other = namespace(b"other key");
+        let alias = first.alias("user-1").unwrap();
+        assert_eq!(first.alias("user-1").unwrap(), alias);
+        assert_eq!(second.alias("user-1").unwrap(), alias);
+        assert_ne!(other.alias("user-1").unwrap(), alias);
+        assert_ne!(first.alias("user-2").unwrap(), alias);
+        assert_eq!(first.label(&alias), Some("user-1"));
+        assert!(first.collision_probability(1).unwrap() == 0.0);
+        assert!(first.collision_probability(1 << 30).unwrap() > 0.5);
+
+        // the birthday bound uses the collision entropy, which matches the rate of equal pairs
+        let gen = Generator::new_custom(word_list::cv::list(), 3).unwrap();
+        let words = Pattern::parse("w");
+        let h2 = words.collision_entropy(&gen).unwrap();
+        assert!(h2 < words.expected_entropy(&gen).unwrap());
+        let mut sampler = Generator::new_custom(word_list::cv::list(), 3).unwrap();
+        sampler.reseed(36);
+        let n = 20_000u64;
+        let mut counts = HashMap::new();
+        for _ in 0..n {
+            *counts
+                .entry(sampler.gen_from_parsed(&words).0)
+                .or_insert(0u64) += 1;
+        }
+        let equal: u64 = counts.values().map(|c| c * (c - 1) / 2).sum();
+        let measured = -(equal as f64 / (n * (n - 1) / 2) as f64).log2();
+        assert!((measured - h2).abs() < 0.3, "{} {}", measured, h2);
+        let mut tiny = namespace(b"key");
+        tiny.set_pattern("w");
+        let expected = batch::collision_probability(h2, 1000);
+        assert_eq!(tiny.collision_probability(1000), Some(expected));
+
+        // ten labels for ten digits: collisions must be resolved
+        tiny.set_pattern("d");
+        let labels: Vec<String> = (0..10).map(|i| format!("label {}", i)).collect();
+        let mut aliases: Vec<String> = labels.iter().map(|l| tiny.alias(l).unwrap()).collect();
+        aliases.sort();
+        aliases.dedup();
+        assert_eq!(aliases.len(), 10);
+        assert_eq!(
+            tiny.alias("label 10"),
+            Err(Error::AliasesExhausted { issued: 10 })
+        );
+        assert!(tiny.collision_probability(10).unwrap() > 0.9);
+
+        let mut restored = namespace(b"key");
+        restored.restore([("user-2", alias.as_str())]);
+        assert_eq!(restored.alias("user-2").unwrap(), alias);
+        assert_ne!(restored.alias("user-1").unwrap(), alias);
+    }
+
+    #[test]
+    fn test_pattern_limits() {
+        use limits::{LimitError, PatternLimits};
+        let gen = Generator::new_custom(word_list::cv::list(), 2).unwrap();
+        let limits = PatternLimits {
+            max_length: 16,
+            max_elements: 6,
+            max_floors: 1,
+            max_expansion: 24,
+        };
+        let pattern = gen.parse_limited("W-w-dd", &limits).unwrap();
+        assert_eq!(pattern, Pattern::parse("W-w-dd"));
+        assert_eq!(
+            gen.parse_limited("w-w-w-w-w-w-w-w-w", &limits),
+            Err(LimitError::PatternTooLong {
+                length: 17,
+                max: 16
+            })
+        );
+        assert_eq!(
+            gen.parse_limited("ddddddd", &limits),
+            Err(LimitError::TooManyElements {
+                elements: 7,
+                max: 6
+            })
+        );
+        assert_eq!(
+            gen.parse_limited("w{>=4}w{>=4}", &limits),
+            Err(LimitError::TooManyFloors { floors: 2, max: 1 })
+        );
+        assert_eq!(
+            gen.parse_limited("w-w-w", &limits),
+            Err(LimitError::ExpansionTooLarge {
+                length: 26,
+                max: 24
+            })
+        );
+        assert!(gen.parse_limited("{roman:1-9}-dd", &limits).is_ok());
+        let err = gen.parse_limited(&"\\x".repeat(150), &PatternLimits::default());
+        assert_eq!(
+            err.unwrap_err().to_string(),
+            "the pattern is 300 bytes long, more than the limit of 256"
+        );
+    }
+
+    #[test]
+    fn test_untrusted_pattern() {
+        use limits::LimitError;
+        use untrusted::{UntrustedPattern, UNTRUSTED_LIMITS};
+        let mut gen = Generator::new_custom(word_list::cv::list(), 2).unwrap();
+        let pattern = UntrustedPattern::parse(&gen, "W-w-\\{env:y}-dd").unwrap();
+        assert_eq!(pattern.pattern(), &Pattern::parse("W-w-\\{env:y}-dd"));
+        gen.reseed(0x749);
+        let (secret, entropy) = gen.gen_untrusted(&pattern);
+        gen.reseed(0x749);
+        assert_eq!(gen.gen_from_pattern("W-w-\\{env:y}-dd"), (secret, entropy));
+
+        assert_eq!(
+            UntrustedPattern::parse(&gen, "w-{arg:user}"),
+            Err(LimitError::Substitution {
+                source: "arg".to_string(),
+                name: "user".to_string()
+            })
+        );
+        assert_eq!(
+            UntrustedPattern::parse(&gen, "w{>=4}"),
+            Err(LimitError::TooManyFloors { floors: 1, max: 0 })
+        );
+        let long = "{env:y}".repeat(UNTRUSTED_LIMITS.max_length);
+        assert!(matches!(
+            UntrustedPattern::parse(&gen, &long),
+            Err(LimitError::PatternTooLong { .. })
+        ));
+        assert!(matches!(
+            UntrustedPattern::parse(&gen, &"w".repeat(40)),
+            Err(LimitError::TooManyElements { .. })
+        ));
+        assert!(matches!(
+            UntrustedPattern::parse(&gen, &"w-".repeat(31)),
+            Err(LimitError::ExpansionTooLarge { .. })
+        ));
+    }
+
+    #[test]
+    fn test_builder() {
+        use builder::{BuildError, GeneratorBuilder};
+        let build = || {
+            GeneratorBuilder::new()
+                .wordlist(word_list::cv::list())
+                .depth(2)
+                .symbols("+-")
+                .digits("01")
+        };
+        let mut gen = build().word_length(5).rng_seed(0x751).build().unwrap();
+        let (secret, entropy) = gen.gen_from_pattern("w-s-dd");
+        let mut expected = Generator::new_custom(word_list::cv::list(), 2).unwrap();
+        expected.set_symbols("+-").unwrap();
+        expected.set_digits("01").unwrap();
+        expected.set_word_length(5).unwrap();
+        expected.reseed(0x751);
+        assert_eq!(
+            expected.gen_from_pattern("w-s-dd"),
+            (secret.clone(), entropy)
+        );
+        let (_, digits) = secret.rsplit_once("-").unwrap();
+        assert!(digits.chars().all(|c| c == '0' || c == '1'));
+        assert_eq!(
+            gen.word_list_fingerprint(),
+            expected.word_list_fingerprint()
+        );
+
+        assert_eq!(build().depth(0).build().err(), Some(BuildError::ZeroDepth));
+        assert_eq!(
+            build().symbols("").build().err(),
+            Some(BuildError::EmptySymbols)
+        );
+        assert_eq!(
+            build().digits("").build().err(),
+            Some(BuildError::EmptyDigits)
+        );
+        assert_eq!(
+            build().word_length(0).build().err(),
+            Some(BuildError::ZeroWordLength)
+        );
+        assert_eq!(expected.set_word_length(0), Err(Error::ZeroWordLength));
+        assert_eq!(
+            build().wordlist(vec![" ".to_string()]).build().err(),
+            Some(BuildError::EmptyWordList)
+        );
+
+        // the date-safe groups only apply to the decimal digits
+        let mut binary = build().build().unwrap();
+        binary.set_date_safe_digits(true);
+        assert_eq!(binary.gen_from_pattern("dddd").1, 4.0);
+    }
+
+    #[test]
+    fn test_degenerate_lists() {
+        let one_word = vec!["cryptirust".to_string()];
+        let mut gen = Generator::new_custom(one_word, 3).unwrap();
+        let (_, entropy) = gen.gen_from_pattern("W-w-c");
+        assert_eq!(entropy, 0.0);
+        assert_eq!(gen.max_achievable_entropy("W-w-c"), Some(0.0));
+        let max = gen.max_achievable_entropy("w-dd-s").unwrap();
+        assert!((max - (100.0 * SYMBOLS.len() as f64).log2()).abs() < 1e-9);
+        assert!(matches!(
+            gen.try_gen_from_pattern("w-w-w"),
+            Err(Error::WeakSecret { entropy, .. }) if entropy == 0.0
+        ));
+        assert!(gen.gen_batch_distinct("w-w", 2, 1).is_err());
+
+        // one character: texts differ only by their length
+        let unary: Vec<String> = ["a", "aa", "aaa"].iter().map(|w| w.to_string()).collect();
+        let mut gen = Generator::new_custom(unary, 2).unwrap();
+        for pattern in ["w", "w-w", "ww"] {
+            let (password, entropy) = gen.gen_from_pattern(pattern);
+            let exact = gen
+                .exact_entropy(&Pattern::parse(pattern), &password)
+                .unwrap();
+            assert_eq!(entropy.to_bits(), exact.to_bits());
+        }
+        let (_, entropy) = gen.gen_from_pattern("c");
+        assert!(entropy > 0.0);
+
+        let gen = Generator::new_custom(word_list::cv::list(), 2).unwrap();
+        let pattern = Pattern::parse("w-w-dd");
+        let max = gen.max_achievable_entropy("w-w-dd").unwrap();
+        assert!(max > pattern.expected_entropy(&gen).unwrap());
+        let mut sampler = Generator::new_custom(word_list::cv::list(), 2).unwrap();
+        sampler.reseed(0x747);
+        for _ in 0..200 {
+            assert!(sampler.gen_from_parsed(&pattern).1 <= max + 1e-9);
+        }
+        assert_eq!(gen.max_achievable_entropy("w{>=8}"), None);
+        assert_eq!(
+            Generator::new_custom(vec![" ".to_string()], 2).map(|_| ()),
+            None
+        );
+
+        // no secret is weaker than the minimum, floors and alternations included
+        for pattern in ["w-w-dd", "W{>=6}c", "[w|dd]-s"] {
+            let min = gen.min_achievable_entropy(pattern).unwrap();
+            let parsed = Pattern::parse(pattern);
+            let mut lowest = f64::INFINITY;
+            for _ in 0..300 {
+                lowest = lowest.min(sampler.gen_from_parsed(&parsed).1);
+            }
+            assert!(min > 0.0 && min <= lowest + 1e-9, "{}: {}", pattern, min);
+        }
+        let min = gen.min_achievable_entropy("w-w-dd").unwrap();
+        assert!(min < pattern.expected_entropy(&gen).unwrap());
+
+        // the floor applies to the weakest secret of the pattern, whatever the draw
+        sampler.set_entropy_floor(min + 0.5);
+        for _ in 0..20 {
+            assert_eq!(
+                sampler.try_gen_from_pattern("w-w-dd"),
+                Err(Error::WeakSecret {
+                    entropy: min,
+                    floor: min + 0.5
+                })
+            );
+        }
+        sampler.set_entropy_floor(min);
+        assert!(sampler.try_gen_from_pattern("w-w-dd").is_ok());
+        sampler.set_entropy_floor(f64::NAN);
+        assert!(sampler.try_gen_from_pattern("w-w-dd").is_err());
+    }
+
+    #[test]
+    fn test_weakest_secret_cache() {
+        // the weakest secret is found once, then every call costs about as much as a draw
+        let mut gen = Generator::new();
+        let started = Instant::now();
+        assert!(gen.try_gen_from_pattern("w-w-w-w-dd").is_ok());
+        let first = started.elapsed();
+        let started = Instant::now();
+        for _ in 0..100 {
+            assert!(gen.try_gen_from_pattern("w-w-w-w-dd").is_ok());
+        }
+        assert!(
+            started.elapsed() < first * 10,
+            "{:?} {:?}",
+            started.elapsed(),
+            first
+        );
+
+        // settings the weakest secret depends on discard it
+        let min = gen.min_achievable_entropy("w-dd").unwrap();
+        gen.set_entropy_floor(min);
+        assert!(gen.try_gen_from_pattern("w-dd").is_ok());
+        gen.set_word_length(2).unwrap();
+        assert!(matches!(
+            gen.try_gen_from_pattern("w-dd"),
+            Err(Error::WeakSecret { .. })
+        ));
+        gen.set_word_length(8).unwrap();
+        assert!(gen.try_gen_from_pattern("w-dd").is_ok());
+        gen.set_dictionary((0..4).map(|i| i.to_string()).collect());
+        gen.set_entropy_floor(gen.min_achievable_entropy("w-x").unwrap());
+        assert!(gen.try_gen_from_pattern("w-x").is_ok());
+        gen.set_dictionary(vec!["alpha".to_string(), "beta".to_string()]);
+        assert!(matches!(
+            gen.try_gen_from_pattern("w-x"),
+            Err(Error::WeakSecret { .. })
+        ));
+
+        // a pattern whose weakest secret is too long to find is refused, without walking
+        // again at every call
+        let mut deep = Generator::new();
+        deep.set_empirical_word_lengths(true);
+        let words = "w".repeat(200);
+        let too_complex = Err(Error::PatternTooComplex {
+            max_steps: pattern::MAX_WALK_STEPS,
+        });
+        let started = Instant::now();
+        assert_eq!(deep.try_gen_from_pattern(&words), too_complex);
+        let first = started.elapsed();
+        let started = Instant::now();
+        assert_eq!(deep.try_gen_from_pattern(&words), too_complex);
+        assert!(
+            started.elapsed() * 10 < first,
+            "{:?} {:?}",
+            started.elapsed(),
+            first
+        );
+    }
+
+    #[test]
+    fn test_word_list_fingerprint() {
+        let list = word_list::cv::list();
+        let fingerprint = word_list::fingerprint(&list);
+        assert_eq!(fingerprint.len(), 64);
+        assert_eq!(
+            word_list::fingerprint(&[]),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+        // the order and repetitions of the words do not matter, their boundaries do
+        let mut reordered = list.clone();
+        reordered.swap(0, 1);
+        reordered.push(list[0].clone());
+        assert_eq!(word_list::fingerprint(&reordered), fingerprint);
+        let split = |words: &[&str]| {
+            let words: Vec<String> = words.iter().map(|w| w.to_string()).collect();
+            word_list::fingerprint(&words)
+        };
+        assert_ne!(split(&["a\nb"]), split(&["a", "b"]));
+        assert_ne!(split(&["ab", "c"]), split(&["a", "bc"]));
+        assert_ne!(split(&[""]), split(&[]));
+
+        let mut trained = Generator::new_custom(list, 2).unwrap();
+        assert_eq!(trained.word_list_fingerprint(), Some(fingerprint.as_str()));
+        let json = trained.model_json();
+        assert_eq!(
+            ngram::parse_json(&json).unwrap().len(),
+            trained.model_stats().transitions
+        );
+        let mut loaded = Generator::from_model_json(&json, &fingerprint).unwrap();
+        assert_eq!(loaded.model_digest(), trained.model_digest());
+        trained.reseed(0x746);
+        loaded.reseed(0x746);
+        assert_eq!(
+            trained.gen_from_pattern("w-w-dd"),
+            loaded.gen_from_pattern("w-w-dd")
+        );
+
+        let other = word_list::fingerprint(&reordered[1..]);
+        let err = Generator::from_model_json(&json, &other).err().unwrap();
+        assert_eq!(err.line, 2);
+        assert!(err.message.contains(&fingerprint) && err.message.contains(&other));
+        let imported = Generator::from_ngram_counts(ngram::parse_json(&json).unwrap()).unwrap();
+        assert_eq!(imported.word_list_fingerprint(), None);
+        assert!(Generator::from_model_json(&imported.model_json(), &fingerprint).is_err());
+        assert!(Generator::from_model_json("[]", &fingerprint).is_err());
+    }
+
+    #[test]
+    fn test_ngram_import() {
+        let mut trained = Generator::new_custom(word_list::cv::list(), 2).unwrap();
+        let from_json = ngram::parse_json(&trained.model_json()).unwrap();
+        let csv: String = trained
+            .transitions()
+            .map(|(state, next, count)| format!("\"{}\",{},{}\n", state, next, count))
+            .collect();
+        let from_csv = ngram::parse_csv(&format!("{}\r\n{}", ngram::CSV_HEADER, csv)).unwrap();
+        assert_eq!(from_json, from_csv);
+        let mut imported = Generator::from_ngram_counts(from_json).unwrap();
+        assert_eq!(imported.model_stats(), trained.model_stats());
+        trained.reseed(0x4E6);
+        imported.reseed(0x4E6);
+        assert_eq!(
+            trained.gen_from_pattern("w-c-W-dd"),
+            imported.gen_from_pattern("w-c-W-dd")
+        );
+        imported.set_empirical_word_lengths(true);
+        imported.gen_from_pattern("w");
+
+        let counts = ngram::parse_json(
+            r#"[{"next": "Ab", "count": 2, "state": "", "source": {"corpus": [1, "x"]}},
+                {"state": "ab", "next": "\u0063d", "count": 0}]"#,
+        )
+        .unwrap();
+        assert_eq!(counts[1], ("ab".to_string(), "cd".to_string(), 0));
+        let mut gen = Generator::from_ngram_counts(counts).unwrap();
+        assert_eq!(gen.gen_from_pattern("c"), ("ab".to_string(), 0.0));
+        // imported models have no word lengths, drawing one must not panic
+        gen.set_empirical_word_lengths(true);
+        assert!(!gen.empirical_lengths);
+        gen.empirical_lengths = true;
+        assert_eq!(gen.gen_word_length(), None);
+        assert_eq!(gen.gen_from_pattern("w").1, 0.0);
+        let err = ngram::parse_json(
+            "[{\"state\": \"\", \"next\": \"a\", \"count\": 1},\n{\"state\": \"\", \"next\": 3}]",
+        )
+        .unwrap_err();
+        assert_eq!(
+            (err.line, err.message.as_str()),
+            (2, "next is not a string")
+        );
+        assert_eq!(
+            ngram::parse_json("[{\"state\": \"\", \"next\": \"a\", \"count\": 1.5}]")
+                .unwrap_err()
+                .message,
+            "the count is not a non-negative integer"
+        );
+        // surrogate pairs are decoded, lone surrogates refused
+        let counts = ngram::parse_json(r#"[{"state": "", "next": "\ud83d\ude00", "count": 1}]"#);
+        assert_eq!(counts.unwrap()[0].1, "\u{1F600}");
+        assert!(ngram::parse_json(r#"[{"state": "", "next": "\ud83d", "count": 1}]"#).is_err());
+        // nesting is bounded, hostile input cannot exhaust the stack
+        let deep = format!(
+            "[{{\"state\": \"\", \"x\": {}1{}}}]",
+            "[".repeat(100_000),
+            "]".repeat(100_000)
+        );
+        assert_eq!(
+            ngram::parse_json(&deep).unwrap_err().message,
+            "too deeply nested"
+        );
+        assert_eq!(
+            ngram::parse_csv("state,next,count\n,ab,x")
+                .unwrap_err()
+                .line,
+            2
+        );
+        assert!(ngram::parse_csv(",ab,1").is_err());
+        assert!(Generator::from_ngram_counts(Vec::new()).is_none());
+    }
+
+    #[test]
+    fn test_canonicalize() {
+        use canonical::canonicalize;
+        assert_eq!(canonicalize("  E\u{301}TÉ-Çà  ", false), "été-çà");
+        assert_eq!(canonicalize("a\u{301}\u{301}", false), "á\u{301}");
+        assert_eq!(canonicalize("\u{301}x-Straße 🦀", true), "xstraße");
+        assert_eq!(canonicalize("", true), "");
+    }
+
+    #[test]
+    fn test_async_generator() {
+        use std::future::Future;
+        use std::task::{Context, Poll, Waker};
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<Generator>();
+        assert_send_sync::<nonblocking::AsyncGenerator>();
+
+        let mut gen = Generator::new_custom(word_list::cv::list(), 2).unwrap();
+        gen.reseed(0xA5);
+        let mut generator = nonblocking::AsyncGenerator::new(gen, "w-dd");
+        let count = 2 * nonblocking::CHUNK + 1;
+        let mut batch = Box::pin(generator.gen_batch(count));
+        let mut cx = Context::from_waker(Waker::noop());
+        let mut yields = 0;
+        let secrets = loop {
+            match batch.as_mut().poll(&mut cx) {
+                Poll::Ready(secrets) => break secrets,
+                Poll::Pending => yields += 1,
+            }
+        };
+        assert_eq!((secrets.len(), yields), (count, 2));
+        drop(batch);
+
+        // polled as a stream, the secrets continue the batch and yield every chunk
+        let mut polled = Vec::new();
+        let mut yields = 0;
+        while polled.len() < count {
+            match generator.poll_next(&mut cx) {
+                Poll::Ready(Some(secret)) => polled.push(secret),
+                Poll::Ready(None) => unreachable!(),
+                Poll::Pending => yields += 1,
+            }
+        }
+        assert_eq!(yields, 2);
+        let mut gen = generator.into_inner();
+        gen.reseed(0xA5);
+        assert_eq!(gen.gen_batch("w-dd", count), secrets);
+        assert_eq!(gen.gen_batch("w-dd", count), polled);
+    }
+
+    #[test]
+    fn test_constant_time_sampling() {
+        let mut gen = Generator::new_custom(word_list::cv::list(), 2).unwrap();
+        let mut outputs = Vec::new();
+        for constant_time in [false, true] {
+            gen.set_constant_time_sampling(constant_time);
+            gen.reseed(0xC7);
+            outputs.push(
+                (0..200)
+                    .map(|_| gen.gen_from_pattern("Ww-c-dds"))
+                    .collect::<Vec<_>>(),
+            );
+        }
+        assert_eq!(outputs[0], outputs[1]);
+        let items: Vec<String> = ["a", "", "çé", "bbb"].map(String::from).to_vec();
+        for (i, item) in items.iter().enumerate() {
+            assert_eq!(&ct::select_string(&items, i), item);
+        }
+        assert_eq!(ct::select_char(&['a', '€', '🦀'], 2), '🦀');
+        assert_eq!(ct::count_at_most(&[2, 5, 9], 5), 2);
+    }
+
+    #[test]
+    fn test_symbol_uniformity() {
+        for layout in layout::Layout::ALL {
+            let symbols: Vec<char> = layout.symbols().chars().collect();
+            let distinct: std::collections::HashSet<&char> = symbols.iter().collect();
+            assert_eq!(distinct.len(), symbols.len(), "{}", layout.name());
+        }
+        let mut gen = Generator::new_custom(word_list::cv::list(), 2).unwrap();
+        gen.set_symbols("€a€£🦀£").unwrap();
+        assert_eq!(gen.symbols, ['€', 'a', '£', '🦀']);
+        let pattern = Pattern::parse("s");
+        let mut counts = HashMap::new();
+        for d in 0..gen.symbols.len() {
+            // every index drawn by the RNG maps to a different symbol
+            assert!(counts.insert(gen.symbols[d], 0).is_none());
+            assert_eq!(ct::select_char(&gen.symbols, d), gen.symbols[d]);
+        }
+        gen.reseed(0x5E7);
+        let draws = 40_000;
+        for _ in 0..draws {
+            let (symbol, entropy) = gen.gen_from_parsed(&pattern);
+            assert_eq!(entropy, 2.0);
+            *counts.get_mut(&symbol.chars().next().unwrap()).unwrap() += 1;
+        }
+        let expected = draws as f64 / 4.0;
+        let chi2: f64 = counts
+            .values()
+            .map(|&c| (c as f64 - expected).powi(2) / expected)
+            .sum();
+        // 99.9th percentile of the chi-squared distribution with 3 degrees of freedom
+        assert!(chi2 < 16.27, "{}", chi2);
+        assert_eq!(gen.exact_entropy(&pattern, "🦀"), Some(2.0));
+    }
+
+    #[test]
+    fn test_custom_character_sets() {
+        let mut gen = Generator::new_custom(word_list::cv::list(), 2).unwrap();
+        gen.set_symbols("_.").unwrap();
+        gen.set_digits("2345678923").unwrap();
+        let pattern = Pattern::parse("s-ddd");
+        let expected = 1.0 + 3.0 * 8f64.log2();
+        let (secret, entropy) = gen.gen_from_parsed(&pattern);
+        assert!((entropy - expected).abs() < 1e-9);
+        assert!("_.".contains(&secret[..1]));
+        assert!(secret[2..].chars().all(|c| ('2'..='9').contains(&c)));
+        assert!((pattern.expected_entropy(&gen).unwrap() - expected).abs() < 1e-9);
+        assert!((gen.max_achievable_entropy("s-ddd").unwrap() - expected).abs() < 1e-9);
+        assert!((gen.exact_entropy(&pattern, &secret).unwrap() - expected).abs() < 1e-9);
+        assert_eq!(gen.exact_entropy(&pattern, "_-001"), None);
+        // empty sets are refused and leave the current ones in place
+        assert_eq!(gen.set_symbols(""), Err(Error::EmptySymbols));
+        assert_eq!(gen.set_digits(""), Err(Error::EmptyDigits));
+        assert_eq!(gen.symbols, ['_', '.']);
+        assert_eq!(gen.digits.len(), 8);
+    }
+
+    #[test]
+    fn test_generator_pool() {
+        use pool::{GeneratorPool, ReseedPolicy};
+        let build = || {
+            let mut gen = Generator::new_custom(word_list::cv::list(), 2).unwrap();
+            gen.reseed(0x9001);
+            gen
+        };
+        let mut pool = GeneratorPool::new(2, build);
+        let first = pool.checkout().gen_from_pattern("w-w");
+        // the generator which was just returned is lent again, and keeps its state
+        assert_ne!(pool.checkout().gen_from_pattern("w-w"), first);
+        let a = pool.try_checkout().unwrap();
+        let b = pool.try_checkout().unwrap();
+        assert!(pool.try_checkout().is_none());
+        assert_eq!(pool.available(), 0);
+        drop((a, b));
+        assert_eq!(pool.available(), pool.size());
+
+        pool.set_reseed_policy(ReseedPolicy::Always);
+        let mut reseeded = pool.checkout();
+        let mut fresh = build();
+        assert_ne!(
+            reseeded.gen_from_pattern("w-w"),
+            fresh.gen_from_pattern("w-w")
+        );
+        drop(reseeded);
+
+        let pool = GeneratorPool::new(3, build);
+        let generated = std::sync::atomic::AtomicUsize::new(0);
+        std::thread::scope(|s| {
+            for _ in 0..8 {
+                s.spawn(|| {
+                    for _ in 0..50 {
+                        pool.checkout().gen_from_pattern("w-dd");
+                        generated.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                    }
+                });
+            }
+        });
+        assert_eq!(generated.into_inner(), 400);
+        assert_eq!(pool.available(), 3);
+
+        // members built with the same seed draw independent streams all the same
+        let (mut a, mut b) = (pool.checkout(), pool.checkout());
+        assert_ne!(a.gen_from_pattern("w-w-w"), b.gen_from_pattern("w-w-w"));
+        drop((a, b));
+        let streams = |master_seed: &[u8]| {
+            let pool = GeneratorPool::with_master_seed(2, build, master_seed);
+            let (mut a, mut b) = (pool.checkout(), pool.checkout());
+            (a.gen_from_pattern("w-w-w"), b.gen_from_pattern("w-w-w"))
+        };
+        let (a, b) = streams(b"master seed");
+        assert_ne!(a, b);
+        assert_eq!(streams(b"master seed"), (a.clone(), b));
+        assert_ne!(streams(b"other seed").0, a);
+    }
+
+    #[test]
+    fn test_metrics() {
+        use metrics::{Counters, Policy};
+        let counters = Arc::new(Counters::new());
+        let mut gen = Generator::new_custom(word_list::cv::list(), 2).unwrap();
+        gen.set_metrics(Some(counters.clone()));
+        let entropy: f64 = (0..20).map(|_| gen.gen_from_pattern("w-dd").1).sum();
+        assert_eq!(counters.generated(), 20);
+        assert!((counters.mean_entropy() - entropy / 20.0).abs() < 1e-9);
+        gen.gen_batch_distinct("d", 10, 1).unwrap();
+        assert_eq!(
+            counters.generated(),
+            30 + counters.resampled(Policy::MinDistance)
+        );
+        assert!(counters.resampled(Policy::MinDistance) > 0);
+        let mut words: Vec<String> = ('b'..='z').map(|c| format!("aa{}", c)).collect();
+        words.extend(["bcd", "cde"].map(String::from));
+        let mut skewed = Generator::new_custom(words, 2).unwrap();
+        skewed.set_metrics(Some(counters.clone()));
+        skewed.reseed(3);
+        for _ in 0..20 {
+            skewed.gen_from_pattern("c{>=4}");
+        }
+        assert!(counters.resampled(Policy::ElementFloor) > 0);
+        let text = counters.to_prometheus("test");
+        assert!(text.contains(&format!(
+            "test_generation_seconds_bucket{{le=\"+Inf\"}} {}",
+            counters.generated()
+        )));
+        let generated = counters.generated();
+        gen.set_metrics(None);
+        gen.gen_from_pattern("w");
+        assert_eq!(counters.generated(), generated);
+    }
+
+    #[test]
+    fn test_arbitrary_inputs_never_panic() {
+        let alphabet: Vec<char> = "cCwWsd\\ .-aé€ßİ🦀(){}2[|]".chars().collect();
+        let lists = vec![
+            debug::list(),
+            vec![String::from("a")],
+            vec![String::from("aaaa")],
+            vec![
+                String::from("çàé"),
+                String::from("İstanbul"),
+                String::from("🦀🦀"),
+            ],
+        ];
+        let mut rng = ChaCha8Rng::seed_from_u64(42);
+        for list in lists {
+            for depth in [0, 1, 3, usize::MAX] {
+                let mut gen = Generator::new_custom(list.clone(), depth).unwrap();
+                gen.reseed(7);
+                for _ in 0..200 {
+                    let len = rng.gen_range(0..12);
+                    let pattern: String = (0..len)
+                        .map(|_| alphabet[rng.gen_range(0..alphabet.len())])
+                        .collect();
+                    let (pw, h) = gen.gen_from_pattern(&pattern);
+                    assert!(h >= 0.0, "{} {}", pattern, pw);
+                    let _ = gen.gen_next_token(&pw);
+                }
+            }
+        }
+        assert!(Generator::new_custom(vec![], 2).is_none());
+        assert!(Generator::new_custom(vec![String::from(" \t")], 2).is_none());
+        assert_eq!(
+            word_list::from_bytes(b"11 ab\n\xff\xfe\n\ncd\n\xc3"),
+            (vec!["ab".to_string(), "cd".to_string()], 2)
+        );
+    }
+
+    #[test]
+    fn test_normalize() {
+        use normalize::{normalize, NormalizationForm};
+        let composed = "caff\u{e8}-\u{401}\u{439}-\u{390}-\u{105}\u{301}";
+        let decomposed =
+            "caffe\u{300}-\u{415}\u{308}\u{438}\u{306}-\u{3b9}\u{308}\u{301}-a\u{328}\u{301}";
+        assert_eq!(normalize(composed, NormalizationForm::Nfd), decomposed);
+        assert_eq!(normalize(decomposed, NormalizationForm::Nfc), composed);
+        assert_eq!(normalize(decomposed, NormalizationForm::Nfd), decomposed);
+        assert_eq!(normalize(composed, NormalizationForm::Nfc), composed);
+        // marks are reordered by combining class before composing
+        assert_eq!(
+            normalize("a\u{301}\u{328}", NormalizationForm::Nfc),
+            "\u{105}\u{301}"
+        );
+        // the whole database: Vietnamese, Hangul, singletons and composition exclusions
+        let cases = [
+            ("\u{1ec7}", "\u{1ec7}", "e\u{323}\u{302}"),
+            ("e\u{302}\u{323}", "\u{1ec7}", "e\u{323}\u{302}"),
+            (
+                "\u{d55c}\u{ae00}",
+                "\u{d55c}\u{ae00}",
+                "\u{1112}\u{1161}\u{11ab}\u{1100}\u{1173}\u{11af}",
+            ),
+            (
+                "\u{1100}\u{1161}\u{11a8}",
+                "\u{ac01}",
+                "\u{1100}\u{1161}\u{11a8}",
+            ),
+            ("\u{212b}", "\u{c5}", "A\u{30a}"),
+            ("\u{958}", "\u{915}\u{93c}", "\u{915}\u{93c}"),
+        ];
+        for (text, nfc, nfd) in cases {
+            assert_eq!(normalize(text, NormalizationForm::Nfc), nfc, "{}", text);
+            assert_eq!(normalize(text, NormalizationForm::Nfd), nfd, "{}", text);
+        }
+    }
+
+    #[test]
+    fn test_pattern_repetition() {
+        use pattern::{Pattern, MAX_REPETITION};
+        assert_eq!(Pattern::parse("w{4}"), Pattern::parse("wwww"));
+        assert_eq!(Pattern::parse("d{6}"), Pattern::parse("dddddd"));
+        assert_eq!(Pattern::parse("(w-){3}w"), Pattern::parse("w-w-w-w"));
+        assert_eq!(
+            Pattern::parse("w{>=12}{2}"),
+            Pattern::parse("w{>=12}w{>=12}")
+        );
+        assert_eq!(
+            Pattern::parse("{roman:1-3}{2}"),
+            Pattern::parse("{roman:1-3}{roman:1-3}")
+        );
+        assert_eq!(
+            Pattern::parse("(W(.d){2}){2}"),
+            Pattern::parse("W.d.dW.d.d")
+        );
+        assert_eq!(Pattern::parse("-(w){0}-"), Pattern::parse("--"));
+        // counts after literals, and parentheses without a count, are text
+        assert_eq!(Pattern::parse("z{3}"), Pattern::parse("z\\{3}"));
+        assert_eq!(Pattern::parse("(w)"), Pattern::parse("\\(w\\)"));
+        assert_eq!(Pattern::parse("(w{2}"), Pattern::parse("\\(ww"));
+        assert_eq!(Pattern::parse("d{2,3}"), Pattern::parse("d\\{2,3}"));
+        assert_eq!(Pattern::parse("(\\)){2}"), Pattern::parse("\\)\\)"));
+        // escaped text stays text
+        let literal = "(a){2}";
+        assert_eq!(
+            Pattern::parse(&Pattern::escape_literal(literal)).segments(),
+            &[pattern::Segment::Literal(literal.to_string())]
+        );
+        // huge repetitions, even nested, are not expanded
+        let max = format!("d{{{}}}", MAX_REPETITION);
+        assert_eq!(Pattern::parse(&max).segments().len(), MAX_REPETITION);
+        let over = format!("d{{{}}}", MAX_REPETITION + 1);
+        assert_eq!(Pattern::parse(&over).segments().len(), 2);
+        let nested = "((((d{1000}){1000}){1000}){1000}){99999999999999999999}";
+        assert!(Pattern::parse(nested).segments().len() <= 4 * MAX_REPETITION);
+        let deep = format!("{}w{}", "(".repeat(10_000), "){2}".repeat(10_000));
+        assert!(Pattern::parse(&deep).segments().len() <= deep.len());
+        // brackets are matched in a single pass, unclosed ones do not rescan the pattern
+        let unclosed = "([".repeat(200_000);
+        assert_eq!(
+            Pattern::parse(&unclosed),
+            Pattern::parse(&unclosed.replace('(', "\\(").replace('[', "\\["))
+        );
+        assert_eq!(Pattern::parse("[(a]b){2}"), Pattern::parse("[\\(a]b\\){2}"));
+        assert_eq!(Pattern::parse("([a){2}]"), Pattern::parse("(\\[a){2}\\]"));
+        let mut gen = Generator::new();
+        let (secret, _) = gen.gen_from_pattern("(d-){2}d{3}");
+        assert_eq!(secret.len(), 7);
+        for (i, c) in secret.chars().enumerate() {
+            assert_eq!(c == '-', i == 1 || i == 3, "{}", secret);
+        }
+    }
+
+    #[test]
+    fn test_pattern_alternation() {
+        use pattern::{Pattern, Segment};
+        let choice = |alternatives: &[&str]| {
+            Segment::Choice(alternatives.iter().map(|a| Pattern::parse(a)).collect())
+        };
+        assert_eq!(Pattern::parse("[sd]").segments(), &[choice(&["s", "d"])]);
+        assert_eq!(Pattern::parse("[w|c]").segments(), &[choice(&["w", "c"])]);
+        assert_eq!(
+            Pattern::parse("[W-d|dd|]z").segments(),
+            &[
+                choice(&["W-d", "dd", ""]),
+                Segment::Literal("z".to_string())
+            ]
+        );
+        assert_eq!(
+            Pattern::parse("[d{2}s\\|]").segments(),
+            &[choice(&["dd", "s", "\\|"])]
+        );
+        assert_eq!(
+            Pattern::parse("[[s|d]|w]").segments(),
+            &[Segment::Choice(vec![
+                Pattern::parse("[s|d]"),
+                Pattern::parse("w")
+            ])]
+        );
+        assert_eq!(Pattern::parse("[sd]{2}").segments().len(), 2);
+        // unclosed and empty brackets are text
+        assert_eq!(Pattern::parse("[sd"), Pattern::parse("\\[sd"));
+        assert_eq!(Pattern::parse("z[]"), Pattern::parse("z\\[\\]"));
+        assert_eq!(
+            Pattern::parse(&Pattern::escape_literal("[a|b]")).segments(),
+            &[Segment::Literal("[a|b]".to_string())]
+        );
+
+        let mut gen = Generator::new();
+        gen.reseed(3);
+        let mut seen = HashSet::new();
+        for _ in 0..200 {
+            let secret = gen.gen_annotated(&Pattern::parse("[s|dd]-w"));
+            let (head, _) = secret.text.split_once('-').unwrap();
+            let digits = head.chars().all(|c| c.is_ascii_digit());
+            seen.insert(digits);
+            // the choice adds one bit, which the spans leave out
+            let spans: f64 = secret.spans.iter().map(|span| span.entropy).sum();
+            assert!(
+                (secret.entropy - spans - 1.0).abs() < 1e-9,
+                "{}",
+                secret.text
+            );
+            assert_eq!(secret.words.len(), 1);
+        }
+        assert_eq!(seen.len(), 2);
+        let (secret, entropy) = gen.gen_from_pattern("[[a|b]|e]");
+        let expected = if secret == "e" { 1.0 } else { 2.0 };
+        assert!((entropy - expected).abs() < 1e-9, "{}", secret);
+        // identical alternatives are a single outcome
+        assert_eq!(gen.gen_from_pattern("[|]"), (String::new(), 0.0));
+        assert_eq!(gen.gen_from_pattern("[a|a]{80}"), ("a".repeat(80), 0.0));
+        let (secret, entropy) = gen.gen_from_pattern("[ab|ab|e]");
+        let expected = if secret == "e" { 3.0f64 } else { 1.5 };
+        assert!((entropy - expected.log2()).abs() < 1e-9, "{}", secret);
+        // analyses which cannot handle alternations refuse them
+        let pattern = Pattern::parse("w[sd]");
+        assert!(pattern.expected_entropy(&gen).is_none());
+        assert!(gen.enumerate(&pattern).is_none());
+        // the exact figure merges alternatives producing the same text
+        let (secret, entropy) = gen.gen_from_parsed(&pattern);
+        assert!(gen.exact_entropy(&pattern, &secret).unwrap() <= entropy + 1e-9);
+        let exact = gen
+            .exact_entropy(&Pattern::parse("[a|[a|b]]"), "a")
+            .unwrap();
+        assert!((exact - (4.0f64 / 3.0).log2()).abs() < 1e-9, "{}", exact);
+        assert_eq!(
+            gen.exact_entropy(&Pattern::parse("[a|a]{80}"), &"a".repeat(80)),
+            Some(0.0)
+        );
+        assert_eq!(gen.exact_entropy(&Pattern::parse("[a|b]"), "c"), None);
+        let choices = Pattern::parse(&"[s|d]".repeat(12));
+        assert!(gen.exact_entropy(&choices, &"1".repeat(12)).is_none());
+        gen.set_exact_entropy(true);
+        let (_, exact) = gen.gen_from_pattern("[ddd|s]");
+        assert!(exact > 1.0);
+        assert_eq!(gen.gen_from_pattern("[a|[a]]{80}"), ("a".repeat(80), 0.0));
+        let (secret, exact) = gen.gen_from_pattern("[a|[a|b]]");
+        let expected = if secret == "a" {
+            (4.0f64 / 3.0).log2()
+        } else {
+            2.0
+        };
+        assert!((exact - expected).abs() < 1e-9, "{} {}", secret, exact);
+        // every alternative counts towards the limits
+        let limits = limits::PatternLimits {
+            max_elements: 3,
+            ..Default::default()
+        };
+        assert!(gen.parse_limited("[w|c|d]", &limits).is_err());
+        assert!(gen.parse_limited("[w|c]", &limits).is_ok());
+    }
+
+    #[test]
+    fn test_messages() {
+        use analysis::{PortabilityWarning, Strength};
+        use messages::{Catalog, ENGLISH, FRENCH, GERMAN, ITALIAN, SPANISH};
+        assert_eq!(Strength::VeryStrong.name(), "very strong");
+        assert_eq!(
+            PortabilityWarning::BcryptTruncation { bytes: 74 }.description(),
+            "74 bytes long, bcrypt ignores everything past 72 bytes"
+        );
+        assert_eq!(Catalog::for_language("IT"), &ITALIAN);
+        assert_eq!(Catalog::for_language("de-AT"), &GERMAN);
+        assert_eq!(Catalog::for_language("fr_CA"), &FRENCH);
+        assert_eq!(Catalog::for_language("es"), &SPANISH);
+        assert_eq!(Catalog::for_language("tlh"), &ENGLISH);
+        assert_eq!(Catalog::for_language(""), &Catalog::default());
+        for catalog in [ENGLISH, ITALIAN, GERMAN, FRENCH, SPANISH] {
+            let names: HashSet<&str> = catalog.strengths.iter().copied().collect();
+            assert_eq!(names.len(), 5);
+            let warning = catalog.warning(&PortabilityWarning::BcryptTruncation { bytes: 99 });
+            assert!(
+                warning.contains("99") && warning.contains("72"),
+                "{}",
+                warning
+            );
+            assert!(!warning.contains('{'), "{}", warning);
+        }
+        assert_eq!(ITALIAN.strength(Strength::Fair), "discreta");
+    }
 }
@@ -0,0 +1,219 @@
+//! Limits on patterns from untrusted input.
+//!
+//! Every string is a valid pattern, and generating from a pattern takes time and memory in
+//! proportion to its elements and to the secret it expands to. Services which accept patterns
+//! from their users, e.g. over HTTP, must bound both. [`Generator::parse_limited`] checks a
+//! pattern against [`PatternLimits`] before it is used, failing with a [`LimitError`] which
+//! tells which limit was exceeded: the length of the pattern is checked before parsing it, the
+//! number of elements and of [entropy floors](crate::pattern::MAX_ELEMENT_FLOOR), and their
+//! height, after, and
+//! the real words the pattern draws and the length of the longest secret it can produce with
+//! the generator last.
+//!
+//! Check patterns after [expanding their variables](crate::pattern::expand_variables), which
+//! can make them arbitrarily longer.
+//!
+//! ```rust
+//! use cryptirust::limits::{LimitError, PatternLimits};
+//! use cryptirust::Generator;
+//!
+//! let mut generator = Generator::new();
+//! let limits = PatternLimits::default();
+//! let pattern = generator.parse_limited("w-w-w-dd", &limits).unwrap();
+//! let (passphrase, entropy) = generator.gen_from_parsed(&pattern);
+//!
+//! let hostile = "w".repeat(1 << 20);
+//! assert!(matches!(
+//!     generator.parse_limited(&hostile, &limits),
+//!     Err(LimitError::PatternTooLong { .. })
+//! ));
+//! ```
+use crate::pattern::{Pattern, Segment, MAX_ELEMENT_FLOOR};
+use crate::Generator;
+use rand::{CryptoRng, RngCore};
+use std::fmt;
+
+/// Longest pattern by default, in bytes.
+pub const DEFAULT_MAX_PATTERN_LENGTH: usize = 256;
+/// Most elements in a pattern by default.
+pub const DEFAULT_MAX_ELEMENTS: usize = 64;
+/// Most elements with an entropy floor in a pattern by default.
+pub const DEFAULT_MAX_FLOORS: usize = 8;
+/// Longest secret a pattern may expand to by default, in characters.
+pub const DEFAULT_MAX_EXPANSION: usize = 1024;
+
+/// Limits checked by [`Generator::parse_limited`]; the default values are the `DEFAULT_`
+/// constants of this module.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PatternLimits {
+    /// Longest pattern, in bytes.
+    pub max_length: usize,
+    /// Most elements, i.e. segments other than literal text, counting those of every
+    /// alternative of an alternation.
+    pub max_elements: usize,
+    /// Most elements with an entropy floor, `w{>=bits}`, which are resampled and whose
+    /// entropy accounting enumerates the outcomes below the floor.
+    pub max_floors: usize,
+    /// Longest secret the pattern may produce, in characters.
+    pub max_expansion: usize,
+}
+
+impl Default for PatternLimits {
+    fn default() -> Self {
+        PatternLimits {
+            max_length: DEFAULT_MAX_PATTERN_LENGTH,
+            max_elements: DEFAULT_MAX_ELEMENTS,
+            max_floors: DEFAULT_MAX_FLOORS,
+            max_expansion: DEFAULT_MAX_EXPANSION,
+        }
+    }
+}
+
+/// A pattern exceeding one of its [`PatternLimits`]; the variants of the limits carry the
+/// measured value and the limit.
+#[derive(Debug, Clone, PartialEq)]
+pub enum LimitError {
+    /// The pattern is longer than [`PatternLimits::max_length`] bytes.
+    PatternTooLong { length: usize, max: usize },
+    /// The pattern has more than [`PatternLimits::max_elements`] elements.
+    TooManyElements { elements: usize, max: usize },
+    /// The pattern has more than [`PatternLimits::max_floors`] entropy floors.
+    TooManyFloors { floors: usize, max: usize },
+    /// The pattern can produce secrets longer than [`PatternLimits::max_expansion`]
+    /// characters.
+    ExpansionTooLarge { length: usize, max: usize },
+    /// The pattern has an `{env:NAME}` or `{arg:NAME}` placeholder, which
+    /// [untrusted patterns](crate::untrusted) cannot use.
+    Substitution { source: String, name: String },
+    /// The pattern draws real words, `x` or `X`, and the generator has no
+    /// [dictionary](crate::Generator::set_dictionary).
+    NoDictionary,
+    /// An element has an entropy floor above [`MAX_ELEMENT_FLOOR`] bits.
+    FloorTooHigh { bits: f64, max: f64 },
+    /// A `{roman}` or `{ordinal}` element has a range going beyond the supported numbers, 1
+    /// to `largest`.
+    NumeralOutOfRange { min: u32, max: u32, largest: u32 },
+}
+
+impl fmt::Display for LimitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LimitError::PatternTooLong { length, max } => write!(
+                f,
+                "the pattern is {} bytes long, more than the limit of {}",
+                length, max
+            ),
+            LimitError::TooManyElements { elements, max } => write!(
+                f,
+                "the pattern has {} elements, more than the limit of {}",
+                elements, max
+            ),
+            LimitError::TooManyFloors { floors, max } => write!(
+                f,
+                "the pattern has {} entropy floors, more than the limit of {}",
+                floors, max
+            ),
+            LimitError::ExpansionTooLarge { length, max } => write!(
+                f,
+                "the pattern can produce secrets of {} characters, more than the limit of {}",
+                length, max
+            ),
+            LimitError::Substitution { source, name } => write!(
+                f,
+                "the pattern substitutes {{{}:{}}}, which untrusted patterns cannot do",
+                source, name
+            ),
+            LimitError::NoDictionary => write!(
+                f,
+                "the pattern draws real words and the generator has no dictionary"
+            ),
+            LimitError::FloorTooHigh { bits, max } => write!(
+                f,
+                "an element has an entropy floor of {} bits, more than the limit of {}",
+                bits, max
+            ),
+            LimitError::NumeralOutOfRange { min, max, largest } => write!(
+                f,
+                "the numbers {} to {} go beyond the supported ones, 1 to {}",
+                min, max, largest
+            ),
+        }
+    }
+}
+
+impl std::error::Error for LimitError {}
+
+impl<R: RngCore + CryptoRng> Generator<R> {
+    /// Parses `pattern` if it stays within `limits`, see the [module](crate::limits)
+    /// documentation; the length of the secrets depends on the word list and the settings of
+    /// the generator.
+    pub fn parse_limited(
+        &self,
+        pattern: &str,
+        limits: &PatternLimits,
+    ) -> Result<Pattern, LimitError> {
+        if pattern.len() > limits.max_length {
+            return Err(LimitError::PatternTooLong {
+                length: pattern.len(),
+                max: limits.max_length,
+            });
+        }
+        let parsed = Pattern::parse(pattern);
+        // the elements of every alternative count, as a bound on the work they take
+        let segments = parsed.all_segments();
+        let elements = segments
+            .iter()
+            .filter(|segment| !matches!(segment, Segment::Literal(_)))
+            .count();
+        if elements > limits.max_elements {
+            return Err(LimitError::TooManyElements {
+                elements,
+                max: limits.max_elements,
+            });
+        }
+        let floors = segments
+            .iter()
+            .filter(|segment| {
+                matches!(
+                    segment,
+                    Segment::Word {
+                        min_entropy: Some(_),
+                        ..
+                    } | Segment::Token {
+                        min_entropy: Some(_),
+                        ..
+                    }
+                )
+            })
+            .count();
+        if floors > limits.max_floors {
+            return Err(LimitError::TooManyFloors {
+                floors,
+                max: limits.max_floors,
+            });
+        }
+        if let Some(bits) = parsed
+            .highest_floor()
+            .filter(|&bits| bits > MAX_ELEMENT_FLOOR)
+        {
+            return Err(LimitError::FloorTooHigh {
+                bits,
+                max: MAX_ELEMENT_FLOOR,
+            });
+        }
+        if let Some((min, max, largest)) = parsed.numeral_out_of_range() {
+            return Err(LimitError::NumeralOutOfRange { min, max, largest });
+        }
+        if self.lacks_dictionary(&parsed) {
+            return Err(LimitError::NoDictionary);
+        }
+        let length = self.max_length(&parsed);
+        if length > limits.max_expansion {
+            return Err(LimitError::ExpansionTooLarge {
+                length,
+                max: limits.max_expansion,
+            });
+        }
+        Ok(parsed)
+    }
+}
@@ -1,4 +0,0 @@
-use cryptirust::crypticli::cli::cli_main;
-pub fn main() {
-    cli_main();
-}
@@ -0,0 +1,270 @@
+//! Manifests of generation runs.
+//!
+//! Provisioning runs often need a record of how their secrets were made, for reproducibility
+//! and compliance, without the secrets themselves. A [`Manifest`] collects the parameters of a
+//! run, i.e. the crate and [algorithm](crate::test_vectors::ALGORITHM_VERSION) versions, the
+//! [digest of the model](Generator::model_digest), the
+//! [word list fingerprint](Generator::word_list_fingerprint), the depth, the pattern and the policy, and
+//! records the entropy of every generated secret; [`Manifest::to_json`] serializes the
+//! parameters with the counts and the entropy statistics.
+//!
+//! Runs whose random number generator is [seeded from a secret](crate::kdf::SecretSeed) can
+//! record the [`Seeding`] and their command-line arguments, so that [`Manifest::from_json`]
+//! gives tools everything they need, but the secret, to reproduce the run.
+//!
+//! ```rust
+//! use cryptirust::{manifest::Manifest, Generator};
+//!
+//! let mut generator = Generator::new();
+//! let mut manifest = Manifest::new(&generator, "w-w-w-dd", 3);
+//! manifest.add_policy("min-entropy", "40");
+//! for (_secret, entropy) in generator.gen_batch("w-w-w-dd", 3) {
+//!     manifest.record(entropy);
+//! }
+//! let json = manifest.to_json();
+//! assert!(json.contains("\"generated\":3"));
+//! ```
+use crate::json::{self, json_string, Value};
+use crate::test_vectors::ALGORITHM_VERSION;
+use crate::Generator;
+use rand::{CryptoRng, RngCore};
+use std::fmt;
+
+/// Version of the crate writing the manifest.
+pub const CRATE_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Record of a generation run, see the [module](self) documentation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Manifest {
+    /// Version of the crate, [`CRATE_VERSION`].
+    pub crate_version: String,
+    /// Version of the generation algorithm, [`ALGORITHM_VERSION`].
+    pub algorithm_version: u32,
+    /// Hexadecimal [digest of the model](Generator::model_digest).
+    pub model_digest: String,
+    /// [Fingerprint of the word list](Generator::word_list_fingerprint) of the model, if known.
+    pub word_list: Option<String>,
+    /// Depth of the markov model.
+    pub depth: usize,
+    /// Pattern of the secrets.
+    pub pattern: String,
+    /// Settings constraining the secrets, as name and value, e.g. `min-entropy` and `40`.
+    pub policy: Vec<(String, String)>,
+    /// Command-line arguments of the run, without secrets, for tools which can replay it.
+    pub arguments: Vec<String>,
+    /// How the random number generator was seeded from a secret, if it was.
+    pub seeding: Option<Seeding>,
+    /// Number of secrets requested.
+    pub requested: usize,
+    /// Entropy of every generated secret, in bits.
+    pub entropies: Vec<f64>,
+}
+
+impl Manifest {
+    /// A manifest for a run generating `requested` secrets following `pattern` with
+    /// `generator`.
+    pub fn new<R: RngCore + CryptoRng>(
+        generator: &Generator<R>,
+        pattern: &str,
+        requested: usize,
+    ) -> Manifest {
+        Manifest {
+            crate_version: CRATE_VERSION.to_string(),
+            algorithm_version: ALGORITHM_VERSION,
+            model_digest: generator.model_digest(),
+            word_list: generator.word_list_fingerprint().map(str::to_string),
+            depth: generator.depth,
+            pattern: pattern.to_string(),
+            policy: Vec::new(),
+            arguments: Vec::new(),
+            seeding: None,
+            requested,
+            entropies: Vec::new(),
+        }
+    }
+
+    /// Adds a setting to the policy.
+    pub fn add_policy(&mut self, name: &str, value: &str) {
+        self.policy.push((name.to_string(), value.to_string()));
+    }
+
+    /// Records a generated secret by its entropy.
+    pub fn record(&mut self, entropy: f64) {
+        self.entropies.push(entropy);
+    }
+
+    /// The manifest as a JSON object with the members `crate_version`, `algorithm_version`,
+    /// `model_digest`, `word_list` (`null` when unknown), `depth`, `pattern`, `policy` (an
+    /// object of strings), `arguments` (an array of strings), `seeding` (an object with the
+    /// members `salt`, `iterations` and `check`, `null` when not seeded), `requested`,
+    /// `generated` and `entropy`, an object with the `min`, `mean` and `max` entropy in bits,
+    /// `null` when no secret was generated.
+    pub fn to_json(&self) -> String {
+        let policy: Vec<String> = self
+            .policy
+            .iter()
+            .map(|(name, value)| format!("{}:{}", json_string(name), json_string(value)))
+            .collect();
+        let entropy = if self.entropies.is_empty() {
+            String::from("null")
+        } else {
+            let min = self.entropies.iter().copied().fold(f64::INFINITY, f64::min);
+            let max = self.entropies.iter().copied().fold(0.0, f64::max);
+            let mean = self.entropies.iter().sum::<f64>() / self.entropies.len() as f64;
+            format!(
+                "{{\"min\":{:.2},\"mean\":{:.2},\"max\":{:.2}}}",
+                min, mean, max
+            )
+        };
+        let arguments: Vec<String> = self.arguments.iter().map(|a| json_string(a)).collect();
+        let seeding = self
+            .seeding
+            .as_ref()
+            .map_or(String::from("null"), |seeding| {
+                format!(
+                    "{{\"salt\":{},\"iterations\":{},\"check\":{}}}",
+                    json_string(&seeding.salt),
+                    seeding.iterations,
+                    json_string(&seeding.check)
+                )
+            });
+        format!(
+            "{{\"crate_version\":{},\"algorithm_version\":{},\"model_digest\":{},\
+             \"word_list\":{},\"depth\":{},\"pattern\":{},\"policy\":{{{}}},\"arguments\":[{}],\
+             \"seeding\":{},\"requested\":{},\"generated\":{},\"entropy\":{}}}",
+            json_string(&self.crate_version),
+            self.algorithm_version,
+            json_string(&self.model_digest),
+            self.word_list
+                .as_deref()
+                .map_or(String::from("null"), json_string),
+            self.depth,
+            json_string(&self.pattern),
+            policy.join(","),
+            arguments.join(","),
+            seeding,
+            self.requested,
+            self.entropies.len(),
+            entropy
+        )
+    }
+
+    /// Parses a manifest written by [`Manifest::to_json`]. Only the statistics of the entropy
+    /// are serialized, so the parsed manifest has no recorded entropy.
+    pub fn from_json(text: &str) -> Result<Manifest, ManifestError> {
+        let value = json::parse(text).map_err(|e| ManifestError::new(e.to_string()))?;
+        let string = |name: &str| {
+            value
+                .get(name)
+                .and_then(Value::as_str)
+                .map(str::to_string)
+                .ok_or_else(|| ManifestError::new(format!("missing string {}", name)))
+        };
+        let number = |name: &str| {
+            value
+                .get(name)
+                .and_then(Value::as_count)
+                .ok_or_else(|| ManifestError::new(format!("missing count {}", name)))
+        };
+        let policy = match value.get("policy") {
+            Some(Value::Object(members)) => members
+                .iter()
+                .map(|(name, v)| {
+                    v.as_str()
+                        .map(|v| (name.clone(), v.to_string()))
+                        .ok_or_else(|| {
+                            ManifestError::new(format!("policy {} is not a string", name))
+                        })
+                })
+                .collect::<Result<_, _>>()?,
+            _ => return Err(ManifestError::new("missing object policy")),
+        };
+        // manifests written before the arguments and the seeding were recorded have neither
+        let arguments = match value.get("arguments") {
+            Some(Value::Array(items)) => items
+                .iter()
+                .map(|a| a.as_str().map(str::to_string))
+                .collect::<Option<_>>()
+                .ok_or_else(|| ManifestError::new("arguments are not strings"))?,
+            None => Vec::new(),
+            _ => return Err(ManifestError::new("arguments are not an array")),
+        };
+        let seeding = match value.get("seeding") {
+            Some(seeding @ Value::Object(_)) => {
+                let field = |name: &str| {
+                    seeding
+                        .get(name)
+                        .ok_or_else(|| ManifestError::new(format!("missing seeding {}", name)))
+                };
+                let text = |name: &str| {
+                    field(name)?.as_str().map(str::to_string).ok_or_else(|| {
+                        ManifestError::new(format!("seeding {} is not a string", name))
+                    })
+                };
+                let iterations = field("iterations")?
+                    .as_f64()
+                    .filter(|n| *n >= 1.0 && *n <= u32::MAX as f64 && n.fract() == 0.0)
+                    .ok_or_else(|| ManifestError::new("invalid seeding iterations"))?;
+                Some(Seeding {
+                    salt: text("salt")?,
+                    iterations: iterations as u32,
+                    check: text("check")?,
+                })
+            }
+            Some(Value::Null) | None => None,
+            _ => return Err(ManifestError::new("seeding is not an object")),
+        };
+        Ok(Manifest {
+            crate_version: string("crate_version")?,
+            algorithm_version: u32::try_from(number("algorithm_version")?)
+                .map_err(|_| ManifestError::new("invalid algorithm_version"))?,
+            model_digest: string("model_digest")?,
+            word_list: match value.get("word_list") {
+                Some(Value::Null) | None => None,
+                _ => Some(string("word_list")?),
+            },
+            depth: number("depth")?,
+            pattern: string("pattern")?,
+            policy,
+            arguments,
+            seeding,
+            requested: number("requested")?,
+            entropies: Vec::new(),
+        })
+    }
+}
+
+/// How the random number generator of a run was seeded from a secret with
+/// [`SecretSeed`](crate::kdf::SecretSeed).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Seeding {
+    /// Hexadecimal salt of the derivation.
+    pub salt: String,
+    /// PBKDF2 iterations of the derivation.
+    pub iterations: u32,
+    /// Hexadecimal [check value](crate::kdf::SecretSeed::check) of the seed.
+    pub check: String,
+}
+
+/// A manifest which could not be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ManifestError {
+    /// What is wrong.
+    pub message: String,
+}
+
+impl ManifestError {
+    fn new(message: impl Into<String>) -> ManifestError {
+        ManifestError {
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for ManifestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid manifest: {}", self.message)
+    }
+}
+
+impl std::error::Error for ManifestError {}
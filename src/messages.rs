@@ -0,0 +1,232 @@
+//! Localized feedback of the strength analysis.
+//!
+//! The [analysis](crate::analysis) reports its findings as values, [`Strength`] classes,
+//! [`PortabilityWarning`]s, the [`PassphraseIssue`]s and [`Suggestion`]s of
+//! [`check_user_passphrase`](crate::analysis::check_user_passphrase) and the [`Strengthening`]s
+//! of [`suggest_strengthening`](crate::analysis::suggest_strengthening), which applications
+//! show to their users. A [`Catalog`] holds the texts of these values in one language: the crate
+//! bundles catalogs for English (the texts of [`Strength::name`],
+//! [`PortabilityWarning::description`] and the like), Italian, German, French and Spanish,
+//! chosen by [`Catalog::for_language`] from a BCP 47 language tag such as the
+//! `navigator.language` of a browser. Applications needing another language, or other
+//! wording, fill a [`Catalog`] of their own.
+//!
+//! ```rust
+//! use cryptirust::analysis::{self, PortabilityWarning, Strength};
+//! use cryptirust::messages::{Catalog, ENGLISH};
+//!
+//! let catalog = Catalog::for_language("it-IT");
+//! assert_eq!(catalog.strength(Strength::VeryWeak), "molto debole");
+//! let estimate = analysis::estimate("Tr0ub4dor&3");
+//! println!("{}", catalog.strength(estimate.strength));
+//! for warning in analysis::portability_warnings(&"ж".repeat(40)) {
+//!     println!("{}", catalog.warning(&warning));
+//! }
+//!
+//! let pirate = Catalog {
+//!     strengths: ["scurvy", "feeble", "seaworthy", "stout", "legendary"],
+//!     ..ENGLISH
+//! };
+//! assert_eq!(pirate.strength(Strength::Fair), "seaworthy");
+//! assert_eq!(
+//!     pirate.warning(&PortabilityWarning::BcryptTruncation { bytes: 80 }),
+//!     "80 bytes long, bcrypt ignores everything past 72 bytes"
+//! );
+//! ```
+use crate::analysis::{
+    PassphraseIssue, PortabilityWarning, Strength, Strengthening, Suggestion, BCRYPT_MAX_BYTES,
+    MIN_PASSPHRASE_CHARS,
+};
+
+/// The texts of the analysis feedback in one language, see the [module](self) documentation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Catalog {
+    /// Names of the strength classes, from [`Strength::VeryWeak`] to [`Strength::VeryStrong`].
+    pub strengths: [&'static str; 5],
+    /// Description of [`PortabilityWarning::BcryptTruncation`], where `{bytes}` stands for the
+    /// length of the password and `{max}` for [`BCRYPT_MAX_BYTES`].
+    pub bcrypt_truncation: &'static str,
+    /// Description of [`PortabilityWarning::SurroundingWhitespace`].
+    pub surrounding_whitespace: &'static str,
+    /// Description of [`PortabilityWarning::Normalization`].
+    pub normalization: &'static str,
+    /// Description of [`PassphraseIssue::TooShort`], where `{min}` stands for
+    /// [`MIN_PASSPHRASE_CHARS`].
+    pub too_short: &'static str,
+    /// Description of [`PassphraseIssue::Blocklisted`].
+    pub blocklisted: &'static str,
+    /// Description of [`PassphraseIssue::DictionaryWords`], where `{words}` stands for the
+    /// words.
+    pub dictionary_words: &'static str,
+    /// Description of [`PassphraseIssue::Predictable`], where `{words}` stands for the runs of
+    /// letters.
+    pub predictable: &'static str,
+    /// Description of [`Suggestion::AppendWords`] with a single word, where `{words}` stands
+    /// for the word.
+    pub append_word: &'static str,
+    /// Description of [`Suggestion::AppendWords`], where `{count}` stands for the number of
+    /// words and `{words}` for the words.
+    pub append_words: &'static str,
+    /// Description of [`Suggestion::Replace`], where `{passphrase}` stands for the new
+    /// passphrase.
+    pub replace: &'static str,
+    /// Description of a [`Strengthening`], where `{passphrase}` stands for the strengthened
+    /// passphrase.
+    pub strengthen: &'static str,
+}
+
+/// English texts, the default.
+pub const ENGLISH: Catalog = Catalog {
+    strengths: ["very weak", "weak", "fair", "strong", "very strong"],
+    bcrypt_truncation: "{bytes} bytes long, bcrypt ignores everything past {max} bytes",
+    surrounding_whitespace: "leading or trailing whitespace, often trimmed",
+    normalization: "characters changed by Unicode normalization",
+    too_short: "shorter than {min} characters",
+    blocklisted: "a common or leaked password",
+    dictionary_words: "dictionary words, which attackers try first: {words}",
+    predictable: "letters in a predictable order: {words}",
+    append_word: "append a random word: {words}",
+    append_words: "append {count} random words: {words}",
+    replace: "or use a new passphrase: {passphrase}",
+    strengthen: "or keep it, strengthened: {passphrase}",
+};
+
+/// Italian texts.
+pub const ITALIAN: Catalog = Catalog {
+    strengths: ["molto debole", "debole", "discreta", "forte", "molto forte"],
+    bcrypt_truncation: "lunga {bytes} byte, bcrypt ignora tutto oltre i {max} byte",
+    surrounding_whitespace: "spazi iniziali o finali, spesso rimossi",
+    normalization: "caratteri modificati dalla normalizzazione Unicode",
+    too_short: "più corta di {min} caratteri",
+    blocklisted: "una password comune o trapelata",
+    dictionary_words: "parole del dizionario, che gli attaccanti provano per prime: {words}",
+    predictable: "lettere in un ordine prevedibile: {words}",
+    append_word: "aggiungi una parola casuale: {words}",
+    append_words: "aggiungi {count} parole casuali: {words}",
+    replace: "oppure usa una nuova passphrase: {passphrase}",
+    strengthen: "oppure mantienila, rafforzata: {passphrase}",
+};
+
+/// German texts.
+pub const GERMAN: Catalog = Catalog {
+    strengths: ["sehr schwach", "schwach", "mittel", "stark", "sehr stark"],
+    bcrypt_truncation: "{bytes} Bytes lang, bcrypt ignoriert alles nach {max} Bytes",
+    surrounding_whitespace: "Leerzeichen am Anfang oder Ende, oft entfernt",
+    normalization: "Zeichen, die durch Unicode-Normalisierung verändert werden",
+    too_short: "kürzer als {min} Zeichen",
+    blocklisted: "ein häufiges oder geleaktes Passwort",
+    dictionary_words: "Wörterbuchwörter, die Angreifer zuerst probieren: {words}",
+    predictable: "Buchstaben in vorhersehbarer Reihenfolge: {words}",
+    append_word: "ein zufälliges Wort anhängen: {words}",
+    append_words: "{count} zufällige Wörter anhängen: {words}",
+    replace: "oder eine neue Passphrase verwenden: {passphrase}",
+    strengthen: "oder sie verstärkt behalten: {passphrase}",
+};
+
+/// French texts.
+pub const FRENCH: Catalog = Catalog {
+    strengths: ["très faible", "faible", "moyen", "fort", "très fort"],
+    bcrypt_truncation: "{bytes} octets, bcrypt ignore tout au-delà de {max} octets",
+    surrounding_whitespace: "espaces au début ou à la fin, souvent supprimés",
+    normalization: "caractères modifiés par la normalisation Unicode",
+    too_short: "moins de {min} caractères",
+    blocklisted: "un mot de passe courant ou divulgué",
+    dictionary_words: "des mots du dictionnaire, que les attaquants essaient en premier : {words}",
+    predictable: "des lettres dans un ordre prévisible : {words}",
+    append_word: "ajoutez un mot aléatoire : {words}",
+    append_words: "ajoutez {count} mots aléatoires : {words}",
+    replace: "ou utilisez une nouvelle phrase de passe : {passphrase}",
+    strengthen: "ou gardez-la, renforcée : {passphrase}",
+};
+
+/// Spanish texts.
+pub const SPANISH: Catalog = Catalog {
+    strengths: ["muy débil", "débil", "aceptable", "fuerte", "muy fuerte"],
+    bcrypt_truncation: "{bytes} bytes de longitud, bcrypt ignora todo a partir de {max} bytes",
+    surrounding_whitespace: "espacios al principio o al final, a menudo eliminados",
+    normalization: "caracteres modificados por la normalización Unicode",
+    too_short: "menos de {min} caracteres",
+    blocklisted: "una contraseña común o filtrada",
+    dictionary_words: "palabras del diccionario, que los atacantes prueban primero: {words}",
+    predictable: "letras en un orden predecible: {words}",
+    append_word: "añade una palabra aleatoria: {words}",
+    append_words: "añade {count} palabras aleatorias: {words}",
+    replace: "o usa una nueva frase de contraseña: {passphrase}",
+    strengthen: "o mantenla, reforzada: {passphrase}",
+};
+
+impl Default for Catalog {
+    fn default() -> Self {
+        ENGLISH
+    }
+}
+
+impl Catalog {
+    /// The bundled catalog for a BCP 47 language tag such as `it` or `de-AT`, [`ENGLISH`] for
+    /// the other languages.
+    pub fn for_language(tag: &str) -> &'static Catalog {
+        let language = tag.split(['-', '_']).next().unwrap_or_default();
+        match language.to_ascii_lowercase().as_str() {
+            "it" => &ITALIAN,
+            "de" => &GERMAN,
+            "fr" => &FRENCH,
+            "es" => &SPANISH,
+            _ => &ENGLISH,
+        }
+    }
+
+    /// Name of the strength class `strength`.
+    pub fn strength(&self, strength: Strength) -> &'static str {
+        self.strengths[strength as usize]
+    }
+
+    /// Description of `warning`.
+    pub fn warning(&self, warning: &PortabilityWarning) -> String {
+        match *warning {
+            PortabilityWarning::BcryptTruncation { bytes } => self
+                .bcrypt_truncation
+                .replace("{bytes}", &bytes.to_string())
+                .replace("{max}", &BCRYPT_MAX_BYTES.to_string()),
+            PortabilityWarning::SurroundingWhitespace => self.surrounding_whitespace.to_string(),
+            PortabilityWarning::Normalization => self.normalization.to_string(),
+        }
+    }
+
+    /// Description of `issue`.
+    pub fn issue(&self, issue: &PassphraseIssue) -> String {
+        match issue {
+            PassphraseIssue::TooShort { .. } => self
+                .too_short
+                .replace("{min}", &MIN_PASSPHRASE_CHARS.to_string()),
+            PassphraseIssue::Blocklisted => self.blocklisted.to_string(),
+            PassphraseIssue::DictionaryWords { words } => {
+                self.dictionary_words.replace("{words}", &words.join(", "))
+            }
+            PassphraseIssue::Predictable { words } => {
+                self.predictable.replace("{words}", &words.join(", "))
+            }
+        }
+    }
+
+    /// Description of `suggestion`.
+    pub fn suggestion(&self, suggestion: &Suggestion) -> String {
+        match suggestion {
+            Suggestion::AppendWords {
+                words, count: 1, ..
+            } => self.append_word.replace("{words}", words),
+            Suggestion::AppendWords { words, count, .. } => self
+                .append_words
+                .replace("{count}", &count.to_string())
+                .replace("{words}", words),
+            Suggestion::Replace { passphrase, .. } => {
+                self.replace.replace("{passphrase}", passphrase)
+            }
+        }
+    }
+
+    /// Description of `strengthening`.
+    pub fn strengthening(&self, strengthening: &Strengthening) -> String {
+        self.strengthen
+            .replace("{passphrase}", &strengthening.passphrase)
+    }
+}
@@ -0,0 +1,183 @@
+//! Instrumentation hooks for services embedding the generator.
+//!
+//! A generator given a [`Metrics`] implementation with [`Generator::set_metrics`] reports
+//! every secret it generates, with its entropy and generation latency, and every candidate
+//! drawn again because of a policy: an [element floor](crate::pattern::MAX_ELEMENT_FLOOR) or
+//! the [minimum distance](Generator::gen_batch_distinct) of a batch. Services can forward the
+//! events to their metrics library, or use [`Counters`], which keeps Prometheus-style counters
+//! and a latency histogram and renders them in the Prometheus text format.
+//!
+//! ```rust
+//! use cryptirust::metrics::Counters;
+//! use cryptirust::Generator;
+//! use std::sync::Arc;
+//!
+//! let counters = Arc::new(Counters::new());
+//! let mut generator = Generator::new();
+//! generator.set_metrics(Some(counters.clone()));
+//! for _ in 0..10 {
+//!     generator.gen_from_pattern("w-w-w-w");
+//! }
+//! assert_eq!(counters.generated(), 10);
+//! println!("{}", counters.to_prometheus("cryptirust"));
+//! ```
+use crate::Generator;
+use rand::{CryptoRng, RngCore};
+use std::fmt::Write;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Receiver of the events of a [`Generator`], see the [module](self) documentation.
+pub trait Metrics: Send + Sync {
+    /// A secret was generated, with `entropy` bits, in `latency`. Candidates rejected by a
+    /// policy are reported too.
+    fn secret_generated(&self, entropy: f64, latency: Duration);
+
+    /// A candidate was rejected because of `policy` and drawn again.
+    fn candidate_resampled(&self, policy: Policy);
+}
+
+/// Policy causing a candidate to be drawn again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Policy {
+    /// An element with an entropy floor, e.g. `w{>=12}`; the candidate is the element.
+    ElementFloor,
+    /// The minimum distance between the secrets of a batch; the candidate is the secret.
+    MinDistance,
+}
+
+/// Upper bounds, in seconds, of the buckets of the latency histogram kept by [`Counters`].
+pub const LATENCY_BUCKETS: [f64; 8] = [1e-6, 5e-6, 1e-5, 5e-5, 1e-4, 5e-4, 1e-3, 1e-2];
+
+/// Thread-safe counters implementing [`Metrics`].
+#[derive(Debug, Default)]
+pub struct Counters {
+    generated: AtomicU64,
+    resampled: [AtomicU64; 2],
+    /// Sum of the entropies, as the bits of an `f64`.
+    entropy: AtomicU64,
+    /// Sum of the latencies in nanoseconds.
+    latency: AtomicU64,
+    /// Number of latencies in each bucket, the last one counting those above every bound.
+    buckets: [AtomicU64; LATENCY_BUCKETS.len() + 1],
+}
+
+impl Counters {
+    /// Counters at zero.
+    pub fn new() -> Counters {
+        Counters::default()
+    }
+
+    /// Number of secrets generated.
+    pub fn generated(&self) -> u64 {
+        self.generated.load(Ordering::Relaxed)
+    }
+
+    /// Number of candidates drawn again because of `policy`.
+    pub fn resampled(&self, policy: Policy) -> u64 {
+        self.resampled[policy as usize].load(Ordering::Relaxed)
+    }
+
+    /// Mean entropy of the generated secrets in bits, zero before the first one.
+    pub fn mean_entropy(&self) -> f64 {
+        let total = f64::from_bits(self.entropy.load(Ordering::Relaxed));
+        total / self.generated().max(1) as f64
+    }
+
+    /// Number of candidates drawn again, for any policy, per generated secret.
+    pub fn resample_rate(&self) -> f64 {
+        let resampled: u64 = self
+            .resampled
+            .iter()
+            .map(|c| c.load(Ordering::Relaxed))
+            .sum();
+        resampled as f64 / self.generated().max(1) as f64
+    }
+
+    /// The counters in the Prometheus text exposition format, with metric names starting
+    /// with `prefix`.
+    pub fn to_prometheus(&self, prefix: &str) -> String {
+        let mut out = String::new();
+        let generated = self.generated();
+        let _ = writeln!(out, "# TYPE {}_secrets_generated_total counter", prefix);
+        let _ = writeln!(out, "{}_secrets_generated_total {}", prefix, generated);
+        let _ = writeln!(out, "# TYPE {}_candidates_resampled_total counter", prefix);
+        for (policy, name) in [
+            (Policy::ElementFloor, "element_floor"),
+            (Policy::MinDistance, "min_distance"),
+        ] {
+            let _ = writeln!(
+                out,
+                "{}_candidates_resampled_total{{policy=\"{}\"}} {}",
+                prefix,
+                name,
+                self.resampled(policy)
+            );
+        }
+        let _ = writeln!(out, "# TYPE {}_secret_entropy_bits_sum counter", prefix);
+        let _ = writeln!(
+            out,
+            "{}_secret_entropy_bits_sum {}",
+            prefix,
+            f64::from_bits(self.entropy.load(Ordering::Relaxed))
+        );
+        let _ = writeln!(out, "# TYPE {}_generation_seconds histogram", prefix);
+        let mut cumulative = 0;
+        for (i, bound) in LATENCY_BUCKETS.iter().enumerate() {
+            cumulative += self.buckets[i].load(Ordering::Relaxed);
+            let _ = writeln!(
+                out,
+                "{}_generation_seconds_bucket{{le=\"{}\"}} {}",
+                prefix, bound, cumulative
+            );
+        }
+        let _ = writeln!(
+            out,
+            "{}_generation_seconds_bucket{{le=\"+Inf\"}} {}",
+            prefix, generated
+        );
+        let latency = self.latency.load(Ordering::Relaxed) as f64 / 1e9;
+        let _ = writeln!(out, "{}_generation_seconds_sum {}", prefix, latency);
+        let _ = writeln!(out, "{}_generation_seconds_count {}", prefix, generated);
+        out
+    }
+}
+
+impl Metrics for Counters {
+    fn secret_generated(&self, entropy: f64, latency: Duration) {
+        let _ = self
+            .entropy
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |bits| {
+                Some((f64::from_bits(bits) + entropy).to_bits())
+            });
+        let seconds = latency.as_secs_f64();
+        let bucket = LATENCY_BUCKETS
+            .iter()
+            .position(|&bound| seconds <= bound)
+            .unwrap_or(LATENCY_BUCKETS.len());
+        self.buckets[bucket].fetch_add(1, Ordering::Relaxed);
+        let nanos = u64::try_from(latency.as_nanos()).unwrap_or(u64::MAX);
+        self.latency.fetch_add(nanos, Ordering::Relaxed);
+        self.generated.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn candidate_resampled(&self, policy: Policy) {
+        self.resampled[policy as usize].fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+impl<R: RngCore + CryptoRng> Generator<R> {
+    /// Sets the receiver of the events of the generator, `None` (the default) to disable
+    /// instrumentation; see the [`metrics`](crate::metrics) module.
+    pub fn set_metrics(&mut self, metrics: Option<Arc<dyn Metrics>>) {
+        self.metrics = metrics;
+    }
+
+    /// Reports a candidate drawn again because of `policy`.
+    pub(crate) fn report_resample(&self, policy: Policy) {
+        if let Some(metrics) = &self.metrics {
+            metrics.candidate_resampled(policy);
+        }
+    }
+}
@@ -0,0 +1,840 @@
+//! Inspection and maintenance of the trained markov model.
+//!
+//! Word lists scraped from dirty corpora contain typos and oddities which end up in the model
+//! as transitions observed once or twice. [`Generator::prune`] drops the transitions observed
+//! fewer than a minimum number of times, renormalizing the remaining ones, and reports the
+//! size of the model before and after.
+//!
+//! ```rust
+//! use cryptirust::Generator;
+//!
+//! # #[cfg(feature = "wordlist-eff")]
+//! # {
+//! // the EFF list, whose rarest transitions are seen once
+//! let mut generator = Generator::new();
+//! let before = generator.model_stats();
+//! let stats = generator.prune(2).unwrap();
+//! assert_eq!(stats.before, before);
+//! assert!(stats.after.transitions < stats.before.transitions);
+//! let (passphrase, entropy) = generator.gen_from_pattern("w-w-w-w");
+//! # }
+//! ```
+//!
+//! # Model salt
+//!
+//! Bundled word lists are public, so an attacker knows the model of a generator using them.
+//! [`Generator::salt_model`] multiplies the weight of every transition by a factor between 1
+//! and [`SALT_WEIGHTS`] derived from a secret salt with HMAC-SHA-256, so that the model is
+//! only known to those holding the salt. This is defense in depth for server-side deployments,
+//! not a substitute for entropy: the reported entropy still assumes that the attacker knows
+//! the salted model, and it is usually slightly lower than with the public model since the salted
+//! transitions are less uniform. [`SaltImpact`] measures both effects.
+//!
+//! ```rust
+//! use cryptirust::Generator;
+//!
+//! let mut generator = Generator::new();
+//! let impact = generator.salt_model(b"secret salt, stored with the server configuration");
+//! assert!(impact.after < impact.before);
+//! assert!(impact.divergence > 0.0);
+//! let (passphrase, entropy) = generator.gen_from_pattern("w-w-w-w");
+//! ```
+//!
+//! # Export
+//!
+//! [`Generator::transitions`] lists the transitions of the model with their number of
+//! observations, [`Generator::model_json`] serializes them as a JSON array of
+//! `{"state", "next", "count"}` objects and [`Generator::model_dot`] draws the transition
+//! graph for Graphviz, to understand and debug models trained on custom corpora. Prune the
+//! model first to keep the graph readable. [`Generator::model_digest`] identifies a model by
+//! the SHA-256 digest of its JSON form, e.g. in [manifests](crate::manifest).
+//!
+//! The JSON form records the [fingerprint](crate::word_list::fingerprint) of the word list
+//! the model was trained on, and [`Generator::from_model_json`] refuses to load a model
+//! trained on another version of the list. It records the
+//! [dictionary](Generator::set_dictionary) as well, so that the digest tells apart generators
+//! drawing different real words.
+//!
+//! ```rust
+//! use cryptirust::{word_list, Generator};
+//!
+//! let generator = Generator::new_custom(word_list::debug::list(), 2).unwrap();
+//! let (state, next, count) = generator.transitions().next().unwrap();
+//! assert!(generator.model_json().contains(&format!("\"count\":{}", count)));
+//! assert!(generator.model_dot().starts_with("digraph model {"));
+//! ```
+//!
+//! # Caching
+//!
+//! Training the model on a word list takes longer than generating a few secrets, a waste for
+//! short-lived processes. [`Generator::to_model_bytes`] serializes the trained model in a
+//! compact binary form, with the word lengths observed in the list, its fingerprint and the
+//! [dictionary](Generator::set_dictionary) of the `x` pattern element, and
+//! [`Generator::from_model_bytes`] loads it back without training. Models stay equal through
+//! the round trip, including pruned and salted ones, and the depth of the chain is recorded
+//! rather than guessed from the longest state. Generators never hold an empty model, which
+//! [`Generator::prune`] refuses to leave, so every model written loads back and a cache
+//! file replaces training; the other settings of the generator are not saved. Compare the
+//! [fingerprint](Generator::word_list_fingerprint) of a cached model with that of the list to
+//! detect a stale cache.
+//!
+//! ```rust
+//! use cryptirust::{word_list, Generator};
+//!
+//! let generator = Generator::new();
+//! let bytes = generator.to_model_bytes();
+//! // later, e.g. at the next start of the process
+//! let mut cached = Generator::from_model_bytes(&bytes).unwrap();
+//! assert_eq!(cached.model_digest(), generator.model_digest());
+//! let fingerprint = word_list::fingerprint(&word_list::default_list());
+//! assert_eq!(cached.word_list_fingerprint(), Some(fingerprint.as_str()));
+//! let (passphrase, entropy) = cached.gen_from_pattern("w-w-w-w");
+//! ```
+//!
+//! # Flat arrays
+//!
+//! [`Generator::to_flat_model`] lays the model out as a [`FlatModel`] of flat arrays, in the
+//! compressed sparse row layout of sparse matrices, so that sampling can be ported to GPUs
+//! and array frameworks, which handle numbers better than maps of strings:
+//!
+//! - states are numbered in sorted order, the empty state, where words start, first; the
+//!   text of state `s` is `state_bytes[state_offsets[s]..state_offsets[s + 1]]`, in UTF-8;
+//! - tokens are numbered likewise, with `token_bytes` and `token_offsets`;
+//! - the transitions of state `s` are those from `row_offsets[s]` to `row_offsets[s + 1]`,
+//!   each with the token it appends, `next_token`, its number of observations, `counts`, the
+//!   running sum of the counts of its row, `cumulative`, and its probability;
+//! - `next_state` is the state the generator looks up after the token is appended to the
+//!   state, the longest one of at most `depth` characters ending the text, or `-1` when
+//!   there is none and the word ends;
+//! - the words of the [dictionary](Generator::set_dictionary) are laid out like the states,
+//!   with `dictionary_bytes` and `dictionary_offsets`.
+//!
+//! Drawing `n` uniformly below the last cumulative count of the row and taking the first
+//! transition whose cumulative count exceeds `n` samples a transition as the generator does.
+//! [`Generator::from_flat_model`] loads the arrays back, so that exports can be checked
+//! against the crate: the round trip gives the same model.
+//!
+//! ```rust
+//! use cryptirust::Generator;
+//! use rand::Rng;
+//!
+//! let generator = Generator::new();
+//! let flat = generator.to_flat_model().unwrap();
+//! // a word of up to 3 tokens, sampled with the arrays alone
+//! let (mut state, mut word) = (0, String::new());
+//! for _ in 0..3 {
+//!     let row = flat.row_offsets[state] as usize..flat.row_offsets[state + 1] as usize;
+//!     let n = rand::thread_rng().gen_range(0..flat.cumulative[row.end - 1]);
+//!     let t = row.start + flat.cumulative[row.clone()].partition_point(|&c| c <= n);
+//!     word.push_str(flat.token(flat.next_token[t] as usize));
+//!     match flat.next_state[t] {
+//!         -1 => break,
+//!         next => state = next as usize,
+//!     }
+//! }
+//! assert!(!word.is_empty());
+//!
+//! let loaded = Generator::from_flat_model(&flat).unwrap();
+//! assert_eq!(loaded.model_digest(), generator.model_digest());
+//! ```
+use crate::encode::format_hex;
+use crate::json::json_string;
+use crate::sha256::{sha256, HmacSha256};
+use crate::{is_dictionary, lookup_distribution, Distribution, Error, Generator, WordLengths};
+use rand::{CryptoRng, RngCore};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{self, Write};
+use std::ops::Range;
+
+/// Largest factor applied to the weight of a transition by [`Generator::salt_model`].
+pub const SALT_WEIGHTS: usize = 4;
+
+/// Leading bytes of a model serialized by [`Generator::to_model_bytes`].
+const MODEL_MAGIC: &[u8] = b"CRMODEL";
+
+/// Version of the serialization format written by [`Generator::to_model_bytes`]. Version 2
+/// records the depth of the chain, version 3 the dictionary.
+const MODEL_VERSION: u8 = 3;
+
+/// A serialized model which could not be read by [`Generator::from_model_bytes`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModelError {
+    /// The data is not a serialized model, or it is truncated or corrupted.
+    NotAModel,
+    /// The model was written by another version of the format.
+    UnsupportedVersion(u8),
+    /// The model has too many states, tokens or transitions for the 32-bit offsets and
+    /// indices of a [`FlatModel`].
+    TooLarge,
+}
+
+impl fmt::Display for ModelError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ModelError::NotAModel => write!(f, "not a serialized model"),
+            ModelError::UnsupportedVersion(version) => {
+                write!(f, "unsupported model version {}", version)
+            }
+            ModelError::TooLarge => write!(f, "model too large for flat arrays"),
+        }
+    }
+}
+
+impl std::error::Error for ModelError {}
+
+/// Appends `n` as 4 big-endian bytes, as lengths and numbers of items are written.
+fn push_len(bytes: &mut Vec<u8>, n: usize) {
+    bytes.extend_from_slice(&(n as u32).to_be_bytes());
+}
+
+/// Appends `text` preceded by its length in bytes.
+fn push_str(bytes: &mut Vec<u8>, text: &str) {
+    push_len(bytes, text.len());
+    bytes.extend_from_slice(text.as_bytes());
+}
+
+/// Reads the fields of a serialized model, failing with [`ModelError::NotAModel`] past its
+/// end.
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8], ModelError> {
+        if n > self.bytes.len() {
+            return Err(ModelError::NotAModel);
+        }
+        let (taken, rest) = self.bytes.split_at(n);
+        self.bytes = rest;
+        Ok(taken)
+    }
+
+    fn len(&mut self) -> Result<usize, ModelError> {
+        Ok(u32::from_be_bytes(self.take(4)?.try_into().unwrap()) as usize)
+    }
+
+    fn count(&mut self) -> Result<usize, ModelError> {
+        let count = u64::from_be_bytes(self.take(8)?.try_into().unwrap());
+        usize::try_from(count).map_err(|_| ModelError::NotAModel)
+    }
+
+    fn string(&mut self) -> Result<String, ModelError> {
+        let len = self.len()?;
+        let bytes = self.take(len)?;
+        String::from_utf8(bytes.to_vec()).map_err(|_| ModelError::NotAModel)
+    }
+}
+
+/// The model as flat arrays, see the [module](self#flat-arrays) documentation. Offsets and
+/// indices are `u32`, which bounds the size of exportable models far above that of word
+/// lists; [`Generator::to_flat_model`] fails with [`ModelError::TooLarge`] beyond.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FlatModel {
+    /// Depth of the chain, the longest state or token in characters.
+    pub depth: usize,
+    /// Texts of the states, concatenated.
+    pub state_bytes: Vec<u8>,
+    /// Start of the text of every state in `state_bytes`, and its total length last.
+    pub state_offsets: Vec<u32>,
+    /// Texts of the tokens, concatenated.
+    pub token_bytes: Vec<u8>,
+    /// Start of the text of every token in `token_bytes`, and its total length last.
+    pub token_offsets: Vec<u32>,
+    /// First transition of every state, and the number of transitions last.
+    pub row_offsets: Vec<u32>,
+    /// Token appended by every transition.
+    pub next_token: Vec<u32>,
+    /// State looked up after every transition, `-1` when there is none.
+    pub next_state: Vec<i32>,
+    /// Number of observations of every transition in the training data.
+    pub counts: Vec<u64>,
+    /// Sum of the counts of the transitions of the row up to every transition, included.
+    pub cumulative: Vec<u64>,
+    /// Probability of every transition given its state.
+    pub probabilities: Vec<f32>,
+    /// Number of words of every length, in characters, in the training list.
+    pub word_lengths: Vec<u64>,
+    /// [Fingerprint](Generator::word_list_fingerprint) of the word list, if known.
+    pub word_list: Option<String>,
+    /// Words of the [dictionary](Generator::set_dictionary), concatenated.
+    pub dictionary_bytes: Vec<u8>,
+    /// Start of every word in `dictionary_bytes`, and their total length last.
+    pub dictionary_offsets: Vec<u32>,
+}
+
+impl FlatModel {
+    /// Number of states.
+    pub fn states(&self) -> usize {
+        self.state_offsets.len().saturating_sub(1)
+    }
+
+    /// Text of state `s`.
+    ///
+    /// # Panics
+    ///
+    /// If `s` is not a state, or the offsets are out of bounds or not on character
+    /// boundaries.
+    pub fn state(&self, s: usize) -> &str {
+        text_at(&self.state_bytes, &self.state_offsets, s).expect("invalid state")
+    }
+
+    /// Text of token `t`.
+    ///
+    /// # Panics
+    ///
+    /// As [`FlatModel::state`].
+    pub fn token(&self, t: usize) -> &str {
+        text_at(&self.token_bytes, &self.token_offsets, t).expect("invalid token")
+    }
+
+    /// Word `i` of the dictionary.
+    ///
+    /// # Panics
+    ///
+    /// As [`FlatModel::state`].
+    pub fn dictionary_word(&self, i: usize) -> &str {
+        text_at(&self.dictionary_bytes, &self.dictionary_offsets, i).expect("invalid word")
+    }
+
+    /// The transitions of state `s`.
+    pub fn row(&self, s: usize) -> Range<usize> {
+        self.row_offsets[s] as usize..self.row_offsets[s + 1] as usize
+    }
+}
+
+/// Text `i` of the strings concatenated in `bytes` at `offsets`, `None` when out of bounds or
+/// not UTF-8.
+fn text_at<'a>(bytes: &'a [u8], offsets: &[u32], i: usize) -> Option<&'a str> {
+    let start = *offsets.get(i)? as usize;
+    let end = *offsets.get(i + 1)? as usize;
+    std::str::from_utf8(bytes.get(start..end)?).ok()
+}
+
+/// Appends `text` to `bytes` and its end to `offsets`.
+fn push_text(bytes: &mut Vec<u8>, offsets: &mut Vec<u32>, text: &str) -> Result<(), ModelError> {
+    bytes.extend_from_slice(text.as_bytes());
+    offsets.push(u32::try_from(bytes.len()).map_err(|_| ModelError::TooLarge)?);
+    Ok(())
+}
+
+/// `n` as a `u32` index or offset of a [`FlatModel`].
+fn flat_index(n: usize) -> Result<u32, ModelError> {
+    u32::try_from(n).map_err(|_| ModelError::TooLarge)
+}
+
+/// `flat` without the states whose row is empty, which the generator never holds: a state
+/// without transitions is looked up as if it were absent. Fails if a transition leads to such
+/// a state, or if a state or an offset is out of bounds.
+fn without_empty_rows(flat: &FlatModel) -> Result<FlatModel, ModelError> {
+    let mut ids = Vec::with_capacity(flat.states());
+    let mut compact = FlatModel {
+        state_bytes: Vec::new(),
+        state_offsets: vec![0],
+        row_offsets: vec![0],
+        next_state: Vec::with_capacity(flat.next_state.len()),
+        ..flat.clone()
+    };
+    for s in 0..flat.states() {
+        if flat.row(s).is_empty() {
+            ids.push(None);
+            continue;
+        }
+        ids.push(Some(compact.states()));
+        let state =
+            text_at(&flat.state_bytes, &flat.state_offsets, s).ok_or(ModelError::NotAModel)?;
+        push_text(&mut compact.state_bytes, &mut compact.state_offsets, state)?;
+        compact.row_offsets.push(flat.row_offsets[s + 1]);
+    }
+    for &next in &flat.next_state {
+        let next = match usize::try_from(next) {
+            Ok(next) => ids
+                .get(next)
+                .copied()
+                .flatten()
+                .ok_or(ModelError::NotAModel)?,
+            Err(_) if next == -1 => {
+                compact.next_state.push(-1);
+                continue;
+            }
+            Err(_) => return Err(ModelError::NotAModel),
+        };
+        compact
+            .next_state
+            .push(i32::try_from(next).map_err(|_| ModelError::TooLarge)?);
+    }
+    Ok(compact)
+}
+
+/// Size of a markov model.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ModelStats {
+    /// Number of states, i.e. contexts with at least one outgoing transition.
+    pub states: usize,
+    /// Number of distinct transitions from a state to a token.
+    pub transitions: usize,
+    /// Number of observations of the transitions in the training data.
+    pub observations: usize,
+}
+
+/// Effect of [`Generator::prune`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PruneStats {
+    /// The model before pruning.
+    pub before: ModelStats,
+    /// The model after pruning.
+    pub after: ModelStats,
+}
+
+/// Effect of [`Generator::salt_model`], in bits per token, averaging the states by the
+/// number of observations of their transitions.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SaltImpact {
+    /// Entropy of the transitions before salting.
+    pub before: f64,
+    /// Entropy of the transitions after salting, the figure reported to users.
+    pub after: f64,
+    /// Kullback-Leibler divergence of the salted transitions from the public ones: the extra
+    /// bits per token, on average, needed by an attacker guessing with the public model.
+    pub divergence: f64,
+}
+
+impl<R: RngCore + CryptoRng> Generator<R> {
+    /// Size of the model.
+    pub fn model_stats(&self) -> ModelStats {
+        ModelStats {
+            states: self.jump_table.len(),
+            transitions: self.jump_table.values().map(|d| d.tokens.len()).sum(),
+            observations: self.jump_table.values().map(|d| d.total).sum(),
+        }
+    }
+
+    /// Drops the transitions observed fewer than `min_count` times in the training data and
+    /// renormalizes the probabilities of the others; states left without transitions are
+    /// dropped as well. Entropies reported afterwards reflect the pruned model.
+    ///
+    /// Fails with [`Error::EmptyModel`], leaving the model untouched, if no transition from
+    /// the empty state, where words start, would remain: such a model only generates empty
+    /// words.
+    pub fn prune(&mut self, min_count: usize) -> Result<PruneStats, Error> {
+        let before = self.model_stats();
+        let pruned: BTreeMap<String, Distribution> = self
+            .jump_table
+            .iter()
+            .filter_map(|(state, distribution)| {
+                let kept: Vec<(String, usize)> = (0..distribution.tokens.len())
+                    .map(|i| (distribution.tokens[i].clone(), distribution.frequency(i)))
+                    .filter(|&(_, freq)| freq >= min_count)
+                    .collect();
+                (!kept.is_empty()).then(|| (state.clone(), Distribution::from_frequencies(kept)))
+            })
+            .collect();
+        if !pruned.contains_key("") {
+            return Err(Error::EmptyModel { min_count });
+        }
+        self.jump_table = pruned;
+        self.patterns.forget_bounds();
+        Ok(PruneStats {
+            before,
+            after: self.model_stats(),
+        })
+    }
+
+    /// Perturbs the weights of the transitions deterministically from the secret `salt`, see
+    /// the [module](self) documentation. The same salt applied to the same model always gives
+    /// the same salted model.
+    pub fn salt_model(&mut self, salt: &[u8]) -> SaltImpact {
+        let prf = HmacSha256::new(salt);
+        let mut impact = SaltImpact {
+            before: 0.0,
+            after: 0.0,
+            divergence: 0.0,
+        };
+        let observations: usize = self.jump_table.values().map(|d| d.total).sum();
+        for (state, distribution) in self.jump_table.iter_mut() {
+            let salted: Vec<(String, usize)> = (0..distribution.tokens.len())
+                .map(|i| {
+                    let mut message = state.as_bytes().to_vec();
+                    message.push(0);
+                    message.extend_from_slice(distribution.tokens[i].as_bytes());
+                    let mac = prf.mac(&message);
+                    let draw = u64::from_be_bytes(mac[..8].try_into().unwrap());
+                    let weight = 1 + (draw % SALT_WEIGHTS as u64) as usize;
+                    (
+                        distribution.tokens[i].clone(),
+                        distribution.frequency(i) * weight,
+                    )
+                })
+                .collect();
+            let salted = Distribution::from_frequencies(salted);
+            let share = distribution.total as f64 / observations as f64;
+            for i in 0..salted.tokens.len() {
+                let (p, q) = (distribution.probability(i), salted.probability(i));
+                impact.before += share * p * distribution.entropies[i];
+                impact.after += share * q * salted.entropies[i];
+                impact.divergence += share * q * (q / p).log2();
+            }
+            *distribution = salted;
+        }
+        self.patterns.forget_bounds();
+        impact
+    }
+    /// The transitions of the model as `(state, next, count)`: the context, the token which
+    /// follows it and the number of times it was observed in the training data, ordered by
+    /// state and token. Words start from the empty state.
+    pub fn transitions(&self) -> impl Iterator<Item = (&str, &str, usize)> + '_ {
+        self.jump_table.iter().flat_map(|(state, distribution)| {
+            (0..distribution.tokens.len()).map(move |i| {
+                (
+                    state.as_str(),
+                    distribution.tokens[i].as_str(),
+                    distribution.frequency(i),
+                )
+            })
+        })
+    }
+
+    /// [Fingerprint](crate::word_list::fingerprint) of the word list the model was trained
+    /// on, `None` for models imported from [transition counts](crate::ngram).
+    pub fn word_list_fingerprint(&self) -> Option<&str> {
+        self.word_list.as_deref()
+    }
+
+    /// The [transitions](Generator::transitions) as a JSON array of objects with the members
+    /// `state`, `next` and `count`, one per line, preceded by an object with the member
+    /// `word_list`, the [fingerprint](Generator::word_list_fingerprint) of the word list, when
+    /// it is known, and by an object with the member `dictionary`, the array of the words of
+    /// the [dictionary](Generator::set_dictionary), when there are any.
+    pub fn model_json(&self) -> String {
+        let word_list = self
+            .word_list
+            .iter()
+            .map(|fingerprint| format!("{{\"word_list\":{}}}", json_string(fingerprint)));
+        let words: Vec<String> = self.dictionary.iter().map(|w| json_string(w)).collect();
+        let dictionary =
+            (!words.is_empty()).then(|| format!("{{\"dictionary\":[{}]}}", words.join(",")));
+        let rows: Vec<String> = word_list
+            .chain(dictionary)
+            .chain(self.transitions().map(|(state, next, count)| {
+                format!(
+                    "{{\"state\":{},\"next\":{},\"count\":{}}}",
+                    json_string(state),
+                    json_string(next),
+                    count
+                )
+            }))
+            .collect();
+        format!("[\n{}\n]\n", rows.join(",\n"))
+    }
+
+    /// The model in a compact binary form, see the [module](self#caching) documentation: the
+    /// depth of the chain, the transitions with their counts, the word lengths observed in the
+    /// training list, the [fingerprint](Generator::word_list_fingerprint) of the list and the
+    /// [dictionary](Generator::set_dictionary).
+    pub fn to_model_bytes(&self) -> Vec<u8> {
+        let mut bytes = MODEL_MAGIC.to_vec();
+        bytes.push(MODEL_VERSION);
+        push_len(&mut bytes, self.depth);
+        match &self.word_list {
+            Some(fingerprint) => {
+                bytes.push(1);
+                push_str(&mut bytes, fingerprint);
+            }
+            None => bytes.push(0),
+        }
+        push_len(&mut bytes, self.word_lengths.counts.len());
+        for &count in &self.word_lengths.counts {
+            bytes.extend_from_slice(&(count as u64).to_be_bytes());
+        }
+        push_len(&mut bytes, self.dictionary.len());
+        for word in &self.dictionary {
+            push_str(&mut bytes, word);
+        }
+        push_len(&mut bytes, self.jump_table.len());
+        for (state, distribution) in &self.jump_table {
+            push_str(&mut bytes, state);
+            push_len(&mut bytes, distribution.tokens.len());
+            for (i, token) in distribution.tokens.iter().enumerate() {
+                push_str(&mut bytes, token);
+                bytes.extend_from_slice(&(distribution.frequency(i) as u64).to_be_bytes());
+            }
+        }
+        bytes
+    }
+
+    /// The model as flat arrays, see the [module](self#flat-arrays) documentation. Fails with
+    /// [`ModelError::TooLarge`] if the model does not fit the 32-bit offsets and indices.
+    pub fn to_flat_model(&self) -> Result<FlatModel, ModelError> {
+        let ids: BTreeMap<&str, usize> = self
+            .jump_table
+            .keys()
+            .enumerate()
+            .map(|(i, state)| (state.as_str(), i))
+            .collect();
+        let tokens: BTreeMap<&str, usize> = self
+            .jump_table
+            .values()
+            .flat_map(|d| d.tokens.iter().map(String::as_str))
+            .collect::<BTreeSet<&str>>()
+            .into_iter()
+            .enumerate()
+            .map(|(i, token)| (token, i))
+            .collect();
+        let mut flat = FlatModel {
+            depth: self.depth,
+            state_bytes: Vec::new(),
+            state_offsets: vec![0],
+            token_bytes: Vec::new(),
+            token_offsets: vec![0],
+            row_offsets: vec![0],
+            next_token: Vec::new(),
+            next_state: Vec::new(),
+            counts: Vec::new(),
+            cumulative: Vec::new(),
+            probabilities: Vec::new(),
+            word_lengths: self.word_lengths.counts.iter().map(|&c| c as u64).collect(),
+            word_list: self.word_list.clone(),
+            dictionary_bytes: Vec::new(),
+            dictionary_offsets: vec![0],
+        };
+        for &token in tokens.keys() {
+            push_text(&mut flat.token_bytes, &mut flat.token_offsets, token)?;
+        }
+        for word in &self.dictionary {
+            push_text(
+                &mut flat.dictionary_bytes,
+                &mut flat.dictionary_offsets,
+                word,
+            )?;
+        }
+        for (state, distribution) in &self.jump_table {
+            push_text(&mut flat.state_bytes, &mut flat.state_offsets, state)?;
+            for (i, token) in distribution.tokens.iter().enumerate() {
+                let context = format!("{}{}", state, token);
+                let reached = match lookup_distribution(
+                    &self.jump_table,
+                    self.depth,
+                    self.locale,
+                    &context,
+                ) {
+                    Some((next, _)) => {
+                        i32::try_from(ids[next]).map_err(|_| ModelError::TooLarge)?
+                    }
+                    None => -1,
+                };
+                flat.next_token.push(flat_index(tokens[token.as_str()])?);
+                flat.next_state.push(reached);
+                flat.counts.push(distribution.frequency(i) as u64);
+                flat.cumulative.push(distribution.counts[i] as u64);
+                flat.probabilities.push(distribution.probability(i) as f32);
+            }
+            flat.row_offsets.push(flat_index(flat.next_token.len())?);
+        }
+        Ok(flat)
+    }
+
+    /// Hexadecimal SHA-256 digest of the [model as JSON](Generator::model_json), which
+    /// identifies the word list, depth and pruning the generator was trained with.
+    pub fn model_digest(&self) -> String {
+        format_hex(&sha256(self.model_json().as_bytes()))
+    }
+
+    /// The transition graph in the Graphviz DOT language: every state is a node, and every
+    /// transition an edge labelled with its token and probability, leading to the state the
+    /// generator looks up next or to `end` when the model has no continuation. Words start
+    /// from the `start` node.
+    pub fn model_dot(&self) -> String {
+        let ids: BTreeMap<&str, usize> = self
+            .jump_table
+            .keys()
+            .enumerate()
+            .map(|(i, state)| (state.as_str(), i))
+            .collect();
+        let mut dot = String::from("digraph model {\n    end [shape=doublecircle];\n");
+        for (&state, id) in &ids {
+            let label = if state.is_empty() { "start" } else { state };
+            let _ = writeln!(dot, "    s{} [label={}];", id, dot_string(label));
+        }
+        for (state, distribution) in &self.jump_table {
+            for i in 0..distribution.tokens.len() {
+                let token = &distribution.tokens[i];
+                let context = format!("{}{}", state, token);
+                let reached =
+                    lookup_distribution(&self.jump_table, self.depth, self.locale, &context)
+                        .map_or("end".to_string(), |(next, _)| format!("s{}", ids[next]));
+                let _ = writeln!(
+                    dot,
+                    "    s{} -> {} [label={}];",
+                    ids[state.as_str()],
+                    reached,
+                    dot_string(&format!("{} {:.3}", token, distribution.probability(i)))
+                );
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+/// `text` as a quoted string of the DOT language, which unlike JSON only escapes quotes and
+/// backslashes: other characters stand for themselves, as escapes such as `\n` or `\r` are
+/// line breaks in labels.
+fn dot_string(text: &str) -> String {
+    let mut quoted = String::from("\"");
+    for c in text.chars() {
+        if c == '"' || c == '\\' {
+            quoted.push('\\');
+        }
+        quoted.push(c);
+    }
+    quoted.push('"');
+    quoted
+}
+
+impl Generator {
+    /// Creates a generator from a model serialized by [`Generator::to_model_bytes`], with the
+    /// default settings but the dictionary it records. Fails if the data is not a model, is
+    /// truncated, records no state, a state, a token or a word twice, or a depth of zero, or
+    /// was written by another version of the format.
+    pub fn from_model_bytes(bytes: &[u8]) -> Result<Generator, ModelError> {
+        if bytes.len() <= MODEL_MAGIC.len() || !bytes.starts_with(MODEL_MAGIC) {
+            return Err(ModelError::NotAModel);
+        }
+        let version = bytes[MODEL_MAGIC.len()];
+        if version != MODEL_VERSION {
+            return Err(ModelError::UnsupportedVersion(version));
+        }
+        let mut reader = Reader {
+            bytes: &bytes[MODEL_MAGIC.len() + 1..],
+        };
+        let depth = reader.len()?;
+        let word_list = match reader.take(1)? {
+            [0] => None,
+            [1] => Some(reader.string()?),
+            _ => return Err(ModelError::NotAModel),
+        };
+        // lengths and counts come from untrusted data, so nothing is preallocated from them
+        let mut counts = Vec::new();
+        for _ in 0..reader.len()? {
+            counts.push(reader.count()?);
+        }
+        let total = counts
+            .iter()
+            .try_fold(0usize, |total, &c| total.checked_add(c))
+            .ok_or(ModelError::NotAModel)?;
+        let mut dictionary = Vec::new();
+        for _ in 0..reader.len()? {
+            dictionary.push(reader.string()?);
+        }
+        if !is_dictionary(&dictionary) {
+            return Err(ModelError::NotAModel);
+        }
+        let mut jump_table = BTreeMap::new();
+        for _ in 0..reader.len()? {
+            let state = reader.string()?;
+            let mut frequencies = Vec::new();
+            for _ in 0..reader.len()? {
+                let token = reader.string()?;
+                let count = reader.count()?;
+                frequencies.push((token, count));
+            }
+            let total = frequencies
+                .iter()
+                .try_fold(0usize, |total, &(_, c)| total.checked_add(c));
+            let mut tokens = BTreeSet::new();
+            if total.is_none_or(|total| total == 0)
+                || frequencies
+                    .iter()
+                    .any(|(token, c)| token.is_empty() || *c == 0 || !tokens.insert(token))
+                || jump_table.contains_key(&state)
+            {
+                return Err(ModelError::NotAModel);
+            }
+            jump_table.insert(state, Distribution::from_frequencies(frequencies));
+        }
+        if !reader.bytes.is_empty() {
+            return Err(ModelError::NotAModel);
+        }
+        let word_lengths = WordLengths { counts, total };
+        let mut generator = Generator::from_jump_table(jump_table, word_lengths, depth)
+            .ok_or(ModelError::NotAModel)?;
+        generator.word_list = word_list;
+        generator.dictionary = dictionary;
+        Ok(generator)
+    }
+
+    /// Creates a generator from a model exported by [`Generator::to_flat_model`], with the
+    /// default settings but its dictionary, see the [module](self#flat-arrays) documentation.
+    /// The model is built
+    /// from the states, tokens and counts; states with an empty row are valid and dropped, as
+    /// the generator looks them up as if they were absent. Fails with
+    /// [`ModelError::NotAModel`] if the arrays are inconsistent, if a transition leads to a
+    /// state with an empty row, or if exporting the model built from them does not give them
+    /// back, e.g. when states are out of order or repeated, or the derived arrays do not
+    /// match the counts.
+    pub fn from_flat_model(flat: &FlatModel) -> Result<Generator, ModelError> {
+        let rows = flat.row_offsets.len();
+        if rows != flat.state_offsets.len()
+            || flat.row_offsets.first() != Some(&0)
+            || flat.row_offsets.windows(2).any(|w| w[0] > w[1])
+            || flat.row_offsets.last().map(|&n| n as usize) != Some(flat.counts.len())
+            || flat.next_token.len() != flat.counts.len()
+            || flat.next_state.len() != flat.counts.len()
+        {
+            return Err(ModelError::NotAModel);
+        }
+        let flat = &without_empty_rows(flat)?;
+        let counts: Vec<usize> = flat
+            .word_lengths
+            .iter()
+            .map(|&c| usize::try_from(c).map_err(|_| ModelError::NotAModel))
+            .collect::<Result<_, _>>()?;
+        let total = counts
+            .iter()
+            .try_fold(0usize, |total, &c| total.checked_add(c))
+            .ok_or(ModelError::NotAModel)?;
+        let mut jump_table = BTreeMap::new();
+        for s in 0..flat.states() {
+            let state = text_at(&flat.state_bytes, &flat.state_offsets, s);
+            let mut frequencies = Vec::new();
+            for t in flat.row(s) {
+                let token = text_at(
+                    &flat.token_bytes,
+                    &flat.token_offsets,
+                    flat.next_token[t] as usize,
+                );
+                let count = usize::try_from(flat.counts[t]).ok().filter(|&c| c > 0);
+                match (token, count) {
+                    (Some(token), Some(count)) if !token.is_empty() => {
+                        frequencies.push((token.to_string(), count))
+                    }
+                    _ => return Err(ModelError::NotAModel),
+                }
+            }
+            let state = state.ok_or(ModelError::NotAModel)?;
+            if frequencies
+                .iter()
+                .try_fold(0usize, |total, &(_, c)| total.checked_add(c))
+                .is_none()
+            {
+                return Err(ModelError::NotAModel);
+            }
+            jump_table.insert(
+                state.to_string(),
+                Distribution::from_frequencies(frequencies),
+            );
+        }
+        let word_lengths = WordLengths { counts, total };
+        let mut generator = Generator::from_jump_table(jump_table, word_lengths, flat.depth)
+            .ok_or(ModelError::NotAModel)?;
+        generator.word_list = flat.word_list.clone();
+        generator.dictionary = (0..flat.dictionary_offsets.len().saturating_sub(1))
+            .map(|i| text_at(&flat.dictionary_bytes, &flat.dictionary_offsets, i).map(String::from))
+            .collect::<Option<_>>()
+            .filter(|words: &Vec<String>| is_dictionary(words))
+            .ok_or(ModelError::NotAModel)?;
+        if generator.to_flat_model().as_ref() != Ok(flat) {
+            return Err(ModelError::NotAModel);
+        }
+        Ok(generator)
+    }
+}
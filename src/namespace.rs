@@ -0,0 +1,135 @@
+//! Stable pronounceable aliases for identifiers.
+//!
+//! UUIDs and other opaque identifiers are hard to read out in logs, URLs or support calls. A
+//! [`NamespaceGenerator`] maps every label, e.g. a UUID, to a pronounceable alias such as
+//! `vatoris-plenty-42`: the alias is generated with a seed derived from the label by
+//! HMAC-SHA-256 under a secret namespace key, so the same key always gives the same alias for
+//! the same label, while aliases reveal nothing about the labels to those without the key.
+//!
+//! Aliases are short, so two labels can draw the same one. The generator remembers the
+//! aliases it issued and gives a later label colliding with an earlier one the next alias
+//! derived from it instead; aliases therefore stay stable across runs as long as labels are
+//! first seen in the same order, or the issued aliases are restored with
+//! [`NamespaceGenerator::restore`]. [`NamespaceGenerator::collision_probability`] tells how
+//! likely collisions are for a number of labels; when the pattern runs out of aliases, e.g.
+//! with a generator trained on a handful of words, [`NamespaceGenerator::alias`] fails with
+//! [`Error::AliasesExhausted`] rather than searching forever.
+//!
+//! ```rust
+//! use cryptirust::namespace::NamespaceGenerator;
+//!
+//! let mut aliases = NamespaceGenerator::new(b"namespace key of the support tooling");
+//! let alias = aliases.alias("5f0c6a1e-8d55-4c57-9a53-0d6f1ef07c11")?;
+//! assert_eq!(alias, aliases.alias("5f0c6a1e-8d55-4c57-9a53-0d6f1ef07c11")?);
+//!
+//! let mut again = NamespaceGenerator::new(b"namespace key of the support tooling");
+//! assert_eq!(alias, again.alias("5f0c6a1e-8d55-4c57-9a53-0d6f1ef07c11")?);
+//! # Ok::<(), cryptirust::Error>(())
+//! ```
+use crate::batch::{collision_probability, MAX_REJECTIONS};
+use crate::pattern::Pattern;
+use crate::sha256::HmacSha256;
+use crate::{Error, Generator};
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use std::collections::HashMap;
+
+/// Pattern of the aliases by default.
+pub const DEFAULT_ALIAS_PATTERN: &str = "w-w-dd";
+
+/// Stable aliases for labels, see the [module](self) documentation.
+pub struct NamespaceGenerator {
+    prf: HmacSha256,
+    generator: Generator,
+    pattern: Pattern,
+    /// Label of every issued alias.
+    labels: HashMap<String, String>,
+    /// Alias of every label.
+    aliases: HashMap<String, String>,
+}
+
+impl NamespaceGenerator {
+    /// Aliases derived from `namespace_key`, generated by [`Generator::new`] from
+    /// [`DEFAULT_ALIAS_PATTERN`].
+    pub fn new(namespace_key: &[u8]) -> NamespaceGenerator {
+        NamespaceGenerator::with_generator(namespace_key, Generator::new())
+    }
+
+    /// Aliases derived from `namespace_key`, generated by `generator`; aliases depend on the
+    /// word list, the depth and the settings of the generator.
+    pub fn with_generator(namespace_key: &[u8], generator: Generator) -> NamespaceGenerator {
+        NamespaceGenerator {
+            prf: HmacSha256::new(namespace_key),
+            generator,
+            pattern: Pattern::parse(DEFAULT_ALIAS_PATTERN),
+            labels: HashMap::new(),
+            aliases: HashMap::new(),
+        }
+    }
+
+    /// Sets the pattern of the aliases, which changes all of them.
+    pub fn set_pattern(&mut self, pattern: &str) {
+        self.pattern = Pattern::parse(pattern);
+        self.labels.clear();
+        self.aliases.clear();
+    }
+
+    /// The alias of `label`, the same at every call; fails when no alias free for `label`
+    /// is found within [`MAX_REJECTIONS`] attempts.
+    pub fn alias(&mut self, label: &str) -> Result<String, Error> {
+        if let Some(alias) = self.aliases.get(label) {
+            return Ok(alias.clone());
+        }
+        let mut found = None;
+        for attempt in 0..MAX_REJECTIONS as u32 {
+            let alias = self.derive(label, attempt);
+            if !self.labels.contains_key(&alias) {
+                found = Some(alias);
+                break;
+            }
+        }
+        let alias = found.ok_or(Error::AliasesExhausted {
+            issued: self.labels.len(),
+        })?;
+        self.labels.insert(alias.clone(), label.to_string());
+        self.aliases.insert(label.to_string(), alias.clone());
+        Ok(alias)
+    }
+
+    /// The label of an alias issued by this generator.
+    pub fn label(&self, alias: &str) -> Option<&str> {
+        self.labels.get(alias).map(String::as_str)
+    }
+
+    /// Registers previously issued `(label, alias)` pairs, e.g. loaded from a database, so that
+    /// new labels never receive them. Pairs whose label or alias is already registered are
+    /// skipped.
+    pub fn restore<'a, I: IntoIterator<Item = (&'a str, &'a str)>>(&mut self, issued: I) {
+        for (label, alias) in issued {
+            if self.aliases.contains_key(label) || self.labels.contains_key(alias) {
+                continue;
+            }
+            self.labels.insert(alias.to_string(), label.to_string());
+            self.aliases.insert(label.to_string(), alias.to_string());
+        }
+    }
+
+    /// Probability that some of `labels` labels draw the same alias and need the collision
+    /// fallback, by the birthday bound, given the
+    /// [collision entropy](Pattern::collision_entropy) of the aliases, which accounts for the
+    /// likelier ones; `None` when the pattern has none, e.g. with entropy floors.
+    pub fn collision_probability(&self, labels: usize) -> Option<f64> {
+        let bits = self.pattern.collision_entropy(&self.generator)?;
+        Some(collision_probability(bits, labels as u64))
+    }
+
+    /// The alias derived from `label` at the given collision `attempt`.
+    fn derive(&mut self, label: &str, attempt: u32) -> String {
+        let mut message = label.as_bytes().to_vec();
+        message.push(0);
+        message.extend_from_slice(&attempt.to_be_bytes());
+        let seed = self.prf.mac(&message);
+        self.generator.set_rng(ChaCha8Rng::from_seed(seed));
+        self.generator.gen_annotated(&self.pattern).text
+    }
+}
@@ -0,0 +1,263 @@
+//! Import of markov models trained by other tools.
+//!
+//! Large corpora are more conveniently processed offline, e.g. in Python, than fed to
+//! [`Generator::new_custom`] as a word list. [`Generator::from_ngram_counts`] builds a
+//! generator from a table of transition counts in the schema exported by
+//! [`Generator::model_json`]: every row has a `state`, the lowercase context preceding a token
+//! (empty at the start of a word), the token which comes `next`, and the `count` of its
+//! observations. [`parse_csv`] reads the table from CSV with the header `state,next,count`,
+//! [`parse_json`] from a JSON array of objects with those three members:
+//!
+//! ```text
+//! state,next,count
+//! ,ab,12
+//! ab,le,3
+//! ```
+//!
+//! ```text
+//! [{"state": "", "next": "ab", "count": 12}, {"state": "ab", "next": "le", "count": 3}]
+//! ```
+//!
+//! The JSON form may start with an object recording the
+//! [fingerprint](crate::word_list::fingerprint) of the word list, `{"word_list": "..."}`, and
+//! one recording the [dictionary](Generator::set_dictionary), `{"dictionary": [...]}`, as
+//! written by [`Generator::model_json`]; [`Generator::from_model_json`] loads such a model only
+//! against the same word list, with its dictionary.
+//!
+//! Rows with the same state and token are added together. States longer than the tokens are
+//! fine, but the generator only looks up the last `depth` characters of the text, `depth`
+//! being the length of the longest state or token. The table carries no word lengths, so
+//! [empirical word lengths](Generator::set_empirical_word_lengths) are not available.
+//!
+//! ```rust
+//! use cryptirust::{ngram, Generator};
+//!
+//! let table = "state,next,count\n,ab,12\n,le,4\nab,le,3\nle,ab,2\n";
+//! let counts = ngram::parse_csv(table).unwrap();
+//! let mut generator = Generator::from_ngram_counts(counts).unwrap();
+//! let (passphrase, entropy) = generator.gen_from_pattern("w-w-w");
+//! ```
+use crate::json::{self, Value};
+use crate::{is_dictionary, jump_table_from_transition_matrix, max_depth, Generator, WordLengths};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Header of the CSV schema.
+pub const CSV_HEADER: &str = "state,next,count";
+
+/// A transition count: state, next token and number of observations.
+pub type NgramCount = (String, String, usize);
+
+/// A table of transition counts which could not be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NgramError {
+    /// Line of the error, starting from 1.
+    pub line: usize,
+    /// What is wrong.
+    pub message: String,
+}
+
+impl fmt::Display for NgramError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for NgramError {}
+
+impl Generator {
+    /// Creates a generator from a table of transition counts, see the [module](crate::ngram)
+    /// documentation. States and tokens are lowercased and rows with an empty token or a zero
+    /// count are skipped. Returns `None` if no transition remains.
+    pub fn from_ngram_counts<I: IntoIterator<Item = NgramCount>>(counts: I) -> Option<Generator> {
+        let mut transition_matrix: BTreeMap<String, BTreeMap<String, usize>> = BTreeMap::new();
+        for (state, next, count) in counts {
+            if next.is_empty() || count == 0 {
+                continue;
+            }
+            *transition_matrix
+                .entry(state.to_lowercase())
+                .or_default()
+                .entry(next.to_lowercase())
+                .or_default() += count;
+        }
+        let jump_table = jump_table_from_transition_matrix(transition_matrix);
+        let word_lengths = WordLengths::from_tokens(&[]);
+        let depth = max_depth(&jump_table);
+        Generator::from_jump_table(jump_table, word_lengths, depth)
+    }
+
+    /// Creates a generator from a model serialized by [`Generator::model_json`], checking that
+    /// it was trained on the word list with the [fingerprint](crate::word_list::fingerprint)
+    /// `word_list`, and restoring the [dictionary](Generator::set_dictionary) it records. Fails
+    /// if the model records another word list or none, so that a model is never silently used
+    /// with a different version of its list, and if it has no transitions.
+    pub fn from_model_json(text: &str, word_list: &str) -> Result<Generator, NgramError> {
+        let table = parse_model(text)?;
+        match table.word_list {
+            Some((_, fingerprint)) if fingerprint == word_list => {}
+            Some((line, fingerprint)) => {
+                return Err(NgramError {
+                    line,
+                    message: format!(
+                        "the model was trained on the word list {}, not {}",
+                        fingerprint, word_list
+                    ),
+                })
+            }
+            None => {
+                return Err(NgramError {
+                    line: 1,
+                    message: String::from("the model does not record its word list"),
+                })
+            }
+        }
+        let mut generator =
+            Generator::from_ngram_counts(table.counts).ok_or_else(|| NgramError {
+                line: 1,
+                message: String::from("the model has no transitions"),
+            })?;
+        generator.word_list = Some(word_list.to_string());
+        generator.dictionary = table.dictionary;
+        Ok(generator)
+    }
+}
+
+/// Parses a CSV table with the header [`CSV_HEADER`]. Fields may be quoted with double
+/// quotes, doubling the quotes inside them, and blank lines are ignored.
+pub fn parse_csv(text: &str) -> Result<Vec<NgramCount>, NgramError> {
+    let mut counts = Vec::new();
+    let mut header = true;
+    for (i, line) in text.lines().enumerate() {
+        let error = |message: &str| NgramError {
+            line: i + 1,
+            message: message.to_string(),
+        };
+        let line = line.strip_suffix('\r').unwrap_or(line);
+        if line.trim().is_empty() {
+            continue;
+        }
+        let fields = split_csv(line).ok_or_else(|| error("unterminated quoted field"))?;
+        if header {
+            header = false;
+            if fields.join(",") != CSV_HEADER {
+                return Err(error(&format!("expected the header {}", CSV_HEADER)));
+            }
+            continue;
+        }
+        let [state, next, count]: [String; 3] =
+            fields.try_into().map_err(|_| error("expected 3 fields"))?;
+        let count = count
+            .trim()
+            .parse()
+            .map_err(|_| error("the count is not a non-negative integer"))?;
+        counts.push((state, next, count));
+    }
+    if header {
+        return Err(NgramError {
+            line: 1,
+            message: format!("expected the header {}", CSV_HEADER),
+        });
+    }
+    Ok(counts)
+}
+
+/// Splits a CSV line into its fields, `None` if a quoted field is not terminated.
+fn split_csv(line: &str) -> Option<Vec<String>> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut chars = line.chars().peekable();
+    let mut quoted = false;
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            '"' if quoted => quoted = false,
+            '"' if field.is_empty() => quoted = true,
+            ',' if !quoted => fields.push(std::mem::take(&mut field)),
+            c => field.push(c),
+        }
+    }
+    if quoted {
+        return None;
+    }
+    fields.push(field);
+    Some(fields)
+}
+
+/// Parses a JSON array of objects with the members `state` and `next`, strings, and `count`,
+/// a non-negative integer. Other members are ignored.
+/// Objects with a `word_list` or a `dictionary` member and no `state`, which record the
+/// [fingerprint](crate::word_list::fingerprint) of the word list and the
+/// [dictionary](Generator::set_dictionary), are skipped.
+pub fn parse_json(text: &str) -> Result<Vec<NgramCount>, NgramError> {
+    parse_model(text).map(|table| table.counts)
+}
+
+/// A JSON table of counts, with what it records of the word list.
+struct Table {
+    counts: Vec<NgramCount>,
+    /// Fingerprint of the word list, with its line.
+    word_list: Option<(usize, String)>,
+    dictionary: Vec<String>,
+}
+
+/// Parses a JSON table as [`parse_json`], returning the word list fingerprint and the
+/// dictionary it records, if any, with the counts.
+fn parse_model(text: &str) -> Result<Table, NgramError> {
+    let items = json::parse_array(text).map_err(|e| NgramError {
+        line: e.line(text),
+        message: e.message.to_string(),
+    })?;
+    let mut table = Table {
+        counts: Vec::new(),
+        word_list: None,
+        dictionary: Vec::new(),
+    };
+    for (start, item) in items {
+        let line = json::line_at(text, start);
+        let error = |message: &str| NgramError {
+            line,
+            message: message.to_string(),
+        };
+        if !matches!(item, Value::Object(_)) {
+            return Err(error("a row is not an object"));
+        }
+        let string = |name: &str| match item.get(name) {
+            None => Ok(None),
+            Some(value) => value
+                .as_str()
+                .map(|s| Some(s.to_string()))
+                .ok_or_else(|| error(&format!("{} is not a string", name))),
+        };
+        let count = match item.get("count") {
+            None => None,
+            Some(value) => Some(
+                value
+                    .as_count()
+                    .ok_or_else(|| error("the count is not a non-negative integer"))?,
+            ),
+        };
+        match (string("state")?, string("next")?, count) {
+            (Some(state), Some(next), Some(count)) => table.counts.push((state, next, count)),
+            (None, None, None) if item.get("word_list").is_some() => {
+                table.word_list = Some((line, string("word_list")?.unwrap_or_default()))
+            }
+            (None, None, None) if item.get("dictionary").is_some() => {
+                table.dictionary = match item.get("dictionary") {
+                    Some(Value::Array(words)) => words
+                        .iter()
+                        .map(|word| word.as_str().map(str::to_string))
+                        .collect::<Option<_>>()
+                        .filter(|words: &Vec<String>| is_dictionary(words))
+                        .ok_or_else(|| error("the dictionary is not an array of distinct words"))?,
+                    _ => return Err(error("the dictionary is not an array of distinct words")),
+                }
+            }
+            _ => return Err(error("a row lacks one of state, next and count")),
+        }
+    }
+    Ok(table)
+}
@@ -0,0 +1,119 @@
+//! Generation from async code.
+//!
+//! [`Generator`] is `Send` and `Sync`, so it can be moved into tasks or kept behind a lock by
+//! async web handlers. Generating a secret takes microseconds and never blocks on I/O, but
+//! large batches keep the executor thread busy for their whole duration. [`AsyncGenerator`]
+//! generates them in chunks of [`CHUNK`] secrets and yields to the executor between chunks, so
+//! that other tasks make progress without `spawn_blocking`.
+//!
+//! Secrets are produced when asked for: a consumer awaiting [`AsyncGenerator::next`] at its
+//! own pace, e.g. while writing a response body, applies backpressure naturally, and nothing is
+//! buffered ahead of it. The facade only uses `std::future`, so it works with any executor.
+//!
+//! The crate does not depend on `futures`, so it does not implement `futures::Stream`;
+//! [`AsyncGenerator::poll_next`] has the signature of `Stream::poll_next` instead, and
+//! `futures::stream::poll_fn(move |cx| generator.poll_next(cx))` turns the generator into an
+//! endless stream of secrets.
+//!
+//! ```rust
+//! use cryptirust::{nonblocking::AsyncGenerator, Generator};
+//!
+//! async fn handler(generator: &mut AsyncGenerator) -> Vec<String> {
+//!     generator
+//!         .gen_batch(10_000)
+//!         .await
+//!         .into_iter()
+//!         .map(|(secret, _)| secret)
+//!         .collect()
+//! }
+//!
+//! let generator = AsyncGenerator::new(Generator::new(), "w-w-w-w-dd");
+//! ```
+use crate::pattern::Pattern;
+use crate::Generator;
+use rand::{CryptoRng, RngCore};
+use rand_chacha::ChaCha8Rng;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// Number of secrets generated between two yields to the executor.
+pub const CHUNK: usize = 256;
+
+/// A [`Generator`] bound to a pattern, with async methods, see the [module](self)
+/// documentation.
+pub struct AsyncGenerator<R = ChaCha8Rng> {
+    generator: Generator<R>,
+    pattern: Pattern,
+    /// Secrets returned by [`AsyncGenerator::poll_next`] since it last yielded.
+    polled: usize,
+}
+
+impl<R: RngCore + CryptoRng> AsyncGenerator<R> {
+    /// Generates secrets from `pattern` with `generator`.
+    pub fn new(generator: Generator<R>, pattern: &str) -> AsyncGenerator<R> {
+        AsyncGenerator {
+            generator,
+            pattern: Pattern::parse(pattern),
+            polled: 0,
+        }
+    }
+
+    /// The next secret with its entropy in bits.
+    pub async fn next(&mut self) -> (String, f64) {
+        self.generator.gen_from_parsed(&self.pattern)
+    }
+
+    /// `count` secrets with their entropy in bits, yielding to the executor every [`CHUNK`]
+    /// secrets.
+    pub async fn gen_batch(&mut self, count: usize) -> Vec<(String, f64)> {
+        let mut secrets = Vec::with_capacity(count);
+        while secrets.len() < count {
+            let chunk = CHUNK.min(count - secrets.len());
+            secrets.extend((0..chunk).map(|_| self.generator.gen_from_parsed(&self.pattern)));
+            if secrets.len() < count {
+                YieldNow(false).await;
+            }
+        }
+        secrets
+    }
+
+    /// Polls for the next secret with its entropy in bits, as `futures::Stream::poll_next`,
+    /// see the [module](self) documentation. The secrets never end; every [`CHUNK`] secrets
+    /// the task is woken and `Pending` returned once, yielding to the executor.
+    pub fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Option<(String, f64)>> {
+        if self.polled == CHUNK {
+            self.polled = 0;
+            cx.waker().wake_by_ref();
+            return Poll::Pending;
+        }
+        self.polled += 1;
+        Poll::Ready(Some(self.generator.gen_from_parsed(&self.pattern)))
+    }
+
+    /// The underlying generator, e.g. to change its settings.
+    pub fn generator_mut(&mut self) -> &mut Generator<R> {
+        &mut self.generator
+    }
+
+    /// Gives back the underlying generator.
+    pub fn into_inner(self) -> Generator<R> {
+        self.generator
+    }
+}
+
+/// Future returning `Pending` once, after asking to be polled again.
+struct YieldNow(bool);
+
+impl Future for YieldNow {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.0 {
+            return Poll::Ready(());
+        }
+        self.0 = true;
+        cx.waker().wake_by_ref();
+        Poll::Pending
+    }
+}
@@ -0,0 +1,150 @@
+//! Unicode normalization of secrets.
+//!
+//! The same text can be written with different code points: `é` is either the single code
+//! point U+00E9 or `e` followed by the combining acute accent U+0301. Systems comparing
+//! secrets byte by byte, e.g. a hash stored by a service whose login form normalizes its
+//! input, reject a secret written in the other form. [`normalize`] rewrites a text in
+//! [NFC](NormalizationForm::Nfc), composed, or [NFD](NormalizationForm::Nfd), decomposed.
+//!
+//! ```rust
+//! use cryptirust::normalize::{normalize, NormalizationForm};
+//!
+//! let composed = "caff\u{e8}-\u{439}";
+//! let decomposed = "caffe\u{300}-\u{438}\u{306}";
+//! assert_eq!(normalize(composed, NormalizationForm::Nfd), decomposed);
+//! assert_eq!(normalize(decomposed, NormalizationForm::Nfc), composed);
+//! ```
+//!
+//! The normalization follows Unicode Standard Annex #15 with the data of the Unicode Character
+//! Database, version 14.0, including the algorithmic composition of Hangul syllables.
+
+mod tables;
+
+use std::cmp::Ordering;
+use tables::{COMBINING_CLASSES, COMPOSITIONS, DECOMPOSITIONS, SINGLETONS};
+
+/// A Unicode normalization form.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NormalizationForm {
+    /// Canonical composition: letters with diacritics as single code points where possible.
+    Nfc,
+    /// Canonical decomposition: letters followed by their combining diacritics.
+    Nfd,
+}
+
+// Hangul syllables are `S_BASE + (l * V_COUNT + v) * T_COUNT + t`, for a leading consonant
+// `L_BASE + l`, a vowel `V_BASE + v` and an optional trailing consonant `T_BASE + t`.
+const S_BASE: u32 = 0xAC00;
+const L_BASE: u32 = 0x1100;
+const V_BASE: u32 = 0x1161;
+const T_BASE: u32 = 0x11A7;
+const L_COUNT: u32 = 19;
+const V_COUNT: u32 = 21;
+const T_COUNT: u32 = 28;
+const S_COUNT: u32 = L_COUNT * V_COUNT * T_COUNT;
+
+/// `c` as a char, for code points computed from valid characters.
+fn hangul(c: u32) -> char {
+    char::from_u32(c).unwrap()
+}
+
+/// Canonical combining class of `c`, 0 for starters.
+fn combining_class(c: char) -> u8 {
+    let c = c as u32;
+    match COMBINING_CLASSES.binary_search_by(|&(first, last, _)| {
+        if last < c {
+            Ordering::Less
+        } else if first > c {
+            Ordering::Greater
+        } else {
+            Ordering::Equal
+        }
+    }) {
+        Ok(i) => COMBINING_CLASSES[i].2,
+        Err(_) => 0,
+    }
+}
+
+/// Appends the full canonical decomposition of `c` to `out`.
+fn decompose(c: char, out: &mut Vec<char>) {
+    let s = (c as u32).wrapping_sub(S_BASE);
+    if s < S_COUNT {
+        out.push(hangul(L_BASE + s / (V_COUNT * T_COUNT)));
+        out.push(hangul(V_BASE + s % (V_COUNT * T_COUNT) / T_COUNT));
+        if !s.is_multiple_of(T_COUNT) {
+            out.push(hangul(T_BASE + s % T_COUNT));
+        }
+    } else if let Ok(i) = DECOMPOSITIONS.binary_search_by_key(&c, |&(composed, _, _)| composed) {
+        let (_, first, second) = DECOMPOSITIONS[i];
+        decompose(first, out);
+        decompose(second, out);
+    } else if let Ok(i) = SINGLETONS.binary_search_by_key(&c, |&(composed, _)| composed) {
+        decompose(SINGLETONS[i].1, out);
+    } else {
+        out.push(c);
+    }
+}
+
+/// The primary composite of `first` followed by `second`, if any.
+fn compose(first: char, second: char) -> Option<char> {
+    let (a, b) = (first as u32, second as u32);
+    let l = a.wrapping_sub(L_BASE);
+    let v = b.wrapping_sub(V_BASE);
+    if l < L_COUNT && v < V_COUNT {
+        return Some(hangul(S_BASE + (l * V_COUNT + v) * T_COUNT));
+    }
+    let s = a.wrapping_sub(S_BASE);
+    let t = b.wrapping_sub(T_BASE);
+    if s < S_COUNT && s.is_multiple_of(T_COUNT) && (1..T_COUNT).contains(&t) {
+        return Some(hangul(a + t));
+    }
+    COMPOSITIONS
+        .binary_search_by_key(&(first, second), |&(a, b, _)| (a, b))
+        .ok()
+        .map(|i| COMPOSITIONS[i].2)
+}
+
+/// `text` in the normalization form `form`, see the [module](self) documentation.
+pub fn normalize(text: &str, form: NormalizationForm) -> String {
+    let mut chars = Vec::with_capacity(text.len());
+    for c in text.chars() {
+        decompose(c, &mut chars);
+    }
+    // canonical ordering: stable sort of every run of combining marks by class
+    let mut start = 0;
+    while start < chars.len() {
+        if combining_class(chars[start]) == 0 {
+            start += 1;
+            continue;
+        }
+        let len = chars[start..]
+            .iter()
+            .take_while(|&&c| combining_class(c) != 0)
+            .count();
+        chars[start..start + len].sort_by_key(|&c| combining_class(c));
+        start += len;
+    }
+    if form == NormalizationForm::Nfd {
+        return chars.into_iter().collect();
+    }
+    // canonical composition of every mark with the last starter, unless blocked
+    let mut out: Vec<char> = Vec::with_capacity(chars.len());
+    let mut starter: Option<usize> = None;
+    let mut last_class = 0;
+    for c in chars {
+        let class = combining_class(c);
+        if let Some(i) = starter {
+            let blocked = out.len() > i + 1 && (last_class == 0 || last_class >= class);
+            if let (false, Some(composed)) = (blocked, compose(out[i], c)) {
+                out[i] = composed;
+                continue;
+            }
+        }
+        if class == 0 {
+            starter = Some(out.len());
+        }
+        last_class = class;
+        out.push(c);
+    }
+    out.into_iter().collect()
+}
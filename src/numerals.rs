@@ -0,0 +1,101 @@
+//! Roman numerals and ordinal words in patterns.
+//!
+//! The `{roman}` element of a pattern produces a Roman numeral such as `XIV`, the `{ordinal}`
+//! element an English ordinal word such as `third` or `forty-second`, both drawn uniformly,
+//! for themed credentials and display names. The range of the numbers defaults to 1 through
+//! [`MAX_ROMAN`] and [`MAX_ORDINAL`] and is set as `{roman:1-12}` or `{ordinal:1-31}`. Bounds
+//! beyond the supported numbers are refused by
+//! [`Generator::try_gen_from_pattern`](crate::Generator::try_gen_from_pattern) and
+//! [`Generator::parse_limited`](crate::Generator::parse_limited), see
+//! [`Pattern::numeral_out_of_range`](crate::pattern::Pattern::numeral_out_of_range), and
+//! lowered to the supported numbers otherwise. Every number has a distinct numeral or
+//! word, so the element adds `log2(max - min + 1)` bits of entropy.
+//!
+//! ```rust
+//! use cryptirust::{numerals, Generator};
+//!
+//! assert_eq!(numerals::roman(1994).unwrap(), "MCMXCIV");
+//! assert_eq!(numerals::ordinal(42).unwrap(), "forty-second");
+//!
+//! let mut generator = Generator::new();
+//! let (name, entropy) = generator.gen_from_pattern("the-{ordinal:1-16}-{roman:1-4}");
+//! assert_eq!(entropy, 6.0);
+//! ```
+
+/// Largest number written in Roman numerals.
+pub const MAX_ROMAN: u32 = 3999;
+/// Largest number written as an ordinal word.
+pub const MAX_ORDINAL: u32 = 99;
+
+/// `n` in Roman numerals, `None` unless `1 <= n <= MAX_ROMAN`.
+pub fn roman(n: u32) -> Option<String> {
+    if !(1..=MAX_ROMAN).contains(&n) {
+        return None;
+    }
+    const VALUES: [(u32, &str); 13] = [
+        (1000, "M"),
+        (900, "CM"),
+        (500, "D"),
+        (400, "CD"),
+        (100, "C"),
+        (90, "XC"),
+        (50, "L"),
+        (40, "XL"),
+        (10, "X"),
+        (9, "IX"),
+        (5, "V"),
+        (4, "IV"),
+        (1, "I"),
+    ];
+    let mut rest = n;
+    let mut numeral = String::new();
+    for (value, letters) in VALUES {
+        while rest >= value {
+            numeral.push_str(letters);
+            rest -= value;
+        }
+    }
+    Some(numeral)
+}
+
+/// `n` as an English ordinal word, e.g. `twenty-first`, `None` unless
+/// `1 <= n <= MAX_ORDINAL`.
+pub fn ordinal(n: u32) -> Option<String> {
+    const UNITS: [&str; 20] = [
+        "",
+        "first",
+        "second",
+        "third",
+        "fourth",
+        "fifth",
+        "sixth",
+        "seventh",
+        "eighth",
+        "ninth",
+        "tenth",
+        "eleventh",
+        "twelfth",
+        "thirteenth",
+        "fourteenth",
+        "fifteenth",
+        "sixteenth",
+        "seventeenth",
+        "eighteenth",
+        "nineteenth",
+    ];
+    const TENS: [&str; 10] = [
+        "", "", "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety",
+    ];
+    match n {
+        1..=19 => Some(UNITS[n as usize].to_string()),
+        20..=MAX_ORDINAL => {
+            let tens = TENS[(n / 10) as usize];
+            Some(match n % 10 {
+                // twentieth, thirtieth, ...
+                0 => tens.trim_end_matches('y').to_string() + "ieth",
+                unit => format!("{}-{}", tens, UNITS[unit as usize]),
+            })
+        }
+        _ => None,
+    }
+}
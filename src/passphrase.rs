@@ -0,0 +1,212 @@
+//! Passphrases described by a structured specification instead of a pattern string.
+//!
+//! A [`PassphraseSpec`] gives the number of words, the separator between them, their
+//! [`Case`] and the number of digits at the end, and is easier to build from a configuration
+//! form or file than a pattern. [`Generator::gen_passphrase`] checks the specification with
+//! [`PassphraseSpec::validate`] before generating, so that a choice which would make the
+//! passphrase weaker than reported fails with a [`SpecError`].
+//!
+//! ```rust
+//! use cryptirust::passphrase::{Case, PassphraseSpec};
+//! use cryptirust::Generator;
+//!
+//! let mut generator = Generator::new();
+//! let spec = PassphraseSpec {
+//!     words: 5,
+//!     separator: String::from("-"),
+//!     case: Case::Title,
+//!     suffix_digits: 2,
+//! };
+//! let (passphrase, entropy) = generator.gen_passphrase(&spec).unwrap();
+//! assert_eq!(passphrase.split('-').count(), 6);
+//!
+//! let run_together = PassphraseSpec {
+//!     separator: String::new(),
+//!     ..PassphraseSpec::default()
+//! };
+//! assert!(generator.gen_passphrase(&run_together).is_err());
+//! ```
+use crate::pattern::{Pattern, Segment};
+use crate::Generator;
+use rand::{CryptoRng, RngCore};
+use std::collections::{BTreeSet, HashSet};
+use std::fmt;
+
+/// Most words of a passphrase, as the default
+/// [element limit](crate::limits::DEFAULT_MAX_ELEMENTS) of patterns.
+pub const MAX_WORDS: usize = 64;
+
+/// Most digits after the last word of a passphrase, as for words.
+pub const MAX_SUFFIX_DIGITS: usize = 64;
+
+/// Capitalization of the words of a passphrase.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Case {
+    /// `correct-horse`.
+    #[default]
+    Lower,
+    /// `Correct-Horse`, as the `W` element.
+    Title,
+    /// `CORRECT-HORSE`.
+    Upper,
+}
+
+/// Structure of a passphrase generated by [`Generator::gen_passphrase`]; the default is five
+/// lowercase words separated by `-`, without digits.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PassphraseSpec {
+    /// Number of words, from 1 to [`MAX_WORDS`].
+    pub words: usize,
+    /// Text between consecutive words, and before the digits.
+    pub separator: String,
+    /// Capitalization of the words; the separator is written as given.
+    pub case: Case,
+    /// Number of random digits after the last word, up to [`MAX_SUFFIX_DIGITS`].
+    pub suffix_digits: usize,
+}
+
+impl Default for PassphraseSpec {
+    fn default() -> Self {
+        PassphraseSpec {
+            words: 5,
+            separator: String::from("-"),
+            case: Case::Lower,
+            suffix_digits: 0,
+        }
+    }
+}
+
+/// A [`PassphraseSpec`] which cannot generate a passphrase.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpecError {
+    /// The number of words is zero.
+    NoWords,
+    /// The number of words is over [`MAX_WORDS`].
+    TooManyWords { words: usize },
+    /// The number of digits is over [`MAX_SUFFIX_DIGITS`].
+    TooManyDigits { digits: usize },
+    /// The separator is empty, or has letters, and the words are not title case: the
+    /// boundaries between words cannot be told apart, and the same passphrase can be produced
+    /// by several sequences of words, which makes it weaker than reported.
+    AmbiguousSeparator,
+    /// The words are in [`Case::Upper`], and uppercasing does not keep apart every character
+    /// of the model under the [locale](Generator::set_locale) of the generator, e.g. `i` and
+    /// `ı` both give `I`, and `ß` gives `SS`: different words can give the same passphrase,
+    /// which makes it weaker than reported.
+    AmbiguousUppercase,
+}
+
+impl fmt::Display for SpecError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SpecError::NoWords => write!(f, "the passphrase must have at least 1 word"),
+            SpecError::TooManyWords { words } => write!(
+                f,
+                "the passphrase has {} words, more than the maximum of {}",
+                words, MAX_WORDS
+            ),
+            SpecError::TooManyDigits { digits } => write!(
+                f,
+                "the passphrase has {} digits, more than the maximum of {}",
+                digits, MAX_SUFFIX_DIGITS
+            ),
+            SpecError::AmbiguousSeparator => write!(
+                f,
+                "the separator does not mark the boundaries between words"
+            ),
+            SpecError::AmbiguousUppercase => write!(
+                f,
+                "uppercasing makes different words of the model look the same"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SpecError {}
+
+impl PassphraseSpec {
+    /// Checks that the specification can generate a passphrase as strong as reported.
+    pub fn validate(&self) -> Result<(), SpecError> {
+        if self.words == 0 {
+            return Err(SpecError::NoWords);
+        }
+        if self.words > MAX_WORDS {
+            return Err(SpecError::TooManyWords { words: self.words });
+        }
+        if self.suffix_digits > MAX_SUFFIX_DIGITS {
+            return Err(SpecError::TooManyDigits {
+                digits: self.suffix_digits,
+            });
+        }
+        let marks_boundaries =
+            !self.separator.is_empty() && !self.separator.chars().any(char::is_alphabetic);
+        if self.words > 1 && !marks_boundaries && self.case != Case::Title {
+            return Err(SpecError::AmbiguousSeparator);
+        }
+        Ok(())
+    }
+
+    /// The pattern generating the passphrase, in lowercase for [`Case::Upper`].
+    fn pattern(&self) -> Pattern {
+        let word = Segment::Word {
+            capitalized: self.case == Case::Title,
+            min_entropy: None,
+        };
+        let separator = Segment::Literal(self.separator.clone());
+        let mut segments = vec![word.clone()];
+        for _ in 1..self.words {
+            segments.extend([separator.clone(), word.clone()]);
+        }
+        if self.suffix_digits > 0 {
+            segments.push(separator);
+            segments.extend(std::iter::repeat_n(Segment::Digit, self.suffix_digits));
+        }
+        segments.retain(|segment| segment != &Segment::Literal(String::new()));
+        Pattern::from_segments(segments)
+    }
+}
+
+impl<R: RngCore + CryptoRng> Generator<R> {
+    /// Generates a passphrase following `spec`, see the [module](crate::passphrase)
+    /// documentation, with its entropy. Fails when [`PassphraseSpec::validate`] does.
+    ///
+    /// [`Case::Upper`] reports the entropy of [`Case::Lower`], which holds when uppercasing
+    /// keeps the words distinct: every character of the model must have an uppercase of its
+    /// own, a single character, under the [locale](Generator::set_locale) of the generator,
+    /// or generation fails with [`SpecError::AmbiguousUppercase`].
+    pub fn gen_passphrase(&mut self, spec: &PassphraseSpec) -> Result<(String, f64), SpecError> {
+        spec.validate()?;
+        if spec.case == Case::Upper && !self.uppercase_is_injective() {
+            return Err(SpecError::AmbiguousUppercase);
+        }
+        let generated = self.gen_annotated(&spec.pattern());
+        if spec.case != Case::Upper {
+            return Ok((generated.text, generated.entropy));
+        }
+        let mut passphrase = String::with_capacity(generated.text.len());
+        let mut last = 0;
+        for word in &generated.words {
+            passphrase.push_str(&generated.text[last..word.range.start]);
+            passphrase.push_str(&self.locale.uppercase(&generated.text[word.range.clone()]));
+            last = word.range.end;
+        }
+        passphrase.push_str(&generated.text[last..]);
+        Ok((passphrase, generated.entropy))
+    }
+
+    /// Whether uppercasing, under the locale, maps the characters of the model to distinct
+    /// single characters, so that it maps distinct texts to distinct texts.
+    fn uppercase_is_injective(&self) -> bool {
+        let alphabet: BTreeSet<char> = self
+            .jump_table
+            .values()
+            .flat_map(|distribution| distribution.tokens.iter())
+            .flat_map(|token| token.chars())
+            .collect();
+        let mut images = HashSet::new();
+        alphabet.into_iter().all(|c| {
+            let upper = self.locale.uppercase(c.encode_utf8(&mut [0; 4]));
+            upper.chars().count() == 1 && images.insert(upper)
+        })
+    }
+}
@@ -0,0 +1,653 @@
+//! Parsed representation of the pattern mini-language understood by
+//! [`Generator::gen_from_pattern`](crate::Generator::gen_from_pattern).
+//!
+//! Parsing a pattern once and generating from the parsed form with
+//! [`Generator::gen_from_parsed`](crate::Generator::gen_from_parsed) avoids parsing the same
+//! string over and over, and gives programs access to the structure of a pattern.
+//!
+//! ```rust
+//! use cryptirust::pattern::{Pattern, Segment};
+//!
+//! let pattern = Pattern::parse("W-\\dd");
+//! assert_eq!(
+//!     pattern.segments(),
+//!     &[
+//!         Segment::Word { capitalized: true, min_entropy: None },
+//!         Segment::Literal(String::from("-d")),
+//!         Segment::Digit,
+//!     ]
+//! );
+//! ```
+//!
+//! A `w`, `W`, `c` or `C` element followed by `{>=bits}`, e.g. `w{>=12}`, is resampled until
+//! its own entropy reaches `bits`, bounding the weakest link of a passphrase, see
+//! [`MAX_ELEMENT_FLOOR`].
+//!
+//! An element followed by `{n}` is repeated `n` times, e.g. `d{6}` for six digits or
+//! `w{>=12}{3}` for three words with a floor, and so is a group in parentheses, e.g. `(w-){3}w`
+//! for four words separated by hyphens. Parentheses not followed by a count, and counts after
+//! literal text, are copied verbatim, and so is the count of a repetition which would produce
+//! more than [`MAX_REPETITION`] segments.
+//!
+//! ```rust
+//! use cryptirust::pattern::Pattern;
+//!
+//! assert_eq!(Pattern::parse("(w-){3}w.d{4}"), Pattern::parse("w-w-w-w.dddd"));
+//! assert_eq!(Pattern::parse("((W){2}s){2}"), Pattern::parse("WWsWWs"));
+//! assert_eq!(Pattern::parse("(w)-z{2}"), Pattern::parse("\\(w\\)-z\\{2}"));
+//! ```
+//!
+//! Brackets pick one of several alternatives, each equally likely: the parts separated by `|`,
+//! e.g. `[w|c]` for a word or a token and `[W-d|dd]`, or every element when there is no
+//! separator, e.g. `[sd]` for a symbol or a digit. The choice adds `log2` of the number of
+//! alternatives to the entropy, which assumes that the alternatives produce distinct texts:
+//! `[w|c]` overstates it by the little probability that a word is also a token. An alternation
+//! can be repeated, each repetition choosing again, and an unclosed bracket is copied verbatim.
+//!
+//! ```rust
+//! use cryptirust::pattern::{Pattern, Segment};
+//!
+//! let pattern = Pattern::parse("w[sd]{2}");
+//! assert_eq!(pattern.segments().len(), 3);
+//! assert_eq!(
+//!     pattern.segments()[1],
+//!     Segment::Choice(vec![Pattern::parse("s"), Pattern::parse("d")])
+//! );
+//! assert_eq!(Pattern::parse("[w|cc]"), Pattern::parse("[w|(c){2}]"));
+//! ```
+//!
+//! `x` and `X` produce a real word of the word list, drawn uniformly and copied verbatim, or
+//! capitalized, instead of a pseudo-word, to mix diceware words with pronounceable ones, e.g.
+//! `x-w-x-dd`; see [`Generator::set_dictionary`](crate::Generator::set_dictionary).
+//!
+//! `{roman}` and `{ordinal}` produce a Roman numeral or an ordinal word, with an optional range
+//! such as `{roman:1-12}`, see [numerals]. `{seq}` produces the number of the secret in a
+//! batch, see [`Generator::set_sequence`](crate::Generator::set_sequence).
+//!
+//! Scripts parameterize patterns with `{env:NAME}` and `{arg:NAME}` placeholders, replaced by
+//! [`expand_variables`] with the value of an environment variable or of an argument before
+//! parsing. Values are escaped, so that they are copied verbatim to the output and cannot
+//! inject pattern elements:
+//!
+//! ```rust
+//! use cryptirust::pattern::{expand_variables, Pattern, Segment};
+//!
+//! let pattern = expand_variables("{arg:site}-w-w", |source, name| match (source, name) {
+//!     ("arg", "site") => Some("dw.com".to_string()),
+//!     ("env", name) => std::env::var(name).ok(),
+//!     _ => None,
+//! })
+//! .unwrap();
+//! assert_eq!(
+//!     Pattern::parse(&pattern).segments()[0],
+//!     Segment::Literal(String::from("dw.com-"))
+//! );
+//! ```
+
+use crate::numerals::{self, MAX_ORDINAL, MAX_ROMAN};
+#[cfg(feature = "wordlist-pos")]
+use crate::story::PartOfSpeech;
+use std::collections::HashMap;
+use std::fmt;
+
+/// Characters with a special meaning in patterns.
+const SPECIAL: &str = "cCwWxXsd{()[|]\\";
+
+/// Largest entropy floor of a single element, in bits. Larger floors are lowered to it when
+/// generating; [`Generator::try_gen_from_pattern`](crate::Generator::try_gen_from_pattern) and
+/// [`Generator::parse_limited`](crate::Generator::parse_limited) refuse them, see
+/// [`Pattern::highest_floor`].
+///
+/// The reported entropy of an element with a floor accounts for the resampling, which requires
+/// enumerating the outcomes below the floor: their number grows as `2^bits`.
+pub const MAX_ELEMENT_FLOOR: f64 = 20.0;
+
+/// Most steps taken by the walks through the chain states which compute the
+/// [expected](Pattern::expected_entropy), [largest](crate::Generator::max_achievable_entropy)
+/// and [smallest](crate::Generator::min_achievable_entropy) entropy of a pattern; each step
+/// extends a context by one outcome. Beyond it they give up, returning `None`, and
+/// [`Generator::try_gen_from_pattern`](crate::Generator::try_gen_from_pattern) refuses the
+/// pattern.
+///
+/// The walks visit every context the text can end with: a few hundred thousand steps for
+/// separated words with the default depth of 3, but deeper models, empirical word lengths and
+/// words written without separators multiply them. The limit keeps a walk within a few
+/// hundred milliseconds.
+pub const MAX_WALK_STEPS: usize = 1 << 22;
+
+/// Most segments a single repetition, `d{6}` or `(w-){3}`, may produce; the counts of larger
+/// repetitions are copied verbatim, so that a short pattern cannot expand to a huge one.
+pub const MAX_REPETITION: usize = 1024;
+
+/// One element of a parsed pattern.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Segment {
+    /// Text copied verbatim to the output.
+    Literal(String),
+    /// A symbol, `s`.
+    Symbol,
+    /// A digit, `d`.
+    Digit,
+    /// A markov chain token, `c` or `C`, with an optional entropy floor in bits.
+    Token {
+        capitalized: bool,
+        min_entropy: Option<f64>,
+    },
+    /// A pseudo-word made of markov chain tokens, `w` or `W`, with an optional entropy floor
+    /// in bits.
+    Word {
+        capitalized: bool,
+        min_entropy: Option<f64>,
+    },
+    /// A real word drawn verbatim from the word list of the generator, `x` or `X`, see
+    /// [`Generator::set_dictionary`](crate::Generator::set_dictionary).
+    Dictionary { capitalized: bool },
+    /// A word from a part-of-speech list, `{adj}`, `{noun}` or `{verb}`.
+    #[cfg(feature = "wordlist-pos")]
+    Part(PartOfSpeech),
+    /// A Roman numeral of a number from `min` to `max`, `{roman}` or `{roman:min-max}`.
+    Roman { min: u32, max: u32 },
+    /// An ordinal word of a number from `min` to `max`, `{ordinal}` or `{ordinal:min-max}`.
+    Ordinal { min: u32, max: u32 },
+    /// The number of the secret in a batch counting from `start`, zero-padded to `width`
+    /// digits, `{seq}` or `{seq:start}`; it is not random and adds no entropy.
+    Sequence { start: u64, width: usize },
+    /// One of several equally likely alternatives, `[sd]` or `[w|c]`.
+    Choice(Vec<Pattern>),
+}
+
+impl Segment {
+    /// Whether the segment produces a word: a markov chain token, a pseudo-word, a real word
+    /// or a word from a part-of-speech list.
+    pub fn is_word(&self) -> bool {
+        match self {
+            Segment::Token { .. } | Segment::Word { .. } | Segment::Dictionary { .. } => true,
+            #[cfg(feature = "wordlist-pos")]
+            Segment::Part(_) => true,
+            _ => false,
+        }
+    }
+
+    /// The outcomes of a `{roman}` or `{ordinal}` element, all equally likely, `None` for
+    /// other segments; bounds beyond the supported numbers are lowered to them.
+    pub(crate) fn numerals(&self) -> Option<Vec<String>> {
+        match *self {
+            Segment::Roman { min, max } => (min.max(1)..=max.min(MAX_ROMAN))
+                .map(numerals::roman)
+                .collect(),
+            Segment::Ordinal { min, max } => (min.max(1)..=max.min(MAX_ORDINAL))
+                .map(numerals::ordinal)
+                .collect(),
+            _ => None,
+        }
+    }
+}
+
+/// A parsed pattern.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pattern {
+    segments: Vec<Segment>,
+}
+
+impl Pattern {
+    /// Parses a pattern string; every string is a valid pattern.
+    pub fn parse(pattern: &str) -> Pattern {
+        let mut segments = Vec::new();
+        parse_into(pattern, &Brackets::new(pattern), 0, &mut segments);
+        Pattern { segments }
+    }
+
+    /// Builds a pattern from its segments.
+    pub fn from_segments(segments: Vec<Segment>) -> Pattern {
+        Pattern { segments }
+    }
+
+    /// The segments of the pattern, consecutive literal characters are merged.
+    pub fn segments(&self) -> &[Segment] {
+        &self.segments
+    }
+
+    /// Whether some element of the pattern, or of its alternatives, has an entropy floor,
+    /// e.g. `w{>=12}`.
+    pub fn has_floors(&self) -> bool {
+        self.segments.iter().any(|segment| match segment {
+            Segment::Choice(alternatives) => alternatives.iter().any(Pattern::has_floors),
+            _ => matches!(
+                segment,
+                Segment::Word {
+                    min_entropy: Some(_),
+                    ..
+                } | Segment::Token {
+                    min_entropy: Some(_),
+                    ..
+                }
+            ),
+        })
+    }
+
+    /// The highest entropy floor of the elements of the pattern, or of its alternatives, as
+    /// written; floors above [`MAX_ELEMENT_FLOOR`] are lowered to it when generating.
+    pub fn highest_floor(&self) -> Option<f64> {
+        self.all_segments()
+            .into_iter()
+            .filter_map(|segment| match segment {
+                Segment::Word { min_entropy, .. } | Segment::Token { min_entropy, .. } => {
+                    *min_entropy
+                }
+                _ => None,
+            })
+            .reduce(f64::max)
+    }
+
+    /// The range of the first `{roman}` or `{ordinal}` element, or of its alternatives, going
+    /// beyond the supported numbers, as `(min, max, largest)` with the bounds as written and
+    /// the largest supported number; those bounds are lowered to the supported numbers when
+    /// generating.
+    pub fn numeral_out_of_range(&self) -> Option<(u32, u32, u32)> {
+        self.all_segments()
+            .into_iter()
+            .filter_map(|segment| match *segment {
+                Segment::Roman { min, max } => Some((min, max, MAX_ROMAN)),
+                Segment::Ordinal { min, max } => Some((min, max, MAX_ORDINAL)),
+                _ => None,
+            })
+            .find(|&(min, max, largest)| min < 1 || max > largest)
+    }
+
+    /// Whether the pattern has an alternation, e.g. `[sd]`.
+    pub fn has_choices(&self) -> bool {
+        self.segments
+            .iter()
+            .any(|segment| matches!(segment, Segment::Choice(_)))
+    }
+
+    /// Whether the pattern, or one of its alternatives, draws real words from the
+    /// [dictionary](crate::Generator::set_dictionary) with `x` or `X`.
+    pub fn has_dictionary_words(&self) -> bool {
+        self.all_segments()
+            .iter()
+            .any(|segment| matches!(segment, Segment::Dictionary { .. }))
+    }
+
+    /// The segments of the pattern and, recursively, of the alternatives of its choices.
+    pub(crate) fn all_segments(&self) -> Vec<&Segment> {
+        let mut all = Vec::new();
+        for segment in &self.segments {
+            all.push(segment);
+            if let Segment::Choice(alternatives) = segment {
+                all.extend(alternatives.iter().flat_map(Pattern::all_segments));
+            }
+        }
+        all
+    }
+
+    /// Escapes every character of `literal` which has a special meaning in patterns, so that
+    /// user supplied text (e.g. a site name) can be interpolated into a pattern and is copied
+    /// verbatim to the output.
+    ///
+    /// ```rust
+    /// use cryptirust::pattern::{Pattern, Segment};
+    ///
+    /// let site = "dw.com";
+    /// let pattern = format!("{}:w-w", Pattern::escape_literal(site));
+    /// assert_eq!(pattern, "\\d\\w.\\com:w-w");
+    /// assert_eq!(
+    ///     Pattern::parse(&pattern).segments()[0],
+    ///     Segment::Literal(String::from("dw.com:"))
+    /// );
+    /// ```
+    pub fn escape_literal(literal: &str) -> String {
+        let mut escaped = String::with_capacity(literal.len());
+        for c in literal.chars() {
+            if SPECIAL.contains(c) {
+                escaped.push('\\');
+            }
+            escaped.push(c);
+        }
+        escaped
+    }
+}
+
+/// A placeholder of [`expand_variables`] without a value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MissingVariable {
+    /// `env` or `arg`.
+    pub source: String,
+    /// Name of the variable.
+    pub name: String,
+}
+
+impl fmt::Display for MissingVariable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "no value for {{{}:{}}}", self.source, self.name)
+    }
+}
+
+impl std::error::Error for MissingVariable {}
+
+/// `pattern` with every `{env:NAME}` and `{arg:NAME}` placeholder replaced by the value given
+/// by `lookup` for the source, `env` or `arg`, and the name, escaped with
+/// [`Pattern::escape_literal`]; escaped braces, `\{`, are left alone. Fails on the first
+/// placeholder for which `lookup` returns `None`.
+pub fn expand_variables<F>(pattern: &str, mut lookup: F) -> Result<String, MissingVariable>
+where
+    F: FnMut(&str, &str) -> Option<String>,
+{
+    let mut expanded = String::with_capacity(pattern.len());
+    let mut rest = pattern;
+    while let Some(c) = rest.chars().next() {
+        let placeholder = rest.strip_prefix('{').and_then(|body| {
+            let end = body.find('}')?;
+            let (source, name) = body[..end].split_once(':')?;
+            matches!(source, "env" | "arg").then_some((source, name, end + 2))
+        });
+        match placeholder {
+            Some((source, name, len)) => {
+                let value = lookup(source, name).ok_or_else(|| MissingVariable {
+                    source: source.to_string(),
+                    name: name.to_string(),
+                })?;
+                expanded.push_str(&Pattern::escape_literal(&value));
+                rest = &rest[len..];
+            }
+            None => {
+                let len = match rest.strip_prefix('\\').and_then(|r| r.chars().next()) {
+                    Some(escaped) => 1 + escaped.len_utf8(),
+                    None => c.len_utf8(),
+                };
+                expanded.push_str(&rest[..len]);
+                rest = &rest[len..];
+            }
+        }
+    }
+    Ok(expanded)
+}
+
+/// Most nested groups and alternations, deeper brackets are copied verbatim.
+const MAX_GROUP_DEPTH: usize = 16;
+
+/// Parses `pattern`, nested in `depth` groups or alternations, and appends its segments to
+/// `segments`, merging consecutive literals.
+fn parse_into(pattern: &str, brackets: &Brackets, depth: usize, segments: &mut Vec<Segment>) {
+    let mut rest = pattern;
+    while let Some(element) = parse_element(&mut rest, brackets, depth) {
+        for segment in element {
+            push_segment(segments, segment);
+        }
+    }
+}
+
+/// Appends `segment` to `segments`, merging it into a literal before it.
+pub(crate) fn push_segment(segments: &mut Vec<Segment>, segment: Segment) {
+    match (segments.last_mut(), segment) {
+        (Some(Segment::Literal(prev)), Segment::Literal(next)) => prev.push_str(&next),
+        (_, segment) => segments.push(segment),
+    }
+}
+
+/// Parses the element at the start of `rest`, with its count, and advances `rest` past it;
+/// `None` at the end of the pattern.
+fn parse_element(rest: &mut &str, brackets: &Brackets, depth: usize) -> Option<Vec<Segment>> {
+    let c = rest.chars().next()?;
+    *rest = &rest[c.len_utf8()..];
+    // the segments of the element, and whether a count may repeat them
+    let (element, repeatable) = match c {
+        '\\' => match rest.chars().next() {
+            Some(cn) => {
+                *rest = &rest[cn.len_utf8()..];
+                (vec![Segment::Literal(cn.to_string())], false)
+            }
+            None => (Vec::new(), false),
+        },
+        'w' | 'W' | 'c' | 'C' => {
+            let min_entropy = element_floor(rest).map(|(bits, len)| {
+                *rest = &rest[len..];
+                bits
+            });
+            let segment = if c == 'w' || c == 'W' {
+                Segment::Word {
+                    capitalized: c == 'W',
+                    min_entropy,
+                }
+            } else {
+                Segment::Token {
+                    capitalized: c == 'C',
+                    min_entropy,
+                }
+            };
+            (vec![segment], true)
+        }
+        'x' | 'X' => (
+            vec![Segment::Dictionary {
+                capitalized: c == 'X',
+            }],
+            true,
+        ),
+        's' => (vec![Segment::Symbol], true),
+        'd' => (vec![Segment::Digit], true),
+        '{' => match directive(rest) {
+            Some((segment, len)) => {
+                *rest = &rest[len..];
+                (vec![segment], true)
+            }
+            None => (vec![Segment::Literal(c.to_string())], false),
+        },
+        '(' => match brackets
+            .closing(rest)
+            .filter(|&end| depth < MAX_GROUP_DEPTH && repetition(&rest[end + 1..], 0).is_some())
+        {
+            Some(end) => {
+                let mut group = Vec::new();
+                parse_into(&rest[..end], brackets, depth + 1, &mut group);
+                *rest = &rest[end + 1..];
+                (group, true)
+            }
+            None => (vec![Segment::Literal(c.to_string())], false),
+        },
+        '[' => match brackets.closing(rest).filter(|_| depth < MAX_GROUP_DEPTH) {
+            Some(end) => {
+                let alternatives = alternatives(&rest[..end], brackets, depth + 1);
+                *rest = &rest[end + 1..];
+                if alternatives.is_empty() {
+                    (vec![Segment::Literal(String::from("[]"))], false)
+                } else {
+                    (vec![Segment::Choice(alternatives)], true)
+                }
+            }
+            None => (vec![Segment::Literal(c.to_string())], false),
+        },
+        _ => (vec![Segment::Literal(c.to_string())], false),
+    };
+    let count = match repetition(rest, weight(&element)).filter(|_| repeatable) {
+        Some((count, len)) => {
+            *rest = &rest[len..];
+            count
+        }
+        None => 1,
+    };
+    let mut repeated = Vec::with_capacity(count * element.len());
+    for _ in 0..count {
+        for segment in &element {
+            push_segment(&mut repeated, segment.clone());
+        }
+    }
+    Some(repeated)
+}
+
+/// Parses the alternatives of `body`, the text between the brackets of an alternation: the
+/// parts separated by `|`, or every element when there is no separator.
+fn alternatives(body: &str, brackets: &Brackets, depth: usize) -> Vec<Pattern> {
+    let mut parts = Vec::new();
+    let mut nested = 0;
+    let mut escaped = false;
+    let mut start = 0;
+    for (i, c) in body.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '[' => nested += 1,
+            ']' => nested -= 1,
+            '|' if nested == 0 => {
+                parts.push(&body[start..i]);
+                start = i + 1;
+            }
+            _ => (),
+        }
+    }
+    if parts.is_empty() {
+        let mut rest = body;
+        let mut alternatives = Vec::new();
+        while let Some(element) = parse_element(&mut rest, brackets, depth) {
+            if !element.is_empty() {
+                alternatives.push(Pattern::from_segments(element));
+            }
+        }
+        return alternatives;
+    }
+    parts.push(&body[start..]);
+    parts
+        .into_iter()
+        .map(|part| {
+            let mut segments = Vec::new();
+            parse_into(part, brackets, depth, &mut segments);
+            Pattern::from_segments(segments)
+        })
+        .collect()
+}
+
+/// The matching brackets of a pattern, found in a single pass so that parsing stays linear
+/// however many brackets are nested or left open. Parentheses and square brackets are
+/// matched independently, skipping escaped characters.
+struct Brackets<'a> {
+    pattern: &'a str,
+    /// Offset of the closing bracket, by offset of the text following the opening one.
+    closing: HashMap<usize, usize>,
+}
+
+impl<'a> Brackets<'a> {
+    fn new(pattern: &'a str) -> Brackets<'a> {
+        let mut closing = HashMap::new();
+        let mut parens = Vec::new();
+        let mut squares = Vec::new();
+        let mut escaped = false;
+        for (i, c) in pattern.char_indices() {
+            let open = match c {
+                _ if escaped => {
+                    escaped = false;
+                    continue;
+                }
+                '\\' => {
+                    escaped = true;
+                    continue;
+                }
+                '(' | ')' => &mut parens,
+                '[' | ']' => &mut squares,
+                _ => continue,
+            };
+            if c == '(' || c == '[' {
+                open.push(i + 1);
+            } else if let Some(start) = open.pop() {
+                closing.insert(start, i);
+            }
+        }
+        Brackets { pattern, closing }
+    }
+
+    /// The position in `rest`, the text following an opening bracket of the pattern, of the
+    /// matching closing bracket, when `rest` contains it.
+    fn closing(&self, rest: &str) -> Option<usize> {
+        // `rest` is a slice of the pattern, ending where the enclosing group does
+        let start = rest.as_ptr() as usize - self.pattern.as_ptr() as usize;
+        let end = self.closing.get(&start)? - start;
+        (end < rest.len()).then_some(end)
+    }
+}
+
+/// Number of segments of `segments`, counting those of the alternatives of choices, which
+/// bounds the memory taken by their repetitions.
+fn weight(segments: &[Segment]) -> usize {
+    segments
+        .iter()
+        .map(|segment| match segment {
+            Segment::Choice(alternatives) => {
+                1 + alternatives
+                    .iter()
+                    .map(|alternative| weight(alternative.segments()))
+                    .sum::<usize>()
+            }
+            _ => 1,
+        })
+        .sum()
+}
+
+/// Parses the `{n}` count which may follow an element or a group of `len` segments, returning
+/// the count and the length of the consumed text, unless the repetition would produce more
+/// than [`MAX_REPETITION`] segments.
+fn repetition(rest: &str, len: usize) -> Option<(usize, usize)> {
+    let body = rest.strip_prefix('{')?;
+    let end = body.find('}')?;
+    let digits = &body[..end];
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let count: usize = digits.parse().ok()?;
+    (count.checked_mul(len)? <= MAX_REPETITION).then_some((count, end + 2))
+}
+
+/// Parses the `adj}`, `roman:1-12}` or `seq:001}` following an opening brace, returning the segment and
+/// the length of the consumed text.
+fn directive(rest: &str) -> Option<(Segment, usize)> {
+    let end = rest.find('}')?;
+    let body = &rest[..end];
+    #[cfg(feature = "wordlist-pos")]
+    if let Some(part) = PartOfSpeech::from_name(body) {
+        return Some((Segment::Part(part), end + 1));
+    }
+    let (name, range) = match body.split_once(':') {
+        Some((name, range)) => (name, Some(range)),
+        None => (body, None),
+    };
+    if name == "seq" {
+        let start = range.unwrap_or("1");
+        if start.is_empty() || !start.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        let segment = Segment::Sequence {
+            start: start.parse().ok()?,
+            width: start.len(),
+        };
+        return Some((segment, end + 1));
+    }
+    let largest = match name {
+        "roman" => MAX_ROMAN,
+        "ordinal" => MAX_ORDINAL,
+        _ => return None,
+    };
+    let (min, max) = match range {
+        Some(range) => {
+            let (min, max) = range.split_once('-')?;
+            let min: u32 = min.trim().parse().ok()?;
+            let max: u32 = max.trim().parse().ok()?;
+            (min, max)
+        }
+        None => (1, largest),
+    };
+    // ranges without a supported number are copied verbatim
+    if min > max || min > largest || max < 1 {
+        return None;
+    }
+    let segment = match name {
+        "roman" => Segment::Roman { min, max },
+        _ => Segment::Ordinal { min, max },
+    };
+    Some((segment, end + 1))
+}
+
+/// Parses the `{>=bits}` floor which may follow a word or token element, returning the floor
+/// and the length of the consumed text.
+fn element_floor(rest: &str) -> Option<(f64, usize)> {
+    let body = rest.strip_prefix("{>=")?;
+    let end = body.find('}')?;
+    let bits: f64 = body[..end].trim().parse().ok()?;
+    (bits >= 0.0).then_some((bits, end + 4))
+}
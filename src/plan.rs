@@ -0,0 +1,200 @@
+//! Synthesis of patterns meeting an entropy target.
+//!
+//! Designing a pattern is usually done by trial and error: add a word, check the entropy, swap
+//! a digit for a symbol, check the length. [`Generator::plan_pattern`] automates it: given a
+//! target entropy and constraints on length and character classes, it returns the pattern with
+//! the most words (the easiest to memorize) which meets them, completing the words with digits
+//! and symbols where the length is tight.
+//!
+//! ```rust
+//! use cryptirust::{pattern::Pattern, plan::PlanConstraints, Generator};
+//!
+//! let generator = Generator::new();
+//! let constraints = PlanConstraints {
+//!     max_length: Some(20),
+//!     digit: true,
+//!     ..PlanConstraints::default()
+//! };
+//! let pattern = generator.plan_pattern(70.0, &constraints).unwrap();
+//! let expected = Pattern::parse(&pattern).expected_entropy(&generator).unwrap();
+//! assert!(expected >= 70.0);
+//! ```
+use crate::pattern::{Pattern, Segment};
+use crate::Generator;
+use rand::{CryptoRng, RngCore};
+
+/// Separator placed between the words of a planned pattern.
+const SEPARATOR: char = '-';
+
+/// Largest entropy target of [`Generator::plan_pattern`], in bits.
+pub const MAX_PLAN_BITS: f64 = 512.0;
+
+/// Most words, and most digits or symbols completing them, in a planned pattern.
+pub const MAX_PLAN_ELEMENTS: usize = 64;
+
+/// Constraints of [`Generator::plan_pattern`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PlanConstraints {
+    /// Maximum length of the secrets, in characters.
+    pub max_length: Option<usize>,
+    /// Whether the secrets must contain a digit.
+    pub digit: bool,
+    /// Whether the secrets must contain a symbol.
+    pub symbol: bool,
+    /// Whether the secrets must contain an uppercase letter.
+    pub uppercase: bool,
+}
+
+impl<R: RngCore + CryptoRng> Generator<R> {
+    /// A pattern whose [expected entropy](Pattern::expected_entropy) is at least `target_bits`
+    /// and whose outputs always satisfy `constraints`, see the [module](self) documentation.
+    ///
+    /// Since the target is met on average, individual secrets can fall below it; combine with
+    /// [`Generator::set_entropy_floor`] to reject them. Returns `None` when no pattern fits,
+    /// e.g. when the length is too short for the target, when the target is not between 0 and
+    /// [`MAX_PLAN_BITS`] or needs more than [`MAX_PLAN_ELEMENTS`] words, or when a
+    /// [theme](crate::theme) is set.
+    pub fn plan_pattern(&self, target_bits: f64, constraints: &PlanConstraints) -> Option<String> {
+        if !(0.0..=MAX_PLAN_BITS).contains(&target_bits) {
+            return None;
+        }
+        let entropy = |pattern: &str| Pattern::parse(pattern).expected_entropy(self);
+        let Some(max_length) = constraints.max_length else {
+            // without a length limit, words alone reach any target up to the word limit
+            let mut previous = 0.0;
+            for words in 0..=MAX_PLAN_ELEMENTS {
+                let pattern = self.plan_with_words(words, 0, constraints);
+                let bits = entropy(&pattern)?;
+                if bits >= target_bits {
+                    return Some(pattern);
+                }
+                if words > 0 && bits <= previous {
+                    return None;
+                }
+                previous = bits;
+            }
+            return None;
+        };
+        let fits = |pattern: &str| self.max_length(&Pattern::parse(pattern)) <= max_length;
+        let mut best = None;
+        for words in 0..=MAX_PLAN_ELEMENTS {
+            let mut pattern = self.plan_with_words(words, 0, constraints);
+            if !fits(&pattern) {
+                break;
+            }
+            // complete the words with the character class giving the most bits per character
+            let mut bits = entropy(&pattern)?;
+            for extra in 1..=MAX_PLAN_ELEMENTS {
+                if bits >= target_bits {
+                    break;
+                }
+                let extended = self.plan_with_words(words, extra, constraints);
+                if !fits(&extended) {
+                    break;
+                }
+                bits = entropy(&extended)?;
+                pattern = extended;
+            }
+            if bits >= target_bits {
+                best = Some(pattern);
+            }
+        }
+        best
+    }
+
+    /// The pattern made of `words` words followed by the required digit and symbol, and by
+    /// `extra` more digits or symbols.
+    fn plan_with_words(&self, words: usize, extra: usize, constraints: &PlanConstraints) -> String {
+        let mut pattern = String::new();
+        for i in 0..words {
+            if i > 0 {
+                pattern.push(SEPARATOR);
+            }
+            pattern.push(if i == 0 && constraints.uppercase {
+                'W'
+            } else {
+                'w'
+            });
+        }
+        if words == 0 && constraints.uppercase {
+            pattern.push('C');
+        }
+        let mut suffix = String::new();
+        if constraints.digit {
+            suffix.push('d');
+        }
+        if constraints.symbol {
+            suffix.push('s');
+        }
+        suffix.extend(std::iter::repeat_n(self.strongest_element(), extra));
+        if words > 0 && !suffix.is_empty() {
+            pattern.push(SEPARATOR);
+        }
+        pattern + &suffix
+    }
+
+    /// The single character element with the most entropy, `d` or `s`.
+    fn strongest_element(&self) -> char {
+        if self.symbols.len() > self.digits.len() {
+            's'
+        } else {
+            'd'
+        }
+    }
+
+    /// Length, in characters, of the longest secret which `pattern` can produce.
+    pub(crate) fn max_length(&self, pattern: &Pattern) -> usize {
+        let token = self
+            .jump_table
+            .values()
+            .flat_map(|d| d.tokens.iter())
+            .map(|t| t.chars().count())
+            .max()
+            .unwrap_or(0);
+        let word = if self.empirical_lengths {
+            // the last token may overshoot the drawn length
+            self.word_lengths.counts.len().saturating_sub(1) + token.saturating_sub(1)
+        } else {
+            self.word_length.div_ceil(self.depth) * token
+        };
+        let segments = pattern.segments();
+        let grouping = self.digit_grouping.as_ref();
+        segments
+            .iter()
+            .enumerate()
+            .map(|(i, segment)| match segment {
+                Segment::Literal(text) => text.chars().count(),
+                Segment::Sequence { start, width } => self.sequence_number(*start, *width).len(),
+                Segment::Symbol => 1,
+                Segment::Digit => {
+                    1 + grouping.map_or(0, |g| g.separator_before(segments, i).is_some() as usize)
+                }
+                Segment::Token { .. } => token,
+                Segment::Word { .. } => word,
+                Segment::Dictionary { .. } => self
+                    .dictionary
+                    .iter()
+                    .map(|w| w.chars().count())
+                    .max()
+                    .unwrap_or(0),
+                #[cfg(feature = "wordlist-pos")]
+                Segment::Part(part) => part.words().map(|w| w.chars().count()).max().unwrap_or(0),
+                Segment::Roman { .. } | Segment::Ordinal { .. } => segment
+                    .numerals()
+                    .into_iter()
+                    .flatten()
+                    .map(|n| n.chars().count())
+                    .max()
+                    .unwrap_or(0),
+                // the digits of the alternative can extend a run of digits of the pattern,
+                // adding a separator at each end
+                Segment::Choice(alternatives) => alternatives
+                    .iter()
+                    .map(|alternative| self.max_length(alternative))
+                    .max()
+                    .unwrap_or(0)
+                    .saturating_add(2 * grouping.is_some() as usize),
+            })
+            .sum()
+    }
+}
@@ -0,0 +1,200 @@
+//! A pool of generators for multi-threaded services.
+//!
+//! Generating a secret needs `&mut Generator`, so threads sharing one generator must take turns
+//! behind a lock. A [`GeneratorPool`] holds several independently seeded generators instead:
+//! [`GeneratorPool::checkout`] lends one for exclusive use and it returns to the pool when the
+//! [`Pooled`] guard is dropped. With at least as many generators as worker threads, workers
+//! never wait for each other except for the short lock on the list of free generators.
+//!
+//! The pool seeds every generator itself, whatever the seed of the generators returned by the
+//! build function, so that two members never draw the same stream of secrets even when they
+//! are clones of one generator: from the operating system with [`GeneratorPool::new`], or
+//! derived from a master seed and the index of the member with
+//! [`GeneratorPool::with_master_seed`]. Generators in [demo mode](Generator::set_demo_mode)
+//! keep their fixed seed.
+//!
+//! ```rust
+//! use cryptirust::pool::{GeneratorPool, ReseedPolicy};
+//! use cryptirust::Generator;
+//!
+//! let mut pool = GeneratorPool::new(4, Generator::new);
+//! pool.set_reseed_policy(ReseedPolicy::Every(1000));
+//! std::thread::scope(|s| {
+//!     for _ in 0..4 {
+//!         s.spawn(|| {
+//!             let mut generator = pool.checkout();
+//!             let (passphrase, entropy) = generator.gen_from_pattern("w-w-w-w-dd");
+//!         });
+//!     }
+//! });
+//! ```
+use crate::sha256::HmacSha256;
+use crate::Generator;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use std::ops::{Deref, DerefMut};
+use std::sync::{Condvar, Mutex, MutexGuard, PoisonError};
+
+/// When the generators of a [`GeneratorPool`] are reseeded from the operating system.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReseedPolicy {
+    /// Never, generators keep the seed drawn when the pool was built.
+    Never,
+    /// At every checkout.
+    Always,
+    /// Every given number of checkouts of the same generator.
+    Every(usize),
+}
+
+/// A generator in the pool, with the number of checkouts since it was last reseeded.
+struct Slot {
+    generator: Generator,
+    checkouts: usize,
+}
+
+/// Generators lent to threads, see the [module](self) documentation.
+pub struct GeneratorPool {
+    free: Mutex<Vec<Slot>>,
+    returned: Condvar,
+    size: usize,
+    policy: ReseedPolicy,
+}
+
+impl GeneratorPool {
+    /// A pool of `size` generators built by `build`, at least one, each seeded from the
+    /// operating system.
+    pub fn new<F: FnMut() -> Generator>(size: usize, build: F) -> GeneratorPool {
+        GeneratorPool::seeded(size, build, |_| ChaCha8Rng::from_entropy())
+    }
+
+    /// A pool of `size` generators built by `build`, at least one, the generator number `i`
+    /// seeded with HMAC-SHA-256 of `i` under `master_seed`, so that the pool draws the same
+    /// streams at every run while its members draw independent ones.
+    ///
+    /// Which member serves a checkout depends on the timing of the threads, and
+    /// [reseeding](GeneratorPool::set_reseed_policy) draws from the operating system, so only
+    /// single-threaded use without reseeding is reproducible. Use a secret, high-entropy
+    /// `master_seed`: anyone holding it can regenerate the secrets.
+    pub fn with_master_seed<F: FnMut() -> Generator>(
+        size: usize,
+        build: F,
+        master_seed: &[u8],
+    ) -> GeneratorPool {
+        let prf = HmacSha256::new(master_seed);
+        GeneratorPool::seeded(size, build, |i| {
+            ChaCha8Rng::from_seed(prf.mac(&(i as u64).to_be_bytes()))
+        })
+    }
+
+    fn seeded<F, S>(size: usize, mut build: F, mut seed: S) -> GeneratorPool
+    where
+        F: FnMut() -> Generator,
+        S: FnMut(usize) -> ChaCha8Rng,
+    {
+        let size = size.max(1);
+        let free = (0..size)
+            .map(|i| {
+                let mut generator = build();
+                if !generator.demo {
+                    generator.set_rng(seed(i));
+                }
+                Slot {
+                    generator,
+                    checkouts: 0,
+                }
+            })
+            .collect();
+        GeneratorPool {
+            free: Mutex::new(free),
+            returned: Condvar::new(),
+            size,
+            policy: ReseedPolicy::Never,
+        }
+    }
+
+    /// Sets when the generators are reseeded, [`ReseedPolicy::Never`] by default.
+    pub fn set_reseed_policy(&mut self, policy: ReseedPolicy) {
+        self.policy = policy;
+    }
+
+    /// Number of generators in the pool.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Number of generators not checked out.
+    pub fn available(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Lends a generator, waiting for one to be returned if all are checked out.
+    pub fn checkout(&self) -> Pooled<'_> {
+        let mut free = self.lock();
+        loop {
+            if let Some(slot) = free.pop() {
+                return self.lend(slot);
+            }
+            free = self
+                .returned
+                .wait(free)
+                .unwrap_or_else(PoisonError::into_inner);
+        }
+    }
+
+    /// Lends a generator, or returns `None` if all are checked out.
+    pub fn try_checkout(&self) -> Option<Pooled<'_>> {
+        let slot = self.lock().pop()?;
+        Some(self.lend(slot))
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Vec<Slot>> {
+        // a panic while holding the lock cannot leave the list of free generators inconsistent
+        self.free.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn lend(&self, mut slot: Slot) -> Pooled<'_> {
+        slot.checkouts += 1;
+        let reseed = match self.policy {
+            ReseedPolicy::Never => false,
+            ReseedPolicy::Always => true,
+            ReseedPolicy::Every(n) => slot.checkouts >= n.max(1),
+        };
+        if reseed && !slot.generator.demo {
+            slot.generator.set_rng(ChaCha8Rng::from_entropy());
+            slot.checkouts = 0;
+        }
+        Pooled {
+            pool: self,
+            slot: Some(slot),
+        }
+    }
+}
+
+/// A generator checked out of a [`GeneratorPool`], returned to it when dropped.
+pub struct Pooled<'a> {
+    pool: &'a GeneratorPool,
+    slot: Option<Slot>,
+}
+
+impl Deref for Pooled<'_> {
+    type Target = Generator;
+
+    fn deref(&self) -> &Generator {
+        &self.slot.as_ref().unwrap().generator
+    }
+}
+
+impl DerefMut for Pooled<'_> {
+    fn deref_mut(&mut self) -> &mut Generator {
+        &mut self.slot.as_mut().unwrap().generator
+    }
+}
+
+impl Drop for Pooled<'_> {
+    fn drop(&mut self) {
+        if let Some(slot) = self.slot.take() {
+            self.pool.lock().push(slot);
+            self.pool.returned.notify_one();
+        }
+    }
+}
@@ -0,0 +1,248 @@
+//! Passphrases mixing several languages, with the language of every word.
+//!
+//! A [`Registry`] holds one generator per language. [`Registry::gen_annotated`] draws the
+//! language of every word uniformly among the registered ones and returns an [`Annotated`]
+//! secret, which tells where each word is and which language it comes from, for users who
+//! memorize words by language association.
+//!
+//! The choice of the language is not counted in the reported entropy, which is therefore a
+//! lower bound. Elements other than words (digits, symbols, literals) are generated by the
+//! first registered generator. Every word is generated on its own, so settings relating
+//! several words, such as [themes](crate::theme), have no effect.
+//!
+//! ```rust
+//! use cryptirust::{pattern::Pattern, registry::Registry};
+//!
+//! let mut registry = Registry::bundled(3);
+//! let secret = registry.gen_annotated(&Pattern::parse("w-w-w-w-dd"));
+//! println!("{} ({:.2} bits)", secret.text, secret.entropy);
+//! for word in &secret.words {
+//!     println!("{} is {}", &secret.text[word.range.clone()], word.language);
+//! }
+//! println!("{}", secret.tagged());
+//! ```
+use crate::normalize::{normalize, NormalizationForm};
+use crate::pattern::Pattern;
+use crate::word_list;
+use crate::{Generator, DEMO_WATERMARK};
+use rand::{CryptoRng, RngCore};
+use rand_chacha::ChaCha8Rng;
+use std::ops::Range;
+
+/// A word of an [`Annotated`] secret.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaggedWord {
+    /// Byte range of the word in [`Annotated::text`].
+    pub range: Range<usize>,
+    /// Language of the word, see [`Generator::set_language`].
+    pub language: String,
+}
+
+/// The output of one pattern element, see [`Annotated::spans`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Span {
+    /// Byte range of the output in [`Annotated::text`].
+    pub range: Range<usize>,
+    /// Realized entropy of the element, in bits; zero for literal text.
+    pub entropy: f64,
+}
+
+/// A secret together with the position and language of its words.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Annotated {
+    /// The secret.
+    pub text: String,
+    /// Entropy of the secret, in bits.
+    pub entropy: f64,
+    /// The words of the secret, in order.
+    pub words: Vec<TaggedWord>,
+    /// The output of every pattern element producing text, in order, with its own entropy,
+    /// showing which parts of a secret are weak. With
+    /// [exact entropy](Generator::set_exact_entropy) the entropies of the spans add up to the
+    /// entropy of the way the secret was generated, which can exceed [`Annotated::entropy`].
+    pub spans: Vec<Span>,
+}
+
+impl Annotated {
+    /// Prefixes the text with the [`DEMO_WATERMARK`], shifting the ranges.
+    pub(crate) fn watermark(&mut self) {
+        let shift = DEMO_WATERMARK.len();
+        self.text.insert_str(0, DEMO_WATERMARK);
+        for word in self.words.iter_mut() {
+            word.range = word.range.start + shift..word.range.end + shift;
+        }
+        for span in self.spans.iter_mut() {
+            span.range = span.range.start + shift..span.range.end + shift;
+        }
+    }
+
+    /// The secret with the language of every word appended in brackets, e.g.
+    /// `dolce[it]-hazard[en]-12`, for display only.
+    pub fn tagged(&self) -> String {
+        let mut tagged = String::with_capacity(self.text.len() + 4 * self.words.len());
+        let mut last = 0;
+        for word in &self.words {
+            tagged.push_str(&self.text[last..word.range.end]);
+            if !word.language.is_empty() {
+                tagged.push('[');
+                tagged.push_str(&word.language);
+                tagged.push(']');
+            }
+            last = word.range.end;
+        }
+        tagged.push_str(&self.text[last..]);
+        tagged
+    }
+
+    /// Languages of the words, in order.
+    pub fn languages(&self) -> Vec<&str> {
+        self.words.iter().map(|w| w.language.as_str()).collect()
+    }
+
+    /// Rewrites the secret in the normalization form `form`, see
+    /// [`normalize`], keeping the ranges of the words and spans
+    /// on the same parts of the text.
+    pub fn normalize(&mut self, form: NormalizationForm) {
+        let mut bounds: Vec<usize> = self
+            .words
+            .iter()
+            .map(|w| &w.range)
+            .chain(self.spans.iter().map(|s| &s.range))
+            .flat_map(|r| [r.start, r.end])
+            .chain([0, self.text.len()])
+            .collect();
+        bounds.sort_unstable();
+        bounds.dedup();
+        let mut text = String::with_capacity(self.text.len());
+        let mut moved = vec![(0, 0)];
+        for pair in bounds.windows(2) {
+            text.push_str(&normalize(&self.text[pair[0]..pair[1]], form));
+            moved.push((pair[1], text.len()));
+        }
+        let remap = |offset: usize| {
+            moved
+                .iter()
+                .find(|&&(old, _)| old == offset)
+                .map_or(offset, |&(_, new)| new)
+        };
+        for range in self
+            .words
+            .iter_mut()
+            .map(|w| &mut w.range)
+            .chain(self.spans.iter_mut().map(|s| &mut s.range))
+        {
+            *range = remap(range.start)..remap(range.end);
+        }
+        self.text = text;
+    }
+}
+
+/// Generators for several languages, see the [module](self) documentation.
+pub struct Registry<R = ChaCha8Rng> {
+    generators: Vec<Generator<R>>,
+}
+
+impl<R> Default for Registry<R> {
+    fn default() -> Self {
+        Registry {
+            generators: Vec::new(),
+        }
+    }
+}
+
+impl Registry {
+    /// An empty registry.
+    pub fn new() -> Registry {
+        Registry::default()
+    }
+
+    /// A registry with the bundled natural language lists enabled by cargo features: `en`
+    /// (EFF) and `it` (Italian), trained with the given markov chain `depth`.
+    pub fn bundled(depth: usize) -> Registry {
+        let mut registry = Registry::new();
+        #[cfg(feature = "wordlist-eff")]
+        registry.add(
+            "en",
+            Generator::new_custom(word_list::eff::list(), depth).unwrap(),
+        );
+        #[cfg(feature = "wordlist-italian")]
+        registry.add(
+            "it",
+            Generator::new_custom(word_list::italian::list(), depth).unwrap(),
+        );
+        if registry.generators.is_empty() {
+            registry.add(
+                "cv",
+                Generator::new_custom(word_list::cv::list(), depth).unwrap(),
+            );
+        }
+        registry
+    }
+}
+
+impl<R: RngCore + CryptoRng> Registry<R> {
+    /// Registers `generator` for the language `label`.
+    pub fn add(&mut self, label: &str, mut generator: Generator<R>) {
+        generator.set_language(label);
+        self.generators.push(generator);
+    }
+
+    /// The registered generators, e.g. to configure them.
+    pub fn generators_mut(&mut self) -> &mut [Generator<R>] {
+        &mut self.generators
+    }
+
+    /// Generates a secret following `pattern`, drawing the language of each word at random.
+    ///
+    /// # Panics
+    ///
+    /// If the registry is empty.
+    pub fn gen_annotated(&mut self, pattern: &Pattern) -> Annotated {
+        assert!(!self.generators.is_empty(), "empty registry");
+        let mut secret = Annotated {
+            text: String::new(),
+            entropy: 0.0,
+            words: Vec::new(),
+            spans: Vec::new(),
+        };
+        // alternations are resolved first, so that their words draw a language as well
+        let (resolved, choices) = self.generators[0].resolve_choices(pattern);
+        if !self.generators[0].demo {
+            secret.entropy = choices;
+        }
+        let segments = resolved.segments();
+        let mut i = 0;
+        while i < segments.len() {
+            let (generator, run) = if segments[i].is_word() {
+                let n = self.generators.len();
+                (self.generators[0].below(n), 1)
+            } else {
+                let run = segments[i..].iter().take_while(|s| !s.is_word()).count();
+                (0, run)
+            };
+            let part = Pattern::from_segments(segments[i..i + run].to_vec());
+            let generated = self.generators[generator].gen_unmarked(&part);
+            let offset = secret.text.len();
+            secret.text.push_str(&generated.text);
+            secret.entropy += generated.entropy;
+            secret
+                .words
+                .extend(generated.words.into_iter().map(|w| TaggedWord {
+                    range: w.range.start + offset..w.range.end + offset,
+                    language: w.language,
+                }));
+            secret
+                .spans
+                .extend(generated.spans.into_iter().map(|s| Span {
+                    range: s.range.start + offset..s.range.end + offset,
+                    entropy: s.entropy,
+                }));
+            i += run;
+        }
+        // marked once as a whole, not once per part
+        if self.generators.iter().any(|g| g.demo) {
+            secret.watermark();
+        }
+        secret
+    }
+}
@@ -0,0 +1,75 @@
+//! Word lists in non-Latin scripts.
+//!
+//! Training and generation work on characters, so lists in Cyrillic, Greek, Arabic or Hebrew
+//! script are used like Latin ones, with pattern literals in any script, and capitalization
+//! follows the [case rules](crate::casing) of the script; Arabic and Hebrew have no case and
+//! `W` and `C` leave them unchanged. The `wordlist-russian` feature, enabled by default,
+//! bundles [`word_list::russian`](crate::word_list::russian), a list of common Russian nouns.
+//!
+//! Right-to-left words need care on display: the bidirectional algorithm of terminals and
+//! browsers reorders a passphrase such as `كتاب-قلم-42`, so that it is read in a different
+//! order than it must be typed. [`isolate_rtl`] wraps every right-to-left run in Unicode
+//! directional isolates, which keep the order of the parts as generated; the isolates are for
+//! display only and must not be stored as part of the secret.
+//!
+//! ```rust
+//! use cryptirust::script;
+//!
+//! # #[cfg(feature = "wordlist-russian")]
+//! # {
+//! use cryptirust::{word_list, Generator};
+//!
+//! let mut generator = Generator::new_custom(word_list::russian::list(), 2).unwrap();
+//! let (passphrase, entropy) = generator.gen_from_pattern("W·w·w·dd");
+//! assert!(passphrase.chars().next().unwrap().is_uppercase());
+//! # }
+//!
+//! let shown = script::isolate_rtl("كتاب-قلم-42");
+//! assert_eq!(shown, "\u{2067}كتاب\u{2069}-\u{2067}قلم\u{2069}-42");
+//! assert_eq!(script::strip_isolates(&shown), "كتاب-قلم-42");
+//! ```
+
+/// Start of a right-to-left isolate, U+2067.
+pub const RIGHT_TO_LEFT_ISOLATE: char = '\u{2067}';
+/// End of a directional isolate, U+2069.
+pub const POP_DIRECTIONAL_ISOLATE: char = '\u{2069}';
+
+/// Whether `c` belongs to a right-to-left script: Hebrew, Arabic, Syriac, Thaana, N'Ko and
+/// the related presentation forms.
+pub fn is_rtl(c: char) -> bool {
+    matches!(c,
+        '\u{0590}'..='\u{08FF}'
+        | '\u{FB1D}'..='\u{FDFF}'
+        | '\u{FE70}'..='\u{FEFF}'
+        | '\u{10800}'..='\u{10FFF}'
+        | '\u{1E800}'..='\u{1EFFF}')
+}
+
+/// `text` with every run of right-to-left letters, and the marks within it, wrapped in a
+/// right-to-left isolate, for display.
+pub fn isolate_rtl(text: &str) -> String {
+    let mut shown = String::with_capacity(text.len() + 6);
+    let mut open = false;
+    for c in text.chars() {
+        let rtl = is_rtl(c);
+        if rtl && !open {
+            shown.push(RIGHT_TO_LEFT_ISOLATE);
+            open = true;
+        } else if !rtl && open {
+            shown.push(POP_DIRECTIONAL_ISOLATE);
+            open = false;
+        }
+        shown.push(c);
+    }
+    if open {
+        shown.push(POP_DIRECTIONAL_ISOLATE);
+    }
+    shown
+}
+
+/// `text` without the isolates added by [`isolate_rtl`].
+pub fn strip_isolates(text: &str) -> String {
+    text.chars()
+        .filter(|&c| c != RIGHT_TO_LEFT_ISOLATE && c != POP_DIRECTIONAL_ISOLATE)
+        .collect()
+}
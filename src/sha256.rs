@@ -0,0 +1,31 @@
+//! SHA-256 and HMAC-SHA-256 for the fingerprints, digests and keyed hashes of the crate, from
+//! the RustCrypto `sha2` and `hmac` crates.
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256};
+
+/// SHA-256 digest of `data`.
+pub(crate) fn sha256(data: &[u8]) -> [u8; 32] {
+    Sha256::digest(data).into()
+}
+
+/// HMAC-SHA-256, keyed once so that many messages can be authenticated with the same key
+/// cheaply.
+#[derive(Clone)]
+pub(crate) struct HmacSha256 {
+    keyed: Hmac<Sha256>,
+}
+
+impl HmacSha256 {
+    pub(crate) fn new(key: &[u8]) -> HmacSha256 {
+        HmacSha256 {
+            keyed: Hmac::new_from_slice(key).expect("HMAC takes keys of any length"),
+        }
+    }
+
+    /// Authentication tag of `message`.
+    pub(crate) fn mac(&self, message: &[u8]) -> [u8; 32] {
+        let mut mac = self.keyed.clone();
+        mac.update(message);
+        mac.finalize().into_bytes().into()
+    }
+}
@@ -0,0 +1,435 @@
+//! Pluggable sets of already generated secrets.
+//!
+//! Services handing out secrets often must never hand out the same one twice, across
+//! processes and restarts. [`Generator::gen_unique`] draws secrets until one is missing from a
+//! [`HistoryStore`], and records it there. The crate provides five stores:
+//!
+//! - [`MemoryStore`], the SHA-256 digests of the secrets in memory, for a single process;
+//! - [`BloomStore`], a Bloom filter keyed with HMAC-SHA-256, compact enough to keep millions
+//!   of secrets in a file: it can mistake a new secret for a seen one, which only costs a
+//!   redraw, but never the opposite;
+//! - [`RotatingStore`], two generations of Bloom filters, the older one dropped whenever the
+//!   newer one fills up, for services which must never repeat a recent secret but cannot
+//!   keep all of them;
+//! - `SqliteStore`, a SQLite table of the keyed hashes of the secrets, exact and shared by
+//!   the processes using the database, with the `sqlite` feature;
+//! - `history::History`, the encrypted history of the `history` feature.
+//!
+//! [`Generator::gen_batch_unique`] generates whole batches against a store, and `crypticli`
+//! keeps a rotating store across runs with `--seen`.
+//!
+//! Services backing collision avoidance with another database implement the trait on a handle
+//! to it, as `SqliteStore` does: a table with a unique index on a keyed hash of the secrets,
+//! where `insert` is an `INSERT OR IGNORE` reporting whether a row was added.
+//!
+//! ```rust
+//! use cryptirust::store::{BloomStore, HistoryStore, MemoryStore};
+//! use cryptirust::Generator;
+//!
+//! let mut generator = Generator::new();
+//! let mut seen = MemoryStore::new();
+//! let (first, _) = generator.gen_unique("w-w-dd", &mut seen).unwrap();
+//! assert!(seen.contains(&first));
+//!
+//! let mut bloom = BloomStore::with_capacity(b"filter key", 10_000, 1e-6).unwrap();
+//! assert!(bloom.insert(&first));
+//! assert!(!bloom.insert(&first));
+//! let restored = BloomStore::from_bytes(&bloom.to_bytes(), b"filter key").unwrap();
+//! assert!(restored.contains(&first));
+//! ```
+use crate::batch::MAX_REJECTIONS;
+use crate::pattern::Pattern;
+use crate::sha256::{sha256, HmacSha256};
+use crate::{Error, Generator};
+use rand::rngs::OsRng;
+use rand::{CryptoRng, RngCore};
+use std::collections::HashSet;
+use std::fmt;
+use std::fs::OpenOptions;
+use std::io::{self, Write};
+use std::path::Path;
+
+#[cfg(feature = "sqlite")]
+mod sqlite;
+#[cfg(feature = "sqlite")]
+pub use sqlite::SqliteStore;
+
+/// A set of secrets, see the [module](self) documentation.
+///
+/// Stores whose backend can fail, such as a remote database, should report secrets as seen
+/// when they cannot tell, so that no secret is handed out twice.
+pub trait HistoryStore {
+    /// Whether `secret` was inserted, possibly a false positive for approximate stores.
+    fn contains(&self, secret: &str) -> bool;
+
+    /// Inserts `secret`, returning whether it was missing.
+    fn insert(&mut self, secret: &str) -> bool;
+}
+
+/// Secrets kept in memory as their SHA-256 digests.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryStore {
+    digests: HashSet<[u8; 32]>,
+}
+
+impl MemoryStore {
+    /// An empty store.
+    pub fn new() -> MemoryStore {
+        MemoryStore::default()
+    }
+
+    /// Number of secrets in the store.
+    pub fn len(&self) -> usize {
+        self.digests.len()
+    }
+
+    /// Whether the store is empty.
+    pub fn is_empty(&self) -> bool {
+        self.digests.is_empty()
+    }
+}
+
+impl HistoryStore for MemoryStore {
+    fn contains(&self, secret: &str) -> bool {
+        self.digests.contains(&sha256(secret.as_bytes()))
+    }
+
+    fn insert(&mut self, secret: &str) -> bool {
+        self.digests.insert(sha256(secret.as_bytes()))
+    }
+}
+
+/// First bytes of a serialized [`BloomStore`].
+const BLOOM_MAGIC: &[u8] = b"CRBLOOM";
+/// Version of the serialization of [`BloomStore`].
+const BLOOM_VERSION: u8 = 1;
+/// Length of the header of a serialized [`BloomStore`]: the magic, the version, the number of
+/// hashes as a big-endian `u32` and the number of insertions as a big-endian `u64`.
+const BLOOM_HEADER_LEN: usize = BLOOM_MAGIC.len() + 1 + 4 + 8;
+/// Largest size in bytes of the bits of a [`BloomStore`], 1 GiB.
+pub const MAX_BLOOM_BYTES: usize = 1 << 30;
+/// Attempts at finding an unused name for the temporary file of [`BloomStore::save`].
+const NAME_ATTEMPTS: usize = 16;
+
+/// A Bloom filter of secrets, hashed with HMAC-SHA-256 under a key which is not stored with
+/// the filter, so that whoever reads the filter cannot test guesses against it.
+#[derive(Clone)]
+pub struct BloomStore {
+    key: HmacSha256,
+    bits: Vec<u8>,
+    hashes: u32,
+    inserted: u64,
+}
+
+/// A [`BloomStore`] which could not be created or read.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StoreError {
+    /// The data is not a serialized Bloom filter.
+    NotAFilter,
+    /// The filter was written by a newer version of the format.
+    UnsupportedVersion(u8),
+    /// The capacity is zero, or needs more than [`MAX_BLOOM_BYTES`] at the requested false
+    /// positive rate.
+    Capacity(usize),
+    /// The false positive rate is not above 0 and at most 0.5.
+    FalsePositiveRate(f64),
+}
+
+impl fmt::Display for StoreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StoreError::NotAFilter => write!(f, "not a Bloom filter"),
+            StoreError::UnsupportedVersion(version) => {
+                write!(f, "unsupported Bloom filter version {}", version)
+            }
+            StoreError::Capacity(capacity) => write!(
+                f,
+                "a Bloom filter cannot hold {} secrets, the capacity must be positive and \
+                 need at most {} bytes",
+                capacity, MAX_BLOOM_BYTES
+            ),
+            StoreError::FalsePositiveRate(rate) => write!(
+                f,
+                "invalid false positive rate {}, it must be above 0 and at most 0.5",
+                rate
+            ),
+        }
+    }
+}
+
+impl std::error::Error for StoreError {}
+
+impl BloomStore {
+    /// An empty filter sized for `capacity` secrets with a false positive rate of
+    /// `false_positive_rate` once full, hashed under `key`.
+    ///
+    /// Fails with [`StoreError::FalsePositiveRate`] unless `0 < false_positive_rate <= 0.5`,
+    /// and with [`StoreError::Capacity`] when `capacity` is zero or the filter would take more
+    /// than [`MAX_BLOOM_BYTES`].
+    pub fn with_capacity(
+        key: &[u8],
+        capacity: usize,
+        false_positive_rate: f64,
+    ) -> Result<BloomStore, StoreError> {
+        let rate = false_positive_rate;
+        if !(rate > 0.0 && rate <= 0.5) {
+            return Err(StoreError::FalsePositiveRate(rate));
+        }
+        let ln2 = std::f64::consts::LN_2;
+        let bits = (-(capacity as f64) * rate.ln() / (ln2 * ln2)).ceil();
+        if capacity == 0 || bits / 8.0 > MAX_BLOOM_BYTES as f64 {
+            return Err(StoreError::Capacity(capacity));
+        }
+        let bytes = (bits as usize).div_ceil(8).max(1);
+        let hashes = ((bytes * 8) as f64 / capacity as f64 * ln2).round();
+        Ok(BloomStore {
+            key: HmacSha256::new(key),
+            bits: vec![0; bytes],
+            hashes: hashes.clamp(1.0, 32.0) as u32,
+            inserted: 0,
+        })
+    }
+
+    /// An empty filter with the key and size of this one.
+    pub fn emptied(&self) -> BloomStore {
+        BloomStore {
+            key: self.key.clone(),
+            bits: vec![0; self.bits.len()],
+            hashes: self.hashes,
+            inserted: 0,
+        }
+    }
+
+    /// Reads a filter serialized by [`BloomStore::to_bytes`], which must be used with the key
+    /// it was created with: with another key every secret looks new.
+    pub fn from_bytes(bytes: &[u8], key: &[u8]) -> Result<BloomStore, StoreError> {
+        if bytes.len() <= BLOOM_HEADER_LEN || !bytes.starts_with(BLOOM_MAGIC) {
+            return Err(StoreError::NotAFilter);
+        }
+        let version = bytes[BLOOM_MAGIC.len()];
+        if version != BLOOM_VERSION {
+            return Err(StoreError::UnsupportedVersion(version));
+        }
+        let at = BLOOM_MAGIC.len() + 1;
+        let hashes = u32::from_be_bytes(bytes[at..at + 4].try_into().unwrap());
+        let inserted = u64::from_be_bytes(bytes[at + 4..at + 12].try_into().unwrap());
+        if !(1..=32).contains(&hashes) || bytes.len() - BLOOM_HEADER_LEN > MAX_BLOOM_BYTES {
+            return Err(StoreError::NotAFilter);
+        }
+        Ok(BloomStore {
+            key: HmacSha256::new(key),
+            bits: bytes[BLOOM_HEADER_LEN..].to_vec(),
+            hashes,
+            inserted,
+        })
+    }
+
+    /// The filter as bytes, without its key.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(BLOOM_HEADER_LEN + self.bits.len());
+        bytes.extend_from_slice(BLOOM_MAGIC);
+        bytes.push(BLOOM_VERSION);
+        bytes.extend_from_slice(&self.hashes.to_be_bytes());
+        bytes.extend_from_slice(&self.inserted.to_be_bytes());
+        bytes.extend_from_slice(&self.bits);
+        bytes
+    }
+
+    /// Reads a filter from a file written by [`BloomStore::save`].
+    pub fn open<P: AsRef<Path>>(path: P, key: &[u8]) -> std::io::Result<BloomStore> {
+        BloomStore::from_bytes(&std::fs::read(path)?, key)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+    }
+
+    /// Writes the filter to a file, atomically: the bytes go to a new file with a random name
+    /// in the same directory, which replaces `path` once synced to disk, so that a crash never
+    /// leaves a truncated filter, which would forget the secrets handed out.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let path = path.as_ref();
+        let name = path
+            .file_name()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "not a file path"))?;
+        let mut attempts = 0;
+        let (mut file, tmp_path) = loop {
+            let mut tmp_name = std::ffi::OsString::from(".");
+            tmp_name.push(name);
+            tmp_name.push(format!(".{:016x}.tmp", OsRng.next_u64()));
+            let tmp_path = path.with_file_name(tmp_name);
+            match OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(&tmp_path)
+            {
+                Ok(file) => break (file, tmp_path),
+                Err(e) if e.kind() == io::ErrorKind::AlreadyExists && attempts < NAME_ATTEMPTS => {
+                    attempts += 1;
+                }
+                Err(e) => return Err(e),
+            }
+        };
+        let result = file
+            .write_all(&self.to_bytes())
+            .and_then(|()| file.sync_all())
+            .and_then(|()| std::fs::rename(&tmp_path, path));
+        if result.is_err() {
+            let _ = std::fs::remove_file(&tmp_path);
+        }
+        result
+    }
+
+    /// Number of secrets inserted while missing from the filter, false positives excluded.
+    pub fn inserted(&self) -> u64 {
+        self.inserted
+    }
+
+    /// Whether at least half of the bits are set, the state of a filter holding the capacity
+    /// it was created for: beyond it, the false positive rate grows quickly.
+    pub fn is_full(&self) -> bool {
+        let set: u64 = self.bits.iter().map(|b| u64::from(b.count_ones())).sum();
+        set * 2 >= self.bits.len() as u64 * 8
+    }
+
+    /// Indices of the bits of `secret`, by double hashing.
+    fn indices(&self, secret: &str) -> impl Iterator<Item = usize> {
+        let mac = self.key.mac(secret.as_bytes());
+        let h1 = u64::from_be_bytes(mac[..8].try_into().unwrap());
+        let h2 = u64::from_be_bytes(mac[8..16].try_into().unwrap()) | 1;
+        let bits = self.bits.len() as u64 * 8;
+        (0..self.hashes as u64).map(move |i| (h1.wrapping_add(i.wrapping_mul(h2)) % bits) as usize)
+    }
+}
+
+impl HistoryStore for BloomStore {
+    fn contains(&self, secret: &str) -> bool {
+        self.indices(secret)
+            .all(|i| self.bits[i / 8] & (1 << (i % 8)) != 0)
+    }
+
+    fn insert(&mut self, secret: &str) -> bool {
+        let indices: Vec<usize> = self.indices(secret).collect();
+        let mut missing = false;
+        for i in indices {
+            missing |= self.bits[i / 8] & (1 << (i % 8)) == 0;
+            self.bits[i / 8] |= 1 << (i % 8);
+        }
+        if missing {
+            self.inserted += 1;
+        }
+        missing
+    }
+}
+
+/// Two generations of Bloom filters: secrets are checked against both and inserted into the
+/// current one, which becomes the previous one, dropping the older secrets, as soon as it is
+/// [full](BloomStore::is_full).
+///
+/// Every secret is therefore remembered for at least one full generation, i.e. the capacity
+/// of the filters, and the false positive rate stays below twice the rate of one filter.
+///
+/// ```rust
+/// use cryptirust::store::{BloomStore, HistoryStore, RotatingStore};
+///
+/// let filter = BloomStore::with_capacity(b"filter key", 100, 1e-6).unwrap();
+/// let mut seen = RotatingStore::new(filter, None);
+/// for i in 0..1000 {
+///     assert!(seen.insert(&format!("secret-{}", i)));
+/// }
+/// // the recent secrets are remembered, the oldest ones were rotated out
+/// assert!(seen.contains("secret-999"));
+/// assert!(!seen.contains("secret-0"));
+/// ```
+#[derive(Clone)]
+pub struct RotatingStore {
+    current: BloomStore,
+    previous: Option<BloomStore>,
+}
+
+impl RotatingStore {
+    /// A store inserting into `current`, and still checking `previous` if any.
+    pub fn new(current: BloomStore, previous: Option<BloomStore>) -> RotatingStore {
+        RotatingStore { current, previous }
+    }
+
+    /// The filter secrets are inserted into.
+    pub fn current(&self) -> &BloomStore {
+        &self.current
+    }
+
+    /// The filter of the previous generation, if the store was rotated.
+    pub fn previous(&self) -> Option<&BloomStore> {
+        self.previous.as_ref()
+    }
+}
+
+impl HistoryStore for RotatingStore {
+    fn contains(&self, secret: &str) -> bool {
+        self.current.contains(secret) || self.previous.iter().any(|p| p.contains(secret))
+    }
+
+    fn insert(&mut self, secret: &str) -> bool {
+        if self.previous.iter().any(|p| p.contains(secret)) {
+            return false;
+        }
+        let missing = self.current.insert(secret);
+        if self.current.is_full() {
+            let next = self.current.emptied();
+            self.previous = Some(std::mem::replace(&mut self.current, next));
+        }
+        missing
+    }
+}
+
+impl<R: RngCore + CryptoRng> Generator<R> {
+    /// Generates a secret from `pattern` missing from `store`, and inserts it, see the
+    /// [module](crate::store) documentation. The reported entropy ignores the excluded
+    /// secrets, a negligible difference as long as the store holds a small fraction of the
+    /// outputs of the pattern.
+    ///
+    /// Fails with [`Error::SecretsExhausted`] when [`MAX_REJECTIONS`] candidates in a row are
+    /// in the store.
+    pub fn gen_unique(
+        &mut self,
+        pattern: &str,
+        store: &mut dyn HistoryStore,
+    ) -> Result<(String, f64), Error> {
+        for _ in 0..MAX_REJECTIONS {
+            let (secret, entropy) = self.gen_from_pattern(pattern);
+            if store.insert(&secret) {
+                return Ok((secret, entropy));
+            }
+        }
+        Err(Error::SecretsExhausted {
+            attempts: MAX_REJECTIONS,
+        })
+    }
+
+    /// Generates `count` secrets following `pattern`, as [`Generator::gen_batch`] does, each
+    /// missing from `store` and inserted into it, so that the batch has no duplicates and
+    /// repeats no secret handed out before.
+    ///
+    /// Fails with [`Error::SecretsExhausted`] when [`MAX_REJECTIONS`] candidates in a row are
+    /// in the store; the secrets generated until then stay inserted.
+    pub fn gen_batch_unique(
+        &mut self,
+        pattern: &str,
+        count: usize,
+        store: &mut dyn HistoryStore,
+    ) -> Result<Vec<(String, f64)>, Error> {
+        let pattern = Pattern::parse(pattern);
+        let mut secrets = Vec::with_capacity(count);
+        'batch: while secrets.len() < count {
+            self.sequence = secrets.len() as u64;
+            for _ in 0..MAX_REJECTIONS {
+                let (secret, entropy) = self.gen_from_parsed(&pattern);
+                if store.insert(&secret) {
+                    secrets.push((secret, entropy));
+                    continue 'batch;
+                }
+            }
+            return Err(Error::SecretsExhausted {
+                attempts: MAX_REJECTIONS,
+            });
+        }
+        Ok(secrets)
+    }
+}
@@ -0,0 +1,98 @@
+//! A [`HistoryStore`] backed by a SQLite database, for the `sqlite` feature.
+use super::HistoryStore;
+use crate::sha256::HmacSha256;
+use rusqlite::{params, Connection, OptionalExtension};
+use std::path::Path;
+
+/// Table holding the hashes of the secrets.
+const TABLE: &str = "cryptirust_seen";
+
+/// Secrets kept in a SQLite table as their HMAC-SHA-256 under a key which is not stored in the
+/// database, one row each with a unique index, so that processes sharing the database never
+/// hand out the same secret and whoever reads it cannot test guesses against it.
+///
+/// Inserting is an `INSERT OR IGNORE` reporting whether a row was added. When the database
+/// fails, e.g. because it is locked for too long, secrets are reported as seen, as the
+/// [trait](HistoryStore) requires.
+///
+/// ```rust
+/// use cryptirust::store::{HistoryStore, SqliteStore};
+/// use cryptirust::Generator;
+///
+/// let mut seen = SqliteStore::open_in_memory(b"table key").unwrap();
+/// let mut generator = Generator::new();
+/// let (secret, _) = generator.gen_unique("w-w-dd", &mut seen).unwrap();
+/// assert!(seen.contains(&secret));
+/// assert!(!seen.insert(&secret));
+/// assert_eq!(seen.len().unwrap(), 1);
+/// ```
+pub struct SqliteStore {
+    connection: Connection,
+    key: HmacSha256,
+}
+
+impl SqliteStore {
+    /// Opens the database at `path`, creating it and its table if missing; the store must be
+    /// used with the key the secrets were inserted with: with another key every secret looks
+    /// new.
+    pub fn open<P: AsRef<Path>>(path: P, key: &[u8]) -> rusqlite::Result<SqliteStore> {
+        SqliteStore::from_connection(Connection::open(path)?, key)
+    }
+
+    /// A store in a new in-memory database, for a single process.
+    pub fn open_in_memory(key: &[u8]) -> rusqlite::Result<SqliteStore> {
+        SqliteStore::from_connection(Connection::open_in_memory()?, key)
+    }
+
+    /// A store in the database of `connection`, creating its table if missing.
+    pub fn from_connection(connection: Connection, key: &[u8]) -> rusqlite::Result<SqliteStore> {
+        connection.execute(
+            &format!(
+                "CREATE TABLE IF NOT EXISTS {} (hash BLOB PRIMARY KEY) WITHOUT ROWID",
+                TABLE
+            ),
+            [],
+        )?;
+        Ok(SqliteStore {
+            connection,
+            key: HmacSha256::new(key),
+        })
+    }
+
+    /// Number of secrets in the store.
+    pub fn len(&self) -> rusqlite::Result<u64> {
+        self.connection
+            .query_row(&format!("SELECT COUNT(*) FROM {}", TABLE), [], |row| {
+                row.get(0)
+            })
+    }
+
+    /// Whether the store is empty.
+    pub fn is_empty(&self) -> rusqlite::Result<bool> {
+        Ok(self.len()? == 0)
+    }
+}
+
+impl HistoryStore for SqliteStore {
+    fn contains(&self, secret: &str) -> bool {
+        let hash = self.key.mac(secret.as_bytes());
+        self.connection
+            .query_row(
+                &format!("SELECT 1 FROM {} WHERE hash = ?1", TABLE),
+                params![&hash[..]],
+                |_| Ok(()),
+            )
+            .optional()
+            .map_or(true, |row| row.is_some())
+    }
+
+    fn insert(&mut self, secret: &str) -> bool {
+        let hash = self.key.mac(secret.as_bytes());
+        self.connection
+            .execute(
+                &format!("INSERT OR IGNORE INTO {} (hash) VALUES (?1)", TABLE),
+                params![&hash[..]],
+            )
+            .is_ok_and(|added| added == 1)
+    }
+}
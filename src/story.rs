@@ -0,0 +1,170 @@
+//! Story-mode passphrases: grammatical mini-sentences such as `shy-otter-juggles-ripe-mango`,
+//! easier to memorize than strings of unrelated words.
+//!
+//! Patterns gain three elements drawing a word uniformly from the part-of-speech tagged lists
+//! of [`word_list::pos`](crate::word_list::pos): `{adj}`, `{noun}` and `{verb}`. They can be
+//! mixed with every other pattern element, and [`SHORT`] and [`LONG`] are ready-made stories.
+//! This module requires the `wordlist-pos` feature, enabled by default.
+//!
+//! ```rust
+//! use cryptirust::{story, Generator};
+//!
+//! let mut generator = Generator::new();
+//! let (passphrase, entropy) = generator.gen_from_pattern(story::LONG);
+//! assert_eq!(passphrase.split('-').count(), 5);
+//! let (passphrase, entropy) = generator.gen_from_pattern("the-{adj}-{noun}-{verb}-dd");
+//! assert!(passphrase.starts_with("the-"));
+//! ```
+//!
+//! # Mnemonic sentences
+//!
+//! [`mnemonic_sentence`] turns the words of a passphrase into a simple sentence, such as
+//! `the shy otter juggles a ripe mango`, adding articles and linking verbs where the words
+//! alone do not read as one. The added words are a memory aid, not part of the secret: they
+//! follow from the words by fixed rules and add no entropy.
+//!
+//! ```rust
+//! use cryptirust::story;
+//!
+//! let mnemonic = story::mnemonic_sentence(&["shy", "otter", "mango"]);
+//! assert_eq!(mnemonic.template, "the <1> <2> meets a <3>");
+//! assert_eq!(mnemonic.sentence, "the shy otter meets a mango");
+//! ```
+use crate::word_list::pos;
+
+/// Adjective, noun, verb, noun.
+pub const SHORT: &str = "{adj}-{noun}-{verb}-{noun}";
+/// Adjective, noun, verb, adjective, noun.
+pub const LONG: &str = "{adj}-{noun}-{verb}-{adj}-{noun}";
+
+/// The part of speech of a story word.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PartOfSpeech {
+    /// `{adj}`
+    Adjective,
+    /// `{noun}`
+    Noun,
+    /// `{verb}`
+    Verb,
+}
+
+impl PartOfSpeech {
+    /// Parses the name used in patterns, e.g. `adj` for `{adj}`.
+    pub fn from_name(name: &str) -> Option<PartOfSpeech> {
+        match name {
+            "adj" => Some(PartOfSpeech::Adjective),
+            "noun" => Some(PartOfSpeech::Noun),
+            "verb" => Some(PartOfSpeech::Verb),
+            _ => None,
+        }
+    }
+
+    /// The name used in patterns.
+    pub fn name(&self) -> &'static str {
+        match self {
+            PartOfSpeech::Adjective => "adj",
+            PartOfSpeech::Noun => "noun",
+            PartOfSpeech::Verb => "verb",
+        }
+    }
+
+    /// The part of speech of `word`, if it is in one of the tagged lists.
+    pub fn of(word: &str) -> Option<PartOfSpeech> {
+        let word = word.to_lowercase();
+        [
+            PartOfSpeech::Noun,
+            PartOfSpeech::Verb,
+            PartOfSpeech::Adjective,
+        ]
+        .into_iter()
+        .find(|part| part.words().any(|w| w == word))
+    }
+
+    /// The candidate words.
+    pub(crate) fn words(&self) -> std::str::Split<'static, char> {
+        match self {
+            PartOfSpeech::Adjective => pos::ADJECTIVES,
+            PartOfSpeech::Noun => pos::NOUNS,
+            PartOfSpeech::Verb => pos::VERBS,
+        }
+        .split(';')
+    }
+}
+
+/// Verbs linking two nouns which follow each other in a passphrase, used in turn.
+const LINKING_VERBS: [&str; 4] = ["meets", "follows", "greets", "visits"];
+
+/// A sentence built around the words of a passphrase, see [`mnemonic_sentence`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mnemonic {
+    /// The sentence, in lowercase.
+    pub sentence: String,
+    /// The sentence with the words of the passphrase replaced by their position, e.g.
+    /// `the <1> <2> meets a <3>`.
+    pub template: String,
+}
+
+/// Builds a sentence around `words`, keeping their order, see the [module](self)
+/// documentation. Words missing from the tagged lists, such as words generated by the markov
+/// chain, are taken as nouns.
+pub fn mnemonic_sentence(words: &[&str]) -> Mnemonic {
+    let mut template: Vec<String> = Vec::new();
+    let mut sentence: Vec<String> = Vec::new();
+    let mut push = |template_word: String, word: String| {
+        template.push(template_word);
+        sentence.push(word);
+    };
+    let mut nouns = 0;
+    let mut links = 0;
+    // inside a noun phrase, after its article and before its noun
+    let mut open_phrase = false;
+    // whether the last word closed a noun phrase
+    let mut after_noun = false;
+    for (i, word) in words.iter().enumerate() {
+        let word = word.to_lowercase();
+        let placeholder = format!("<{}>", i + 1);
+        let part = PartOfSpeech::of(&word).unwrap_or(PartOfSpeech::Noun);
+        if part == PartOfSpeech::Verb {
+            if open_phrase {
+                push("one".to_string(), "one".to_string());
+                open_phrase = false;
+                after_noun = true;
+                nouns += 1;
+            }
+            if !after_noun {
+                let filler = if i == 0 { "someone" } else { "and" };
+                push(filler.to_string(), filler.to_string());
+            }
+            push(placeholder, word);
+            after_noun = false;
+            continue;
+        }
+        if !open_phrase {
+            if after_noun {
+                let verb = LINKING_VERBS[links % LINKING_VERBS.len()];
+                push(verb.to_string(), verb.to_string());
+                links += 1;
+            }
+            let article = match nouns {
+                0 => "the",
+                _ if word.starts_with(['a', 'e', 'i', 'o', 'u']) => "an",
+                _ => "a",
+            };
+            push(article.to_string(), article.to_string());
+            open_phrase = true;
+        }
+        push(placeholder, word);
+        if part == PartOfSpeech::Noun {
+            open_phrase = false;
+            after_noun = true;
+            nouns += 1;
+        }
+    }
+    if open_phrase {
+        push("one".to_string(), "one".to_string());
+    }
+    Mnemonic {
+        sentence: sentence.join(" "),
+        template: template.join(" "),
+    }
+}
@@ -0,0 +1,63 @@
+//! Secrets grown until they reach an entropy target.
+//!
+//! Instead of guessing how many words make 80 bits, [`Generator::gen_with_min_entropy`]
+//! appends secrets generated from a short pattern, the unit, until their entropy adds up to
+//! the target. The number of units depends on the draws: words from a low probability path
+//! carry more bits, so fewer of them are needed.
+//!
+//! ```rust
+//! use cryptirust::Generator;
+//!
+//! let mut generator = Generator::new();
+//! let (passphrase, entropy) = generator.gen_with_min_entropy("W", 80.0).unwrap();
+//! assert!(entropy >= 80.0);
+//! ```
+//!
+//! The stopping rule only depends on the units generated so far, so the reported entropy is
+//! that of the whole secret, as for a pattern: it is the sum of the entropies of the units.
+use crate::pattern::Pattern;
+use crate::{Error, Generator};
+use rand::{CryptoRng, RngCore};
+
+/// Most units appended by [`Generator::gen_with_min_entropy`].
+pub const MAX_UNITS: usize = 1000;
+
+impl<R: RngCore + CryptoRng> Generator<R> {
+    /// Concatenates secrets generated from the pattern `unit` until their entropy reaches
+    /// `min_bits`, see the [module](self) documentation. Units are joined as they are, so use
+    /// a unit starting with a separator, such as `-w`, and trim the first one if needed.
+    ///
+    /// Fails with [`Error::EntropyUnreachable`] when the unit has no entropy, e.g. in
+    /// [demo mode](Generator::set_demo_mode), or when [`MAX_UNITS`] units do not reach the
+    /// target.
+    pub fn gen_with_min_entropy(
+        &mut self,
+        unit: &str,
+        min_bits: f64,
+    ) -> Result<(String, f64), Error> {
+        let mut secret = String::new();
+        let mut entropy = 0.0;
+        if self.max_achievable_entropy(unit) == Some(0.0) {
+            return Err(Error::EntropyUnreachable {
+                entropy,
+                target: min_bits,
+            });
+        }
+        let pattern = Pattern::parse(unit);
+        for _ in 0..MAX_UNITS {
+            if entropy >= min_bits {
+                return Ok((secret, entropy));
+            }
+            let (text, h) = self.gen_from_parsed(&pattern);
+            secret.push_str(&text);
+            entropy += h;
+        }
+        if entropy >= min_bits {
+            return Ok((secret, entropy));
+        }
+        Err(Error::EntropyUnreachable {
+            entropy,
+            target: min_bits,
+        })
+    }
+}
@@ -0,0 +1,67 @@
+//! Placeholder substitution for text templates.
+//!
+//! A template is any text containing placeholders of the form `{{cryptirust:<pattern>}}`; each
+//! placeholder is replaced by a freshly generated secret following `<pattern>`, see
+//! [`Generator::gen_from_pattern`](crate::Generator::gen_from_pattern).
+//!
+//! ```rust
+//! use cryptirust::{template, Generator};
+//!
+//! let mut generator = Generator::new();
+//! let rendered = template::render(
+//!     &mut generator,
+//!     "db_password = \"{{cryptirust:w-w-dd}}\"\napi_key = \"{{cryptirust:cccccc}}\"\n",
+//! )?;
+//! println!("{}", rendered.text);
+//! assert_eq!(rendered.entropies.len(), 2);
+//! # Ok::<(), cryptirust::Error>(())
+//! ```
+use crate::{Error, Generator};
+use rand::{CryptoRng, RngCore};
+
+const OPEN: &str = "{{cryptirust:";
+const CLOSE: &str = "}}";
+
+/// Result of rendering a template.
+pub struct Rendered {
+    /// The template with every placeholder replaced.
+    pub text: String,
+    /// Entropy of each generated secret, in order of appearance.
+    pub entropies: Vec<f64>,
+}
+
+/// Replaces every `{{cryptirust:<pattern>}}` placeholder in `template` with a new secret.
+///
+/// A placeholder ends with the last `}}` of a run of closing braces, so that patterns may end
+/// with a brace, e.g. `{{cryptirust:d{6}}}`. Unterminated placeholders are copied verbatim,
+/// while a placeholder without a pattern, `{{cryptirust:}}`, is refused with
+/// [`Error::EmptyPlaceholder`].
+pub fn render<R: RngCore + CryptoRng>(
+    generator: &mut Generator<R>,
+    template: &str,
+) -> Result<Rendered, Error> {
+    let mut text = String::with_capacity(template.len());
+    let mut entropies = Vec::new();
+    let mut rest = template;
+    while let Some(start) = rest.find(OPEN) {
+        let after = &rest[start + OPEN.len()..];
+        let end = match after.find(CLOSE) {
+            Some(end) => end,
+            None => break,
+        };
+        // the placeholder ends with the last two braces of a run, as patterns like `d{3}` end
+        // with a brace
+        let end = end + after[end..].bytes().take_while(|&b| b == b'}').count() - CLOSE.len();
+        if end == 0 {
+            let offset = template.len() - rest.len() + start;
+            return Err(Error::EmptyPlaceholder { offset });
+        }
+        text.push_str(&rest[..start]);
+        let (secret, entropy) = generator.gen_from_pattern(&after[..end]);
+        text.push_str(&secret);
+        entropies.push(entropy);
+        rest = &after[end + CLOSE.len()..];
+    }
+    text.push_str(rest);
+    Ok(Rendered { text, entropies })
+}
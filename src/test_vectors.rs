@@ -0,0 +1,96 @@
+//! Frozen seed → output vectors, to confirm bit-exact behavior on new platforms.
+//!
+//! Every change to the generation algorithm which alters the outputs obtained under a fixed
+//! seed bumps [`ALGORITHM_VERSION`] and adds vectors for the new version. Packagers porting the
+//! crate to a new architecture can run [`verify_test_vectors`] to check that seeded outputs,
+//! and the entropies reported with them, match the reference platform.
+//!
+//! ```rust
+//! use cryptirust::test_vectors;
+//!
+//! let checked = test_vectors::verify_test_vectors().unwrap();
+//! assert!(checked > 0);
+//! ```
+use crate::word_list;
+use crate::Generator;
+use std::fmt;
+
+/// Version of the generation algorithm, as far as seeded outputs are concerned. Version 2
+/// adds repeated groups, alternations and real words to the pattern grammar, which change
+/// the meaning of patterns using `(`, `[` or `x`.
+pub const ALGORITHM_VERSION: u32 = 2;
+
+/// The vectors, one per line: version, word list, depth, seed, pattern, output and entropy,
+/// separated by tabs.
+const VECTORS: &str = include_str!("test_vectors.tsv");
+
+/// A test vector which the current platform does not reproduce.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Mismatch {
+    /// Line of the vector in the data file.
+    pub line: usize,
+    /// Expected output and entropy.
+    pub expected: (String, f64),
+    /// Output and entropy obtained on this platform.
+    pub actual: (String, f64),
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "test vector {}: expected {:?} ({:.6} bits), got {:?} ({:.6} bits)",
+            self.line, self.expected.0, self.expected.1, self.actual.0, self.actual.1
+        )
+    }
+}
+
+impl std::error::Error for Mismatch {}
+
+/// Word list used by the vectors, `None` when its feature is disabled.
+fn list(name: &str) -> Option<Vec<String>> {
+    match name {
+        #[cfg(feature = "wordlist-eff")]
+        "eff" => Some(word_list::eff::list()),
+        #[cfg(feature = "wordlist-italian")]
+        "italian" => Some(word_list::italian::list()),
+        #[cfg(feature = "wordlist-russian")]
+        "russian" => Some(word_list::russian::list()),
+        "cv" => Some(word_list::cv::list()),
+        "debug" => Some(word_list::debug::list()),
+        _ => None,
+    }
+}
+
+/// Checks the vectors of the current [`ALGORITHM_VERSION`] whose word list is compiled in,
+/// returning how many were checked or the first one which does not match.
+pub fn verify_test_vectors() -> Result<usize, Mismatch> {
+    let mut checked = 0;
+    for (i, line) in VECTORS.lines().enumerate() {
+        let fields: Vec<&str> = line.split('\t').collect();
+        if line.starts_with('#') || fields.len() != 7 {
+            continue;
+        }
+        if fields[0].parse() != Ok(ALGORITHM_VERSION) {
+            continue;
+        }
+        let Some(list) = list(fields[1]) else {
+            continue;
+        };
+        let depth = fields[2].parse().unwrap();
+        let seed = fields[3].parse().unwrap();
+        let expected = (fields[5].to_string(), fields[6].parse::<f64>().unwrap());
+        let mut generator = Generator::new_custom(list, depth).unwrap();
+        generator.reseed(seed);
+        let actual = generator.gen_from_pattern(fields[4]);
+        if actual.0 != expected.0 || (actual.1 - expected.1).abs() > 1e-5 {
+            return Err(Mismatch {
+                line: i + 1,
+                expected,
+                actual,
+            });
+        }
+        checked += 1;
+    }
+    Ok(checked)
+}
@@ -0,0 +1,172 @@
+//! Rate limiting of passphrase entry.
+//!
+//! Offline and embedded apps which check passphrases themselves, e.g. with
+//! [`confirm::verify_typed`](crate::confirm::verify_typed) or
+//! [`canonical::matches`](crate::canonical::matches), must slow down guessing as well. A
+//! [`Throttle`] is a token bucket: every attempt takes a token, tokens come back one per refill
+//! interval up to the capacity, and an attempt without tokens is refused. Failed attempts
+//! count too: every time as many attempts in a row as the capacity fail, entry is locked for
+//! the next duration of the lockout schedule, the last duration repeating. A successful
+//! attempt resets the failures and refills the bucket.
+//!
+//! The throttle takes the current time as an argument rather than reading the clock, so that
+//! tests can run without waiting. Its state is kept in memory only and is not saved: it starts
+//! afresh with the app, so apps which can be restarted by an attacker must keep their own
+//! record of failed attempts.
+//!
+//! ```rust
+//! use cryptirust::throttle::Throttle;
+//! use std::time::{Duration, Instant};
+//!
+//! let mut throttle = Throttle::new(3, Duration::from_secs(10));
+//! let start = Instant::now();
+//! for guess in ["hunter2", "hunter3", "hunter4"] {
+//!     assert_eq!(throttle.check(start, || guess == "correct-horse"), Ok(false));
+//! }
+//! let refused = throttle.check(start, || true).unwrap_err();
+//! assert_eq!(refused.retry_after, Duration::from_secs(30));
+//! let later = start + refused.retry_after;
+//! assert_eq!(throttle.check(later, || true), Ok(true));
+//! ```
+use std::fmt;
+use std::time::{Duration, Instant};
+
+/// Lockouts after the first failures by default: 30 seconds, 5 minutes, 30 minutes, then 2
+/// hours every time.
+pub const DEFAULT_LOCKOUTS: [Duration; 4] = [
+    Duration::from_secs(30),
+    Duration::from_secs(5 * 60),
+    Duration::from_secs(30 * 60),
+    Duration::from_secs(2 * 60 * 60),
+];
+
+/// An attempt refused by a [`Throttle`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Throttled {
+    /// Time to wait before the next attempt.
+    pub retry_after: Duration,
+}
+
+impl fmt::Display for Throttled {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "too many attempts, retry in {} seconds",
+            self.retry_after.as_secs_f64().ceil()
+        )
+    }
+}
+
+impl std::error::Error for Throttled {}
+
+/// Token bucket with lockouts, see the [module](self) documentation.
+#[derive(Debug, Clone)]
+pub struct Throttle {
+    capacity: u32,
+    refill: Duration,
+    lockouts: Vec<Duration>,
+    tokens: u32,
+    /// When the last token was taken or given back.
+    refilled: Option<Instant>,
+    /// Failed attempts since the last success.
+    failures: u32,
+    locked_until: Option<Instant>,
+}
+
+impl Throttle {
+    /// A throttle allowing `attempts` attempts at once, then one more per `refill`, with the
+    /// [`DEFAULT_LOCKOUTS`]; `attempts` is at least 1.
+    pub fn new(attempts: u32, refill: Duration) -> Throttle {
+        let capacity = attempts.max(1);
+        Throttle {
+            capacity,
+            refill,
+            lockouts: DEFAULT_LOCKOUTS.to_vec(),
+            tokens: capacity,
+            refilled: None,
+            failures: 0,
+            locked_until: None,
+        }
+    }
+
+    /// Sets the lockout schedule; without lockouts only the bucket limits attempts.
+    pub fn set_lockouts(&mut self, lockouts: Vec<Duration>) {
+        self.lockouts = lockouts;
+    }
+
+    /// Failed attempts since the last successful one.
+    pub fn failures(&self) -> u32 {
+        self.failures
+    }
+
+    /// Takes a token for an attempt at time `now`, or tells how long to wait for one. Report
+    /// the outcome of the attempt with [`Throttle::failed`] or [`Throttle::succeeded`].
+    pub fn attempt(&mut self, now: Instant) -> Result<(), Throttled> {
+        if let Some(until) = self.locked_until {
+            if now < until {
+                return Err(Throttled {
+                    retry_after: until - now,
+                });
+            }
+            self.locked_until = None;
+        }
+        if let Some(refilled) = self.refilled {
+            let elapsed = now.saturating_duration_since(refilled);
+            let earned = match self.refill.as_nanos() {
+                0 => u128::from(self.capacity),
+                refill => elapsed.as_nanos() / refill,
+            };
+            let earned = earned.min(u128::from(self.capacity - self.tokens)) as u32;
+            self.tokens += earned;
+            self.refilled = if self.tokens == self.capacity {
+                None
+            } else {
+                Some(refilled + self.refill * earned)
+            };
+        }
+        if self.tokens == 0 {
+            let next = self.refilled.map_or(now, |refilled| refilled + self.refill);
+            return Err(Throttled {
+                retry_after: next.saturating_duration_since(now),
+            });
+        }
+        self.tokens -= 1;
+        self.refilled.get_or_insert(now);
+        Ok(())
+    }
+
+    /// Records a failed attempt at time `now`, locking entry when due.
+    pub fn failed(&mut self, now: Instant) {
+        self.failures = self.failures.saturating_add(1);
+        if self.failures.is_multiple_of(self.capacity) && !self.lockouts.is_empty() {
+            let round = (self.failures / self.capacity - 1) as usize;
+            let lockout = self.lockouts[round.min(self.lockouts.len() - 1)];
+            self.locked_until = Some(now + lockout);
+        }
+    }
+
+    /// Records a successful attempt, which resets the failures and refills the bucket.
+    pub fn succeeded(&mut self) {
+        self.failures = 0;
+        self.tokens = self.capacity;
+        self.refilled = None;
+        self.locked_until = None;
+    }
+
+    /// Makes an attempt at time `now` with `verify`, which tells whether the passphrase was
+    /// right, and records its outcome; `verify` is not called when the attempt is refused.
+    pub fn check<F: FnOnce() -> bool>(
+        &mut self,
+        now: Instant,
+        verify: F,
+    ) -> Result<bool, Throttled> {
+        self.attempt(now)?;
+        let right = verify();
+        if right {
+            self.succeeded();
+        } else {
+            self.failed(now);
+        }
+        Ok(right)
+    }
+}
@@ -0,0 +1,80 @@
+//! Token-level record of the random decisions taken by a [`Generator`](crate::Generator).
+//!
+//! A transcript lists, for every random choice, the kind of distribution it was drawn from, the
+//! index of the chosen outcome and its probability. Summing the surprisal of the decisions
+//! reproduces the entropy reported by the generator, which makes transcripts suitable as
+//! evidence when certifying the entropy computation. The RNG seed is never recorded, and
+//! neither is text of the secret: the markov context of a token or the characters drawn.
+//!
+//! ```rust
+//! use cryptirust::Generator;
+//!
+//! let mut generator = Generator::new();
+//! generator.start_transcript();
+//! let (_, entropy) = generator.gen_from_pattern("w-dd-s");
+//! let transcript = generator.take_transcript().unwrap();
+//! assert!((transcript.entropy() - entropy).abs() < 1e-9);
+//! println!("{}", transcript.to_json());
+//! ```
+use crate::json::json_string;
+
+/// A single random choice.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Decision {
+    /// Kind of distribution: `token` for markov tokens, `symbol` and `digit` for the
+    /// characters of `s` and `d`, `dddd` for date-safe digit groups, the name of the element for
+    /// the other elements, `choice` for alternations, `#length` for empirical word lengths (see
+    /// [`Generator::set_empirical_word_lengths`](crate::Generator::set_empirical_word_lengths)),
+    /// `#floor` for the correction of an element resampled to meet an entropy floor, whose
+    /// probability `1 / (1 - q)` exceeds one, `q` being the probability of the rejected outcomes.
+    pub kind: &'static str,
+    /// Index of the chosen outcome within the distribution.
+    pub index: usize,
+    /// Probability of the chosen outcome.
+    pub probability: f64,
+}
+
+impl Decision {
+    /// Surprisal of the decision in bits.
+    pub fn entropy(&self) -> f64 {
+        -self.probability.log2()
+    }
+}
+
+/// Ordered list of the decisions taken while recording.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Transcript {
+    pub decisions: Vec<Decision>,
+}
+
+impl Transcript {
+    /// Total surprisal of the recorded decisions in bits.
+    pub fn entropy(&self) -> f64 {
+        self.decisions.iter().map(Decision::entropy).sum()
+    }
+
+    /// Serializes the transcript as a JSON array of decisions.
+    pub fn to_json(&self) -> String {
+        let items: Vec<String> = self
+            .decisions
+            .iter()
+            .map(|d| {
+                format!(
+                    "{{\"kind\":{},\"index\":{},\"probability\":{:e}}}",
+                    json_string(d.kind),
+                    d.index,
+                    d.probability
+                )
+            })
+            .collect();
+        format!("[{}]", items.join(","))
+    }
+
+    pub(crate) fn record(&mut self, kind: &'static str, index: usize, probability: f64) {
+        self.decisions.push(Decision {
+            kind,
+            index,
+            probability,
+        });
+    }
+}
@@ -0,0 +1,157 @@
+//! ASCII transliteration of secrets, for systems which accept only ASCII.
+//!
+//! [`transliterate`] rewrites Latin letters with diacritics without them (`é` → `e`,
+//! `ß` → `ss`), Cyrillic after GOST 7.79 system B (`ж` → `zh`, `ц` → `cz`, `ь` → `` ` ``, but
+//! `э` → `eh` and `ъ` → `''`, so that no two Cyrillic strings share a transliteration) and
+//! Greek after ELOT 743 (`θ` → `th`); other characters are kept, and characters outside these
+//! scripts stay non-ASCII. Users memorize the original secret and type the ASCII form where
+//! required. This module requires the `transliterate` feature, enabled by default.
+//!
+//! Transliteration preserves entropy only if distinct secrets stay distinct, otherwise an
+//! attacker guesses among fewer ASCII forms. [`Generator::transliteration_is_injective`]
+//! checks this for the characters a generator can produce: when the transliterations of
+//! those characters form a uniquely decodable code the reported entropy still holds,
+//! otherwise it is only an upper bound. The Greek mapping, for instance, merges `ο` and `ω`.
+//!
+//! ```rust
+//! use cryptirust::{pattern::Pattern, translit, word_list, Generator};
+//!
+//! assert_eq!(translit::transliterate("Жёлудь-42"), "Zhyolud`-42");
+//!
+//! # #[cfg(feature = "wordlist-russian")]
+//! # {
+//! let mut generator = Generator::new_custom(word_list::russian::list(), 2).unwrap();
+//! let pattern = Pattern::parse("W-w-w-dd");
+//! assert!(generator.transliteration_is_injective(&pattern));
+//! let secret = generator.gen_annotated(&pattern);
+//! println!("type {} for {}", translit::transliterate(&secret.text), secret.text);
+//! # }
+//! ```
+use crate::pattern::{Pattern, Segment};
+use crate::Generator;
+use rand::{CryptoRng, RngCore};
+use std::collections::BTreeSet;
+
+/// Transliterations of lowercase letters; uppercase letters use the transliteration of
+/// their lowercase form, capitalized.
+#[rustfmt::skip]
+const TABLE: &[(char, &str)] = &[
+    // Latin
+    ('à', "a"), ('á', "a"), ('â', "a"), ('ã', "a"), ('ä', "a"), ('å', "a"), ('æ', "ae"), ('ç', "c"),
+    ('è', "e"), ('é', "e"), ('ê', "e"), ('ë', "e"), ('ì', "i"), ('í', "i"), ('î', "i"), ('ï', "i"),
+    ('ñ', "n"), ('ò', "o"), ('ó', "o"), ('ô', "o"), ('õ', "o"), ('ö', "o"), ('ø', "o"), ('ù', "u"),
+    ('ú', "u"), ('û', "u"), ('ü', "u"), ('ý', "y"), ('ÿ', "y"), ('ß', "ss"), ('œ', "oe"),
+    ('ą', "a"), ('ć', "c"), ('č', "c"), ('ď', "d"), ('ę', "e"), ('ě', "e"), ('ğ', "g"), ('ı', "i"),
+    ('ł', "l"), ('ń', "n"), ('ň', "n"), ('ő', "o"), ('ř', "r"), ('ś', "s"), ('ş', "s"), ('š', "s"),
+    ('ť', "t"), ('ů', "u"), ('ű', "u"), ('ź', "z"), ('ż', "z"), ('ž', "z"),
+    // Cyrillic, GOST 7.79 system B
+    ('а', "a"), ('б', "b"), ('в', "v"), ('г', "g"), ('д', "d"), ('е', "e"), ('ё', "yo"),
+    ('ж', "zh"), ('з', "z"), ('и', "i"), ('й', "j"), ('к', "k"), ('л', "l"), ('м', "m"), ('н', "n"),
+    ('о', "o"), ('п', "p"), ('р', "r"), ('с', "s"), ('т', "t"), ('у', "u"), ('ф', "f"), ('х', "x"),
+    ('ц', "cz"), ('ч', "ch"), ('ш', "sh"), ('щ', "shh"), ('ъ', "''"), ('ы', "y'"), ('ь', "`"),
+    ('э', "eh"), ('ю', "yu"), ('я', "ya"),
+    // Greek, ELOT 743
+    ('α', "a"), ('ά', "a"), ('β', "v"), ('γ', "g"), ('δ', "d"), ('ε', "e"), ('έ', "e"), ('ζ', "z"),
+    ('η', "i"), ('ή', "i"), ('θ', "th"), ('ι', "i"), ('ί', "i"), ('ϊ', "i"), ('ΐ', "i"), ('κ', "k"),
+    ('λ', "l"), ('μ', "m"), ('ν', "n"), ('ξ', "x"), ('ο', "o"), ('ό', "o"), ('π', "p"), ('ρ', "r"),
+    ('σ', "s"), ('ς', "s"), ('τ', "t"), ('υ', "y"), ('ύ', "y"), ('ϋ', "y"), ('ΰ', "y"), ('φ', "f"),
+    ('χ', "ch"), ('ψ', "ps"), ('ω', "o"), ('ώ', "o"),
+];
+
+/// The transliteration of `c`, see the [module](self) documentation.
+pub fn transliterate_char(c: char) -> String {
+    if c.is_ascii() {
+        return c.to_string();
+    }
+    let lower = c.to_lowercase().next().unwrap_or(c);
+    match TABLE.iter().find(|&&(from, _)| from == lower) {
+        Some(&(_, to)) if lower != c => {
+            let mut chars = to.chars();
+            chars.next().map_or(String::new(), |first| {
+                first.to_ascii_uppercase().to_string() + chars.as_str()
+            })
+        }
+        Some(&(_, to)) => to.to_string(),
+        None => c.to_string(),
+    }
+}
+
+/// `text` transliterated to ASCII, see the [module](self) documentation.
+pub fn transliterate(text: &str) -> String {
+    text.chars().map(transliterate_char).collect()
+}
+
+/// Whether every string made of `codewords` has a single decomposition into them, by the
+/// Sardinas-Patterson test.
+fn uniquely_decodable(codewords: &BTreeSet<String>) -> bool {
+    let dangling = |a: &str, b: &str| b.strip_prefix(a).map(String::from);
+    let mut suffixes = BTreeSet::new();
+    for a in codewords {
+        for b in codewords {
+            if a != b {
+                suffixes.extend(dangling(a, b));
+            }
+        }
+    }
+    let mut seen = BTreeSet::new();
+    while !suffixes.is_empty() {
+        if suffixes
+            .iter()
+            .any(|s| s.is_empty() || codewords.contains(s))
+        {
+            return false;
+        }
+        let mut next = BTreeSet::new();
+        for s in &suffixes {
+            if seen.insert(s.clone()) {
+                for c in codewords {
+                    next.extend(dangling(c, s));
+                    next.extend(dangling(s, c));
+                }
+            }
+        }
+        suffixes = next;
+    }
+    true
+}
+
+impl<R: RngCore + CryptoRng> Generator<R> {
+    /// Whether [`transliterate`] maps the secrets generated from `pattern` to distinct ASCII
+    /// strings, so that it preserves their entropy, see the [module](self) documentation. The
+    /// check is conservative: it considers every character of the model, in both cases, the
+    /// symbols, the digits and the literals of `pattern`, in all its alternatives. A letter whose uppercase form has
+    /// the same transliteration, such as `ь`, counts once, since the pattern rather than
+    /// chance decides where letters are capitalized.
+    pub fn transliteration_is_injective(&self, pattern: &Pattern) -> bool {
+        let mut letters: BTreeSet<char> = self
+            .jump_table
+            .values()
+            .flat_map(|d| d.tokens.iter().flat_map(|t| t.chars()))
+            .collect();
+        let mut alphabet: BTreeSet<char> =
+            self.symbols.iter().chain(&self.digits).copied().collect();
+        for segment in pattern.all_segments() {
+            match segment {
+                Segment::Literal(literal) => alphabet.extend(literal.chars()),
+                Segment::Dictionary { .. } => {
+                    letters.extend(self.dictionary.iter().flat_map(|w| w.chars()))
+                }
+                #[cfg(feature = "wordlist-pos")]
+                Segment::Part(part) => letters.extend(part.words().flat_map(|w| w.chars())),
+                Segment::Roman { .. } | Segment::Ordinal { .. } => {
+                    for numeral in segment.numerals().unwrap_or_default() {
+                        alphabet.extend(numeral.chars());
+                    }
+                }
+                _ => (),
+            }
+        }
+        for c in letters {
+            alphabet.insert(c);
+            let lower = transliterate_char(c);
+            alphabet.extend(c.to_uppercase().filter(|&u| transliterate_char(u) != lower));
+        }
+        let codewords: BTreeSet<String> = alphabet.iter().map(|&c| transliterate_char(c)).collect();
+        codewords.len() == alphabet.len() && uniquely_decodable(&codewords)
+    }
+}
@@ -0,0 +1,83 @@
+//! Patterns chosen by end users of a service.
+//!
+//! A service letting its users pick the shape of their secrets must not let them pick how much
+//! work it does. An [`UntrustedPattern`] is checked once against the fixed
+//! [`UNTRUSTED_LIMITS`], stricter than the [defaults](crate::limits::PatternLimits::default)
+//! and not configurable, so that no request can raise them: patterns are short, their secrets
+//! are short, and entropy floors, which resample, are refused. `{env:NAME}` and `{arg:NAME}`
+//! placeholders are refused as well rather than read as text, so that a pattern cannot probe
+//! for the variables of the service even if it expands patterns elsewhere.
+//!
+//! ```rust
+//! use cryptirust::limits::LimitError;
+//! use cryptirust::untrusted::UntrustedPattern;
+//! use cryptirust::Generator;
+//!
+//! let mut generator = Generator::new();
+//! let pattern = UntrustedPattern::parse(&generator, "W-w-dd").unwrap();
+//! let (passphrase, entropy) = generator.gen_untrusted(&pattern);
+//!
+//! assert!(matches!(
+//!     UntrustedPattern::parse(&generator, "{env:DATABASE_URL}"),
+//!     Err(LimitError::Substitution { .. })
+//! ));
+//! assert!(UntrustedPattern::parse(&generator, "w{>=12}").is_err());
+//! ```
+use crate::limits::{LimitError, PatternLimits};
+use crate::pattern::{expand_variables, Pattern};
+use crate::Generator;
+use rand::{CryptoRng, RngCore};
+
+/// Limits of every [`UntrustedPattern`].
+pub const UNTRUSTED_LIMITS: PatternLimits = PatternLimits {
+    max_length: 128,
+    max_elements: 32,
+    max_floors: 0,
+    max_expansion: 256,
+};
+
+/// A pattern from untrusted input within [`UNTRUSTED_LIMITS`], see the [module](self)
+/// documentation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UntrustedPattern {
+    pattern: Pattern,
+}
+
+impl UntrustedPattern {
+    /// Checks `input` for generation with `generator`, since the length of the secrets depends
+    /// on its word list and settings; generate with the same generator, or one trained on the
+    /// same list with the same settings.
+    pub fn parse<R: RngCore + CryptoRng>(
+        generator: &Generator<R>,
+        input: &str,
+    ) -> Result<UntrustedPattern, LimitError> {
+        // the length first, so that the search for placeholders is bounded too
+        if input.len() > UNTRUSTED_LIMITS.max_length {
+            return Err(LimitError::PatternTooLong {
+                length: input.len(),
+                max: UNTRUSTED_LIMITS.max_length,
+            });
+        }
+        if let Err(missing) = expand_variables(input, |_, _| None) {
+            return Err(LimitError::Substitution {
+                source: missing.source,
+                name: missing.name,
+            });
+        }
+        let pattern = generator.parse_limited(input, &UNTRUSTED_LIMITS)?;
+        Ok(UntrustedPattern { pattern })
+    }
+
+    /// The parsed pattern.
+    pub fn pattern(&self) -> &Pattern {
+        &self.pattern
+    }
+}
+
+impl<R: RngCore + CryptoRng> Generator<R> {
+    /// Generates a secret from an [`UntrustedPattern`], see
+    /// [`Generator::gen_from_parsed`].
+    pub fn gen_untrusted(&mut self, pattern: &UntrustedPattern) -> (String, f64) {
+        self.gen_from_parsed(&pattern.pattern)
+    }
+}
@@ -0,0 +1,62 @@
+//! Challenge phrases for voice authentication.
+//!
+//! Call-center verification scripts read a short phrase to the caller, or ask the caller to
+//! read one, and check it against the output of a speech recognizer. [`Generator::gen_challenge`]
+//! draws such phrases uniformly from the bundled [`word_list::voice`] list, whose words are
+//! easy to pronounce and do not sound alike, so recognition errors do not turn one valid
+//! phrase into another. [`verify_challenge`] compares a phrase with what was heard, ignoring
+//! case, punctuation and spacing.
+//!
+//! Challenge phrases are nonces: they are short and meant to be used once, not as passwords.
+//!
+//! ```rust
+//! use cryptirust::{voice, Generator};
+//!
+//! let mut generator = Generator::new();
+//! let (phrase, entropy) = generator.gen_challenge(voice::CHALLENGE_WORDS);
+//! assert!(entropy > 32.0);
+//! assert!(voice::verify_challenge(&phrase, &format!("  {}.", phrase.to_uppercase())));
+//! ```
+use crate::word_list;
+use crate::Generator;
+use rand::{CryptoRng, RngCore};
+
+/// Recommended number of words of a challenge phrase.
+pub const CHALLENGE_WORDS: usize = 4;
+
+impl<R: RngCore + CryptoRng> Generator<R> {
+    /// Generates a challenge phrase of `words` words separated by spaces, returning it with
+    /// its entropy in bits. The phrase does not depend on the model or on the settings of the
+    /// generator, only on its random number generator.
+    pub fn gen_challenge(&mut self, words: usize) -> (String, f64) {
+        let n = word_list::voice::WORDS.split(';').count();
+        let mut phrase = Vec::with_capacity(words);
+        for _ in 0..words {
+            let i = self.below(n);
+            if let Some(t) = self.transcript.as_mut() {
+                t.record("voice", i, 1.0 / n as f64);
+            }
+            phrase.push(word_list::voice::WORDS.split(';').nth(i).unwrap());
+        }
+        (phrase.join(" "), words as f64 * (n as f64).log2())
+    }
+}
+
+/// Whether `heard`, e.g. the output of a speech recognizer, matches the challenge phrase
+/// `expected`. Case and everything but letters are ignored, only the sequence of words counts.
+///
+/// ```rust
+/// use cryptirust::voice::verify_challenge;
+///
+/// assert!(verify_challenge("amber kitten radar", "Amber, kitten... radar!"));
+/// assert!(!verify_challenge("amber kitten radar", "amber kitten"));
+/// ```
+pub fn verify_challenge(expected: &str, heard: &str) -> bool {
+    spoken_words(expected).eq(spoken_words(heard))
+}
+
+fn spoken_words(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphabetic())
+        .filter(|w| !w.is_empty())
+        .map(|w| w.to_lowercase())
+}
@@ -1,4 +1,254 @@
+//! Bundled word lists and helpers to load custom ones.
+//!
+//! The large lists are gated behind cargo features (`wordlist-eff`, `wordlist-italian`,
+//! `wordlist-russian`, `wordlist-pos`, all enabled by default), so that consumers who bring their own tokens don't carry the word data.
+//! The [`cv`] list is generated on the fly and always available, as is the small [`voice`] list.
 pub mod cv;
 pub mod debug;
+#[cfg(feature = "wordlist-eff")]
 pub mod eff;
+pub(crate) mod homophones;
+#[cfg(feature = "wordlist-italian")]
 pub mod italian;
+#[cfg(feature = "wordlist-pos")]
+pub mod pos;
+#[cfg(feature = "wordlist-russian")]
+pub mod russian;
+pub mod voice;
+
+use crate::encode::format_hex;
+use crate::sha256::sha256;
+use std::collections::{BTreeSet, HashMap};
+use std::path::Path;
+
+/// Reads a word list from raw bytes, one word per line.
+///
+/// Only the last whitespace separated field of each line is kept, so diceware style lists
+/// (`11111 abacus`) can be used directly. Empty lines are skipped, and so are the lines which
+/// are not valid UTF-8: returns the words and the number of lines skipped because of their
+/// encoding, so that callers can report them.
+///
+/// ```rust
+/// use cryptirust::word_list;
+///
+/// let (words, invalid) = word_list::from_bytes(b"11111 abacus\n\xff\n\n11112 abdomen\n");
+/// assert_eq!(words, vec!["abacus", "abdomen"]);
+/// assert_eq!(invalid, 1);
+/// ```
+pub fn from_bytes(bytes: &[u8]) -> (Vec<String>, usize) {
+    let mut invalid = 0;
+    let words = bytes
+        .split(|&b| b == b'\n')
+        .filter_map(|line| {
+            let line = std::str::from_utf8(line);
+            invalid += usize::from(line.is_err());
+            line.ok()
+        })
+        .filter_map(|line| line.split_whitespace().last())
+        .map(|word| word.to_string())
+        .collect();
+    (words, invalid)
+}
+
+/// Reads a word list from a file, see [`from_bytes`] for the accepted format and the
+/// returned values.
+pub fn from_file<P: AsRef<Path>>(path: P) -> std::io::Result<(Vec<String>, usize)> {
+    Ok(from_bytes(&std::fs::read(path)?))
+}
+
+/// The list used by [`Generator::new`](crate::Generator::new): the EFF list when the
+/// `wordlist-eff` feature is enabled, the [`cv`] list otherwise.
+pub fn default_list() -> Vec<String> {
+    #[cfg(feature = "wordlist-eff")]
+    {
+        eff::list()
+    }
+    #[cfg(not(feature = "wordlist-eff"))]
+    {
+        cv::list()
+    }
+}
+
+/// Hexadecimal SHA-256 digest of the set of words of a word list, which identifies the version
+/// of the list: [serialized models](crate::Generator::model_json) record the fingerprint of
+/// the list they were trained on, so that [loading them](crate::Generator::from_model_json)
+/// against another version fails.
+///
+/// The words are sorted and deduplicated, as the trained model does not depend on their order,
+/// and each is hashed after its length in bytes, as a big-endian `u64`, so that no two sets of
+/// words hash the same content, whatever characters the words contain.
+///
+/// ```rust
+/// use cryptirust::word_list;
+///
+/// let list = word_list::cv::list();
+/// let mut reversed = list.clone();
+/// reversed.reverse();
+/// assert_eq!(word_list::fingerprint(&list), word_list::fingerprint(&reversed));
+/// assert_ne!(word_list::fingerprint(&list), word_list::fingerprint(&list[1..]));
+/// ```
+pub fn fingerprint(list: &[String]) -> String {
+    let words: BTreeSet<&String> = list.iter().collect();
+    let mut content = Vec::new();
+    for word in words {
+        content.extend_from_slice(&(word.len() as u64).to_be_bytes());
+        content.extend_from_slice(word.as_bytes());
+    }
+    format_hex(&sha256(&content))
+}
+
+/// Report on the suitability of a word list for diceware style passphrases, see [`audit`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Audit {
+    /// Number of distinct words after normalization.
+    pub size: usize,
+    /// Entropy of a word picked uniformly from the list, in bits.
+    pub entropy_per_word: f64,
+    /// Normalized words appearing more than once in the list.
+    pub duplicates: Vec<String>,
+    /// Pairs `(word, longer)` where `word` is a prefix of `longer`: concatenating words
+    /// without separators is ambiguous when the list is not prefix-free.
+    pub prefix_violations: Vec<(String, String)>,
+    /// Average length of the distinct words, in characters.
+    pub average_length: f64,
+}
+
+/// Checks a word list the way one would before adopting it for diceware passphrases.
+///
+/// Words are normalized as for training, i.e. trimmed and lowercased, empty words are ignored.
+///
+/// ```rust
+/// use cryptirust::word_list;
+///
+/// let list: Vec<String> = ["sun", "Sunset", "sun ", "moon"].iter().map(|w| w.to_string()).collect();
+/// let audit = word_list::audit(&list);
+/// assert_eq!(audit.size, 3);
+/// assert_eq!(audit.duplicates, vec!["sun"]);
+/// assert_eq!(audit.prefix_violations, vec![("sun".to_string(), "sunset".to_string())]);
+/// ```
+pub fn audit(list: &[String]) -> Audit {
+    let mut words: Vec<String> = list
+        .iter()
+        .map(|w| w.trim().to_lowercase())
+        .filter(|w| !w.is_empty())
+        .collect();
+    words.sort();
+    let mut duplicates = Vec::new();
+    for pair in words.windows(2) {
+        if pair[0] == pair[1] && duplicates.last() != Some(&pair[0]) {
+            duplicates.push(pair[0].clone());
+        }
+    }
+    words.dedup();
+
+    let mut prefix_violations = Vec::new();
+    for (i, word) in words.iter().enumerate() {
+        // in sorted order all the words extending `word` immediately follow it
+        for longer in words[i + 1..]
+            .iter()
+            .take_while(|w| w.starts_with(word.as_str()))
+        {
+            prefix_violations.push((word.clone(), longer.clone()));
+        }
+    }
+
+    let size = words.len();
+    let total_length: usize = words.iter().map(|w| w.chars().count()).sum();
+    Audit {
+        size,
+        entropy_per_word: if size > 0 { (size as f64).log2() } else { 0.0 },
+        duplicates,
+        prefix_violations,
+        average_length: if size > 0 {
+            total_length as f64 / size as f64
+        } else {
+            0.0
+        },
+    }
+}
+
+/// Makes a word list prefix-free by dropping every word which is a prefix of another word, so
+/// that a concatenation of words of the cleaned list, without separators, splits back into its
+/// words in a single way.
+///
+/// This holds for the words of the list, e.g. when they are drawn as a
+/// [dictionary](crate::Generator::set_dictionary); the words generated by a model trained on
+/// the list are new words, which can still be prefixes of one another.
+///
+/// Words are normalized as for training and duplicates are removed. Returns the cleaned list,
+/// sorted, and the number of distinct words which were dropped because they were prefixes.
+///
+/// ```rust
+/// use cryptirust::word_list;
+///
+/// let list: Vec<String> = ["sun", "sunset", "moon"].iter().map(|w| w.to_string()).collect();
+/// let (cleaned, dropped) = word_list::remove_prefixes(&list);
+/// assert_eq!(cleaned, vec!["moon", "sunset"]);
+/// assert_eq!(dropped, 1);
+/// ```
+pub fn remove_prefixes(list: &[String]) -> (Vec<String>, usize) {
+    let mut words: Vec<String> = list
+        .iter()
+        .map(|w| w.trim().to_lowercase())
+        .filter(|w| !w.is_empty())
+        .collect();
+    words.sort();
+    words.dedup();
+    let before = words.len();
+    // in sorted order a word is a prefix of another word iff it is a prefix of its successor
+    let keep: Vec<bool> = (0..words.len())
+        .map(|i| {
+            !words
+                .get(i + 1)
+                .is_some_and(|next| next.starts_with(&words[i]))
+        })
+        .collect();
+    let mut keep = keep.into_iter();
+    words.retain(|_| keep.next().unwrap_or(true));
+    let dropped = before - words.len();
+    (words, dropped)
+}
+
+/// Merges the words which sound alike, such as `their` and `there` or `two` and `too`, using a
+/// bundled map of English homophones, so that spoken passphrases can be dictated reliably.
+///
+/// Of each group of homophones found in the list only the most common spelling is kept. Words
+/// are normalized as for training and duplicates are removed. Returns the cleaned list, sorted,
+/// and the number of distinct words which were dropped.
+///
+/// ```rust
+/// use cryptirust::word_list;
+///
+/// let list: Vec<String> = ["there", "their", "too", "apple"].iter().map(|w| w.to_string()).collect();
+/// let (cleaned, dropped) = word_list::filter_homophones(&list);
+/// assert_eq!(cleaned, vec!["apple", "their", "too"]);
+/// assert_eq!(dropped, 1);
+/// ```
+pub fn filter_homophones(list: &[String]) -> (Vec<String>, usize) {
+    let words: BTreeSet<String> = list
+        .iter()
+        .map(|w| w.trim().to_lowercase())
+        .filter(|w| !w.is_empty())
+        .collect();
+    // position of every word within its group, the most common spelling coming first
+    let groups: HashMap<&str, (&str, usize)> = homophones::GROUPS
+        .split(';')
+        .flat_map(|group| {
+            group
+                .split(',')
+                .enumerate()
+                .map(move |(i, word)| (word, (group, i)))
+        })
+        .collect();
+    let before = words.len();
+    let cleaned: Vec<String> = words
+        .iter()
+        .filter(|word| match groups.get(word.as_str()) {
+            Some(&(group, i)) => !group.split(',').take(i).any(|other| words.contains(other)),
+            None => true,
+        })
+        .cloned()
+        .collect();
+    let dropped = before - cleaned.len();
+    (cleaned, dropped)
+}
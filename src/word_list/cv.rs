@@ -8,5 +8,5 @@ pub fn list() -> Vec<String> {
             lst.push(v.to_string() + &c.to_string());
         }
     }
-    return lst;
+    lst
 }
@@ -1,4 +1,4 @@
 pub fn list() -> Vec<String> {
-    let libr = vec!["abus", "acum", "adcen", "aealc", "afide", "agit"];
-    return libr.iter().map(|x| x.to_string()).collect();
+    let libr = ["abus", "acum", "adcen", "aealc", "afide", "agit"];
+    libr.iter().map(|x| x.to_string()).collect()
 }
@@ -0,0 +1,6 @@
+//! Groups of English words which sound alike, used by
+//! [`filter_homophones`](crate::word_list::filter_homophones).
+//!
+//! Each group lists its most common spelling first.
+
+pub(crate) const GROUPS: &str = "their,there,they're;to,too,two;for,four,fore;one,won;ate,eight;be,bee;see,sea;son,sun;knight,night;flower,flour;rain,reign,rein;tail,tale;meet,meat,mete;pair,pear,pare;plain,plane;road,rode,rowed;rose,rows;dear,deer;hair,hare;whole,hole;mail,male;not,knot;piece,peace;steel,steal;week,weak;would,wood;sale,sail;right,write,rite;ring,wring;whale,wail;horse,hoarse;board,bored;sell,cell;chilly,chili;kernel,colonel;due,dew;die,dye;flee,flea;fur,fir;great,grate;heal,heel;him,hymn;missed,mist;knows,nose;or,oar,ore;pale,pail;pole,poll;route,root;soul,sole;stair,stare;sweet,suite;toe,tow;waste,waist;wait,weight;way,weigh;where,wear,ware;which,witch;yoke,yolk;berry,bury;cent,scent,sent;core,corps;bread,bred;cereal,serial;course,coarse;need,knead;lone,loan;main,mane;medal,meddle;naval,navel;plum,plumb;prince,prints;guessed,guest;beat,beet;blue,blew;break,brake;by,buy,bye;cheap,cheep;cord,chord;feet,feat;hey,hay;higher,hire;our,hour;led,lead;made,maid;new,knew,gnu;none,nun;pause,paws;site,sight,cite;tea,tee;tide,tied;vain,vein,vane;war,wore;wrap,rap;queue,cue;haul,hall;pedal,peddle;know,no;allowed,aloud;ant,aunt;ball,bawl;band,banned;bare,bear;base,bass;billed,build;boar,bore;bold,bowled;boy,buoy;brews,bruise;bridal,bridle;cache,cash;capital,capitol;ceiling,sealing;cellar,seller;cheque,check;choral,coral;clause,claws;council,counsel;creak,creek;crews,cruise;days,daze;doe,dough;draft,draught;dual,duel;earn,urn;faint,feint;fair,fare;find,fined;flew,flu;flow,floe;foul,fowl;friar,fryer;gait,gate;genes,jeans;gorilla,guerrilla;groan,grown;hangar,hanger;heard,herd;here,hear;hi,high;hoes,hose;holy,wholly;idle,idol;in,inn;jam,jamb;key,quay;knit,nit;knock,nock;lain,lane;lessen,lesson;links,lynx;loot,lute;manner,manor;marshal,martial;might,mite;mind,mined;mode,mowed;moose,mousse;morning,mourning;muscle,mussel;nay,neigh;oh,owe;overdo,overdue;paced,paste;passed,past;patience,patients;peak,peek,pique;peal,peel;pearl,purl;pier,peer;plaice,place;pray,prey;principal,principle;profit,prophet;rack,wrack;rapped,rapt,wrapped;raise,rays,raze;read,reed;rest,wrest;review,revue;wrote,rote;rough,ruff;rung,wrung;rye,wry;scene,seen;seam,seem;seas,sees,seize;sew,so,sow;shear,sheer;shoe,shoo;side,sighed;sighs,size;slay,sleigh;soar,sore;some,sum;stake,steak;stationary,stationery;storey,story;straight,strait;tacks,tax;taught,taut;teas,tease;team,teem;tern,turn;threw,through;throne,thrown;thyme,time;tire,tyre;toad,towed;told,tolled;vial,vile;wade,weighed;warn,worn;wave,waive;we,wee;weather,whether;whine,wine;yew,you;yore,your,you're";
@@ -1,4 +1,4 @@
 pub fn list() -> Vec<String> {
     const RAW_LIST: &str = "abaca;abasie;abbacina;abbai;abbaiava;abballare;abbancate;abbaruffo;abbasso;abbattono;abbellii;abbia;abbicava;abbinando;abbindolo;abbisci;abbocca;abboffate;abbonassi;abbondati;abboni;abborda;abborra;abbozzare;abbraccia;abbrivata;abbrumate;abbrunava;abbrutire;abbuffi;abbuona;abdicando;abdichi;abducenti;abenule;abiatico;abilitano;abiotica;abitacoli;abitati;abiterei;abituano;abituavi;abiurai;abiure;ablatoria;abnegava;aboliremo;abolitive;abomina;aborigeni;aborrivo;abortisse;abradere;abrogassi;abrostini;absidiola;abusano;abusavi;abuso;acantosi;acarpe;accaddero;accaffare;accaldata;accampano;accani;accanissi;accaparro;accasa;accasavi;accasiate;accatto;accecarla;accecatoi;accederai;accediamo;accendera;accennata;accenta;accentuo;accertar;accesero;accettato;acchetare;acchitato;acciaiano;acciarite;accidioso;accingevo;accisa;acclamare;acclimata;accludevi;accocchi;accodasse;accodiamo;accollano;accolsi;accomuni;accontato;accoppio;accoravo;accordava;accorgero;accorpata;accorrevi;accorti;accostera;accozzino;accudendo;accudiste;acculava;accusando;accusata;accuso;aceracee;acerrima;aceta;acetici;acetilica;acetone;acheronte;achilleo;acida;acidula;acile;acinose;acmonital;aconzio;acquata;acquei;acquida;acquisita;acquosi;acridio;acritico;acronimo;acufeni;aculeate;acuminato;acuzie;adagia;adagiata;adamo;adattarti;adatterei;addaziate;addensare;addentare;addentro;addicendo;addirsi;additasse;additiamo;addobbai;addoccio;addolcira;addoppia;addossavi;adduare;adduciamo;adduttive;adeguarti;adeguero;adempie;adempiro;adempiuti;adenoidei;adequate;aderenze;aderirete;aderivate;adescarlo;adesco;adiacente;adibii;adibiste;adige;adinati;adirando;adiravano;adirono;adiuvare;adombrati;adonide;adontati;adoperano;adoprammo;adoprasti;adopro;adorarlo;adoravi;adornando;adornera;adottando;adottavo;adozioni;adriani;adrone;aduggiare;adularono;adulavo;adultere;adunare;adunavate;adunghio;adusi;aeramento;aerazione;aerina;aerodromi;aerogetto;aeromante;aeroposta;aerotermi;afasiche;aferesi;affagotta;affamare;affami;affannera;affarii;affatico;affermare;afferrare;affettare;affettivo;affiatati;affiches;affidasse;affidi;affieniti;affilano;affilerei;affiliavo;affinato;affiochi;affissati;affittato;afflati;affluenti;affoca;affocato;affogaste;affollare;affoltato;affondi;affossati;affranta;affricate;afgane;afoni;africana;afroroso;agamico;ageminano;agerato;agevolero;aggancino;aggelano;aggettato;aggiogai;aggira;aggiravi;aggiungo;aggobbita;aggradare;aggravare;aggredira;aggregava;aggrumare;aggruppo;aghiforme;agieo;agiologo;agisce;agitano;agitata;agiterei;aglaonemi;aglifo;agnellino;agnonese;agognati;agoniche;agorafobi;agosto;agreste;agrifogli;agrodolce;agrumaria;agugliate;aguzzasse;aguzzi;aierini;airedale;aiutarci;aiutate;aiutera;aizzarla;aizziamo;alaggi;alare;alauda;albanelle;albatra;albera;alberesi;alberini;albicata;albino;albori;alcaici;alcanna;alchilico;alcolato;alcologia;aldeidi;aleatoria;alemanni;alesare;aletta;alfabeta;algali;algerini;algie;algogeno;alia;alidada;alienasse;alieni;aligera;aliossi;alita;alivolo;allagarsi;allago;allargati;allarmavi;allaschi;allattavi;allearsi;allegano;alleggi;allegre;allemande;allenasti;allenire;allenti;allertati;allestite;allettero;allevarti;allevero;allevino;allibiate;allibivo;allietare;alligando;allindati;allobrogi;allocca;allodio;allogena;allombata;alloppia;allottata;alludera;allumando;allumo;allungare;allupano;allusive;almagesti;almirante;alocasia;alogenati;alosa;alpigiane;alsazia;altamente;alterammo;alterata;altererai;alternare;altezze;altiforni;altoporti;altruismi;alule;alveolari;alzabile;alzandomi;alzarti;alzatori;alzerei;amache;amammo;amantea;amare;amaricare;amarori;amasse;amatorio;ambagi;ambianese;ambigui;ambiscono;ambizioni;ambrati;ambula;amebei;amene;amera;americi;ametabole;amicabile;amidacea;amigdala;amitotica;ammaccano;ammainano;ammalarci;ammalavo;ammaliavi;ammaniera;ammanniva;ammantano;ammarano;ammarrate;ammassera;ammattivi;ammazzavi;ammenciti;ammestare;ammetta;ammezzano;ammicca;amminica;ammirar;ammirata;ammise;ammogli;ammollita;ammonici;ammonirti;ammonivo;ammonto;ammorsati;ammorzata;ammoscivi;ammucchio;ammulino;ammusire;ammutoli;amomi;amorfa;amostanti;ampia;ampliarla;amplidina;ampollosa;amputaste;amputino;anacardio;anacoreta;anaforico;analda;analizzi;anapesto;anasarca;anatrando;ancate;ancia;anconei;ancorarsi;ancorerei;andai;andandomi;andarlo;andasti;andava;andicappi;andranno;androgeni;anecoiche;anelavamo;anellata;anemie;anerobi;aneurismi;anfibi;anfimacro;anfratti;angelene;anginose;angiomi;anglismo;anglofono;angoli;angoscera;anguinee;angustura;anidrite;animando;animavamo;animiate;animule;annaffiai;annaspa;annaspo;annegano;anneghi;annerisce;annette;annidano;anniderei;annoccare;annodarli;annoderei;annoiarla;annoiava;annose;annotarli;annotero;annottino;annuente;annuisca;annullai;annuncero;annusala;annusata;annuvola;anodizzo;anomalie;anonimie;anosmia;ansata;anserine;ansimato;ansiosa;ante;antenata;anteporro;anteriore;antialghe;anticati;anticorpo;antifona;antigrafo;antimuro;antipasti;antiqua;antirughe;antitempo;antociane;antoniani;antracite;anulare;anzianita;aonio;aostano;apatici;aperta;apiarie;apiola;apiretico;aplestie;apneisti;apocopato;apodissi;apografe;apologete;aporetica;apostate;apotema;appagare;appagavo;appaiassi;appaiono;appalto;appanno;apparii;apparita;apparso;apparve;appastati;appellera;appendere;appesero;appesti;appettata;appianare;appiatta;appicco;appigliai;appisola;applicalo;appoggero;apponeva;appoppava;apportata;apposite;apposti;apprende;appretta;approdato;approva;apprua;appuntati;appura;appuravo;appuzzato;aprendovi;aprila;aprir;aprirlo;apritela;aprivo;apuania;aquilarie;aquilotto;arabia;aracea;aragosta;aramaica;aranciata;ararat;arato;aravano;arbitrano;arbitrio;arbustive;arcadie;arcali;arcatelle;archeane;archiatro;arcigna;arcistufi;arcto;arcuature;ardendo;ardero;ardevate;ardirebbe;ardissero;ardivamo;areali;areico;arenasse;arenicolo;areola;areostili;aretine;argentana;argentine;arginale;arginavo;argiria;argolici;arguimmo;arguite;arguzie;aridezze;arietano;arimannie;ariosteo;arlesiane;armaiolo;armasse;armatrice;armeggio;armerebbe;armiate;armino;armonioso;arnia;arpagone;arpie;arpiono;arrabbiai;arraffi;arrangero;arraparle;arrapavi;arrazzata;arrecata;arredano;arredavo;arrembo;arrenavi;arrenerai;arrestale;arresto;arretro;arridate;arringano;arrise;arrivarvi;arrivi;arroccato;arrogai;arrogaste;arrolato;arrossato;arrosta;arrostito;arrotate;arrotino;arruffare;arruolai;arsa;arsi;arsure;artefici;artesiana;artiere;artisti;artrite;aruachi;arzavole;asburgica;ascensa;ascetismo;asciato;asciti;asciugato;ascofori;ascoltano;ascondano;ascospore;ascriveva;asettica;asfaltico;asiaca;asienti;asincrono;asini;asmatici;asoli;asperelle;aspersero;aspettare;aspido;aspirasti;aspirine;aspra;asprini;assali;assalirvi;assalsi;assalto;assedi;assedio;assegno;assennato;assentero;asserendo;asserisse;assertiva;asservivi;assestera;assetino;assevero;assideri;assiepava;assillero;assiro;assisteva;assiti;associato;assodasti;assodo;assoldare;assoluto;assolvono;assonemi;assopi;assopirsi;assopivi;assorbivo;assordi;assortita;assuefai;assumendo;assumesse;assunta;assurdi;astabile;asteggia;asteneste;asteniche;asterge;asterse;astile;aston;astrali;astrofila;astrusi;atabagico;atava;ateiste;ateneo;atesini;atlanti;atollo;atonale;atrabili;atrii;atrofizzo;attaccata;attanti;attardavo;atteggi;attendano;attendo;attenervi;atteniate;attentera;attenuera;attergati;atterravo;atterriva;attestano;attica;attigue;attinga;attiniche;attintura;attirasse;attiriamo;attivati;attivista;attizzi;attorio;attraendo;attrarla;attratti;attriti;attuare;attuasti;attuavo;attuffino;attutata;attutiti;audiolesa;auditorio;augnatura;augurasse;auguri;augustano;auletica;aulos;aumento;aureolato;aurismo;ausiliari;auspicava;austorio;autieri;autocarro;autoctone;autogena;autoguide;automa;autopiano;autori;autotomie;auvergne;avallando;avallera;avances;avanzai;avanzata;avanzume;avariera;avemaria;avendovi;averlo;avesti;aviatore;avidi;avionici;avocate;avole;avrete;avutisi;avvallano;avvampano;avvedeva;avveduti;avvenirsi;avventera;avvera;avveravi;avversare;avvertii;avvertono;avvia;avviato;avviene;avviino;avvilirsi;avvilivi;avvincere;avvisalo;avvisasse;avvisiamo;avvistero;avvitassi;avvitiate;avvivato;avvolgano;avvolti;aziende;azionasse;azoici;azotemie;azulene;azzardano;azzardosi;azzecco;azzerino;azzitta;azzoppai;azzoppita;azzuffavo;azzurrina;babbalea;babbuassa;babelle;bacacci;bacasse;baccalari;bacche;bacchiava;bacerei;bachino;baciapile;baciasti;baciava;bacillo;bacoli;bacucche;badano;badatori;baderemo;badiamo;badiotta;baffuti;bagascia;baggioli;baglivi;bagnarla;bagnate;bagnerei;bagola;bagordato;baia;bailamme;baiulo;balanino;balbetta;balcanica;baldi;balenai;balene;balenio;baliatici;baliste;ballando;ballatore;ballerini;ballonet;baloccano;balogio;balsamite;baluba;balzando;balzata;balzerai;bambagine;bambinita;bambolona;bananeto;bancaccia;banchieri;banconote;banderai;bandimmo;bandiste;bandole;bangkok;banteng;baraccano;barai;barate;baratte;barbacane;barbari;barbatula;barbi;barbini;barbona;barbugli;barcaiolo;barche;barcollio;bardassa;bardavo;bardiate;barellano;barenato;baresana;baribal;barilette;barista;barletti;baro;barocorie;baronesca;barrage;barratura;barricava;barrivano;baruffe;barzoi;basammo;basarla;basate;basco;baserei;basiamoci;basilare;basilissa;basito;basofobie;bassata;bassotta;bastante;bastate;basterna;bastini;bastonai;batacchi;batiali;batocchio;batrace;battemmo;batterico;battessi;battiamo;battime;battitori;battuti;baulavano;bava;bavaro;bavosi;bazze;bazzone;beare;beatles;beccai;beccarono;beccava;becchetto;beccucci;becero;beduina;beffardi;beffata;beffeggio;beghina;beguines;beisa;belavano;belio;belletti;bellide;bellonese;belo;belva;benamate;bendaggio;bendasse;bende;benedette;benefiche;benevoli;bengasina;benigne;bennati;benuscite;benvoluti;benzinaio;benzolo;bequadro;bercia;beretta;berilli;berlinese;bernarda;bernoulli;berretto;bertino;berze;bestemmie;bestiola;betlemiti;bettolano;betun;bevei;beverate;bevero;bevette;bevigione;bevono;bezzicati;biadarono;biadi;biancassi;bianchino;bianconi;biasciare;biasimera;biassiche;biatomici;biberons;bibliche;bicchiere;bicipite;biconiche;bicornute;bidello;bidonarle;bidonavo;bieca;bierre;bietoloni;bifernine;biffi;biforcano;bifronte;bigattaie;bigiare;bignamino;bigordare;biiezioni;bilancia;bilharzia;bilimbi;billette;bilustre;bimensile;binarismo;binaurale;bineranno;binomio;biodi;biogenia;biologia;biondella;bioniche;bioscopi;biotipo;biovulari;bipedi;bipolide;birberia;birboni;birignao;birra;birri;bisarche;bisbiglii;biscazze;bisciole;bisdosso;bisegolo;bisensi;bisestile;bisillabe;bismuti;bisognava;bisoniche;bissavano;bistecche;bistrano;bistrino;biternati;bittare;bitumasse;biuta;bivaccare;bivi;bizzarro;blackout;blandirei;blanditi;blasonano;blastiche;blateri;bleffando;bleffi;blesi;blindi;bloccando;bloccaste;blonde;bluffai;blufferei;board;bobbando;bobiensi;bobolchi;boccalini;bocce;bocchino;bocciata;boccolo;bodole;boeri;bogliolo;boiate;boldine;bolge;bolla;bollava;bollinare;bollisse;bollivi;bolognino;bolzanini;bombace;bombata;bombice;bombo;bonaccie;bonario;bongustai;bonomia;boole;boracere;borbotta;borda;bordatura;bordino;borgatari;boria;boriavi;boriose;borlare;boro;borrarono;borro;borsani;borsettai;borsotto;boscatici;bosco;bosniaca;bossole;botole;bottaccio;bottali;bottegare;bottinato;botulismo;boutique;bovesana;bowling;boxers;bozzagri;bozzolaia;bozzolute;bracati;bracchi;braccino;brachiale;braciaio;braconidi;bragg;brahmani;bramane;bramavano;bramina;bramme;brancato;brand;brando;brasati;brasserie;bratteati;bravato;breaks;breccioso;bremo;bresaole;breva;brezzare;brianteo;briccona;bridge;brigare;brigate;brighi;brillammo;brillati;brillio;brincelli;brindate;brindisi;briologia;brise;brocardi;broccava;brochure;brodi;broglia;brolli;bromoli;bronchiti;bronzando;bronzine;brovare;bruca;brucerei;bruciamo;bruciasse;brucione;brughiera;brulicame;bruma;brunastra;brunicci;brunitori;bruscello;brusisce;bruttando;bruzzico;bubare;bubbolo;bubulche;bucaniere;bucataia;buccale;buccinava;bucentori;buchetta;bucolico;budelle;bueggiano;bufalare;bufera;buffer;bufoli;buggerio;bugie;bugnata;bugolo;bularono;bulbilli;bulgaria;bulinava;bulli;bumasti;bunkerati;buonanime;bupreste;burattati;burchi;burgitano;buridano;burlande;burlaste;burlera;burlevoli;burnus;burri;burroso;buscarne;buscavano;busco;bussammo;bussavamo;bussiate;bustier;butanoli;butirrosa;buttarci;buttata;buttavo;buttino;buzzica;bwana;byroniane;cabile;cabrare;cacca;cacciammo;cacciati;cachi;cacume;cadenza;cadevano;cadremo;caffe;cafonesco;cagna;caicco;calabrone;calamio;calanti;calate;calcagni;calce;calcinata;calco;calcolino;calderai;calendola;calesse;calibro;calighe;callista;calmare;calmavi;calo;calotte;calunnio;calzante;calzatoio;calzetta;camarilla;cambiaria;cambiera;cambuse;camerieri;camicie;camiti;camminate;camorra;campando;campata;camperemo;campino;camuffavo;canali;canapini;canastone;cancrene;candidato;canestra;cangiante;canizze;cannicci;canocchie;canopi;cantando;cantate;canteremo;cantieri;cantore;canzone;capanna;capatina;capesse;capiente;capira;capisaldi;capitani;capitone;capobanda;capofitti;caponerie;capotreno;cappellai;cappuccia;capriate;caprone;carabina;caramella;caratisti;carbonare;carcade;carcinosi;cardature;care;carezzai;carghi;caricai;caricavo;carismi;carmen;carnee;carnicino;carognata;carotieri;carpiato;carraie;carriera;carrubo;cartacei;carteggio;cartoccio;caruncole;casare;cascara;cascine;casello;casinista;cassandre;cassieri;castaldo;castigato;castrato;casuali;catafillo;catara;catarrosi;catecheta;caterva;catodica;catramosa;catturai;caucasico;causa;causati;causeremo;caute;cavalcata;cavallini;cavassi;cavatura;cavedio;cavernosi;cavicchie;cavino;cazzuole;cecita;cedi;cedrina;cefalici;celarono;celavi;celebri;celerini;celiaco;celleraio;celostati;cementi;cenassimo;cenciaie;cenerata;cenero;cenobita;censo;censurano;centaura;centiara;centone;centrine;ceppa;ceramici;ceraunici;cercar;cercava;cerchiate;cerebello;cerilo;cero;certame;certune;cerusico;cervicale;cesarei;cespo;cessati;cessiate;cestini;cetacei;champagne;checche;chenzia;cherubino;chiamammo;chiamati;chiane;chiarire;chiassata;chiavaia;chiazza;chiederai;chiedeva;chiese;chiglie;chimera;chimosine;chinato;chinetosi;chioccia;chiodi;chiostra;chirurghi;chitoni;chiudessi;chiuse;ciabatte;ciana;cianosi;ciarlieri;cibarie;cicatrici;ciccioni;ciclabile;cicloide;cicuta;cifrati;cigne;cigolato;cilecca;ciliegia;cimatura;cimentavi;cimiero;cinabrese;cinefilo;cinerine;cingalese;cingoli;cinnamomo;cinse;cintz;ciondolo;cipollati;circa;circolava;cireneo;cisalpine;cisposita;citai;citassero;citazioni;citi;citologie;citta;ciuffo;civettato;civico;clamidati;clarini;classisti;clave;clero;climax;clipeo;cloaca;clori;closets;coabitato;coane;coazione;cocchi;coccigi;coccoloni;coclea;cocuzzolo;codesto;codoli;coesioni;cofano;coglieva;cogniti;coiffeur;cokizzate;colarono;colatori;colchico;colerica;coliate;colitici;collarini;collegato;colles;collinosa;collocate;collosi;colmare;colmavano;colo;colone;colonnina;coloreria;colossali;colpira;colpiste;colposo;coltivate;coltrici;colza;comandi;combatti;combines;comete;comincino;commando;commessi;commodori;commutava;comodini;comparano;compariva;compatti;comperavi;competeva;compianto;compilavi;compito;complicai;compos;comprare;compravi;compresse;compunto;computi;comunicai;concause;concentri;concerno;conchiusi;conciarsi;concilio;concitati;concordai;concreto;condensa;condiremo;condiva;condomino;condono;conducono;conferire;confesse;confidato;confinati;confiteor;confonde;confuso;congedi;congelera;congiunta;congrega;coniano;coniavano;coniero;coniugata;connette;conosce;conquista;consegno;conservo;consocie;consolero;consono;consulta;consumera;contadine;contante;contatto;contempli;contenti;contesi;contigua;contorta;contrario;contro;convegni;converrai;convince;convive;convocavo;coolie;cooperino;copersero;copiamo;copiato;copierete;copisti;coppietta;coprire;coprite;coraggi;corame;corazza;corazzo;cordami;cordite;coreani;coriacee;corinzi;cormofite;cornei;cornista;corollari;coronassi;coroniamo;corporee;corredato;corremmo;correste;corride;corro;corrosivi;corsa;corsista;corteggi;cortina;cosa;coscienza;cosentini;cosmici;cosperso;cospiravi;costando;costate;costerna;costipato;costosi;costruiti;costure;cotennoso;cotogne;cotoniera;cottimi;coupon;covarono;covavo;coviate;cozzammo;cozzavate;cozzone;cranio;craterico;creando;creati;creavano;credenza;credesti;crediti;credute;creiate;cremato;cremeria;cremonesi;creo;crepare;crepavate;crepita;crepitio;crescerle;cresi;crespi;cretacee;cretosi;criceti;crine;criologia;crisoliti;criticavo;croccante;croci;croda;crollare;crolli;cromatore;cromosomi;croscia;crostose;crucciare;crudezza;cruore;cuba;cubicita;cubiti;cucchiaie;cucinammo;cucinera;cuciono;cuculi;cuius;culla;cullava;cullino;culminero;cultuale;cuneato;cuoceva;cuor;cupola;curabili;curassero;curatolo;curerai;curiate;curiosavo;cursus;curvasse;curve;cuscute;cutanee;czarde;dadaismi;dalie;dama;damigiane;danarose;dandolo;dannare;dannavo;danniamo;dantismi;danzanti;danzavate;dappiu;dargli;darsi;datando;datava;daterei;dativi;dattilo;davidica;dazieri;debite;debordato;decadenti;decalitri;decantato;decatleti;decentro;decidero;deciduo;decifro;decina;declassa;declino;decollera;decoro;decrepita;decretato;decuplo;dedalea;dedicassi;dedita;deduco;defalcata;deferisco;definendo;deflativi;deflui;defluiste;deformate;defraudo;degente;degnare;degnavo;degradare;degustati;deiscente;delatrici;delegata;deleterio;delicato;delirano;delirerai;deliziamo;deliziose;deludere;demagoga;demente;democrito;demolire;demolivi;demordere;denervato;denigro;densa;dentature;denudai;denudavo;denuncie;deperii;deperite;deplorate;depongono;depradato;deprechi;depurammo;depurerai;deraglino;derido;derivando;derive;dermiche;deruba;derubavo;deschetto;desertici;designato;desinano;desinerai;desisti;despotica;destare;destavo;destinate;destrezze;desuete;detenere;deteriora;detestai;detieni;detrito;dettata;detteremo;deturpava;devastano;deverbali;devo;devoniano;diabolica;diadema;diagnosi;diallelo;diamoci;diarroica;diatesi;diavolo;diccelo;dicessi;diciamolo;dicotomi;didietro;dieta;difende;difesero;difetti;diffamate;difficili;diffidino;diffusiva;difuori;digestive;digitato;digiunera;digradano;digredire;dilaghera;dilaniero;dilatassi;dilatiate;dileggiai;dilegui;dilette;dilla;diluiremo;diluiva;diluviera;dimanava;dimenato;dimero;dimezzare;diminuira;dimorare;dinamico;dinastie;diocesano;diopsidi;diottro;dipanatoi;dipartite;dipingere;diplegia;dipoli;diradano;diraderei;diramassi;diramiate;direte;dirglielo;dirigo;dirizzare;dirle;dirottato;dirozzero;diruto;disaggio;disarmano;disastri;discende;discesi;disciolta;discolo;discorso;discusse;disdette;disegnava;disergia;diserto;disfece;disgrega;disilluse;dislessia;dismisure;disopra;disparate;dispensai;disperavo;dispiega;disporsi;disprezzo;disputero;dissero;dissetavi;dissipai;dissodai;dissolse;dissuasa;distammo;distato;distesa;distingue;distomi;distratto;distrussi;disunioni;disutile;ditata;ditole;dittonghi;diuturno;divagava;divampato;dive;diveniste;diventati;diverra;diverti;dividersi;divini;divisoria;divorasti;divoro;divulgati;doble;documenta;doganiera;dogmatica;dolciume;dolevi;dolomia;dolosi;domandate;domarono;domava;domereste;dominando;dominava;domino;donassimo;donatrici;dondolati;donerai;donnaccia;donzella;doposci;doppiezza;doratore;dormente;dormita;dorrebbe;dosando;dosatrice;doseremmo;dossier;dotassimo;dotera;dotta;dovemmo;dovervi;dovevi;dovresti;draga;drago;drapperia;dressage;drizzai;drizzavo;drogata;druda;drupe;dubbia;dubitassi;dubitiate;duellante;dulcamare;duodecimo;duplicata;duramente;durati;dureranno;durino;duumviri;ebbi;ebetismi;ebrieta;eccedendo;eccedetti;eccelsi;eccezione;eccitassi;eccitiate;echidne;eclissano;eclittici;ecologi;economici;ecumeni;edematosa;edificai;edilita;editing;edochiane;educabile;educati;educherei;efedrina;efferato;efficace;effluente;eforati;egioca;egli;egotiste;eguaglino;einaudi;elaborera;elastiche;elegante;elegiaco;elenchera;elettive;elettrodo;elevasti;eleveremo;elicere;eliminava;elioni;elisia;elitropi;ellenismo;elogera;elogiaste;eloquenza;eludere;eluso;emanai;emanavano;emartro;ematomi;embricare;emendasse;emendiamo;emeriti;emette;emigrante;emigrerei;eminenze;emissive;emoftalmo;emorroidi;empatie;empirici;emulando;emulatore;emuliamo;enarmonia;enclitici;endice;endogamia;endoteli;energismo;enfie;enigmi;enofili;enometri;entasi;entottico;entrarono;entravi;entropico;enumerava;enunciato;eolico;eparca;epatte;epicentro;epicuro;epifonemi;epigrafi;epiploon;epistilio;epiteto;epodo;epsomiti;epurarono;epurerai;equanti;equino;equivoche;eran;erbacee;erbati;erborista;erculee;ereditava;eremitico;eretismo;ergativo;ergotismi;erigera;erisipele;erlang;erminetto;ernioso;erogato;eroico;eroso;erpeti;errammo;errati;errerebbe;erroneita;eruttive;esagerare;esagitate;esalatoio;esaltasti;esalto;esaminera;esaptoto;esastilo;esaudi;esaurirsi;esausta;esca;escissa;esclamino;esco;escreti;escussa;esecraste;esecrino;esegetico;eseguiro;esemplare;esentava;esercente;esibisco;esigono;esiliamo;esiliera;esiodeo;esistiti;esito;esodo;esoleto;esonerino;esorcisti;esortante;esortero;esoterico;espandi;espedire;espia;espiate;espiero;espletive;esplicite;esplorati;esplosivi;esportare;esposto;espugnano;espulsi;espurgo;esserle;essudati;estatici;estensivo;esternare;estetica;estingue;estivante;estorta;estratte;estricava;estrusiva;esuli;esultate;esumare;eterei;eterno;etichetta;etiliste;etisie;etnocidio;etologi;ettari;euclidea;eufemismi;euforici;eugeniste;eupepsia;euritmia;eustatico;evacuano;evacuerei;evangelo;evaporo;evellere;evezione;evitando;evitavano;evito;evocativi;evolutive;excelsis;exoterica;eziologia;faber;facciale;facendone;facevamo;facili;facoltoso;faentine;fagiana;fagocita;falaschi;falcera;falciammo;falciavi;faldata;falena;fallare;fallimmo;falliti;falsare;falsifico;familiare;fanaleria;fandanghi;fanga;fanone;fanti;farabutta;faraonico;fardi;farfalla;farinata;fario;farmaco;farragini;fartelo;fasci;fasciasti;fascinosa;fasico;fastosa;fatalisti;fati;faticavi;fatidico;fattivo;fattuali;fatuita;fauste;favaio;favette;favolosa;favorita;fazione;febbroso;feci;fecondero;federare;feeling;felceta;fellah;felpe;felze;femorale;fenditoio;fenicio;fenomeni;feriali;ferirai;ferisco;feritrice;fermarla;fermentio;ferocita;ferraiolo;ferratura;ferrifere;ferrosi;fervere;fese;festanti;festivo;fetali;fetido;feudale;fiaccando;fiacchere;fiammanti;fiandre;fiatate;fiboride;fibula;ficcaste;ficchiamo;fida;fidanzo;fidavano;fideremo;fienaio;fievole;figgicino;figlie;figuracce;figurina;filai;filarata;filata;filature;fileremo;filiamo;filippini;filmabile;filmica;filologi;filosofo;filtri;fina;finanzino;fingendo;fingitore;finirebbe;finiscono;finitime;finivo;fintare;fioche;fiondato;fiorendo;fiorili;fioritura;firmaioli;firmati;firmiamo;fischiamo;fisciu;fisio;fissare;fissatori;fissiate;fistolosi;fittaiole;fittizie;fiutando;fiutavate;flabello;flambes;flano;flautista;flebiti;flessioni;flettere;flippato;flockaggi;florida;flottanti;fluiate;fluiranno;fluisse;fluivi;flute;flyby;focalizza;fochiste;focose;foderasti;foderino;foggiare;fogliame;foglioso;fogno;fole;follata;folloni;fomenti;fondaco;fondasse;fondavo;fondi;fondiste;fonemico;foniche;fonologo;fontane;foraggio;forassi;foravamo;forcacci;forcine;forense;foreste;forge;foriamo;formai;formassi;formature;formero;formio;fornai;fornici;fornisco;fornivano;forti;fortuite;foruncoli;forzasse;forzavi;forzo;foscolo;fosforate;fosforosi;fosso;fotofobie;fotometri;fottete;foulard;fracassio;fragolaio;fraintese;franabile;franava;franci;frangetta;franta;frapporra;frascume;fraterna;frattanto;freatici;freddura;fregasti;fregherai;fregiati;frementi;fremeva;frenabile;frenato;frenica;frequenze;fresconi;freud;friggendo;frigide;frigorie;frittata;frivoli;frodai;frodava;frodino;frollini;frontali;fronzolo;frugale;frugatoio;frugifere;fruibile;fruitrice;frullasti;frullini;fruscoli;frustati;frustra;frutice;fruttasti;frutti;ftalo;fucilati;fuciliere;fucsina;fugare;fugavi;fuggiremo;fughe;fulcro;fulminate;fuma;fumarico;fumato;fumera;fumettari;fumigate;fumoirs;funame;funestare;fungere;funghi;fungoso;funzioni;fuorviato;furbizie;furia;furlani;fusaiole;fusibile;fusoria;fusto;gabarra;gabbiano;gabellare;gabriela;gaelica;gaggio;gaie;galantina;galbuli;galeno;galieno;gallate;galleschi;gallicano;gallomane;galoppa;galoppini;gamba;gambiera;gamellino;gamorra;gandura;ganglio;ganzerino;garantira;garbare;garbugli;gareggero;gargano;garoso;garrula;garzature;gasate;gasolio;gasse;gastigare;gato;gattice;gattoni;gaudeant;gaullista;gavetta;gavotta;gazza;gechi;gelando;gelataio;gelavo;geliate;gellabe;gelso;gemello;gemettero;geminano;gemmando;gemmifera;generai;generate;generici;genetici;geni;genieni;genitrici;gens;genuini;geodi;geografe;geolunare;georgette;geovista;geremia;gericault;germano;germinava;geronte;gessaioli;gessetti;gestatori;gestiro;gestivate;gesuiti;gettarli;gettavano;gettito;ghaneane;gherigli;ghermisci;ghettone;ghiaiate;ghiande;ghignano;ghindando;ghirbe;giacchi;giacenza;giacevate;giacobini;giainismo;giallume;gianni;giava;gibetto;gigiona;gigliucci;gimetro;ginepraio;ginglimo;ginnica;gioberti;giocasti;giochero;giocosita;gioiare;gioiranno;gioiscono;gioivo;giornante;giottesca;giovanni;giovavate;giovero;giovine;giradito;girano;girasti;girava;girellavo;gireresti;girio;girono;girovago;gittando;gittavano;giubilare;giudaismi;giudicava;giuggiare;giugnole;giuliane;giumente;giunga;giungete;giunoni;giuntaste;giunture;giurando;giuravano;giuridica;giustiano;glaces;glasse;glenoidei;glicerine;gliconeo;glissandi;globoide;glochidio;gloriera;glossato;glucidici;gluone;glutinosa;gnocco;gnostici;goccianti;godea;goderono;godevamo;goditore;godronati;goffraggi;goleador;golfisti;gollisti;gomitata;gomme;gommosi;gonfiamo;gonfiavi;gonicita;gonze;gorghi;gorgonia;gotha;gottose;governava;gozzuti;gracilita;gradina;gradita;graduarla;gradui;graffiamo;graffita;grafitati;graia;gramolare;granaioli;granceola;grandini;granfe;graniglia;granivore;granulati;grappino;grasselle;grassoni;graticci;grato;grattato;gratuita;gravar;gravavate;gravide;gravosa;graziaste;graziolo;grecati;grecofoni;grembiale;gremiremo;gremito;gretta;gridai;gridasse;gridera;grifagno;grifoni;griglie;gringos;grinzosi;grippe;grisu;grommati;grondone;grossa;grotti;grufare;gruiste;grumosa;guada;guadassi;guaderai;guaiachi;guaisse;guanachi;guantaio;guarani;guardarla;guardato;guardiani;guardrail;guarirete;guarita;guarnite;guastasse;guasti;guazzammo;guazzoso;guernica;guevara;guida;guidano;guidata;guiderai;guigne;guitte;guizzaste;guizziate;gustabili;gustasti;gustera;gutta;habitues;hallali;hapax;hawaiani;heine;hesse;hiroshima;holmes;hostesses;huroniani;iacamar;ialina;ialurgia;iattanza;ibernata;ibleo;icariani;icona;icosaedro;ideabile;ideata;idem;ideologo;idiomi;idola;idoneo;idrata;idraulica;idrocoria;idrofobo;idrogel;idrolito;idrope;idroscali;idrovoro;iemale;ierologia;igienica;ignavia;ignobili;ignorarlo;ignorero;igrofila;iguana;ilei;illeciti;illiriche;illudermi;illumina;illusoria;ilozoista;imbacare;imballero;imbarcai;imbardato;imbattuto;imbellano;imbestio;imbiacco;imbibiti;imboccata;imboni;imboscata;imbottii;imbozzate;imbranano;imbrifera;imbroncio;imbucammo;imbuco;imburrino;imbuzzite;imetto;imitassi;imitavate;immaginar;immaturi;immense;immersa;immettevo;immigrati;immischio;immolassi;immonda;immoto;immusite;impaccare;impala;impallino;impancato;impaperai;imparasti;imparino;impastata;impaurano;impavidi;impeciare;impedirai;impedisse;impegnate;impelati;impensate;imperava;imperito;impersoni;impetrar;impiaga;impiccare;impiegai;impietoso;impilate;impipate;implacato;implicito;implorera;impola;imponenti;imponi;imporpori;importai;importune;impostato;impotente;imprecavo;imprese;imprimilo;impropria;impruo;impugnata;impulsato;impunito;impuntino;impupano;impurita;imputati;impuzzi;inabita;inaccessa;inacidira;inacutita;inalammo;inalatori;inalerai;inalzasti;inamidate;inani;inarata;inarcato;inaridira;inasinite;inasprivi;inattente;inaudito;incacammo;incacchi;incagliai;incalliro;incalzati;incamicio;incannato;incanto;incappano;incarcati;incarnare;incarniti;incartero;incassano;incastrai;incavare;incazzate;incedeva;incensava;inceppato;incerte;inceste;incettero;inchinavi;inciampa;incidete;incinte;incisive;incitai;incitavo;inclinano;includera;inclusive;incognito;incolli;incolpava;incomba;incongruo;incordano;incorona;incorsato;incredula;incrinano;incroci;incrudeli;incubati;inculammo;inculca;inculino;incuorato;incursore;indachi;indagato;indebito;indegne;indettare;indicami;indicasse;indichino;indigenza;indignava;indirizzi;indiziata;indolcite;indolori;indorare;indossava;indotto;indubbio;indugerei;indugino;indulta;indurire;indurra;induttive;inebriamo;inedie;inerente;inerrante;inesausto;inestinti;infagotti;infamassi;infamia;infanti;infarti;infavate;inferente;infermi;inferto;infestati;infettavi;infeudato;inficia;infierire;infilare;infilerei;infilzano;infiniti;infischio;inflati;influendo;influito;infoibare;infondato;inforcava;informavo;infortuni;infossate;infranti;infuocata;infuriava;ingaggero;ingannavo;ingegneri;ingemmi;ingenuo;ingerisce;ingessano;inghiaia;ingiunto;inglobate;ingoffito;ingolfano;ingolle;ingommati;ingorgare;ingozzera;ingranavo;ingrazi;ingrossai;inibite;iniettate;inique;inizianti;iniziero;inmilla;innalzati;innanzi;innervano;innesco;innestino;innocenzo;innoltra;inoculato;inoltrare;inombrare;inondata;inope;inosite;inquietai;inquinera;inretire;insacco;insanisse;insaporo;inscenati;inscuri;insediera;insegnera;inseguito;insepolte;inseriro;inserte;insidiare;insigni;insilassi;insiliate;insinuati;insiste;insita;insolla;insorditi;insozzata;instabile;instilla;insuete;insultai;insurgere;intacchi;intarla;intarsiai;intasate;intasera;intearsi;integre;intendevi;intenta;interarmi;interdira;interine;internati;interrano;intervie;intessuto;intestina;intigno;intimava;intimista;intingevo;intonachi;intonati;intonsi;intoppate;intorpidi;intralcia;intreccia;intrichi;intrigoni;intronano;intrufola;intuassi;intuiamo;intuisci;intuivate;inumani;inurbata;invada;invaghi;invaiati;invanire;invasano;invecchio;invelata;inventate;inventore;invergano;inversivo;invertiva;investira;invia;inviarti;invida;invidiavo;inviero;inviluppi;inviscato;invita;invitaste;invitino;invocarne;invoco;involgere;involture;inzeppati;inzuppano;iodati;iodurata;ionica;ionizzino;iperacuta;iperemica;iperoni;ipertesti;ipnogeni;ipnotizza;ipocotili;ipodotato;ipogee;iponime;ipostenia;ipoteche;ipotimico;ippiche;ippogrifi;ippurico;iracondie;iranico;irenica;iridarsi;irizzando;ironie;irradiare;irrancidi;irretirmi;irrigammo;irrighera;irriso;irritare;irritera;irrogati;irrorate;irsute;isatati;ischia;iscrivere;isiaco;islandesi;isobarici;isocronia;isogonale;isolabili;isolarlo;isolavamo;isoliate;isomorfa;isopodi;isotonia;isotta;ispanismo;ispessite;ispidezza;ispirasti;israelita;issavano;istamine;isteroide;istigati;istintivi;istituita;istolizza;istradare;istruirai;istruisti;itale;italiote;iterabili;itinerari;ittio;ittri;iure;iuventina;izvestija;jamming;jepponi;johnson;judoka;jupons;kainiti;kapo;kasher;kellerina;kermesses;kieserite;kirsch;koilon;krupp;laban;labiale;laborioso;laccata;laccifera;lacerammo;lacerava;lacerto;laconico;lacrimo;lacustre;ladrerie;laertino;lagnano;lagno;lagrime;laiche;laido;lamanti;lambelli;lambiremo;lambiva;lamellare;lamentava;lametta;laminata;lampanaio;lampioni;lanari;lanci;lanciata;landamani;langaroli;languiate;languisco;languori;lanosa;lanuta;laotiana;lapidare;lapidello;lappatore;larari;lardosa;larghi;lari;laringi;larvicoli;lascero;lasciar;lasciaste;lasciti;lassative;lastima;lastrico;latercoli;laticlavi;latinita;latore;latrate;latria;lattaia;lattea;lattiera;lattogene;laudanina;laudevole;laurearsi;laureero;lauriche;lavabi;lavale;lavandolo;lavasse;lavatoi;lavelli;lavi;lavorando;lavorava;lavorii;lazzari;lealismo;lebbre;leccare;leccature;lecchero;lecita;lederla;legalista;legandosi;legasti;legatorie;legga;leggeri;leggevamo;leggieri;legheremo;legisti;legnaioli;legne;legumiera;lemargo;lemuri;lenii;lenire;lenisse;lenivamo;lentia;leone;lepade;leponzia;leprose;lerciare;lesbicate;lesinato;lesivita;lessati;lesseremo;lestezze;letargie;lettera;lettighe;leucemia;leucorrea;levammo;levapunti;levassero;levatoia;levavi;levi;levigata;levita;levogiri;leziosa;libagioni;libbre;liberali;liberarne;libercolo;liberismo;liberto;libitum;librata;libretti;licatese;licenziai;licio;liete;liftano;ligerino;lignite;liguorino;liliflora;limande;limature;limegna;limicola;limitammo;limitassi;limitiamo;limnetica;limnologo;limoneto;limpide;lincei;lincoln;lineata;linfedema;linguae;lingula;linkage;linoleum;lintee;liofobo;lipemania;lipizzani;lipotrope;liquefai;liquefo;liquidavo;liquorino;liriche;lisate;lisciando;liscio;liso;listati;litargiri;litigare;litighina;litiosa;litogena;litopedio;litotipia;lituani;liutaia;livelli;lividume;lizzano;lobati;lobopodio;locande;locatelli;locazioni;locuste;lodarono;lodativo;loderanno;lodigiani;loffio;loggione;logistici;logopedia;logorasti;logorii;lolismo;lombatina;longarine;lontani;loppe;lordarono;lordotica;loretano;losche;lotta;lottatori;lotti;lubiana;lucchese;luccicavo;lucente;lucevano;lucidata;lucidiamo;lucignoli;lucrative;luculente;ludica;luetica;lugubri;lumai;luminai;luminosa;lunatica;lunettata;lunghino;lunigiani;lupeggia;lupina;lupomi;luridezza;lusingare;lusitania;lussato;lustrale;lustre;lutei;lutroforo;macabre;maccaluba;macchiavi;macedone;macellera;macerasti;maceria;macigno;macinata;maciniamo;macra;maculando;madapolam;madieri;madrasa;madrinato;maestrini;magagnato;magazzeno;maggior;magici;magliaio;maglietti;magmatica;magnano;magnetici;magnum;maharani;maiden;maionese;maizene;malacopia;malagiati;malapaqua;malassare;malauguri;malcelate;malcurate;maledi;maledisca;malefica;malescio;malevole;malfida;malgascio;malie;maliosi;malloppi;malnato;malonici;malpro;maltatori;maltinta;malva;malve;mamelucco;mammella;mammone;manatelli;mancarla;mancavano;manches;mancipati;mandanti;mandassi;mandava;mandiate;mandorle;mandrino;maneggii;manette;manganine;mangi;mangiarlo;mangio;maniacali;manichini;manieri;manila;maniste;mannaro;manolesta;manoregia;mansalva;mante;mantenete;mantica;manto;manucare;manzoni;mapo;mappe;maramao;marasche;marcaggio;marcatura;marceremo;marchi;marciai;marciavi;marcisco;marcivo;maremmani;marezzati;margina;margoni;marin;marinasti;marini;marita;maritasti;maritino;markka;marmista;marmotte;marnoso;marpiona;marronare;marsalare;marsilio;martin;maruzze;marzamini;marzo;mascagno;maschili;masnade;massaio;massetani;massico;massivi;masterino;mastico;mastro;matapane;materassi;materismo;matinee;matricidi;matronali;mattatori;mattinate;mattonato;maturarsi;maturero;mauritane;maxigonne;mazdei;mazzetta;mazzotta;meatori;mecometri;mediai;mediarono;mediava;medicasse;medicona;medioevi;meditassi;meditiate;mefisto;megacosmo;megaloti;megera;melagrani;melanismo;melassa;melchiti;melfitane;melighe;melitosio;mellifluo;melmoso;melodisti;meloni;membrando;memorata;menabrida;menante;menassimo;menavo;mendate;mendichi;menerebbe;menimpipo;menischi;menomo;mensuale;mentimmo;mentisti;mentone;menzioni;merca;mercenari;mercurici;merdone;mergere;merie;meristemi;meritata;meritori;merlavano;meropia;mescalera;mescibile;mescolava;mesero;mesofrio;mesoni;mesotorio;messaline;messicani;mestammo;mestavamo;mesticano;mestoli;mestruava;metafonie;metameri;metanoli;meteco;meticci;metili;metodo;metrature;metropoli;metterete;mettete;mettiamo;meuble;mezzalana;mezzasega;mezzina;miagolate;mialgia;miaulio;miccino;micenee;micie;micotiche;microciti;micropili;midigonne;mieli;mielosi;mietesse;mietitore;migliaio;mignola;milaite;milesia;milita;militavi;milledue;milonga;mimando;mimavano;mimesca;mimi;mimosa;minano;minatrici;minerario;miniate;minimale;ministero;minorata;minturno;minuscolo;minuterie;minuzzoli;miogali;mionemi;miorresi;mira;mirande;mirassimo;miravo;miriadi;mirini;mirrare;mirtilli;mischiai;mischino;miserelle;mishnah;misolidia;missine;misterico;mistral;misurasse;misuri;mitici;mitigasti;mitissimo;mitologa;mitraglia;mitrato;mixare;mnesica;mobiliava;moccicava;moda;modanato;modelle;moderar;modererai;modesti;modista;modulava;moge;mohicane;moivre;molato;moldavi;molestano;molesto;molisane;mollando;molle;molliate;molossi;monacandi;monachina;monandro;moncone;mondati;mondiglie;monet;monetica;monica;monito;monocasi;monocroma;monofagia;monofuni;monolito;monopezzo;monoptote;monossilo;monotono;mons;montaigne;montano;montato;monterai;montone;monzesi;moralismo;morave;morbinosa;morchiose;morderai;mordevate;moreccio;morenico;morfemica;morganite;morioni;morirvi;morivi;mormorare;moro;morremmo;morsicano;mortaio;morticini;mortuari;mosaismo;moschea;mosciami;mosse;mostarda;mostrare;mostrato;mostrino;motivammo;motivate;motivo;motonave;motorio;motosi;motulesa;movendosi;moveremmo;movevamo;moviole;mozzasti;mozzerei;mozzino;mucina;mucronato;muffire;mufti;muggiamo;muggisci;mughetti;mugolando;mugolerei;mugugnava;mulesco;mulinato;multata;multiloba;multisono;mundualdi;mungeva;muni;munirei;munisca;munitevi;munta;muotivi;muoverli;muovevamo;muraiole;murata;murgia;murmure;musare;muscipulo;muscoluta;muselli;musicali;musiciste;mussare;mussurana;mutageno;mutanti;mutative;mutazioni;mutezze;mutilato;mutriona;mutuato;nabis;nafte;naibi;nanerie;naoforo;nappato;narcisate;naretini;narrasse;narravamo;nartece;nascemmo;nasceva;nasconda;nasi;nastie;nasuti;natatori;nativita;naturismi;naupatia;nauseasse;nauseiamo;navali;nave;navigano;navighero;nazianzo;nazista;nebbie;nebulizza;necessito;necrosare;nefande;nefralgie;nefrologo;negano;negativi;negavano;negletta;negoziamo;negri;negrore;nematici;nemee;nenia;neofilia;neogreco;neologico;neoplasmi;neozoica;nepotismi;nerbo;nericce;neritini;neroniano;nervo;nessuna;nettareo;nettatoie;nettuno;neurite;neurone;neutretto;neve;neviere;nevrite;nibbio;nicchie;nichela;nicheloso;nidi;nidiforme;nietzsche;nihil;ninfe;ninne;niobe;nipotini;nisseno;nitrano;nitratori;nitrisce;nitrosati;nivee;nobilesca;nocchi;nocciolo;nocenti;nocessi;nociamo;nocqui;nodo;noematici;noioso;nomadi;nominali;nomine;nonche;nonnulla;nordista;normano;normopese;norvegesi;nostrani;notammo;notasti;notazioni;notevole;notissimo;notonetta;nottivago;noumeni;novantuno;novellare;novenarie;noverava;noviziati;nubecole;nuca;nucleina;nudismi;nullipare;numerassi;numeriate;numidica;nundinne;nuoceremo;nuociate;nuotarono;nuotavo;nuraghe;nutazioni;nutrii;nutrite;nutrivo;nuvolose;obbediamo;obbedisco;obbliando;obbligavo;oberando;oberavi;obeso;obietti;obituario;oblaziona;obliasti;oblieremo;oblita;oblunga;obovate;occamismi;occhialai;occhio;occipiti;occlusa;occorrono;occultava;occuparci;occupavi;oceanici;ocelotti;octastilo;odalische;odiasse;odiavo;odierno;odologia;odoranti;odorava;odoriferi;oeuvre;offendere;offenso;offertoci;officiati;offre;offrirei;offristi;offuscai;ofide;ofiura;oggidi;ohmica;oklahoma;oleacei;olecrano;oleiferi;oleosi;olezzato;olfatto;oliassero;oliavi;oligarca;olimpiaco;olistiche;olivano;oliveta;olivigna;olmi;oloferne;olotipi;oltreche;omarini;ombrasse;ombrellai;ombro;omeletta;omeomorfa;omeostati;omeristi;omettera;ometto;ominaccio;omissiva;omodonte;omofoni;omogenei;omologai;omologie;omoritmia;omotipico;omozigote;onciari;oncotica;ondeggia;ondosi;ondulati;oneglia;onerava;onesti;oniriche;onocefali;onoranza;onorarvi;onorera;onranza;ontico;oociti;oospore;opalino;operanda;operate;operavo;operine;opilioni;opinaste;opinerai;opoponaci;oppilare;opponete;opporre;opposite;opprimete;opsoniche;optassi;opteranno;optional;opzionale;oramai;oratori;orbachi;orbitale;orbitera;orchidea;orciolato;ordiamo;ordinano;ordinate;ordino;ordisci;orditori;orecchia;orezzo;organa;organetto;organo;orgasmo;orgoni;orientale;origami;originera;origliavi;orinatoio;orizzon;orlassi;orlavano;orlerete;orma;orminiaco;ornano;ornatista;ornavano;orni;orografia;orpellaio;orrida;orsaggini;ortaglie;orticari;orto;ortognato;ortosio;orwell;osannano;osate;osche;oscillo;oscurano;oscurera;oseranno;osman;osmometri;ospitano;ospitato;ospodari;ossature;osservare;ossessi;ossianici;ossidati;ossifera;ossimoro;osso;ostaggi;osteggino;ostentata;osteolisi;ostetrico;ostinai;ostini;ostricaio;ostruita;otero;otoiatra;otrantina;ottagono;ottant;ottaviano;ottenerci;ottengano;otterrei;ottimali;ottobrate;ottonaia;ottone;ottuplici;otturato;ottusita;ovaiole;ovariche;ovattati;ovidiani;oviparo;ovoidi;ovulo;ovvie;oxoniana;oziando;oziavano;oziose;pacata;pacchetti;pacemaker;pacifiche;pacis;padane;padellini;padrinite;paesane;paffuto;pagana;pagarne;pagatoria;paggetto;pagina;paglietti;paiamo;paisiello;palaia;palanchi;palatine;palchetto;paleo;palesarvi;palesi;palettato;palina;palladi;palleggi;palliata;pallonata;palmata;palmipedi;palombaro;palpassi;palpeggia;palpito;palustre;pampineo;panamegno;panati;panchetto;panciuti;pandemico;panella;panforti;panicita;panificio;paniuzze;pannello;pannonica;pansa;panteiste;pantomima;panzer;papa;paparelli;papesche;papillosa;papisti;pappate;papperete;papposi;papulare;parabola;paracqua;paradosso;paragio;paralisi;paramento;paraneve;paraocchi;parasole;paratie;parcata;parea;pareli;parentele;paressero;paretine;paricollo;parimente;paritarie;parlano;parlaste;parlavo;parlonne;paro;parolai;paronimi;parrai;parsa;parteggi;partibile;partiremo;partitini;partivate;partorito;parvenue;pasaran;pascione;pascolava;pasque;passagio;passargli;passatoie;passera;passiate;passito;pasta;pasternak;pastiglie;pastorizi;patacca;pataria;patatone;patema;patentini;patetica;patii;patinoso;patirono;patitur;patologi;patrii;patrologa;pattare;pattinati;pattuglie;pattumi;paurosi;pausavano;pavesa;pavidi;pavonie;pazzoide;peccando;peccavano;pece;pecorai;pecorume;pecunarie;pedagoga;pedaliamo;pedario;pediatra;pedinai;pedine;pedogamia;pedula;pegaso;pegoliere;pelai;pelassero;pelavamo;peli;pellaio;pellucide;pelosi;peltati;peluto;penalizza;penate;pencolii;pendendo;pendesse;pendice;pendolini;peneio;penetra;penetri;penieni;pennaioli;pennino;penoso;pensarli;pensatore;pensi;pensosi;pentamere;pentiate;pentiste;pentodo;pentotal;penzolate;peonie;peperita;peponide;peptidi;perano;percepiro;percolati;percuote;perdere;perdeste;perdibile;perdonare;perdurata;perduto;perennita;perequavo;perfido;performer;peri;pericolo;perigei;periodare;periptero;peristoma;peritoneo;perizia;perlati;perlopiu;permea;permette;permuta;perno;peroniste;perpetro;perseide;persiche;perspicua;perticate;pertugi;peruviana;pervasiva;perversi;pesabile;pesarlo;pesatori;pescaie;pescata;pescecane;pesci;peserebbe;pesista;pestaggio;pestatura;pestero;pestosa;petitoria;petrinale;pettide;pettinavo;pettorina;petulco;pezzature;pfennig;piacciati;piaceri;piacevole;piaffano;piaggiare;pialle;piando;pianga;piangesse;pianino;piantala;piantato;piantino;piastrino;piatti;piazzare;piazzera;picacismo;piccarda;picchiai;picchino;picciotti;picconate;piche;picrite;piedritti;piegarvi;pieghera;pieliti;pienoni;pierrot;pietoso;pietristi;piezo;pigeresti;pigiaste;pigiavate;pigliammo;pigliate;pigmeo;pignorata;pigolando;pigoli;pigro;pilature;pilieri;pillolo;pilota;piloto;pimentare;pinastri;pineali;pini;pinnula;pinti;pinzatura;piogenici;piombare;piombiate;pioppeti;piovanati;piovesse;piovuta;pipavano;pipiando;pipolando;pirate;piressia;piriformi;pirofobe;pirolisi;pirosfera;pirrolo;piscatori;piscicole;pisello;pispiglio;pissidi;pistoleri;pitia;pitonesse;pittorico;pitturino;piumini;piuvico;pivieri;pizzarde;pizzicata;placando;placati;placcasse;placenta;placido;plagi;plana;plance;plasma;plasmavo;plasto;platensi;platone;plautine;plebano;plenario;plettri;pleyel;plissee;plumbeo;pluripara;pluvi;pochade;podagrico;podere;podisti;poemetto;poetavano;pogge;poggiassi;poggioli;poise;polaria;polemiste;polentine;polibio;polidromi;polifito;poligoni;polimetra;polipi;poliptoto;polistadi;politica;politura;polizzini;pollastra;polline;polmonata;polonio;polpute;poltrendo;polverii;pomaceo;pomerani;pomfoide;pommer;pompammo;pompavate;pomperai;pompisti;pondera;pondero;ponente;ponevano;ponisti;pontefici;pontina;ponzano;ponziate;popola;popolate;popolosa;poppare;poppute;porcaie;porchette;porfina;porga;porgero;porgli;pornodiva;porporate;porremmo;porsi;portamina;portarla;portata;portauovo;portelle;portiate;portland;portuario;posammo;posarsi;posati;posca;posdatino;posero;positure;posporre;possedere;possesso;postagli;postera;postichi;postini;postulano;postumi;potare;potatori;potendola;potera;potersene;potettero;potorie;potte;povere;povero;pozzolana;pralina;pranzasti;pranzo;praticai;praticole;pratoline;preari;precede;precepti;precipue;precisera;preclusa;precorsa;predarono;predavo;prediamo;prediceva;predirei;predivate;prefazi;prefette;prefisso;pregarli;pregati;pregevoli;pregiando;pregino;pregrechi;prelatizi;prelevato;prelievo;prema;premere;premetto;premiammo;premiata;premiers;premono;premute;prendendo;prendervi;prendine;prenotavi;preordino;preparavo;preposto;presa;prescelse;presemi;presenzio;presieda;pressanti;presserai;pressorio;prestarmi;prestera;presuli;presunti;pretesca;pretonici;preunire;prevedano;prevenire;previdero;preziose;priapea;prillare;prilliamo;primavere;primigeni;primordi;prioni;prismoide;privaci;privarla;privatele;privavi;proave;probativa;procaccio;procedevo;prochella;procoio;procreo;procuravo;prodiero;prodotto;produrlo;proemiato;profanita;profesti;profferta;profili;profughe;profumi;prognate;proibii;proibiti;prolassi;prolisso;promanato;promezio;promovere;prono;pronubo;propagato;propalava;propense;propinare;propinqui;proponi;proposito;prore;prorompe;prosceni;prosieguo;prospero;prosterni;prostravo;proteici;proterve;protestai;protidici;protonema;protragga;protutore;provarne;provava;provenivo;proveremo;provieni;provocato;provvide;prudenza;prugnole;prurigine;psefisma;psico;psoatici;pteroidi;pubblico;puddings;pudori;pugile;pugnalate;pugnetta;pulciai;pulendo;pulimenta;pulirono;pulite;pulivetri;pullulava;pulsare;pulsavamo;pulso;pungano;pungitoi;puni;punirai;punirono;punita;puniva;puntale;puntato;puntella;puntiglio;puntorio;puote;pupazzo;puramente;purgante;purgative;purgherai;purine;puristici;purulenta;puszta;putido;putrefavo;putriva;puzza;puzzavamo;puzzle;quaderne;quadreria;quadrotta;qualche;quamquam;quantomai;quartetto;quarzo;quaterne;quelle;querciolo;quereli;questa;quiddita;quietezza;quinconci;quintetto;quiriti;quotalite;quotava;quotino;rabattuta;rabbinato;rabbonito;rabescare;rabide;raccendo;racchi;raccolgo;raccoppio;raccozzo;rachidea;racket;radarizzi;radente;radermi;radevo;radiarono;radiavamo;radicati;radino;radiologo;radon;radunaste;radunino;raffato;raffilare;raffinavo;raffreddo;raganelle;raggelano;raggianti;raggirare;raggiunse;raggrotto;ragionare;ragliando;ragnatela;ragunata;raid;ralingate;rallentai;ramaia;ramatore;ramazzino;ramerino;ramificai;rammarico;ramo;rampate;rampisti;rampone;rancia;rancori;randonee;raniste;rannodato;rantolosi;rapano;rapavamo;raperesti;rapimmo;rapinosi;rapirle;rapissimo;rapivo;rapprende;raramente;raro;rasati;raschiai;rascia;rasentera;rasi;raspando;raspava;raspii;rassegno;rassodate;rasura;rateato;ratina;rattenere;rattoppa;rattrista;ravaglio;ravenna;ravveduta;ravviato;ravvisavi;ravvivavi;razionale;razzatore;razzie;razzolato;reagendo;reagirono;reale;reami;reattori;reca;recare;recata;recavi;recedessi;receduti;recente;recepita;recetta;rechiate;recidivo;recintano;reciproco;recitano;recitava;reclamare;reclinano;reclutano;recondito;recupero;redazione;redenta;redigere;redigono;redimeste;redingote;redolire;referenze;reflex;refrigera;refuse;regalasse;regalerei;regatanti;reggerai;reggetta;reggibile;reggo;regimato;registe;regnare;regnavi;regolando;regolati;regredi;reietti;reimpiego;reingaggi;reiventa;relatore;relegasse;relievi;relitto;remaste;rembrandt;remiamo;remigine;remore;renali;rendeli;rendero;rendimi;reniccio;renose;reogrammi;repente;reperite;replicano;repressa;reprint;repulsiva;reputaste;reputino;rescindi;resero;residuati;resinati;resistano;resistivi;respighi;respirate;resse;restaro;restauri;restia;restyling;retentivo;reticolo;retinico;retrivi;retrose;rettili;return;revera;revisiona;revocasse;revoco;rhodesia;riabituai;riaccolto;riaffermo;riallacci;rialzassi;rialziate;riamavano;riandare;rianimino;riappaio;riapri;riaprissi;riarmasse;riasciugo;riassunte;riattivo;riavette;riavremo;riavvolge;ribadiamo;ribadisse;ribaltai;ribaltine;ribassera;ribattono;ribellavi;riboccano;ribosoma;ributtare;ricada;ricadeva;ricaduti;ricalcava;ricamano;ricambiai;ricantata;ricascano;ricattato;ricavarla;ricaverai;riccia;ricciuto;ricercate;ricettari;ricevendo;riceverti;ricevono;richiese;riciclati;ricingere;ricolmai;ricolta;ricompre;riconsola;ricoperta;ricopre;ricordano;ricorico;ricorsero;ricottura;ricreata;ricreduta;ricuciano;ricucite;ricusando;ridacchio;ridaremmo;ridavi;riderai;ridessero;ridetta;ridevoli;ridicevi;ridipinse;ridiretta;ridiscute;ridivide;ridona;ridossi;riducano;riduco;ridursi;riebbero;riediti;rielaboro;riemerso;riempirai;riempiste;rientrati;riescano;riesumata;rievocano;rifacesse;rifaremmo;rifascera;riferiate;riferirvi;rifermare;rificchi;rifilassi;rifiliate;rifinirle;rifiniva;rifiuta;riflessa;rifluita;rifondati;riformati;riforniro;rifratta;rifrulla;rifuggiti;rifugiato;rifulso;rigagnoli;rigata;rigavamo;rigeli;rigettava;righeremo;rigidismo;rigirando;rigirerei;rigone;rigorose;rilancera;rilassate;rilavasse;rileccata;rilegaste;rileggeva;rilette;rilevarlo;rileverai;rilimati;rima;rimandino;rimanesse;rimano;rimarita;rimasta;rimavi;rimboccai;rimbucano;rimediate;rimena;rimesso;rimettete;rimini;rimiravo;rimodello;rimontata;rimorda;rimosse;rimovesse;rimpalla;rimpiango;rimpinzai;rimuovere;rimuto;rinascevo;rincara;rincarti;rincasino;rincolla;rincorava;rincrespo;rincupire;rinfaccio;rinfilo;rinforza;rinfusa;ringhiero;ringrazi;rinnegava;rinnovera;rinomati;rinsalda;rinsavite;rintasare;rintoccai;rintuzzo;rinvanga;rinvenita;rinverro;rinviata;rinvili;rinvitera;rionali;ripagando;ripaghera;riparasse;riparero;riparli;ripartivo;ripassi;ripensati;ripentite;ripescati;ripetere;ripetiamo;ripiange;ripidezze;ripiego;ripilate;riponesti;ripopolai;riportate;riposante;riposavi;riposto;ripreso;riprovo;ripudiavi;ripugnavo;ripuliro;ripulivo;ripuntato;risalda;risalgo;risalisse;risaltate;risanando;risanerei;risaputa;riscalda;rischiano;rischiosa;riscopro;riscuote;riseccare;risembra;risentiva;riserbavi;riserva;riservo;risiedera;risieri;risola;risolta;risolvero;risonanza;risonerai;risorgivi;rispecchi;rispinto;risposato;risputa;rissano;ristampa;ristette;ristorera;ristucca;risultava;risuolava;risurga;ritagli;ritardera;ritenerlo;ritengano;ritentera;ritese;ritinti;ritirarsi;ritirerei;ritmica;ritoccavi;ritornano;ritorse;ritraessi;ritrarrai;ritriti;ritrovati;rituale;riunendo;riunirlo;riuniti;riuscendo;riuscisce;riva;rivalta;riveda;rivedessi;rivedrei;rivelare;rivelati;rivenda;rivendo;riverenti;riversate;rivestita;rivettini;rivinto;riviste;rivivono;rivolare;rivolgi;rivoltata;rivoltola;rizoatone;rizotomi;rizzati;rizzi;robbiane;robone;rocaille;roccia;rockaccio;rodasse;rodendolo;roderemo;rodessi;rodico;rodiote;rodono;rogatori;rogito;rolino;rollino;romanci;romanici;romanza;rombai;rombava;rombiche;romeo;rompendo;romperne;rompi;romulidi;roncigli;rondella;ronfa;ronzarono;ronzera;roosevelt;rosacea;rosata;roseo;rosicchio;rosolarsi;rosoli;rossastre;rossini;rosticci;rotacizza;rotariano;rotazioni;rotino;rotolassi;rotolii;rotoplano;rottamata;rotuleo;routinari;rovereto;rovigotte;rovinarti;rovinerei;rovista;rovo;rubalda;rubassi;rubber;ruberanno;rubiale;rubini;rubricato;ruderali;rufola;ruggero;rugginoso;rugiadose;ruinasse;rullammo;rullature;rulletto;rumeni;ruminata;ruminino;rune;ruotando;ruotino;ruppi;ruspando;russando;russavi;russino;rustiche;rutenio;rutinaria;ruttassi;ruttili;ruzzava;ruzzolero;sabatine;sabbatico;sabbioni;sabino;sabotino;saccario;saccherie;sacculari;sacralita;sacrifizi;sadici;saettata;safarista;sagace;saggezze;saggiati;saggino;saglia;sagomo;sagrista;saioni;salamanna;salante;salassano;salaste;salava;salcigno;saldasti;saldavo;salem;saleresti;salgono;salici;salifero;salinella;saliresti;salisti;salivava;salmi;salmonato;salomone;salparono;salpera;salsedine;saltano;saltatoio;salterei;saltuarie;saluresi;salutarne;salutera;saluzzo;salvarle;salvatore;salvero;salvino;sambuco;samoiedo;sanamente;sanati;sanciamo;sancissi;sandali;saneremo;sangui;saniate;sanitari;sanno;sansosi;santippe;santorale;sapendolo;sapersi;sapida;saponaia;saponiere;saporite;saprai;saprobio;saputelli;saracene;saravine;sarchio;sardane;sardista;saresti;sarmazia;sartiare;sartriani;sassarese;sassofoni;satanasso;satinano;satiriche;satollate;saturammo;sature;saturnio;sauro;savigny;saziabili;saziarti;saziavo;sbadati;sbafarsi;sbaffati;sbagliato;sballassi;sballiate;sbalzano;sbalzerei;sbancato;sbandati;sbandiva;sbarbarsi;sbarbino;sbarcatoi;sbarrano;sbarrerei;sbassata;sbastiva;sbattessi;sbatto;sbavasti;sbavazzo;sbeccata;sbeffati;sbendate;sberle;sbiadire;sbiadiva;sbiavati;sbiellava;sbiliarda;sbirciava;sbittato;sbloccati;sboccare;sbocciai;sboffi;sbollendo;sbordare;sborniona;sborsate;sboschino;sbozza;sbozzi;sbracarvi;sbraciato;sbraitavi;sbramini;sbranati;sbranerei;sbrattavo;sbricia;sbrigarvi;sbriglia;sbroccate;sbroglino;sbronzino;sbucando;sbucavi;sbucciava;sbudelli;sbuffati;sbulletta;sbuzzano;scabini;scaccate;scacciamo;scacco;scadeva;scafata;scaggiale;scagliavi;scagnando;scalano;scalavamo;scalcina;scaldata;scaldo;scalero;scalfito;scalinati;scalmo;scalpano;scalpitii;scaltriva;scalzare;scambia;scambista;scampando;scampavo;scanalate;scandii;scandita;scannasse;scanniamo;scansaste;scansioni;scapitano;scapolano;scappasse;scapperai;scaracchi;scardato;scariche;scarna;scarniva;scarpette;scarseggi;scartassi;scartiate;scassaste;scassiamo;scatenavi;scatolano;scattammo;scattavo;scattoso;scavallo;scavatori;scavezzo;scazzasse;scazzone;sceglievo;scelte;scemenza;scenate;scendesse;scenette;scerbando;scerpa;scettrata;schedano;schedone;schema;schermino;schernite;scherzavi;schiaccia;schiarato;schiave;schiena;schieravo;schifasse;schifosi;schiodava;schiudera;schiume;schivarle;schivera;schizzata;school;sciabolo;sciafilo;scialbe;scialone;sciamera;sciancato;sciasse;sciatorie;sciavamo;scierai;sciistico;scimmione;scindere;scindono;scioccati;sciolgo;sciopero;scipite;scirocchi;scissioni;scitico;sciupato;sciupone;scivoli;sclereidi;scoccate;scocciare;scodare;scoglia;scoiati;scolaro;scolavano;scolice;scollasse;scolliamo;scolorina;scolpare;scolpirai;scolpito;scommette;scomode;scomparsa;scomponga;scomunica;sconciate;sconfigge;scongela;sconsigli;scontata;sconti;scontra;scontro;scoop;scopasse;scopavo;scoperto;scopista;scoppiare;scopre;scoprirne;scoprivo;scorciamo;scordare;scorderei;scorgano;scorgeste;scornata;scorra;scorressi;scorsa;scortassi;scortese;scoscende;scosso;scostasti;scosto;scoteste;scotismo;scotola;scottare;scotterai;scovammo;scovavano;scovino;scozzassi;scozzi;scredito;scremava;screzi;scrimoli;scrittura;scriverci;scrivevo;scroccavi;scrofe;scrolli;scrostata;scrovegni;scrutasse;scrutiamo;scuciti;scudieri;sculaccia;scultrici;scuoiarle;scuole;scuotersi;scuotono;scurirsi;scusabile;scusasi;scusavano;scuso;sdate;sdebita;sdegna;sdegnava;sdenta;sdipanato;sdoganavo;sdoppiata;sdorarsi;sdraiarsi;sdraiero;sdrogo;sdutto;seborree;seccarli;seccatore;secchezze;seccumi;secondano;secondo;seda;sedata;sedavi;sederai;sederti;sedevate;sedicente;sediziosa;seducesse;sedulo;seduto;segali;segare;segatura;seggiole;seghetti;segnai;segnalino;segnata;segnavia;segno;segre;segrete;seguiamo;seguirlo;seguitato;seguivano;seienne;selciammo;selcino;selenio;seleziona;sellaste;sellerei;selva;semai;sembrammo;sembravi;sementa;semiala;semicotta;semidensa;semifrasi;seminario;seminerei;seminudo;semipieni;semisse;semivive;semoloso;senapi;senatrice;senilita;senografi;sensile;sensoria;sentenzia;sentirai;sentisca;sentivate;senzadio;separaste;separiamo;seppelli;sequitur;serao;serbano;serbava;serbia;serenelle;seriando;sericina;serimetro;serotina;serpenti;serrafile;serrano;serrature;serri;servate;servibile;servirei;servita;servizio;sessare;sessista;sestario;sestine;setaceo;setolato;settaggio;settenni;settime;settupli;seviziata;sezionali;sezzai;sfaccio;sfaglia;sfaldarsi;sfalsare;sfamarla;sfamavi;sfangare;sfaremo;sfaro;sfasava;sfasciavi;sfatasse;sfatto;sfavoriva;sfegatata;sfeltrava;sferire;sferraste;sferriate;sferzassi;sferziamo;sfiammi;sfiatando;sfiatatoi;sfibbiamo;sfibrammo;sfibravi;sfidante;sfidava;sfidino;sfigurai;sfilacci;sfilata;sfileremo;sfinge;sfinisca;sfinivi;sfiorai;sfiorati;sfiorino;sfioriti;sfittare;sflanella;sfocero;sfociato;sfoderano;sfogammo;sfogate;sfoggia;sfoghi;sfogliavo;sfollasse;sfoltisse;sfondasti;sfondino;sforato;sformava;sfornite;sforzarti;sforzero;sfottersi;sfracella;sfratta;sfreccera;sfregasse;sfreghero;sfregino;sfrenava;sfrigolo;sfrondati;sfruscii;sfruttino;sfuggire;sfuggono;sfumatura;sfumiate;sgama;sgambetta;sganciata;sgarberie;sgarravi;sgarzava;sgassata;sgelati;sgherri;sgnacca;sgobbata;sgobbona;sgolaste;sgolerete;sgombrate;sgomento;sgominino;sgommato;sgonfiavo;sgorbiava;sgorgate;sgoverno;sgozzava;sgradira;sgraffio;sgranatoi;sgranino;sgrassino;sgravassi;sgraviate;sgretolo;sgridasti;sgrido;sgrondava;sgrossano;sgrottare;sguainare;sgualcita;sguarnita;sguazzavi;sgurando;sgusciata;shakers;shimmy;shopper;shuntando;siamesi;siberite;sibilato;sibillini;siccitose;sicofanti;sicuri;sidney;siedono;sierosa;sifilide;sigaro;sigillino;siglatura;sigmi;signorile;silani;silicato;siliconi;sillabico;silometro;siluraste;siluriane;silvani;simatico;similare;simonie;simplex;simulammo;simulcast;sinaitica;sincarpo;sincerino;sincopate;sindacano;sindrome;sinergie;singleton;sinistra;sinodico;sinopie;sinsepali;sintonico;sinusite;sipontina;siriano;sirte;sismologa;sistemera;sistino;sito;situare;situavate;situs;sketch;slabbrare;slacciamo;slama;slancerei;slargando;slatinano;slava;slavizza;sleale;slegasse;sleghera;slentassi;slentiate;slittare;slittero;slogans;slogavano;sloggiati;slombai;slombavo;sloveni;slungasti;smaccato;smagato;smagliero;smagrisce;smalizino;smaltassi;smaltero;smaltisse;smammare;smaniando;smaniglio;smarcate;smarrato;smarrisco;smaschera;smecciato;smembravo;smenti;smentisse;smercero;smerda;smerghi;smerlo;smettero;smettila;smezzato;smielando;sminasse;sminuisce;smisero;smisto;smocciate;smollica;smontammo;smontera;smorfiosi;smorzassi;smorziate;smotti;smovete;smozzava;smuoia;smuoviate;smussare;snaturati;snelli;snervante;snevati;snidavano;sniffi;snobbone;snodano;snodavano;snudando;sobbalzi;sobillavo;socchiudi;soccombo;socie;socratica;sodate;sodezza;sodomizzi;soffiai;soffiava;soffione;soffocavo;soffriamo;soffrissi;soffusi;sofocleo;soggiorna;sogli;sognando;sognatore;sogniate;solario;solcabile;solcatore;solchiate;soldoni;solendo;solessi;solevate;solfatato;solfifere;solfone;solforo;solidali;solino;solito;solleone;sollevero;soluta;solvati;solvi;somasche;somiere;sommare;sommava;sommergo;sommiers;sonando;sonatina;sonda;sondato;sondi;soneresti;sonito;sonori;soperchie;sopirete;sopite;soppalchi;sopperivi;soppesino;soppresso;soprafusi;sopravven;sorbendo;sorbirsi;sorbivano;sordastri;sordino;sorgendo;sorgesti;sorgivi;sornioni;sorprese;sorrida;sorriso;sorti;sorvenire;sorvolino;sospetti;sospirata;sossopra;sostato;sostenevo;sosteremo;sostitute;sottanini;sottesi;sottomise;sottovaso;sottrarlo;sound;sovietica;sovrosso;sovviene;spaccare;spaccero;spacciavi;spadella;spaesato;spaginino;spaglio;spaiano;spalaneve;spalatore;spalero;spallato;spalma;spalmavi;spanando;spanciano;spandere;spaniasse;spanne;sparare;sparavi;sparge;spargevo;sparira;sparita;sparlasse;sparliamo;spartaco;spartirai;spartito;spasimare;spassai;spassavo;spastici;spatola;spaurisco;spavento;spaziasti;spazio;spazzata;spazzini;special;speciosi;speculati;spediamo;spedirli;spedite;spegnando;spegnerli;spegno;spelata;spelera;spellarla;spellera;speme;spendervi;spengo;spennato;spentasi;speranze;sperava;sperdero;sperdono;spergiure;spermio;sperperai;spesai;spesavano;spessa;spetrarsi;spettasse;spettino;spezie;spezzasse;spezzetti;spiaccico;spiaggia;spianate;spianta;spiassi;spiazzare;spicca;spiccati;spicchio;spiccio;spiedate;spiegarle;spiegazzi;spierete;spigate;spigliato;spigolosi;spigrisci;spillante;spillerai;spina;spinava;spingarde;spingersi;spingo;spinosita;spintore;spioni;spiralate;spirasti;spireremo;spiritica;spiumare;splendero;splenduti;spock;spoetizzo;spogliero;spoletino;spolpano;spoltriti;spompata;spondili;spontanei;spopolera;sporcare;sporchi;sporgerai;sporgi;sporofiti;sporte;sposai;sposata;sposero;spossata;spostando;spostata;spot;spranghe;sprecassi;sprecone;spregiava;spremersi;spremono;spreto;sprezzo;sprinters;sprizzino;spronarlo;spronera;spruzzate;spugnata;spularda;spulcio;spumino;spuntasse;spunterai;spunzoni;spurghi;sputarle;sputera;sputtava;squadrino;squalo;squamosa;squartano;squassai;squaws;squilli;squittio;sradico;srotolata;stabile;stabulari;staccarlo;stacchera;stacciate;stadica;staffe;stage;stagira;stagnai;stagnato;staiata;stalliera;stambugio;stampaggi;stampate;stampiamo;stanammo;stanavate;stancasti;standards;stanero;stango;stannosa;stanze;stanziera;stappasti;stappo;starcene;starlets;starni;starter;stasava;statere;statisti;statua;status;stazi;stazzasse;stazzi;steatiti;stecchite;stein;stelle;stemmati;stempiavo;stenderei;stenditoi;stenocoro;stentava;stentorei;stereoma;sterlina;sterne;sterpaia;sterposi;stertore;sterzati;stesero;stevenson;stichico;stigliava;stilava;stilizzai;stillate;stilnovi;stimai;stimaste;stimera;stimolano;stinchi;stinto;stipate;stipera;stipiti;stipulato;stirano;stiratori;stirerie;stirpe;stivali;stivatore;stiviamo;stizzisce;stizzose;stoiaia;stolide;stomacai;stomacoso;stonacato;stonato;stoniamo;stoppato;stopposo;storceste;stordii;stordita;storie;stormito;stornate;storniate;storpie;story;strabiche;straccata;stracco;stradato;strafanno;stragismo;stralla;strambata;stranezze;straniavo;straorzi;strappa;straprega;strasse;strattoni;stravizi;straziano;streaking;strego;stremaste;stremino;stresa;stressavo;strettoio;stribbie;striderai;stridiate;strigarsi;striglino;strillera;strinato;stringevo;strippato;stritoli;strizzavo;strofette;stromba;stroncata;stronzata;stroppo;strozzina;strucchi;struggo;strutti;stuardi;stuccato;studi;studiate;studiosi;stufare;stufavi;stuntcar;stupende;stupidito;stupisca;stupivi;stuprava;sturate;sturi;suasa;subacquei;subartica;subbio;subentro;subimmo;subisca;subissero;subivano;sublimero;subornate;subumana;succedera;successe;succhiera;succinica;succorpi;sudacchia;sudassimo;sudavate;suddivise;sudiate;sudisti;suffeudo;suffuso;suggerii;sugheraia;sugo;suicidino;sulcis;sullodate;sumatra;sunne;suocero;suolo;suonati;suonerete;superandi;superata;supere;superiora;supina;suppliche;supplissi;supponevo;supposi;suppurera;surcontre;suricata;surrogare;suscitar;susini;sussulta;sussurrio;suturando;suzione;svagate;svaghino;svaligino;svalutino;svanendo;svanisca;svanivi;svaporera;svasa;svasso;svegliano;svelami;svelati;svelenito;svellete;svelti;sveltiva;svendendo;svendite;svenii;svenivano;sventava;sventrati;sverditi;svernasse;sverniamo;sverro;sveste;svestirmi;svestito;sveve;svezzati;sviamento;sviaste;svieni;svignasse;svigorire;sviliti;svinava;svisammo;svisavate;svisino;svitata;svitero;sviziava;svola;svolgano;svolgete;svoltai;svoltavi;svolvere;svuotava;swattare;tabaccai;tabaccosi;tabasco;tabici;tabuata;tabulassi;tacca;tacche;tacciando;tacciavi;tacconato;tacere;tacesti;tachisti;taciti;tackles;tafanano;taffi;tagetico;tagliarmi;taglienti;tagliolo;tahiti;taiwan;talassica;talent;talipedi;tallofita;tallose;taluni;tamarri;tamburo;tampinato;tamponava;tanaglio;tanga;tangheri;tankista;tannica;tantalica;tantrico;tapinando;tappabuco;tappava;tappetti;tarabusi;tararono;taravate;tardasse;tarderai;tardivo;tarerete;targets;tariffato;tarlate;tarmarono;tarocca;tarpana;tarpavamo;tarpi;tarsie;tartareo;tartrato;tartufo;tassa;tassati;tassello;tassiana;tassodi;tastate;tastero;tattere;tatuaggi;tatuava;tatuo;tavellati;tavolano;tavolini;tazzette;teatina;teatrone;teca;tecnifico;tedeschi;tediasti;tediero;teflonano;tegeata;tegole;teisti;telamoni;telecitta;teleforo;teleosteo;telesonda;telline;teloneo;temendole;temereste;temeste;temibili;temperati;tempestai;tempifica;temporari;temprato;temute;tenaria;tende;tenders;tendina;tendo;tenebro;tenendone;tenerla;tenersele;tenetelo;tengono;tenimenti;tenone;tensione;tentando;tentati;tentenni;tentoni;tenzonava;teodicea;teoforico;teologica;teorete;teosinte;tepenti;teppismi;terapiche;teredini;tergemino;tergevamo;terlani;terminale;terminino;termoforo;ternale;ternate;terpenico;terraiola;terrazza;terreni;terricola;terrigna;terrosi;terzaroli;terzia;terzina;tesaggi;teschio;tesole;tessalica;tesserato;tesserono;tessili;test;testarda;testatine;testerete;testina;testura;tetra;tetrandri;tetrodo;tettonite;teurgia;thermos;tiaso;tibiali;ticcose;tienili;tifammo;tifavate;tifico;tifoseria;tiglioso;tigrate;tigrino;timbra;timbravo;timeli;timo;timor;timpanato;tinelle;tingesse;tingitane;tinnito;tinteggia;tintrice;tipica;tipo;tirabrace;tiramene;tirandoli;tirannide;tirarlo;tirate;tiratrone;tirelle;tiri;tiroidei;tirossina;tirtaica;tisiologa;titano;titoismo;titolese;titubanza;tituberei;tizio;tocca;toccarli;toccatina;tocchetti;tofane;togliendo;togliesse;tokai;tolentino;tollenone;tolleri;tolosano;tomai;tombali;tombo;tomi;tomografo;tonanti;tonchino;tondeggia;tonema;tonfare;tonifico;tonni;tonsurera;topesco;topino;toporagni;toppone;torbidare;torce;torceste;torchiare;torciate;torcono;toreare;toriche;torme;tornare;tornavano;torneggio;tornero;tornirete;tornitori;toronto;torpidi;torrefare;torri;torsione;tortiglia;tortora;torturare;torulosi;tosasiepi;tosature;tosco;toso;tossici;tossirei;tossito;tostarono;tostavo;totalita;totipista;tovaglia;traballio;trabucato;traccia;trace;tracia;tracomi;tradirci;tradisce;tradivate;traducono;traendoci;traessi;traferro;trafilati;traforata;trafugata;tragetto;tragitti;trainano;trainer;tralascio;tram;tramasti;tramenato;tramezzai;tramonta;tramutava;trancerai;tranciste;transenne;translato;trapanai;trapanino;trapelavi;traporre;trarne;trasali;trascelse;trascinii;trascurai;trasformi;traslato;trasmetto;traspare;trasposi;trastullo;trasudo;trattando;trattarsi;tratteggi;trattini;traumi;travasato;travedano;traversie;traviano;traviavi;travisava;travolse;treatment;trebbiera;trecciare;tregende;tremante;tremavo;tremila;tremolera;tremulo;trenini;trentuno;trepidera;trescando;treto;trevisani;triali;triassico;tribola;triboloso;tributa;tributo;tricipiti;tricomi;triduo;trierarco;trifido;trifora;trigesime;trii;trillai;trillero;trilogia;trimurti;trinato;trincerei;trinciava;trio;trionfera;triplane;triplista;tripolini;trippone;trireme;trisecata;trista;tritai;tritatura;triterai;trittici;triture;trivelli;trocaica;trofeo;trogoloni;troiume;trombato;trombone;troncata;tronconi;troniera;tropicali;trotini;trottava;trotzki;trovando;trovarti;trovavi;troyes;truccarsi;trucchino;trucidato;trudgen;truffate;truffiamo;trullare;truschino;tsigane;tubarici;tubavamo;tuberemmo;tubiamo;tubolata;tucani;tudertina;tufello;tuffate;tufferemo;tufo;tulliana;tumefatta;tumidi;tumulare;tumulto;tunicato;tuonano;tuonerai;tupamaro;turarsi;turavo;turbarti;turbavamo;turbide;turbinero;turca;turchino;turerebbe;turgida;turioni;turlupino;turnista;turrito;tutelai;tutelavo;tutori;tuttologi;typon;ubbidimmo;ubbidiva;ubero;ubriacai;uccellaia;uccidendo;uccidevo;ucraini;udinesi;udisti;uditrici;ufficiai;ufficioso;ugello;uggioli;uggite;uguaglio;ulbricht;uligani;ulna;ultimarti;ultimiamo;ululare;ululerei;umanista;umbellato;umbratili;umidezza;umili;umiliato;umoresche;uncinate;undici;ungheresi;unghiuta;ungulati;unicolore;unificavi;unii;unions;unirete;unissimo;uniticcio;univocita;untorello;unzione;upwelling;uraniche;uranoliti;urbinati;uretano;urgenzare;urgevano;urico;urlasse;urlavi;urlino;urografie;uroscopia;urtata;urteresti;urtoni;usassero;usbeche;uscioli;usciti;usero;ussiti;ustolare;usufruite;usurari;usurpati;utenti;utilita;utricoli;uvula;uzzoli;vaccaie;vaccinata;vacillai;vacue;vaffa;vagantivi;vagavamo;vaghezza;vaginiti;vagissero;vaglia;vaglierai;vagotonia;vaioloidi;valdesi;valersi;valgo;valicava;valide;valisassi;vallesani;valore;valutando;valutavo;valvolami;vamps;vandalici;vanesia;vangatore;vangherei;vanilline;vantano;vantavamo;vantino;vaporosa;varassi;varcabile;varchera;vareremmo;variai;variate;varicose;vario;varrone;vascelli;vasetti;vasti;vaughan;vecciate;vedemmo;vederle;vedete;vedici;vedra;vedutismi;vegetare;veglia;vegliati;veicolo;velano;velatura;veleni;velerie;velifici;velite;velluta;velocista;venate;vendemmo;vendete;vendibili;vendite;venendo;venerate;veneriate;venezio;venir;venisti;venosine;ventesime;vento;ventrigli;venusiano;veratri;verboso;verdesche;verdino;verga;vergelle;vergognai;veridici;verita;vermicare;vermut;vernicino;veroni;verrina;versaioli;versati;verserete;versori;verticale;verzotti;vesciconi;vesperale;vessano;vessavo;vestale;vestigia;vestirti;vestivi;vetrai;vetriata;vetriole;vettura;vezzose;viaggetto;viale;vibrai;vibratore;vibriate;vicario;vicereame;vicinante;vide;videoteca;vidimasse;vidimino;vietabili;vietava;vietino;vigessero;vigilati;vigilo;vigny;vilipese;villeggia;villoso;vinaie;vince;vincessi;vincibile;vincolato;vinile;vinsanto;violammo;violavamo;violerete;violiste;viperini;viramenti;viratore;virerei;virginita;virili;virologia;virulenze;vischioso;viscontei;visiere;visitai;visite;visnuismo;vispezza;vistai;vistavo;vistosita;vitaiolo;vitanda;vitellini;viticole;vitree;vituperio;vivaista;vivere;viveva;vivido;vivresti;viziaste;vizierete;vocali;vocative;vociato;vogare;vogava;vogliamo;voivodi;volantini;volatica;volavi;volerei;volessimo;volgate;voliera;vollisti;volpino;voltaica;voltarvi;voltavo;voltiani;voltura;voluta;vomeraie;vomitasti;vomitive;vorra;vorticosa;votandosi;votato;votereste;vuduismo;vulgate;vuotammo;vuotatori;vuotezza;wallaby;water;weimar;whist;williams;wundt;xenakis;xerobia;xerotermi;xilologa;yakusa;yoghi;yucca;zaffate;zagara;zambiani;zampine;zangola;zanzotto;zappaste;zappera;zarevic;zavorrato;zebre;zelatore;zeolite;zeta;zigana;zigote;zimasi;zincato;zinzino;zither;zizze;zodiaco;zona;zoofago;zoogenico;zoologie;zootomia;zoppico;zozza;zucchetti;zufolammo;zufolerei;zuppiere";
-    return RAW_LIST.split(';').map(|x| x.to_string()).collect();
+    RAW_LIST.split(';').map(|x| x.to_string()).collect()
 }
@@ -0,0 +1,27 @@
+//! Part-of-speech tagged word lists, used by the [story](crate::story) pattern elements.
+//!
+//! Verbs are in the third person singular, so that `adjective noun verb adjective noun`
+//! reads as a sentence.
+
+/// Adjectives.
+pub fn adjectives() -> Vec<String> {
+    split(ADJECTIVES)
+}
+
+/// Nouns.
+pub fn nouns() -> Vec<String> {
+    split(NOUNS)
+}
+
+/// Verbs, in the third person singular.
+pub fn verbs() -> Vec<String> {
+    split(VERBS)
+}
+
+fn split(raw: &str) -> Vec<String> {
+    raw.split(';').map(|x| x.to_string()).collect()
+}
+
+pub(crate) const ADJECTIVES: &str = "able;absent;active;actual;agile;alert;alive;amber;ample;ancient;angry;antique;anxious;arctic;ashen;awake;aware;awful;baggy;bald;bare;basic;bitter;blank;bleak;blind;blond;bold;bony;bossy;brave;brief;bright;brisk;broad;broken;bronze;brown;bubbly;bulky;bumpy;busy;calm;candid;careful;cheap;cheerful;chilly;chubby;civil;clean;clever;close;cloudy;clumsy;coarse;cold;cosmic;cozy;crafty;crazy;creamy;crisp;crooked;cruel;crunchy;cubic;curly;curvy;cute;dainty;damp;dapper;daring;dark;dazzling;dear;deep;dense;dizzy;dry;dull;dusty;eager;early;earnest;easy;elastic;elegant;empty;epic;equal;exotic;faint;fair;false;famous;fancy;fast;fearless;feisty;fierce;firm;flat;fluffy;foggy;fond;formal;fragile;frail;frank;free;fresh;frosty;frozen;funny;fuzzy;gentle;giant;giddy;gifted;glad;gloomy;glossy;golden;good;graceful;grand;grassy;greasy;great;green;grumpy;hairy;handy;happy;hardy;harsh;hasty;heavy;hidden;hollow;honest;hot;huge;humble;hungry;icy;idle;jolly;jumpy;keen;kind;large;late;lazy;leafy;lean;light;little;lively;lonely;loud;loyal;lucky;lumpy;mad;magic;major;mellow;merry;messy;mighty;mild;minor;misty;modern;moody;muddy;murky;mute;narrow;nasty;neat;nervous;nimble;noble;noisy;odd;oily;orange;pale;patient;perky;petty;plain;plump;polite;poor;proud;quick;quiet;rapid;rare;raw;ready;regal;rich;rigid;ripe;rocky;rosy;rough;round;royal;rude;rusty;sad;salty;sandy;scary;shaggy;shiny;short;shy;silent;silky;silly;simple;sleepy;slim;slow;small;smart;smoky;smooth;snowy;soft;solid;sour;spicy;stale;steady;steep;sticky;stormy;strong;sturdy;sunny;super;sweet;swift;tall;tame;tart;tender;tidy;tiny;tough;tricky;ugly;vast;velvet;vivid;warm;wary;weary;weird;wet;wicked;wide;wild;windy;wise;witty;wobbly;wooden;woolly;young;zany;zealous";
+pub(crate) const NOUNS: &str = "acorn;actor;alarm;anchor;angel;ant;apple;apron;arrow;artist;attic;aunt;baby;badger;bagel;baker;balloon;banana;bandit;banjo;barber;barn;basket;bat;beach;bean;bear;beaver;bee;beetle;bell;bicycle;bird;biscuit;bishop;blanket;boat;bonnet;book;boot;bottle;boxer;bridge;broom;bubble;bucket;buffalo;bugle;bunny;butler;butter;button;cabin;cactus;camel;camera;candle;cannon;canoe;captain;carpet;carrot;castle;cat;cello;chef;cherry;chicken;chimney;circus;clown;coconut;comet;cookie;cougar;cousin;cowboy;crab;crayon;cricket;crow;cupcake;dancer;dentist;desert;diamond;dinosaur;doctor;dolphin;donkey;dragon;drum;duck;eagle;elephant;elf;engine;falcon;farmer;feather;fiddle;fisher;flamingo;flute;forest;fox;frog;garden;gecko;ghost;giraffe;goblin;goose;gorilla;guitar;hamster;harp;hawk;hedgehog;helmet;hermit;hippo;hornet;horse;igloo;island;jacket;jaguar;jellyfish;jester;judge;kangaroo;kettle;king;kitten;knight;koala;ladder;lamp;lantern;lemon;leopard;lion;lizard;llama;lobster;magnet;mango;mayor;meadow;melon;mermaid;mitten;monk;monkey;moose;mouse;muffin;mule;nephew;ninja;oak;ocean;octopus;onion;orchard;otter;owl;oyster;panda;parrot;peach;peacock;pebble;pelican;penguin;pepper;piano;pickle;pigeon;pilot;pirate;pizza;planet;plumber;poet;pony;potato;prince;puppy;rabbit;raccoon;radish;robot;rocket;rooster;sailor;salmon;scarf;sheriff;shrimp;skunk;sloth;snail;spider;squid;squirrel;statue;student;sultan;swan;tailor;teacher;tiger;toad;tomato;tractor;trumpet;tulip;turkey;turtle;uncle;unicorn;violin;volcano;waiter;walrus;weasel;whale;wizard;wolf;yak;zebra";
+pub(crate) const VERBS: &str = "admires;adopts;annoys;applauds;bakes;bargains;battles;befriends;bites;blesses;boils;borrows;bounces;bribes;brushes;builds;buries;buys;calls;carries;carves;catches;chases;cheers;chews;chooses;claims;cleans;climbs;clips;coaches;collects;colors;cooks;copies;counts;courts;covers;cradles;crushes;cuddles;dazzles;decorates;defends;delivers;designs;dissolves;divides;dodges;drags;draws;dreams;dresses;drops;dusts;eats;elects;embraces;enchants;escorts;examines;feeds;fetches;fights;fixes;flatters;flips;folds;follows;forgives;freezes;frightens;gathers;grabs;greets;grills;guards;guides;hammers;helps;hides;hires;hugs;hunts;ignores;imitates;impresses;inspects;invites;irons;jiggles;juggles;kicks;kisses;knits;lassoes;launches;leads;licks;lifts;loves;marries;measures;melts;mends;mimics;misses;mocks;nudges;observes;offers;paints;pampers;parks;passes;pats;peels;photographs;pinches;plants;pleases;plucks;pokes;polishes;praises;protects;pulls;punches;pushes;questions;raises;rescues;rewards;rides;roasts;rocks;salutes;saves;scares;scolds;scrubs;seasons;serenades;shakes;shears;shelters;shoves;sketches;slices;smells;smuggles;sniffs;soaks;spies;splashes;squeezes;stacks;startles;steals;stirs;strokes;studies;summons;surprises;swaps;swings;tackles;tames;teaches;teases;tickles;tosses;trades;trains;traps;tugs;tunes;unwraps;uses;visits;wakes;warms;washes;watches;weighs;welcomes;wraps;writes;yanks;zaps";
@@ -0,0 +1,4 @@
+pub fn list() -> Vec<String> {
+    const RAW_LIST: &str = "абрикос;август;автобус;адрес;азбука;аист;айва;акула;аллея;алмаз;альбом;ананас;ангел;анекдот;апельсин;аптека;арбуз;армия;артист;астра;атлас;бабочка;багаж;бадминтон;бак;балкон;банан;банка;барабан;баран;бассейн;башня;бегемот;беда;белка;берег;берёза;бетон;библиотека;билет;бинокль;блин;блокнот;блюдо;бобр;богатырь;бокал;болото;борщ;ботинок;бочка;брат;бревно;бровь;брусника;буква;букет;булка;бумага;буран;бусы;бутерброд;бык;вагон;валенок;ванна;варенье;василёк;ведро;веер;велосипед;веник;ветер;ветка;вечер;взгляд;вилка;вишня;вода;водопад;вокзал;волна;волос;ворона;ворота;восток;время;вулкан;выдра;высота;вьюга;газета;галка;гамак;гвоздь;гепард;гитара;глаз;глобус;гнездо;голубь;гора;город;горох;горшок;гость;гранат;гриб;гроза;груша;губка;гусь;дамба;дверь;дворец;девочка;дедушка;дельфин;день;деревня;дерево;джунгли;диван;дождь;дорога;доска;дракон;друг;дуб;дыня;дятел;жаба;жасмин;жемчуг;жираф;жук;журавль;журнал;жёлудь;забор;завод;загадка;закат;залив;замок;заря;звезда;звонок;здание;зебра;зелень;земля;зеркало;зерно;зима;змея;зонт;зубр;ива;игла;игрушка;изба;изюм;икра;индюк;иней;искра;история;кабан;календарь;камень;канал;капуста;карандаш;карман;карта;картина;каша;каштан;квартира;кедр;кенгуру;кит;клубника;ключ;клён;книга;кнопка;ковёр;коза;колесо;колокол;комар;компас;конверт;конфета;корабль;корзина;корова;костёр;кот;кошка;край;кран;крепость;кролик;крыло;кувшин;кукла;кулак;купол;куст;лавка;лагерь;ладонь;лампа;ласточка;лебедь;лев;лейка;лента;лес;лестница;лето;лимон;липа;лиса;лист;лодка;ложка;лошадь;луг;лук;луна;лыжи;льдина;любовь;лягушка;магазин;мак;малина;мальчик;марка;маска;масло;матрёшка;маяк;медведь;мел;мельница;место;метла;мечта;мир;мишка;молния;молоко;море;морковь;мост;мотылёк;муха;мыло;мышь;мяч;мёд;набор;народ;небо;невод;нитка;норка;нос;ночь;ноябрь;обед;облако;овощ;овёс;огонь;огород;огурец;одеяло;озеро;океан;окно;олень;орех;орёл;осень;осина;остров;отец;охота;очки;павлин;пальма;память;панда;папа;парк;парус;пастух;певец;пельмени;пенал;пень;перо;песня;песок;петух;печенье;пила;пирог;письмо;плита;плот;площадь;пляж;поезд;поле;полка;полёт;помидор;портфель;посуда;поток;почта;праздник;пруд;птица;пуговица;пустыня;пчела;пшеница;пятно;радуга;ракета;ракушка;рассвет;ребёнок;река;репа;рисунок;рог;роза;ромашка;рубашка;ручей;рыба;рынок;рюкзак;рябина;сад;самолёт;сапог;сахар;свеча;север;сено;серп;сестра;сирень;сказка;скамейка;скворец;слива;слон;снег;сова;сокол;солнце;соловей;сосна;спичка;стакан;стена;стол;стрела;струна;стул;сугроб;сумка;сундук;сыр;таблица;танец;тарелка;творог;театр;телефон;тень;тетрадь;тигр;топор;торт;трава;трамвай;тропа;труба;туман;тыква;улей;улитка;улица;утка;утро;учитель;ушанка;фазан;фартук;фасоль;фея;фиалка;флаг;фонарь;фонтан;фрукт;футбол;хвост;хлеб;холм;хомяк;хоровод;хризантема;художник;цапля;цветок;цепь;цирк;цыплёнок;чайка;чайник;часы;чашка;черника;чернила;честь;чудо;чулок;шапка;шар;шарф;шахматы;шишка;школа;шмель;шоколад;шуба;шум;щавель;щегол;щенок;щука;щётка;экран;эхо;юбка;юг;юла;юнга;яблоко;ягода;язык;якорь;январь;ястреб;ящерица;ящик;ёж;ёлка;ёрш";
+    RAW_LIST.split(';').map(|x| x.to_string()).collect()
+}
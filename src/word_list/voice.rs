@@ -0,0 +1,11 @@
+//! Words for spoken challenge phrases, see [voice](crate::voice).
+//!
+//! The words are common, concrete, two or three syllables long where possible, and were chosen
+//! so that no two of them sound alike.
+
+/// The list.
+pub fn list() -> Vec<String> {
+    WORDS.split(';').map(|x| x.to_string()).collect()
+}
+
+pub(crate) const WORDS: &str = "acid;acorn;actor;admiral;agent;album;alpha;amber;anchor;angel;ankle;anvil;apple;apricot;apron;arrow;artist;atlas;attic;avocado;bacon;badge;badger;bagel;balloon;bamboo;banana;bandit;banjo;barrel;basket;beacon;beaver;beetle;bicycle;biscuit;bishop;blanket;blizzard;blossom;bonnet;bottle;boulder;bracket;bramble;bridge;bucket;buffalo;bunny;butter;button;cabbage;cabin;cactus;camel;camera;candle;cannon;canoe;canvas;captain;cargo;carpet;carrot;cashew;castle;cello;cherry;chimney;cinnamon;circus;clover;cobalt;cobra;coconut;comet;compass;copper;cotton;cougar;cowboy;coyote;crayon;cricket;crystal;cupcake;cushion;daisy;dancer;dentist;desert;diamond;dinosaur;doctor;dolphin;domino;donkey;dragon;drummer;eagle;echo;elbow;elephant;ember;emerald;engine;falcon;feather;fiddle;finger;fireworks;flamingo;forest;fossil;fountain;frog;gadget;galaxy;garden;garlic;gecko;giant;ginger;giraffe;glacier;goblin;gorilla;gravel;guitar;hammer;hamster;harbor;harvest;hazel;hedgehog;helmet;hermit;hippo;honey;hornet;hurricane;iceberg;igloo;insect;island;ivory;jacket;jaguar;jasmine;javelin;jelly;jester;jigsaw;jukebox;jungle;kangaroo;kayak;kettle;kitten;kiwi;koala;ladder;lagoon;lantern;lasso;lemon;leopard;lettuce;lizard;llama;lobster;lumber;magnet;magpie;mammoth;mango;marble;meadow;melon;mermaid;meteor;mitten;monkey;mosaic;muffin;mushroom;mustard;napkin;nectar;nickel;noodle;nugget;nutmeg;oasis;octopus;olive;onion;orange;orbit;orchard;ostrich;otter;oyster;paddle;panda;panther;paprika;parrot;parsley;peanut;pebble;pelican;penguin;pepper;piano;pickle;pilgrim;pillow;pirate;pizza;planet;plumber;pocket;poodle;possum;potato;pretzel;puddle;puffin;pumpkin;puppet;pyramid;python;quartz;rabbit;raccoon;radar;radish;raven;rhubarb;ribbon;robot;rocket;rooster;ruby;saddle;salmon;sandal;satchel;saturn;sausage;scooter;seagull;shadow;sheriff;shovel;silver;skunk;sloth;sparrow;spatula;spider;spinach;squid;squirrel;statue;sugar;sultan;summit;sunset;tablet;tadpole;tangerine;teapot;thimble;tiger;timber;toaster;tomato;tornado;tractor;trombone;trumpet;tuba;tulip;tunnel;turkey;turtle;tuxedo;umbrella;unicorn;valley;velvet;violin;volcano;vulture;waffle;wagon;walnut;walrus;whistle;wizard;yodel;yogurt;zebra;zipper";